@@ -0,0 +1,119 @@
+# Deutsche Oberfläche. Fehlende Meldungen kommen aus en.ftl.
+
+## Kopfzeile
+
+app-title = EXECUTIVE DECISION MAKER
+header-box = Radio Shack · { $pack }
+chaos-badge = ⚡ CHAOS-WOCHE ⚡
+chaos-rules = keine Gewichte · keine Siegel · alles ist möglich
+question-label = Frage:
+question-validity =
+    { $days ->
+        [one] gilt 1 Tag · Tab
+       *[other] gilt { $days } Tage · Tab
+    }
+question-validity-unset = Tab: Gültigkeit setzen
+question-timebox = ⏱ { $seconds } s · Strg+T
+question-timebox-unset = Strg+T: Frist setzen
+prompt-think = Denk an deine Frage (oder tippe sie mit i) und drücke Enter oder Leertaste.
+prompt-shuffling = Die Lichter mischen … gleich ist es so weit!
+prompt-charging = Das Orakel lädt sich auf … gleich!
+prompt-countdown = ⏱ { $seconds } s: Enter zum Annehmen, sonst gilt die Antwort bei Fristende.
+prompt-landed = Die Antwort steht fest. Frag jederzeit erneut.
+prompt-first = Hilfe gefällig? Drücke Strg+H.
+prompt-again = Noch eine Frage? Enter oder Leertaste drücken.
+
+## Letzte Antworten unter dem Brett
+
+recent-label = Zuletzt:
+recent-expired = ⟳ { $count } neu zu prüfen (R)
+
+## Fußzeile
+
+status-box = Status
+status-typing = Tippe deine Frage.
+status-shuffling = Das Orakel wird befragt …
+status-charging = Das Orakel lädt sich auf …
+status-answer = Antwort: { $answer }
+status-final = Endgültige Antwort: { $answer }
+status-consider = …aber bedenke: { $answer }
+status-ready = Bereit, wenn du es bist.
+status-sealed = VERSIEGELT
+status-today = Heute: { $tally }
+status-agreement = Zweitmeinung stimmte { $agreed }/{ $asked } zu
+status-best-of = Best-of-{ $rounds }: { $tally }
+status-vetoes = Vetos übrig: { $left }
+status-optimism = Optimismus { $level }
+hint-typing = Enter fragen · Rücktaste löschen · Esc abbrechen
+hint-shuffling = Die Lichter blinken zufällig, bis die Antwort erscheint.
+hint-landed = Die Markierung bleibt kurz stehen, damit du das Ergebnis siehst.
+hint-again = Enter/Leertaste erneut · i Frage · y kopieren · g Pakete · Strg+H Hilfe · q beenden
+hint-first = Enter/Leertaste fragen · i Frage tippen · g Pakete · Strg+H Hilfe · q/Esc beenden
+
+## Tab-Leiste
+
+screen-oracle = Orakel
+screen-history = Verlauf
+screen-stats = Statistik
+screen-settings = Einstellungen
+screen-help = Hilfe
+
+## Hilfe
+
+help-box = Hilfe
+help-how-to = So geht's:
+help-rule-ask = - Enter oder Leertaste lässt eine zufällige Antwort aufleuchten.
+help-rule-flash = - Die markierte Antwort bleibt etwa 1,5 s an.
+help-controls = Tasten:
+help-ask = Fragen (oder diese Hilfe schließen)
+help-copy = Letzte Antwort kopieren
+help-qr = Entscheidung als QR-Code zum Scannen zeigen
+help-screenshot = Bildschirm als SVG- oder HTML-Datei speichern
+help-question = Erst die Frage tippen
+help-gallery = Antwortpakete durchsehen
+help-theme = Themes ansehen (Enter behält, Esc verwirft)
+help-weights = Gewichte anpassen (+/-)
+help-pack = Antworten dieses Pakets bearbeiten
+help-history = Frühere Entscheidungen durchsuchen
+help-note = Notiz zur letzten Antwort schreiben
+help-tags = Letzte Antwort verschlagworten
+help-filter = Nur Entscheidungen mit einem Tag zeigen
+help-profiles = Zu einem anderen Profil wechseln
+help-achievements = Freigeschaltete Erfolge anzeigen
+help-worksheet = Pro-und-Contra-Liste, vom Orakel gewogen
+help-matrix = Entscheidungsmatrix: Optionen nach gewichteten Kriterien bewertet
+help-bracket = Turnier: Optionen scheiden im Duell aus
+help-settings = Einstellungen: Vorlieben und Tasten
+help-screens = Vorheriger / nächster Bildschirm in der Tab-Leiste
+help-veto = Antwort ablehnen und neu fragen (wenige Male am Tag)
+help-eliminate = Antworten nacheinander ausscheiden lassen, bis eine bleibt
+help-optimism = Optimistischere oder pessimistischere Antworten
+help-revalidate = Nächste abgelaufene Entscheidung neu fragen
+help-outcome = Markieren, ob die Entscheidung gut ausging
+help-lock = Bildschirm sperren (braucht lock_passphrase)
+help-contrast = Kontraststarkes Theme ein/aus
+help-debug = Debug-Anzeige (Frame-Zeiten)
+help-palette = Befehlspalette: jede Aktion nach Namen ausführen
+help-help = Hilfe ein/aus
+help-back = Beenden (Esc schließt erst die Hilfe)
+help-quit = Sofort beenden
+help-suspend = In die Shell wechseln (fg kehrt zurück)
+
+## Barrierearmer Modus (--accessible)
+
+accessible-intro = Paket { $pack }. Enter oder Leertaste fragt, i tippt erst eine Frage, Strg+H zeigt die Hilfe, q beendet.
+accessible-unavailable = Im barrierearmen Modus nicht verfügbar.
+accessible-confidence = Das Orakel ist sich zu { $percent } % sicher.
+accessible-timebox = Enter innerhalb von { $seconds } Sekunden nimmt an, sonst gilt die Antwort trotzdem.
+accessible-rationale = Begründung: { $text }
+accessible-second-opinion =
+    Zweitmeinung von { $pack }: { $answer }, { $agrees ->
+        [yes] stimmt zu
+       *[no] widerspricht
+    }.
+
+## Terminal zu klein
+
+too-small = Terminal zu klein
+too-small-enlarge = Bitte auf mindestens { $width }×{ $height } vergrößern.
+too-small-now = Jetzt: { $width }×{ $height }
@@ -0,0 +1,121 @@
+# Interface strings for edm, in Fluent syntax: https://projectfluent.org
+# Translations live next to this file as <language>.ftl and fall back to
+# these messages for anything they leave out.
+
+## Header
+
+app-title = EXECUTIVE DECISION MAKER
+header-box = Radio Shack · { $pack }
+chaos-badge = ⚡ CHAOS WEEK ⚡
+chaos-rules = no weights · no seals · anything goes
+question-label = Question:
+question-validity =
+    { $days ->
+        [one] valid 1 day · Tab
+       *[other] valid { $days } days · Tab
+    }
+question-validity-unset = Tab: set validity
+question-timebox = ⏱ { $seconds } s · Ctrl+T
+question-timebox-unset = Ctrl+T: set timebox
+prompt-think = Think of your question (or type it with i), then press Enter or Space.
+prompt-shuffling = Lights are shuffling... hold tight!
+prompt-charging = Charging the oracle... brace yourself!
+prompt-countdown = ⏱ { $seconds } s: press Enter to accept, or it stands when time runs out.
+prompt-landed = Final answer locked in. Ask again any time.
+prompt-first = Need instructions? Press Ctrl+H for help.
+prompt-again = Ready for another? Press Enter or Space to ask again.
+
+## Recent answers under the board
+
+recent-label = Recent:
+recent-expired = ⟳ { $count } to re-validate (R)
+
+## Footer
+
+status-box = Status
+status-typing = Type your question.
+status-shuffling = Consulting the oracle...
+status-charging = Charging the oracle...
+status-answer = Answer: { $answer }
+status-final = Final Answer: { $answer }
+status-consider = …but consider: { $answer }
+status-ready = Ready when you are.
+status-sealed = SEALED
+status-today = Today: { $tally }
+status-agreement = second opinion agreed { $agreed }/{ $asked }
+status-best-of = best of { $rounds }: { $tally }
+status-vetoes = vetoes left: { $left }
+status-optimism = optimism { $level }
+hint-typing = Enter to ask · Backspace to erase · Esc to cancel
+hint-shuffling = Lights flash in random order before the final answer appears.
+hint-landed = Highlight stays on briefly so you can see the result.
+hint-again = Enter/Space ask again · i question · y copy · g packs · Ctrl+H help · q quit
+hint-first = Enter/Space to ask · i type a question · g packs · Ctrl+H help · q/Esc quit
+
+## Tab bar
+
+screen-oracle = Oracle
+screen-history = History
+screen-stats = Stats
+screen-settings = Settings
+screen-help = Help
+
+## Help screen
+
+help-box = Help
+help-how-to = How to play:
+help-rule-ask = - Press Enter or Space to light up a random answer.
+help-rule-flash = - The highlighted answer stays on for about 1.5 s.
+help-controls = Controls:
+help-ask = Ask (or close this help)
+help-copy = Copy the last answer
+help-qr = Show the decision as a QR code to scan
+help-screenshot = Save the screen as an SVG or HTML file
+help-question = Type your question first
+help-gallery = Browse answer packs
+help-theme = Preview themes (Enter keeps, Esc reverts)
+help-weights = Tune answer weights (+/- adjust)
+help-pack = Edit the answers in this pack
+help-history = Browse and search past decisions
+help-note = Write a note on the last answer
+help-tags = Tag the last answer
+help-filter = Show one tag's decisions
+help-profiles = Switch to another profile
+help-achievements = Show unlocked achievements
+help-worksheet = Pros and cons worksheet, weighed by the oracle
+help-matrix = Decision matrix: options scored against weighted criteria
+help-bracket = Tournament: options knocked out head to head
+help-settings = Settings: preferences and keys
+help-screens = Previous / next screen along the tab bar
+help-veto = Veto the answer and ask again (a few times a day)
+help-eliminate = Knock answers out one by one until one is left
+help-optimism = More or less optimistic answers
+help-revalidate = Re-ask the next expired decision
+help-outcome = Mark whether the decision worked out
+help-lock = Lock the screen (needs lock_passphrase)
+help-contrast = High-contrast theme on/off
+help-debug = Debug overlay (frame timings)
+help-palette = Command palette: run any action by name
+help-help = Toggle help
+help-back = Quit (Esc closes help first)
+help-quit = Quit immediately
+help-suspend = Suspend to the shell (fg resumes)
+
+## Accessible mode (--accessible)
+
+accessible-intro = { $pack } pack. Press Enter or Space to ask, i to type a question first, Ctrl+H for help, q to quit.
+accessible-unavailable = Not available in accessible mode.
+accessible-confidence = The oracle is { $percent }% confident.
+accessible-timebox = Press Enter within { $seconds } seconds to accept, or the answer stands anyway.
+accessible-rationale = Rationale: { $text }
+accessible-second-opinion =
+    Second opinion from { $pack }: { $answer }, { $agrees ->
+        [yes] agrees
+       *[no] disagrees
+    }.
+
+## Terminal too small
+
+too-small = Terminal too small
+too-small-enlarge = Please enlarge it to at least { $width }×{ $height }.
+too-small-now = Now: { $width }×{ $height }
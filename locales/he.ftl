@@ -0,0 +1,120 @@
+# ממשק בעברית. הודעות חסרות מגיעות מ-en.ftl.
+
+## כותרת
+
+app-title = EXECUTIVE DECISION MAKER
+header-box = Radio Shack · { $pack }
+chaos-badge = ⚡ שבוע כאוס ⚡
+chaos-rules = בלי משקלות · בלי חותמות · הכול הולך
+question-label = שאלה:
+question-validity =
+    { $days ->
+        [one] בתוקף יום אחד · Tab
+        [two] בתוקף יומיים · Tab
+       *[other] בתוקף { $days } ימים · Tab
+    }
+question-validity-unset = Tab: קביעת תוקף
+question-timebox = ⏱ { $seconds } שנ׳ · Ctrl+T
+question-timebox-unset = Ctrl+T: קביעת מגבלת זמן
+prompt-think = חשבו על השאלה (או הקלידו אותה עם i), ואז הקישו Enter או רווח.
+prompt-shuffling = האורות מתערבבים... רק רגע!
+prompt-charging = טוענים את האורקל... היכונו!
+prompt-countdown = ⏱ { $seconds } שנ׳: הקישו Enter כדי לקבל, אחרת התשובה תיקבע כשהזמן ייגמר.
+prompt-landed = התשובה נקבעה. אפשר לשאול שוב בכל עת.
+prompt-first = צריכים הסבר? הקישו Ctrl+H לעזרה.
+prompt-again = עוד שאלה? הקישו Enter או רווח.
+
+## תשובות אחרונות מתחת ללוח
+
+recent-label = אחרונות:
+recent-expired = ⟳ { $count } לבדיקה מחדש (R)
+
+## שורת מצב
+
+status-box = מצב
+status-typing = הקלידו את השאלה.
+status-shuffling = מתייעצים עם האורקל...
+status-charging = טוענים את האורקל...
+status-answer = תשובה: { $answer }
+status-final = תשובה סופית: { $answer }
+status-consider = …אבל שקול: { $answer }
+status-ready = מוכנים כשתהיו מוכנים.
+status-sealed = חתום
+status-today = היום: { $tally }
+status-agreement = דעה שנייה הסכימה { $agreed }/{ $asked }
+status-best-of = הטוב מתוך { $rounds }: { $tally }
+status-vetoes = וטו שנותרו: { $left }
+status-optimism = אופטימיות { $level }
+hint-typing = Enter לשאול · Backspace למחוק · Esc לבטל
+hint-shuffling = האורות מהבהבים בסדר אקראי עד שהתשובה מופיעה.
+hint-landed = ההדגשה נשארת לרגע כדי שתראו את התוצאה.
+hint-again = Enter/רווח שוב · i שאלה · y העתקה · g חבילות · Ctrl+H עזרה · q יציאה
+hint-first = Enter/רווח לשאול · i להקליד שאלה · g חבילות · Ctrl+H עזרה · q/Esc יציאה
+
+## סרגל הלשוניות
+
+screen-oracle = האורקל
+screen-history = היסטוריה
+screen-stats = סטטיסטיקה
+screen-settings = הגדרות
+screen-help = עזרה
+
+## חלון העזרה
+
+help-box = עזרה
+help-how-to = איך משחקים:
+help-rule-ask = - הקישו Enter או רווח כדי להדליק תשובה אקראית.
+help-rule-flash = - התשובה המודגשת נשארת דולקת כשנייה וחצי.
+help-controls = מקשים:
+help-ask = לשאול (או לסגור את העזרה)
+help-copy = העתקת התשובה האחרונה
+help-qr = הצגת ההחלטה כקוד QR לסריקה
+help-screenshot = שמירת המסך כקובץ SVG או HTML
+help-question = להקליד קודם את השאלה
+help-gallery = עיון בחבילות תשובות
+help-theme = תצוגת ערכות נושא (Enter שומר, Esc מבטל)
+help-weights = כוונון משקלות (+/-)
+help-pack = עריכת התשובות בחבילה
+help-history = עיון וחיפוש בהחלטות קודמות
+help-note = כתיבת הערה על התשובה האחרונה
+help-tags = תיוג התשובה האחרונה
+help-filter = הצגת החלטות של תגית אחת
+help-profiles = מעבר לפרופיל אחר
+help-achievements = הצגת הישגים שנפתחו
+help-worksheet = טבלת בעד ונגד, שהאורקל שוקל
+help-matrix = מטריצת החלטה: אפשרויות מדורגות לפי קריטריונים משוקללים
+help-bracket = טורניר: אפשרויות מודחות ראש בראש
+help-settings = הגדרות: העדפות ומקשים
+help-screens = המסך הקודם / הבא בסרגל הלשוניות
+help-veto = וטו על התשובה ושאלה מחדש (כמה פעמים ביום)
+help-eliminate = להדיח תשובות אחת אחת עד שנשארת אחת
+help-optimism = תשובות אופטימיות או פסימיות יותר
+help-revalidate = לשאול מחדש את ההחלטה הבאה שפג תוקפה
+help-outcome = לסמן אם ההחלטה התבררה כטובה
+help-lock = נעילת המסך (דורש lock_passphrase)
+help-contrast = ערכת ניגודיות גבוהה (הפעלה/כיבוי)
+help-debug = שכבת ניפוי באגים (זמני פריימים)
+help-palette = לוח פקודות: להפעיל כל פעולה לפי שם
+help-help = הצגת/הסתרת העזרה
+help-back = יציאה (Esc סוגר קודם את העזרה)
+help-quit = יציאה מיידית
+help-suspend = השהיה למעטפת (fg ממשיך)
+
+## מצב נגיש (--accessible)
+
+accessible-intro = חבילת { $pack }. Enter או רווח כדי לשאול, i כדי להקליד שאלה קודם, Ctrl+H לעזרה, q ליציאה.
+accessible-unavailable = לא זמין במצב נגיש.
+accessible-confidence = האורקל בטוח ב־{ $percent }%.
+accessible-timebox = הקישו Enter בתוך { $seconds } שניות כדי לקבל, אחרת התשובה תיקבע בכל מקרה.
+accessible-rationale = נימוק: { $text }
+accessible-second-opinion =
+    דעה שנייה מ-{ $pack }: { $answer }, { $agrees ->
+        [yes] מסכימה
+       *[no] לא מסכימה
+    }.
+
+## המסוף קטן מדי
+
+too-small = המסוף קטן מדי
+too-small-enlarge = נא להגדיל אותו ל-{ $width }×{ $height } לפחות.
+too-small-now = כעת: { $width }×{ $height }
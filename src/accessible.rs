@@ -0,0 +1,267 @@
+//! `--accessible`: a front end for screen readers and braille displays.
+//!
+//! Instead of redrawing a full-screen board, [`LinearFrontend`] stays on the
+//! normal screen and prints a plain line whenever something worth hearing
+//! happens: the shuffle starting, the answer, a notice, the help text. Lines
+//! are only ever added, never redrawn, so a reader following the output
+//! does not lose its place. Screens that only make sense as a grid (pack
+//! gallery, themes, settings, weights, lock, debug overlay) are not offered.
+
+use crate::{
+    app::App,
+    frontend::Frontend,
+    input::{self, Action, Mode},
+    keymap::Keymap,
+    rationale,
+    screen::Screen,
+    ui,
+    widget::State,
+    width,
+};
+use crossterm::{
+    event::{self, Event},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+pub struct LinearFrontend {
+    /// How the app wanted keys read at the last render, and its bindings.
+    mode: Mode,
+    keymap: Keymap,
+    /// What was announced last, so each change is printed once.
+    status: Option<String>,
+    notice: Option<String>,
+    banner: bool,
+    streak: bool,
+    help: bool,
+    rationale: bool,
+    /// The question as echoed so far, while one is being typed.
+    typed: Option<String>,
+    /// Said when a key asks for a screen this front end does not have.
+    unavailable: String,
+    introduced: bool,
+}
+
+impl LinearFrontend {
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Commands,
+            keymap: Keymap::default(),
+            status: None,
+            notice: None,
+            banner: false,
+            streak: false,
+            help: false,
+            rationale: false,
+            typed: None,
+            unavailable: String::new(),
+            introduced: false,
+        }
+    }
+
+    /// Echo the question being typed the way a cooked terminal would:
+    /// characters as they come, backspaces as they erase.
+    fn echo(&mut self, app: &App, out: &mut impl Write) -> io::Result<()> {
+        match (self.typed.as_mut(), app.typing) {
+            (None, false) => {}
+            (None, true) => {
+                write!(
+                    out,
+                    "{} {}",
+                    app.strings.get("question-label"),
+                    app.question
+                )?;
+                self.typed = Some(app.question.clone());
+            }
+            (Some(_), false) => {
+                write!(out, "\r\n")?;
+                self.typed = None;
+            }
+            (Some(typed), true) => {
+                if let Some(added) = app.question.strip_prefix(typed.as_str()) {
+                    write!(out, "{added}")?;
+                } else if let Some(erased) = typed.strip_prefix(app.question.as_str()) {
+                    write!(out, "{}", "\x08 \x08".repeat(width::of(erased)))?;
+                } else {
+                    write!(
+                        out,
+                        "\r\n{} {}",
+                        app.strings.get("question-label"),
+                        app.question
+                    )?;
+                }
+                *typed = app.question.clone();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for LinearFrontend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Frontend for LinearFrontend {
+    fn init(&mut self) -> io::Result<()> {
+        // Raw mode for single-key commands; the screen itself is left alone.
+        enable_raw_mode()
+    }
+
+    fn render(&mut self, app: &App) -> io::Result<()> {
+        self.mode = app.input_mode();
+        if self.keymap != app.keymap {
+            self.keymap = app.keymap.clone();
+        }
+        let strings = &app.strings;
+        if self.unavailable.is_empty() {
+            self.unavailable = strings.get("accessible-unavailable");
+        }
+
+        let mut lines = Vec::new();
+        if !self.introduced {
+            lines.push(strings.get("app-title"));
+            lines.push(strings.format(
+                "accessible-intro",
+                &[("pack", app.engine.pack.title.as_str().into())],
+            ));
+            self.introduced = true;
+        }
+        let banner = app.banner.as_ref().map(|(_, banner, _)| banner);
+        if let (Some(banner), false) = (banner, self.banner) {
+            lines.extend(banner.iter().cloned());
+        }
+        self.banner = banner.is_some();
+        let notice = app.notice.as_ref().map(|(message, _)| message.clone());
+        if notice != self.notice {
+            lines.extend(notice.clone());
+            self.notice = notice;
+        }
+        let help = matches!(app.screen, Screen::Help { .. });
+        if help && !self.help {
+            lines.extend(ui::help_lines(strings));
+        }
+        self.help = help;
+        let status = status(app);
+        if status != self.status {
+            lines.extend(status.clone());
+            if let (Some(opinion), State::Showing { .. }) =
+                (&app.second_opinion, app.decision.state())
+            {
+                lines.push(strings.format(
+                    "accessible-second-opinion",
+                    &[
+                        ("pack", opinion.pack.as_str().into()),
+                        ("answer", opinion.answer.text.as_str().into()),
+                        ("agrees", if opinion.agrees { "yes" } else { "no" }.into()),
+                    ],
+                ));
+            }
+            if let (Some(flavor), State::Showing { .. }) = (&app.flavor, app.decision.state()) {
+                lines.push(flavor.clone());
+            }
+            if let Some(seconds) = app.countdown() {
+                lines.push(strings.format("accessible-timebox", &[("seconds", seconds.into())]));
+            }
+            if let (Some(percent), State::Showing { .. }) = (app.confidence, app.decision.state()) {
+                lines.push(strings.format("accessible-confidence", &[("percent", percent.into())]));
+            }
+            if let (Some(opposite), State::Showing { .. }) =
+                (app.devils_advocate(), app.decision.state())
+            {
+                lines.push(strings.format(
+                    "status-consider",
+                    &[("answer", opposite.text.as_str().into())],
+                ));
+            }
+            self.status = status;
+        }
+        // The rationale turns up after the answer, so it has its own line.
+        let rationale = match &app.rationale {
+            Some(rationale::Status::Ready(text)) => Some(text),
+            _ => None,
+        };
+        if let (Some(text), false) = (rationale, self.rationale) {
+            lines.push(strings.format("accessible-rationale", &[("text", text.as_str().into())]));
+        }
+        self.rationale = rationale.is_some();
+        let streak = app.streak.as_ref().map(|(message, _)| message);
+        if let (Some(message), false) = (streak, self.streak) {
+            lines.push(message.clone());
+        }
+        self.streak = streak.is_some();
+
+        let mut out = io::stdout().lock();
+        self.echo(app, &mut out)?;
+        for line in lines {
+            // Raw mode: a bare `\n` would not return the cursor.
+            write!(out, "{line}\r\n")?;
+        }
+        out.flush()
+    }
+
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let mut out = io::stdout().lock();
+        out.write_all(bytes)?;
+        out.flush()
+    }
+
+    fn handle_input(&mut self, timeout: Option<Duration>) -> io::Result<Option<Action>> {
+        if let Some(timeout) = timeout {
+            if !event::poll(timeout)? {
+                return Ok(None);
+            }
+        }
+        let Event::Key(key) = event::read()? else {
+            return Ok(None);
+        };
+        match input::map(self.mode, &self.keymap, key) {
+            Some(
+                Action::OpenGallery
+                | Action::CycleTheme
+                | Action::OpenSettings
+                | Action::EditWeights
+                | Action::EditPack
+                | Action::OpenProfiles
+                | Action::OpenHistory
+                | Action::OpenAchievements
+                | Action::EditNote
+                | Action::EditTags
+                | Action::CycleTagFilter
+                | Action::Lock
+                | Action::ToggleDebug,
+            ) => {
+                let mut out = io::stdout().lock();
+                write!(out, "{}\r\n", self.unavailable)?;
+                out.flush()?;
+                Ok(None)
+            }
+            action => Ok(action),
+        }
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        if self.typed.take().is_some() {
+            print!("\r\n");
+        }
+        disable_raw_mode()
+    }
+}
+
+/// The line describing where the oracle is: shuffling, or the answer it
+/// gave. `None` before the first ask.
+fn status(app: &App) -> Option<String> {
+    let answer = |index| [("answer", app.answer_text(index).into())];
+    match app.decision.state() {
+        State::Animating { .. } => Some(app.strings.get("status-shuffling")),
+        State::Charging { .. } => Some(app.strings.get("status-charging")),
+        State::Showing { index, .. } => Some(app.strings.format("status-answer", &answer(index))),
+        State::Idle => app
+            .last_answer
+            .map(|index| app.strings.format("status-answer", &answer(index))),
+    }
+}
@@ -0,0 +1,159 @@
+//! Cosmetic achievements ("First Decision", "100 Questions", ...), checked
+//! against the history after every decision. Unlocks are kept with their
+//! time in `achievements.json` in the data directory, so they survive a
+//! cleared history.
+
+use crate::history::History;
+use chrono::{DateTime, Local, NaiveDate, Timelike};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub struct Achievement {
+    /// Key in the achievements file; never change one.
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    earned: fn(&History) -> bool,
+}
+
+/// In the order the achievements screen lists them.
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "first-decision",
+        title: "First Decision",
+        description: "Let the oracle decide something.",
+        earned: |history| history.recent().next().is_some(),
+    },
+    Achievement {
+        id: "dear-diary",
+        title: "Dear Diary",
+        description: "Write a journal note on a decision.",
+        earned: |history| history.recent().any(|decision| decision.note.is_some()),
+    },
+    Achievement {
+        id: "agree-to-disagree",
+        title: "Agree to Disagree",
+        description: "Get a second opinion that disagrees.",
+        earned: |history| {
+            history
+                .recent()
+                .any(|decision| decision.agreed == Some(false))
+        },
+    },
+    Achievement {
+        id: "night-owl",
+        title: "Night Owl",
+        description: "Decide something between midnight and 4 a.m.",
+        earned: |history| {
+            history
+                .recent()
+                .any(|decision| decision.decided_at.hour() < 4)
+        },
+    },
+    Achievement {
+        id: "regular",
+        title: "Regular",
+        description: "Decide on seven different days.",
+        earned: |history| {
+            let days: HashSet<NaiveDate> = history
+                .recent()
+                .map(|decision| decision.decided_at.date_naive())
+                .collect();
+            days.len() >= 7
+        },
+    },
+    Achievement {
+        id: "nevermore",
+        title: "Nevermore",
+        description: "Get NEVER five times in one day.",
+        earned: |history| {
+            let mut nevers: HashMap<NaiveDate, usize> = HashMap::new();
+            for decision in history.recent() {
+                if decision.answer.eq_ignore_ascii_case("NEVER") {
+                    *nevers.entry(decision.decided_at.date_naive()).or_default() += 1;
+                }
+            }
+            nevers.values().any(|&count| count >= 5)
+        },
+    },
+    Achievement {
+        id: "hundred-questions",
+        title: "100 Questions",
+        description: "Ask 100 typed questions.",
+        earned: |history| {
+            history
+                .recent()
+                .filter(|decision| decision.question.is_some())
+                .count()
+                >= 100
+        },
+    },
+];
+
+/// Which achievements are unlocked, and when.
+#[derive(Default)]
+pub struct Achievements {
+    /// Where unlocks are saved; none keeps them for this session.
+    path: Option<PathBuf>,
+    unlocked: BTreeMap<String, DateTime<Local>>,
+}
+
+impl Achievements {
+    /// The unlocks saved at `path`; none if it is missing or unreadable.
+    pub fn load(path: &Path) -> Self {
+        let unlocked = match fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text).unwrap_or_else(|err| {
+                tracing::warn!(path = %path.display(), %err, "ignoring broken achievements file");
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+        Self {
+            path: Some(path.to_path_buf()),
+            unlocked,
+        }
+    }
+
+    /// When `achievement` was unlocked, if it has been.
+    pub fn unlocked_at(&self, achievement: &Achievement) -> Option<DateTime<Local>> {
+        self.unlocked.get(achievement.id).copied()
+    }
+
+    pub fn count(&self) -> usize {
+        ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| self.unlocked_at(achievement).is_some())
+            .count()
+    }
+
+    /// Run after every decision: unlock whatever `history` has earned since
+    /// and save. Returns the newly unlocked achievements.
+    pub fn update(&mut self, history: &History) -> Vec<&'static Achievement> {
+        let now = Local::now();
+        let new: Vec<&'static Achievement> = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| self.unlocked_at(achievement).is_none())
+            .filter(|achievement| (achievement.earned)(history))
+            .collect();
+        for achievement in &new {
+            tracing::info!(id = achievement.id, "achievement unlocked");
+            self.unlocked.insert(achievement.id.to_string(), now);
+        }
+        if !new.is_empty() {
+            if let Err(err) = self.save() {
+                tracing::warn!(%err, "could not save achievements");
+            }
+        }
+        new
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        fs::write(path, serde_json::to_string_pretty(&self.unlocked)?)
+    }
+}
@@ -0,0 +1,42 @@
+//! Outcome-driven weights (`adapt_weights`): answers whose decisions were
+//! later marked as having worked out come up more often, and those that
+//! didn't, less. The pack on disk never changes; draws use a tuned copy.
+//!
+//! An answer's weight is scaled by `(good + 1) / (bad + 1)`, clamped between
+//! a quarter and four times, so a few ratings nudge rather than decide.
+//! Vetoed decisions and answers weighted 0 are left alone.
+
+use crate::{answers::AnswerPack, history::History};
+
+/// The factor an unrated answer gets, in steps of a quarter.
+const NEUTRAL: u32 = 4;
+/// The largest factor, in quarters.
+const MAX_FACTOR: u32 = 16;
+
+/// `pack` with its weights adapted to the outcomes rated in `history`.
+pub fn tuned(pack: &AnswerPack, history: &History) -> AnswerPack {
+    let mut tuned = pack.clone();
+    for answer in &mut tuned.answers {
+        let (mut good, mut bad) = (0, 0);
+        for entry in history.recent() {
+            if entry.pack != pack.id || entry.answer != answer.text || entry.vetoed_at.is_some() {
+                continue;
+            }
+            match entry.worked_out {
+                Some(true) => good += 1,
+                Some(false) => bad += 1,
+                None => {}
+            }
+        }
+        if answer.weight > 0 {
+            answer.weight = (answer.weight.saturating_mul(factor(good, bad)) / NEUTRAL).max(1);
+        }
+    }
+    tuned
+}
+
+/// The weight multiplier for `good` and `bad` outcomes, in quarters.
+fn factor(good: u32, bad: u32) -> u32 {
+    let scaled = NEUTRAL * (good + 1) / (bad + 1);
+    scaled.clamp(1, MAX_FACTOR)
+}
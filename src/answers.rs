@@ -0,0 +1,580 @@
+//! Answer packs: the set of verdicts shown on the board.
+
+use crate::error::{EdmError, Result};
+use crate::pack_check::{self, Level};
+use crate::sentiment::Sentiment::{self, Negative, Neutral, Positive};
+use crate::theme::AnswerStyle;
+use rand::Rng;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The pack used when nothing else is configured.
+pub const DEFAULT_PACK: &str = "classic";
+/// Extension of packs shared with `edm pack export`.
+pub const EXPORT_EXTENSION: &str = "edmpack";
+
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub text: String,
+    pub sentiment: Sentiment,
+    /// Relative chance of being drawn; 0 never comes up.
+    pub weight: u32,
+    /// Colours of the button, instead of the theme's.
+    pub style: AnswerStyle,
+    /// Emoji or other glyph shown before the text on the board.
+    pub icon: Option<String>,
+    /// Text of the answer that argues the other way, for the devil's
+    /// advocate.
+    pub opposite: Option<String>,
+    /// One-liners in the pack's voice, one of which is shown under the
+    /// answer when it comes up.
+    pub flavor: Vec<String>,
+}
+
+impl Answer {
+    /// The text as shown on its button, after the icon if there is one.
+    pub fn label(&self) -> String {
+        label(self.icon.as_deref(), &self.text)
+    }
+
+    /// A random one of the flavor lines, if there are any.
+    pub fn pick_flavor(&self) -> Option<&str> {
+        if self.flavor.is_empty() {
+            return None;
+        }
+        let index = rand::thread_rng().gen_range(0..self.flavor.len());
+        Some(&self.flavor[index])
+    }
+}
+
+fn label(icon: Option<&str>, text: &str) -> String {
+    match icon {
+        Some(icon) => format!("{icon} {text}"),
+        None => text.to_string(),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AnswerPack {
+    /// Short identifier used on the command line and in the config file.
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub answers: Vec<Answer>,
+}
+
+/// The six answers exactly as on the original device.
+const CLASSIC: &[(&str, Sentiment)] = &[
+    ("DEFINITELY", Positive),
+    ("FORGET IT", Negative),
+    ("ASK AGAIN", Neutral),
+    ("NEVER", Negative),
+    ("POSSIBLY", Neutral),
+    ("WHY NOT", Positive),
+];
+
+const CLASSIC_OPPOSITES: &[(&str, &str)] = &[("DEFINITELY", "NEVER"), ("WHY NOT", "FORGET IT")];
+
+const EIGHT_BALL: &[(&str, Sentiment)] = &[
+    ("IT IS CERTAIN", Positive),
+    ("IT IS DECIDEDLY SO", Positive),
+    ("WITHOUT A DOUBT", Positive),
+    ("YES DEFINITELY", Positive),
+    ("YOU MAY RELY ON IT", Positive),
+    ("AS I SEE IT, YES", Positive),
+    ("MOST LIKELY", Positive),
+    ("OUTLOOK GOOD", Positive),
+    ("YES", Positive),
+    ("SIGNS POINT TO YES", Positive),
+    ("REPLY HAZY, TRY AGAIN", Neutral),
+    ("ASK AGAIN LATER", Neutral),
+    ("BETTER NOT TELL YOU NOW", Neutral),
+    ("CANNOT PREDICT NOW", Neutral),
+    ("CONCENTRATE AND ASK AGAIN", Neutral),
+    ("DON'T COUNT ON IT", Negative),
+    ("MY REPLY IS NO", Negative),
+    ("MY SOURCES SAY NO", Negative),
+    ("OUTLOOK NOT SO GOOD", Negative),
+    ("VERY DOUBTFUL", Negative),
+];
+
+const EIGHT_BALL_OPPOSITES: &[(&str, &str)] = &[
+    ("IT IS CERTAIN", "VERY DOUBTFUL"),
+    ("WITHOUT A DOUBT", "MY REPLY IS NO"),
+    ("YES DEFINITELY", "MY SOURCES SAY NO"),
+    ("YOU MAY RELY ON IT", "DON'T COUNT ON IT"),
+    ("OUTLOOK GOOD", "OUTLOOK NOT SO GOOD"),
+];
+
+const DEVOPS: &[(&str, Sentiment)] = &[
+    ("SHIP IT", Positive),
+    ("LGTM", Positive),
+    ("FEATURE FLAG IT", Neutral),
+    ("NEEDS MORE TESTS", Neutral),
+    ("WAIT FOR MONDAY", Neutral),
+    ("WORKS ON MY MACHINE", Neutral),
+    ("ROLL BACK", Negative),
+    ("BLOCKED", Negative),
+    ("PAGE ON-CALL", Negative),
+];
+
+const DEVOPS_OPPOSITES: &[(&str, &str)] = &[("SHIP IT", "ROLL BACK"), ("LGTM", "BLOCKED")];
+
+const LUNCH: &[(&str, Sentiment)] = &[
+    ("PIZZA", Neutral),
+    ("TACOS", Neutral),
+    ("SUSHI", Neutral),
+    ("BURGERS", Neutral),
+    ("RAMEN", Neutral),
+    ("SALAD", Neutral),
+    ("THAI", Neutral),
+    ("LEFTOVERS", Neutral),
+];
+
+impl AnswerPack {
+    /// Pick the index of a random answer, honouring weights.
+    pub fn draw_index(&self) -> usize {
+        draw_weighted(&self.weights())
+    }
+
+    /// Like [`AnswerPack::draw_index`], with the weights skewed by
+    /// `optimism`: above 0, positive answers count `1 + optimism` times;
+    /// below, negative ones do.
+    pub fn draw_index_biased(&self, optimism: i32) -> usize {
+        let boosted = match optimism {
+            0 => return self.draw_index(),
+            1.. => Positive,
+            _ => Negative,
+        };
+        let factor = 1 + optimism.unsigned_abs();
+        let weights: Vec<u32> = self
+            .answers
+            .iter()
+            .map(|answer| match answer.sentiment {
+                sentiment if sentiment == boosted => answer.weight.saturating_mul(factor),
+                _ => answer.weight,
+            })
+            .collect();
+        draw_weighted(&weights)
+    }
+
+    /// Pick the index of a random answer, every answer equally likely.
+    pub fn draw_index_unweighted(&self) -> usize {
+        rand::thread_rng().gen_range(0..self.answers.len())
+    }
+
+    /// The answer that argues against the one at `index`, if the pack names
+    /// one.
+    pub fn opposite(&self, index: usize) -> Option<&Answer> {
+        let opposite = self.answers.get(index)?.opposite.as_deref()?;
+        self.answers.iter().find(|answer| answer.text == opposite)
+    }
+
+    pub fn weights(&self) -> Vec<u32> {
+        self.answers.iter().map(|answer| answer.weight).collect()
+    }
+
+    /// The answer that `ticket` (any number) falls on when every answer owns
+    /// as many consecutive tickets as its weight. Used for sealed draws.
+    pub fn index_for(&self, ticket: u64) -> usize {
+        index_for(&self.weights(), ticket)
+    }
+
+    /// Apply weights by answer text, as stored in the config. Answers not
+    /// listed keep weight 1; a table that would leave nothing drawable is
+    /// ignored.
+    pub fn apply_weights(&mut self, weights: &BTreeMap<String, u32>) {
+        let weight_of = |answer: &Answer| weights.get(&answer.text).copied().unwrap_or(1);
+        if self.answers.iter().all(|answer| weight_of(answer) == 0) {
+            return;
+        }
+        for answer in &mut self.answers {
+            answer.weight = weight_of(answer);
+        }
+    }
+
+    /// A built-in pack; each pair in `opposites` argues against each other.
+    fn from_table(
+        id: &str,
+        title: &str,
+        description: &str,
+        table: &[(&str, Sentiment)],
+        opposites: &[(&str, &str)],
+    ) -> Self {
+        let opposite = |text: &str| {
+            opposites.iter().find_map(|&(a, b)| match text {
+                _ if text == a => Some(b.to_string()),
+                _ if text == b => Some(a.to_string()),
+                _ => None,
+            })
+        };
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            answers: table
+                .iter()
+                .map(|&(text, sentiment)| Answer {
+                    text: text.to_string(),
+                    sentiment,
+                    weight: 1,
+                    style: AnswerStyle::default(),
+                    icon: None,
+                    opposite: opposite(text),
+                    flavor: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A pack as written in a contributed `.toml` file, or shared as an
+/// `.edmpack` (the same format):
+///
+/// ```toml
+/// id = "standup"
+/// title = "Standup"
+/// description = "Answers for the daily standup."
+/// author = "Platform team"   # optional
+///
+/// [[answers]]
+/// text = "SHIP IT"
+/// sentiment = "positive"
+/// translations = { de = "AUSLIEFERN" }
+/// weight = 2          # optional, defaults to 1
+/// color = "#2e7d32"   # optional, the lit button's background
+/// idle_color = "green" # optional, the unlit button's text
+/// icon = "🚀"          # optional, shown before the text
+/// opposite = "ROLL BACK" # optional, shown by the devil's advocate
+/// flavor = ["Aye, full speed ahead, cap'n."] # optional, one shown per reveal
+/// ```
+///
+/// Files in `packs/` next to the config are installed alongside the
+/// built-in packs; one with a built-in pack's id replaces it.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PackFile {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    pub answers: Vec<AnswerEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnswerEntry {
+    pub text: String,
+    pub sentiment: Sentiment,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// A colour name or `#rrggbb`: the lit button's background.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Like `color`: the unlit button's text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Another answer's text: the one that argues against this one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opposite: Option<String>,
+    /// One-liners in the pack's voice; a random one is shown under the
+    /// answer.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flavor: Vec<String>,
+    /// Locale code to translated text.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, String>,
+}
+
+impl AnswerEntry {
+    /// See [`Answer::label`].
+    pub fn label(&self) -> String {
+        label(self.icon.as_deref(), &self.text)
+    }
+}
+
+impl PackFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(|source| EdmError::PackRead {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&text, path)
+    }
+
+    /// Parse `text`, read from `origin` (a file or a URL).
+    pub fn parse(text: &str, origin: &Path) -> Result<Self> {
+        toml::from_str(text).map_err(|source| EdmError::PackParse {
+            path: origin.to_path_buf(),
+            source: Box::new(source),
+        })
+    }
+
+    /// Write the pack to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        tracing::debug!(path = %path.display(), "saving pack");
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    /// `pack` as a file, keeping the author of `previous` and the
+    /// translations it has for answers whose text is unchanged.
+    pub fn from_pack(pack: &AnswerPack, previous: Option<&PackFile>) -> Self {
+        let translations = |text: &str| {
+            previous
+                .and_then(|file| file.answers.iter().find(|entry| entry.text == text))
+                .map(|entry| entry.translations.clone())
+                .unwrap_or_default()
+        };
+        Self {
+            id: pack.id.clone(),
+            title: pack.title.clone(),
+            description: pack.description.clone(),
+            author: previous.and_then(|file| file.author.clone()),
+            answers: pack
+                .answers
+                .iter()
+                .map(|answer| AnswerEntry {
+                    text: answer.text.clone(),
+                    sentiment: answer.sentiment,
+                    weight: answer.weight,
+                    color: answer.style.active_bg.map(|color| color.to_string()),
+                    idle_color: answer.style.fg.map(|color| color.to_string()),
+                    icon: answer.icon.clone(),
+                    opposite: answer.opposite.clone(),
+                    flavor: answer.flavor.clone(),
+                    translations: translations(&answer.text),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_pack(&self) -> AnswerPack {
+        AnswerPack {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            description: self.description.clone(),
+            answers: self
+                .answers
+                .iter()
+                .map(|entry| Answer {
+                    text: entry.text.clone(),
+                    sentiment: entry.sentiment,
+                    weight: entry.weight,
+                    // Unknown colours are reported by `edm pack test`.
+                    style: AnswerStyle {
+                        fg: parse_color(entry.idle_color.as_deref()),
+                        active_bg: parse_color(entry.color.as_deref()),
+                        ..AnswerStyle::default()
+                    },
+                    icon: entry.icon.clone(),
+                    opposite: entry.opposite.clone(),
+                    flavor: entry.flavor.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn parse_color(color: Option<&str>) -> Option<Color> {
+    color.and_then(|color| Color::from_str(color).ok())
+}
+
+/// A random index into `weights`, each chosen in proportion to its weight.
+/// If every weight is 0 they all count as equal; an empty slice gives 0.
+pub fn draw_weighted(weights: &[u32]) -> usize {
+    let total = total_weight(weights);
+    if total == 0 {
+        return rand::thread_rng().gen_range(0..weights.len().max(1));
+    }
+    index_for(weights, rand::thread_rng().gen_range(0..total))
+}
+
+fn total_weight(weights: &[u32]) -> u64 {
+    weights.iter().map(|&weight| u64::from(weight)).sum()
+}
+
+fn index_for(weights: &[u32], ticket: u64) -> usize {
+    let total = total_weight(weights);
+    if total == 0 {
+        return (ticket % weights.len().max(1) as u64) as usize;
+    }
+    let mut ticket = ticket % total;
+    for (index, &weight) in weights.iter().enumerate() {
+        if ticket < u64::from(weight) {
+            return index;
+        }
+        ticket -= u64::from(weight);
+    }
+    unreachable!("ticket is below the total weight")
+}
+
+/// Each answer's chance of being drawn, in percent.
+pub fn probabilities(weights: &[u32]) -> Vec<f64> {
+    let total = total_weight(weights).max(1) as f64;
+    weights
+        .iter()
+        .map(|&weight| f64::from(weight) * 100.0 / total)
+        .collect()
+}
+
+/// All packs that ship with the app, classic first.
+pub fn builtin_packs() -> Vec<AnswerPack> {
+    vec![
+        AnswerPack::from_table(
+            DEFAULT_PACK,
+            "Classic",
+            "The six lights of the original Radio Shack device.",
+            CLASSIC,
+            CLASSIC_OPPOSITES,
+        ),
+        AnswerPack::from_table(
+            "8-ball",
+            "Magic 8-Ball",
+            "Twenty answers from the famous fortune-telling billiard ball.",
+            EIGHT_BALL,
+            EIGHT_BALL_OPPOSITES,
+        ),
+        AnswerPack::from_table(
+            "devops",
+            "DevOps Slang",
+            "Release-day wisdom for deploys, rollbacks, and pager duty.",
+            DEVOPS,
+            DEVOPS_OPPOSITES,
+        ),
+        AnswerPack::from_table(
+            "lunch",
+            "Lunch Spots",
+            "Settle the daily where-do-we-eat debate.",
+            LUNCH,
+            &[],
+        ),
+    ]
+}
+
+/// Look up a built-in pack by its id.
+pub fn builtin(id: &str) -> Option<AnswerPack> {
+    builtin_packs().into_iter().find(|pack| pack.id == id)
+}
+
+/// Every `*.toml` file in `dir`, sorted by name.
+fn pack_paths(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Every pack file in `dirs`, in the order they are installed: later ones
+/// replace earlier ones with the same id.
+pub fn pack_files(dirs: &[PathBuf]) -> Vec<(PathBuf, Result<PackFile>)> {
+    dirs.iter()
+        .flat_map(|dir| pack_paths(dir))
+        .map(|path| {
+            let file = PackFile::load(&path);
+            (path, file)
+        })
+        .collect()
+}
+
+/// Built-in packs followed by every readable pack file in `dirs`. Broken
+/// files, and ones failing [`pack_check::structure`], are skipped and
+/// described in the returned error list.
+pub fn installed_packs(dirs: &[PathBuf]) -> (Vec<AnswerPack>, Vec<String>) {
+    let mut packs = builtin_packs();
+    let mut errors = Vec::new();
+    for (path, file) in pack_files(dirs) {
+        match file {
+            Ok(file) => {
+                let failures: Vec<String> = pack_check::structure(&file)
+                    .into_iter()
+                    .filter(|finding| finding.level == Level::Fail)
+                    .map(|finding| finding.message)
+                    .collect();
+                if !failures.is_empty() {
+                    errors.push(format!(
+                        "invalid pack {}: {}",
+                        path.display(),
+                        failures.join("; ")
+                    ));
+                    continue;
+                }
+                // A user pack may override a built-in one of the same id.
+                packs.retain(|existing| existing.id != file.id);
+                packs.push(file.to_pack());
+            }
+            Err(err) => errors.push(err.to_string()),
+        }
+    }
+    (packs, errors)
+}
+
+/// Look up an installed pack by its id.
+pub fn installed(id: &str, dirs: &[PathBuf]) -> Option<AnswerPack> {
+    installed_packs(dirs)
+        .0
+        .into_iter()
+        .find(|pack| pack.id == id)
+}
+
+/// The file in `dirs` the pack `id` is installed from, and its contents.
+pub fn find_pack_file(dirs: &[PathBuf], id: &str) -> Option<(PathBuf, PackFile)> {
+    pack_files(dirs).into_iter().rev().find_map(|(path, file)| {
+        file.ok()
+            .filter(|file| file.id == id)
+            .map(|file| (path, file))
+    })
+}
+
+/// Where to write the pack `id` in `dir`: the file already holding it, or a
+/// new `<id>.toml`.
+pub fn pack_file(dir: &Path, id: &str) -> PathBuf {
+    find_pack_file(&[dir.to_path_buf()], id)
+        .map(|(path, _)| path)
+        .unwrap_or_else(|| dir.join(format!("{id}.toml")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_for_walks_the_weights() {
+        let weights = [1, 0, 2];
+        let picks: Vec<usize> = (0..4).map(|ticket| index_for(&weights, ticket)).collect();
+        assert_eq!(picks, [0, 2, 2, 0]);
+    }
+
+    #[test]
+    fn zero_weights_draw_evenly_instead_of_panicking() {
+        assert_eq!(index_for(&[0, 0, 0], 4), 1);
+        assert!(draw_weighted(&[0, 0]) < 2);
+        assert_eq!(draw_weighted(&[]), 0);
+    }
+
+    #[test]
+    fn probabilities_survive_large_weights() {
+        let percent = probabilities(&[u32::MAX, u32::MAX]);
+        assert_eq!(percent, [50.0, 50.0]);
+    }
+}
@@ -0,0 +1,2419 @@
+//! The application state machine: what is on screen, what the oracle is
+//! doing, and how each [`Action`] changes it. Front ends drive an [`App`]
+//! through [`crate::frontend::run`] and draw it with [`crate::ui::draw`].
+
+use crate::{
+    answers::{self, Answer, AnswerPack, PackFile},
+    best_of::{self, BestOf},
+    bracket::Bracket,
+    clipboard::Clipboard,
+    clock::Clock,
+    command_palette::{Command, CommandPalette},
+    config::{Config, Sound, MAX_OPTIMISM},
+    demo::Demo,
+    elimination::{self, Elimination},
+    engine::Engine,
+    events::Event,
+    experiments,
+    gallery::Gallery,
+    history_view::HistoryView,
+    input::{Action, Mode},
+    keymap::{Chord, Keymap},
+    locale::Strings,
+    matrix::{self, Matrix, MatrixEditor, Target},
+    notify,
+    opinion::SecondOpinion,
+    pack_editor::PackEditor,
+    palette::ColorDepth,
+    paths,
+    profile::ProfilePicker,
+    qr,
+    rationale::{self, Rationale},
+    recall::{self, Recall},
+    screen::{self, Screen},
+    screenshot,
+    seal::Draw,
+    settings::{self, Recorded, Settings},
+    streaks,
+    suggest::{self, Suggestion},
+    tags,
+    text_area::TextArea,
+    theme::{self, AnswerStyle, Theme, ThemePreview},
+    transliteration::Scheme,
+    weights::WeightEditor,
+    widget::{DecisionState, Shuffle, State, Step},
+    worksheet::Worksheet,
+    workspace,
+};
+use chrono::{DateTime, Local};
+use ratatui::buffer::Buffer;
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+pub const TICK_RATE_MS: u64 = 50;
+/// Validity periods offered with Tab while typing a question, in days.
+pub const VALIDITY_DAYS: [u32; 4] = [1, 7, 30, 90];
+/// Timeboxes offered with Ctrl+T while typing a question, in seconds.
+pub const TIMEBOX_SECS: [u32; 4] = [30, 60, 120, 300];
+const NOTICE_MS: u64 = 2_000;
+const BANNER_MS: u64 = 4_000;
+const STREAK_MS: u64 = 4_000;
+/// Frame interval of the streak celebration's sparkles.
+const SPARKLE_FRAME_MS: u64 = 200;
+/// Frame interval while the lit button's glow fades.
+const GLOW_FRAME_MS: u64 = 33;
+/// Frame interval while the suspense bar fills.
+const CHARGE_FRAME_MS: u64 = 50;
+
+/// Timings of the last frame, measured by [`crate::frontend::run`] for the
+/// F12 debug overlay.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FrameStats {
+    /// Ticking plus drawing the frame.
+    pub render: Duration,
+    /// How much later than its deadline this tick came.
+    pub tick_latency: Duration,
+    /// Input waiting in the front end, if it can tell.
+    pub queued_input: Option<usize>,
+}
+
+pub struct App {
+    /// What fills the terminal, with its state.
+    pub screen: Screen,
+    /// The answer board's lights.
+    pub decision: DecisionState,
+    pub engine: Engine,
+    pub theme: Theme,
+    /// From the config's `[styles]`, laid over every theme in use.
+    answer_styles: BTreeMap<String, AnswerStyle>,
+    /// What the terminal can show; frames are brought down to it.
+    pub color_depth: ColorDepth,
+    /// Interface strings in the user's language.
+    pub strings: Strings,
+    /// Open while cycling through themes with `T`.
+    pub theme_preview: Option<ThemePreview>,
+    /// Latin lines under answers in another script.
+    pub transliteration: Option<Scheme>,
+    /// The matrix last edited, reopened by the next `m`.
+    last_matrix: Option<Matrix>,
+    /// While the board shows a matrix's options, the pack it goes back to.
+    pub matrix_return: Option<AnswerPack>,
+    /// Open while picking an action by name.
+    pub palette: Option<CommandPalette>,
+    /// While answers are being knocked out one by one.
+    pub elimination: Option<Elimination>,
+    /// The bracket last closed, reopened by the next `b`.
+    last_bracket: Option<Bracket>,
+    /// Where installed packs are found; see [`paths::pack_dirs`].
+    pub pack_dirs: Vec<PathBuf>,
+    /// The profile in use.
+    pub profile: String,
+    /// Profiles offered by the picker; see [`paths::profiles`].
+    pub profiles: Vec<String>,
+    /// Set when the app quit to start over on another profile.
+    pub switch_profile: Option<String>,
+    pub keymap: Keymap,
+    /// Set while the screen is locked: the passphrase typed so far.
+    pub lock: Option<String>,
+    /// An earlier answer to nearly the same question, offered before asking.
+    pub recall: Option<Recall>,
+    /// Second opinion on the last answer.
+    pub second_opinion: Option<SecondOpinion>,
+    /// The F12 debug overlay is shown.
+    pub debug_visible: bool,
+    pub frame_stats: FrameStats,
+    /// The question for the current (or last) decision.
+    pub question: String,
+    /// Tags for the current (or last) decision, typed into the question as
+    /// `#tag`.
+    pub tags: Vec<String>,
+    /// Only decisions with this tag count in the recent list and tally.
+    pub tag_filter: Option<String>,
+    /// Open while retagging the decision with the given key: the tags
+    /// typed so far.
+    pub tagging: Option<(String, String)>,
+    /// Keystrokes go into `question` instead of triggering actions.
+    pub typing: bool,
+    /// Earlier questions like the one being typed.
+    pub suggestions: Vec<Suggestion>,
+    /// The suggestion picked with the arrows, if any.
+    pub suggestion: Option<usize>,
+    /// Days the next decision stays valid before it needs re-validation.
+    pub valid_for: Option<u32>,
+    /// Seconds the next answer waits for Enter before it commits itself.
+    pub timebox: Option<u32>,
+    /// An answer that is not in the history yet, and when its timebox runs
+    /// out.
+    pub pending: Option<(usize, Instant)>,
+    pub last_answer: Option<usize>,
+    /// The model's justification of `last_answer` (`llm` feature).
+    pub rationale: Option<rationale::Status>,
+    /// Asks the model for rationales, with an `llm_api_key` configured.
+    llm: Option<Rationale>,
+    /// Id of the latest rationale request; older replies are dropped.
+    rationale_id: u64,
+    /// The pack's one-liner picked for `last_answer`, if it has any.
+    pub flavor: Option<String>,
+    /// How confident the oracle claimed to be in `last_answer`.
+    pub confidence: Option<u8>,
+    /// Asking canned questions on a schedule (`--demo`).
+    pub demo: Option<Demo>,
+    /// The rounds of the current (or last) best-of-N ask.
+    pub best_of: Option<BestOf>,
+    /// Key of the decision behind `last_answer`, for its note, tags,
+    /// veto, and rating.
+    decision_id: Option<String>,
+    /// Open while writing a note on the decision with the given key.
+    pub note: Option<(String, TextArea)>,
+    /// The last answer was sealed for the day rather than random.
+    pub sealed: bool,
+    /// Whether the terminal window currently has focus.
+    focused: bool,
+    /// Short-lived footer message, e.g. "Copied to clipboard".
+    pub notice: Option<(String, Instant)>,
+    /// Banner (workspace summary at launch, or a committed answer): its
+    /// title, its lines, and when it goes away.
+    pub banner: Option<(&'static str, Vec<String>, Instant)>,
+    /// Streak easter egg being celebrated, and when it appeared.
+    pub streak: Option<(String, Instant)>,
+    /// QR code of the last decision on show, as rows of half blocks.
+    pub qr: Option<Vec<String>>,
+    /// Set by `S` until the front end hands over the next frame drawn.
+    pub screenshot_requested: bool,
+    pub clipboard: Clipboard,
+    /// Receives every [`Event`] as a JSON line (`--event-log`).
+    pub event_log: Option<Box<dyn Write>>,
+    /// Something on screen changed since the last render.
+    dirty: bool,
+    /// Bytes for the user's terminal (bell, OSC 9, OSC 52) that the frontend writes
+    /// out after the next render.
+    output: Vec<u8>,
+    pub config: Config,
+    config_path: Option<PathBuf>,
+    clock: Box<dyn Clock>,
+}
+
+impl App {
+    pub fn new(
+        engine: Engine,
+        theme: Theme,
+        config: Config,
+        config_path: Option<PathBuf>,
+        clock: Box<dyn Clock>,
+    ) -> Self {
+        let (keymap, key_errors) = Keymap::from_config(&config.keys);
+        let (answer_styles, style_errors) = theme::parse_styles(&config.styles);
+        let llm = Rationale::from_config(&config, Arc::clone(engine.wake()));
+        let mut app = Self {
+            screen: Screen::Oracle,
+            decision: DecisionState::default(),
+            engine,
+            theme: theme.with_styles(&answer_styles),
+            answer_styles,
+            color_depth: ColorDepth::default(),
+            strings: Strings::default(),
+            theme_preview: None,
+            transliteration: None,
+            last_matrix: None,
+            matrix_return: None,
+            palette: None,
+            elimination: None,
+            last_bracket: None,
+            pack_dirs: paths::pack_dirs(config_path.as_deref(), None),
+            profile: paths::DEFAULT_PROFILE.to_string(),
+            profiles: Vec::new(),
+            switch_profile: None,
+            keymap,
+            lock: None,
+            recall: None,
+            second_opinion: None,
+            debug_visible: false,
+            frame_stats: FrameStats::default(),
+            question: String::new(),
+            tags: Vec::new(),
+            tag_filter: None,
+            tagging: None,
+            typing: false,
+            suggestions: Vec::new(),
+            suggestion: None,
+            valid_for: None,
+            timebox: config.timebox(),
+            pending: None,
+            last_answer: None,
+            best_of: None,
+            demo: None,
+            rationale: None,
+            llm,
+            rationale_id: 0,
+            flavor: None,
+            confidence: None,
+            decision_id: None,
+            note: None,
+            sealed: false,
+            focused: true,
+            notice: None,
+            banner: None,
+            qr: None,
+            screenshot_requested: false,
+            streak: None,
+            clipboard: Clipboard::new(),
+            event_log: None,
+            dirty: true,
+            output: Vec::new(),
+            config,
+            config_path,
+            clock,
+        };
+        if !key_errors.is_empty() {
+            app.show_notice(format!("Ignored key bindings: {}", key_errors.join("; ")));
+        }
+        if !style_errors.is_empty() {
+            app.show_notice(format!(
+                "Ignored answer styles: {}",
+                style_errors.join("; ")
+            ));
+        }
+        app
+    }
+
+    /// Whether a screen other than the board is shown.
+    fn screen_open(&self) -> bool {
+        !matches!(self.screen, Screen::Oracle)
+    }
+
+    /// Close the tab bar screen shown and open `tab` instead. Does nothing
+    /// while the tab bar is covered or a theme is previewed.
+    fn show_tab(&mut self, tab: screen::Tab) {
+        let Some(shown) = self.screen.tab() else {
+            return;
+        };
+        if shown == tab || self.theme_preview.is_some() {
+            return;
+        }
+        tracing::debug!(?shown, ?tab, "switching screen");
+        self.close_screen();
+        match tab {
+            screen::Tab::Oracle => {}
+            screen::Tab::History => {
+                self.screen = Screen::History(HistoryView::new(&self.engine.history));
+            }
+            screen::Tab::Stats => self.screen = Screen::Stats,
+            screen::Tab::Settings => self.open_settings(),
+            screen::Tab::Help => self.screen = Screen::Help { scroll: 0 },
+        }
+    }
+
+    /// Go back to the board. The settings put back the theme they were
+    /// opened with; the bracket and the matrix are kept for the next visit.
+    fn close_screen(&mut self) {
+        match std::mem::replace(&mut self.screen, Screen::Oracle) {
+            Screen::Settings(settings) => self.theme = settings.saved_theme,
+            Screen::Bracket(bracket) => self.last_bracket = Some(bracket),
+            Screen::Matrix(editor) => self.last_matrix = Some(editor.matrix),
+            _ => {}
+        }
+    }
+
+    /// How the front end should read the next key press.
+    pub fn input_mode(&self) -> Mode {
+        if self.lock.is_some()
+            || self.recall.is_some()
+            || self.tagging.is_some()
+            || self.palette.is_some()
+        {
+            return Mode::Typing;
+        }
+        if self.note.is_some() {
+            return Mode::Note;
+        }
+        // Whether an editor is taking text, for the screens that have one.
+        let entry = match &self.screen {
+            Screen::Settings(settings) if settings.recording => return Mode::Recording,
+            Screen::History(view) if view.searching => return Mode::Typing,
+            Screen::PackEditor(editor) => Some(editor.entry.is_some()),
+            Screen::Bracket(bracket) => Some(bracket.entry.is_some()),
+            Screen::Matrix(editor) => Some(editor.entry.is_some()),
+            Screen::Worksheet(sheet) => Some(sheet.entry.is_some()),
+            _ => None,
+        };
+        match entry {
+            Some(true) => Mode::Typing,
+            Some(false) => Mode::Editing,
+            None if self.typing => Mode::Typing,
+            None => Mode::Commands,
+        }
+    }
+
+    pub fn answer_text(&self, index: usize) -> &str {
+        &self.engine.pack.answers[index].text
+    }
+
+    /// The question as asked, if one was typed.
+    pub fn question(&self) -> Option<&str> {
+        Some(self.question.trim()).filter(|question| !question.is_empty())
+    }
+
+    /// Move `#tags` typed into the question over to [`App::tags`].
+    fn take_tags(&mut self) {
+        let (question, tags) = tags::split(&self.question);
+        if !tags.is_empty() {
+            self.question = question;
+            self.tags = tags;
+        }
+    }
+
+    /// Look for earlier questions like the one typed so far.
+    fn refresh_suggestions(&mut self) {
+        self.suggestions = suggest::suggestions(&self.engine.history, &self.question);
+        self.suggestion = None;
+    }
+
+    /// Move the pick through the suggestions; up from the first drops it.
+    fn pick_suggestion(&mut self, delta: isize) {
+        let last = self.suggestions.len().checked_sub(1);
+        self.suggestion = match (self.suggestion, last) {
+            (_, None) => None,
+            (None, Some(_)) if delta > 0 => Some(0),
+            (None, Some(_)) => None,
+            (Some(0), Some(_)) if delta < 0 => None,
+            (Some(index), Some(last)) => Some(index.saturating_add_signed(delta).min(last)),
+        };
+    }
+
+    /// Put the picked suggestion in the question, keeping any typed tags.
+    fn accept_suggestion(&mut self, index: usize) {
+        let (_, typed_tags) = tags::split(&self.question);
+        self.question = self.suggestions[index].question.clone();
+        if !typed_tags.is_empty() {
+            self.question = format!("{} {}", self.question, tags::format(&typed_tags));
+        }
+        self.suggestions.clear();
+        self.suggestion = None;
+    }
+
+    /// Ask, unless nearly the same question was asked recently: then offer
+    /// that answer first, or in commitment mode show it again outright.
+    fn ask_or_recall(&mut self) {
+        self.suggestions.clear();
+        self.suggestion = None;
+        self.take_tags();
+        let now = self.clock.local_now();
+        let committed = self
+            .config
+            .commitment_hours
+            .zip(self.question())
+            .filter(|_| self.engine.rules().recall)
+            .and_then(|(hours, question)| {
+                recall::committed(&self.engine.history, question, hours, now)
+            });
+        if let Some(recall) = committed {
+            tracing::info!(decided_at = %recall.decided_at, "committed answer shown");
+            let when = workspace::time_ago(recall.decided_at, now);
+            let lines = vec![
+                format!("“{}”", recall.question),
+                format!("{} · {when}", recall.answer),
+                "The oracle stands by it.".to_string(),
+            ];
+            self.reuse(recall);
+            self.show_banner(" You already asked ", lines);
+            return;
+        }
+        let recall = self
+            .question()
+            .filter(|_| self.engine.rules().recall)
+            .and_then(|question| recall::find(&self.engine.history, question, now));
+        match recall {
+            Some(recall) => {
+                self.typing = false;
+                self.recall = Some(recall);
+            }
+            None => self.ask(),
+        }
+    }
+
+    /// Re-ask the oldest expired decision with the same validity period.
+    fn revalidate(&mut self) {
+        let now = self.clock.local_now();
+        let Some(next) = self.engine.history.needs_revalidation(now).first().copied() else {
+            self.show_notice("Nothing needs re-validation.");
+            return;
+        };
+        let days = next
+            .expires_at
+            .map(|expires_at| (expires_at - next.decided_at).num_days().max(1));
+        self.question = next.question.clone().unwrap_or_default();
+        self.tags = next.tags.clone();
+        self.valid_for = days.and_then(|days| u32::try_from(days).ok());
+        self.ask();
+    }
+
+    /// The devil's advocate: the opposite of the answer just given, while
+    /// it is on show.
+    pub fn devils_advocate(&self) -> Option<&Answer> {
+        if !self.config.devils_advocate || self.decision.is_animating() {
+            return None;
+        }
+        self.engine.pack.opposite(self.last_answer?)
+    }
+
+    /// The local time by the app's clock.
+    pub fn local_now(&self) -> DateTime<Local> {
+        self.clock.local_now()
+    }
+
+    /// Vetoes left today; `None` with vetoes turned off.
+    pub fn vetoes_left(&self) -> Option<usize> {
+        let budget = usize::try_from(self.config.veto_budget()).unwrap_or(usize::MAX);
+        (budget > 0).then(|| {
+            budget.saturating_sub(self.engine.history.vetoes_today(self.clock.local_now()))
+        })
+    }
+
+    /// Throw out the last answer, on the record, and ask the same question
+    /// again. A sealed answer would only come back, so it can't be vetoed.
+    fn veto(&mut self) {
+        // Nothing is on the record yet, so there is nothing to veto.
+        if self.pending.is_some() {
+            self.ask();
+            return;
+        }
+        let Some(id) = self
+            .decision_id
+            .clone()
+            .filter(|_| self.last_answer.is_some())
+        else {
+            return;
+        };
+        let Some(left) = self.vetoes_left() else {
+            self.show_notice("Vetoes are off; set veto_budget to allow some.");
+            return;
+        };
+        if self.sealed {
+            self.show_notice("A sealed answer can't be vetoed.");
+        } else if left == 0 {
+            self.show_notice("No vetoes left today. The oracle has spoken.");
+        } else if self.engine.history.veto(&id, self.clock.local_now()) {
+            tracing::info!(left = left - 1, "answer vetoed");
+            self.decision_id = None;
+            self.ask();
+        }
+    }
+
+    /// Let the oracle weigh the worksheet: one round, landing on an answer
+    /// whose sentiment follows the scores.
+    fn weigh_worksheet(&mut self) {
+        let Screen::Worksheet(sheet) = &self.screen else {
+            return;
+        };
+        if sheet.pros.is_empty() && sheet.cons.is_empty() {
+            self.show_notice("Add a pro or a con first (a).");
+            return;
+        }
+        let (pros, cons) = sheet.totals();
+        tracing::info!(pros, cons, "worksheet weighed");
+        let index = sheet.verdict(&self.engine.pack);
+        self.screen = Screen::Oracle;
+        self.show_notice(format!("Pros {pros}, cons {cons}: the oracle weighs in."));
+        self.start_ask(
+            Draw {
+                index,
+                sealed: false,
+            },
+            1,
+        );
+    }
+
+    /// Open the matrix edited last, or a new one titled after the question.
+    fn open_matrix(&mut self) {
+        let matrix = self
+            .last_matrix
+            .take()
+            .unwrap_or_else(|| Matrix::new(self.question().unwrap_or("Untitled")));
+        self.screen = Screen::Matrix(MatrixEditor::new(matrix));
+    }
+
+    /// Put the matrix's options on the board and shuffle over them, biased
+    /// by their totals. Esc on the board goes back to the pack from before.
+    fn run_matrix(&mut self) {
+        let Screen::Matrix(editor) = &self.screen else {
+            return;
+        };
+        if editor.matrix.options.len() < matrix::MIN_OPTIONS {
+            self.show_notice(format!(
+                "Add at least {} options first (o).",
+                matrix::MIN_OPTIONS
+            ));
+            return;
+        }
+        let pack = editor.matrix.to_pack();
+        self.close_screen();
+        self.accept();
+        tracing::info!(pack = %pack.id, options = pack.answers.len(), "matrix run");
+        let previous = std::mem::replace(&mut self.engine.pack, pack);
+        if self.matrix_return.is_none() {
+            self.matrix_return = Some(previous);
+        }
+        self.decision.reset();
+        self.last_answer = None;
+        self.second_opinion = None;
+        let index = self.engine.pack.draw_index();
+        self.start_ask(
+            Draw {
+                index,
+                sealed: false,
+            },
+            1,
+        );
+    }
+
+    /// Put the pack from before the matrix back on the board.
+    fn leave_matrix(&mut self) {
+        let Some(pack) = self.matrix_return.take() else {
+            return;
+        };
+        self.accept();
+        self.engine.pack = pack;
+        self.decision.reset();
+        self.last_answer = None;
+        self.second_opinion = None;
+        self.show_notice(format!("Back to {}.", self.engine.pack.title));
+    }
+
+    /// Save the matrix being edited to `matrices/` next to the config.
+    fn save_matrix(&mut self) {
+        let Some(dir) = self.matrices_dir() else {
+            self.show_notice("No config directory to save matrices in.");
+            return;
+        };
+        let Screen::Matrix(editor) = &mut self.screen else {
+            return;
+        };
+        match editor.save(&dir) {
+            Ok(path) => self.show_notice(format!("Saved to {}.", path.display())),
+            Err(err) => {
+                tracing::warn!(%err, "could not save matrix");
+                self.show_notice(format!("Could not save matrix: {err}"));
+            }
+        }
+    }
+
+    /// Load the next saved matrix into the editor.
+    fn load_matrix(&mut self) {
+        let Some(dir) = self.matrices_dir() else {
+            self.show_notice("No config directory to load matrices from.");
+            return;
+        };
+        let Screen::Matrix(editor) = &mut self.screen else {
+            return;
+        };
+        match editor.load_next(&dir) {
+            Ok(Some(_)) => {
+                let title = editor.matrix.title.clone();
+                self.show_notice(format!("Loaded {title}."));
+            }
+            Ok(None) => self.show_notice("No saved matrices yet (s saves)."),
+            Err(err) => {
+                tracing::warn!(%err, "could not load matrix");
+                self.show_notice(format!("Could not load matrix: {err}"));
+            }
+        }
+    }
+
+    /// Knock the answers out one shuffle at a time; the last one left is
+    /// the verdict.
+    fn start_elimination(&mut self) {
+        if self.engine.pack.answers.len() < 2 {
+            return;
+        }
+        self.take_tags();
+        self.decision.enable_all();
+        let draw = Draw {
+            index: elimination::victim(&self.engine.pack, &self.decision),
+            sealed: false,
+        };
+        self.start_ask(draw, 1);
+        self.elimination = Some(Elimination::default());
+        tracing::info!(
+            answers = self.engine.pack.answers.len(),
+            "elimination started"
+        );
+    }
+
+    /// The lights landed on `index`: it is out once its flash ends.
+    fn knock_out(&mut self, index: usize) {
+        let answer = self.answer_text(index).to_string();
+        tracing::debug!(%answer, "knocked out");
+        self.beep(Step::Landed(index));
+        self.show_notice(format!("{answer} is out."));
+        if let Some(elimination) = self.elimination.as_mut() {
+            elimination.landed = Some(index);
+        }
+    }
+
+    /// Grey out the answer that just went, then pause before the next
+    /// round, or reveal the last one standing.
+    fn after_knock_out(&mut self, now: Instant) {
+        let Some(elimination) = self.elimination.as_mut() else {
+            return;
+        };
+        if let Some(index) = elimination.landed {
+            self.decision.disable(index);
+        }
+        let left = elimination::remaining(&self.engine.pack, &self.decision);
+        if let [survivor] = left[..] {
+            self.elimination = None;
+            self.decision
+                .show(survivor, self.engine.pack.answers.len(), now);
+            self.reveal(survivor, now);
+        } else {
+            elimination.pause(now);
+        }
+    }
+
+    /// Start the next round if the pause is over.
+    fn elimination_round(&mut self, now: Instant) {
+        let Some(elimination) = self.elimination.as_mut() else {
+            return;
+        };
+        if !elimination.due(now) {
+            return;
+        }
+        let victim = elimination::victim(&self.engine.pack, &self.decision);
+        self.decision
+            .start(victim, self.engine.pack.answers.len(), now);
+        self.beep(Step::Switched);
+        self.dirty = true;
+    }
+
+    /// Stop knocking answers out and bring them all back.
+    fn stop_elimination(&mut self) {
+        self.elimination = None;
+        self.decision.reset();
+        self.show_notice("Elimination stopped.");
+    }
+
+    /// Start the tournament, or start it over once it has a champion.
+    fn start_bracket(&mut self) {
+        let now = self.clock.now();
+        let (shuffle, flash) = (self.config.animation(), self.config.flash());
+        let Screen::Bracket(bracket) = &mut self.screen else {
+            return;
+        };
+        if bracket.running() {
+            return;
+        }
+        if let Some(problem) = bracket.problem() {
+            self.show_notice(problem);
+            return;
+        }
+        bracket.start(shuffle, flash, now);
+        self.beep(Step::Switched);
+    }
+
+    /// Record how acting on a decision worked out: the one selected on the
+    /// history screen, or else the last one on the board.
+    fn rate(&mut self, worked_out: bool) {
+        let id = match &self.screen {
+            Screen::History(view) => view
+                .matches
+                .get(view.selected)
+                .and_then(|&position| self.engine.history.recent().nth(position))
+                .map(|decision| decision.key()),
+            _ => self
+                .decision_id
+                .clone()
+                .filter(|_| self.last_answer.is_some()),
+        };
+        let Some(id) = id else {
+            return;
+        };
+        if self
+            .engine
+            .history
+            .rate(&id, worked_out, self.clock.local_now())
+        {
+            tracing::info!(worked_out, "outcome rated");
+            self.show_notice(if worked_out {
+                "Marked 👍"
+            } else {
+                "Marked 👎"
+            });
+        }
+    }
+
+    /// Nudge the optimism bias and keep it in the config, so each profile
+    /// has its own.
+    fn adjust_optimism(&mut self, delta: i32) {
+        let level = (self.config.optimism() + delta).clamp(-MAX_OPTIMISM, MAX_OPTIMISM);
+        if level == self.config.optimism() {
+            return;
+        }
+        tracing::info!(level, "optimism changed");
+        self.config.optimism = Some(level).filter(|&level| level != 0);
+        self.engine.optimism = level;
+        match self.save_config() {
+            Ok(()) => self.show_notice(format!("Optimism: {level:+}")),
+            Err(err) => self.show_notice(format!("Could not save config: {err}")),
+        }
+    }
+
+    /// Light the recalled answer again instead of asking.
+    fn reuse(&mut self, recall: Recall) {
+        self.pending = None;
+        self.typing = false;
+        self.second_opinion = None;
+        self.best_of = None;
+        self.flavor = None;
+        self.rationale = None;
+        self.confidence = None;
+        let index = self
+            .engine
+            .pack
+            .answers
+            .iter()
+            .position(|answer| answer.text == recall.answer);
+        if let Some(index) = index {
+            let now = self.clock.now();
+            self.decision
+                .show(index, self.engine.pack.answers.len(), now);
+            self.last_answer = Some(index);
+            self.decision_id = Some(recall.id.clone());
+            self.sealed = false;
+        }
+        let when = workspace::time_ago(recall.decided_at, self.clock.local_now());
+        self.show_notice(format!("Same as {when}: {}", recall.answer));
+    }
+
+    /// The duplicate-question popup: Enter reuses, `f` asks fresh, Esc
+    /// cancels. Ctrl+C still quits.
+    fn handle_recall(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => return true,
+            Action::Ask => {
+                if let Some(recall) = self.recall.take() {
+                    self.reuse(recall);
+                }
+            }
+            Action::Type('f' | 'F') => {
+                self.recall = None;
+                self.ask();
+            }
+            Action::Back => self.recall = None,
+            Action::Focus(focused) => self.set_focus(focused),
+            _ => {}
+        }
+        false
+    }
+
+    pub fn ask(&mut self) {
+        self.take_tags();
+        let draw = self.engine.draw(self.question());
+        self.start_ask(draw, self.config.best_of());
+    }
+
+    /// Run the animation towards `draw`, over `rounds` rounds if more than
+    /// one.
+    fn start_ask(&mut self, draw: Draw, rounds: usize) {
+        if let Some((index, _)) = self.pending.take() {
+            tracing::info!(answer = %self.answer_text(index), "pending answer dropped");
+        }
+        self.elimination = None;
+        self.decision.enable_all();
+        self.sealed = draw.sealed;
+        self.last_answer = None;
+        self.second_opinion = None;
+        self.flavor = None;
+        self.rationale = None;
+        self.confidence = None;
+        self.typing = false;
+        let now = self.clock.now();
+        let count = self.engine.pack.answers.len();
+        self.decision
+            .set_shuffle(if self.config.experiment(experiments::ROULETTE) {
+                Shuffle::Roulette
+            } else {
+                Shuffle::Random
+            });
+        // A sealed answer would win every round, so it gets just the one.
+        self.best_of = (rounds > 1 && !draw.sealed).then(|| BestOf::new(rounds));
+        match &self.best_of {
+            Some(best) => {
+                self.decision.set_timings(
+                    best.round_time(self.config.animation()),
+                    Duration::from_millis(best_of::ROUND_FLASH_MS),
+                );
+                self.decision.set_suspense(None);
+            }
+            None => {
+                self.decision
+                    .set_timings(self.config.animation(), self.config.flash());
+                self.decision.set_suspense(self.config.suspense());
+            }
+        }
+        self.decision.start(draw.index, count, now);
+        tracing::info!(
+            pack = %self.engine.pack.id,
+            typed = self.question().is_some(),
+            sealed = draw.sealed,
+            "ask started"
+        );
+        self.beep(Step::Switched);
+        self.report_progress(Some(0));
+        self.emit(Event::AskStarted {
+            question: self.question().map(str::to_string),
+            pack: self.engine.pack.id.clone(),
+        });
+    }
+
+    /// The verdict at `index` is in. With a timebox it waits for Enter (or
+    /// the end of the timebox) before it counts; otherwise it counts now.
+    fn reveal(&mut self, index: usize, now: Instant) {
+        self.flavor = self.engine.pack.answers[index]
+            .pick_flavor()
+            .map(str::to_string);
+        if let Some(llm) = &self.llm {
+            self.rationale_id += 1;
+            llm.request(rationale::Request {
+                id: self.rationale_id,
+                question: self.question().map(str::to_string),
+                answer: self.answer_text(index).to_string(),
+                pack: self.engine.pack.title.clone(),
+            });
+            self.rationale = Some(rationale::Status::Loading { since: now });
+        }
+        let Some(secs) = self.timebox else {
+            self.landed(index, now);
+            return;
+        };
+        tracing::info!(index, secs, "answer awaiting acceptance");
+        self.last_answer = Some(index);
+        self.decision_id = None;
+        self.pending = Some((index, now + Duration::from_secs(secs.into())));
+        self.report_progress(None);
+        self.beep(Step::Landed(index));
+    }
+
+    /// Commit the pending answer: accepted, timed out, or left behind on
+    /// the way out.
+    pub fn accept(&mut self) {
+        if let Some((index, _)) = self.pending.take() {
+            let now = self.clock.now();
+            self.landed(index, now);
+        }
+    }
+
+    /// Seconds left to accept the pending answer, rounded up.
+    pub fn countdown(&self) -> Option<u64> {
+        let (_, until) = self.pending?;
+        let left = until.saturating_duration_since(self.clock.now());
+        Some(left.as_millis().div_ceil(1000) as u64)
+    }
+
+    /// The verdict at `index` is in: record it and tell everyone.
+    fn landed(&mut self, index: usize, now: Instant) {
+        tracing::info!(index, answer = %self.answer_text(index), "answer landed");
+        self.last_answer = Some(index);
+        let question = self.question().map(str::to_string);
+        let expires_at = self
+            .valid_for
+            .map(|days| self.clock.local_now() + chrono::Duration::days(days.into()));
+        self.second_opinion = self.engine.settle(
+            index,
+            question.as_deref(),
+            expires_at,
+            &self.tags,
+            self.clock.local_now(),
+        );
+        let decision = self.engine.history.session().last();
+        self.decision_id = decision.map(|decision| decision.key());
+        self.confidence = decision.and_then(|decision| decision.confidence);
+        if !self.focused {
+            notify::answer_landed(self.answer_text(index));
+            if self.config.osc_notifications {
+                let message = format!("The oracle has spoken: {}", self.answer_text(index));
+                self.output.extend(notify::osc9(&message));
+            }
+        }
+        if let Some(message) = streaks::check(self.engine.history.recent()) {
+            tracing::info!(%message, "streak");
+            self.streak = Some((message, now));
+        }
+        let unlocked = self.engine.take_unlocked();
+        if !unlocked.is_empty() {
+            let titles: Vec<&str> = unlocked
+                .iter()
+                .map(|achievement| achievement.title)
+                .collect();
+            self.show_notice(format!("🏆 Achievement unlocked: {}", titles.join(", ")));
+        }
+        self.report_progress(None);
+        self.beep(Step::Landed(index));
+        let answer = &self.engine.pack.answers[index];
+        self.emit(Event::AnswerFinal {
+            index,
+            answer: answer.text.clone(),
+            sentiment: answer.sentiment,
+            sealed: self.sealed,
+        });
+        for achievement in unlocked {
+            self.emit(Event::from(achievement));
+        }
+    }
+
+    /// Ask a canned question every `every` from now on, for `--demo`.
+    pub fn start_demo(&mut self, every: Duration) {
+        tracing::info!(secs = every.as_secs(), "demo mode");
+        self.demo = Some(Demo::new(every, self.clock.now()));
+    }
+
+    /// The next canned question, if one is due and the board is free for it.
+    fn demo_ask(&mut self, now: Instant) {
+        let busy = self.decision.is_animating()
+            || self.screen_open()
+            || self.input_mode() != Mode::Commands;
+        let Some(demo) = self.demo.as_mut() else {
+            return;
+        };
+        if busy {
+            demo.postpone(now);
+            return;
+        }
+        let Some(question) = demo.due(now) else {
+            return;
+        };
+        self.question = question.to_string();
+        self.tags.clear();
+        self.valid_for = None;
+        self.ask();
+    }
+
+    /// Take in the model's reply to the latest rationale request.
+    fn receive_rationale(&mut self) {
+        let Some(llm) = &self.llm else {
+            return;
+        };
+        let reply = llm
+            .replies()
+            .filter(|(id, _)| *id == self.rationale_id)
+            .last();
+        if matches!(self.rationale, Some(rationale::Status::Loading { .. })) {
+            self.dirty = true;
+            if let Some((_, reply)) = reply {
+                self.rationale = Some(match reply {
+                    Ok(text) => rationale::Status::Ready(text),
+                    Err(err) => rationale::Status::Failed(err),
+                });
+            }
+        }
+    }
+
+    /// Which frame the rationale spinner is on, while the model thinks.
+    pub fn spinner_frame(&self) -> Option<usize> {
+        let Some(rationale::Status::Loading { since }) = &self.rationale else {
+            return None;
+        };
+        let elapsed = self.clock.now().saturating_duration_since(*since);
+        Some((elapsed.as_millis() / u128::from(rationale::SPINNER_FRAME_MS)) as usize)
+    }
+
+    /// Shuffle again for the next round of a best-of-N ask.
+    fn next_round(&mut self, now: Instant) {
+        let draw = self.engine.draw(self.question());
+        self.decision
+            .start(draw.index, self.engine.pack.answers.len(), now);
+        self.report_progress(Some(0));
+    }
+
+    pub fn tick(&mut self) {
+        let now = self.clock.now();
+        if matches!(self.notice, Some((_, until)) if now >= until) {
+            self.notice = None;
+            self.dirty = true;
+        }
+        if matches!(self.banner, Some((_, _, until)) if now >= until) {
+            self.banner = None;
+            self.dirty = true;
+        }
+        if let Some((_, shown_at)) = self.streak {
+            if now >= shown_at + Duration::from_millis(STREAK_MS) {
+                self.streak = None;
+            }
+            self.dirty = true;
+        }
+        self.tick_screen(now);
+        if let Some((_, until)) = self.pending {
+            if now >= until {
+                tracing::info!("timebox ran out");
+                self.accept();
+                self.show_notice("Time's up: the answer stands.");
+            }
+            self.dirty = true;
+        }
+        self.demo_ask(now);
+        self.elimination_round(now);
+        self.receive_rationale();
+        let step = self.decision.tick(now);
+        self.dirty |= step.is_some() || self.fading() || self.charging();
+        match step {
+            Some(Step::Landed(index)) if self.elimination.is_some() => self.knock_out(index),
+            Some(Step::Cleared) if self.elimination.is_some() => self.after_knock_out(now),
+            Some(Step::Landed(index)) => match self.best_of.as_mut() {
+                Some(best) if !best.finished() => {
+                    best.record(index);
+                    let (round, rounds) = (best.drawn(), best.rounds());
+                    if let Some(verdict) = best.verdict().filter(|_| best.finished()) {
+                        self.decision
+                            .set_timings(self.config.animation(), self.config.flash());
+                        self.decision
+                            .show(verdict, self.engine.pack.answers.len(), now);
+                        self.reveal(verdict, now);
+                    } else {
+                        let answer = self.answer_text(index).to_string();
+                        tracing::debug!(round, %answer, "round landed");
+                        self.beep(Step::Switched);
+                        self.show_notice(format!("Round {round} of {rounds}: {answer}"));
+                    }
+                }
+                _ => self.reveal(index, now),
+            },
+            Some(Step::Switched) => {
+                tracing::trace!(active = ?self.decision.active(), "light switched");
+                self.beep(Step::Switched);
+                self.report_progress(self.decision.progress(now));
+                if let Some(index) = self.decision.active() {
+                    self.emit(Event::LightChanged {
+                        index,
+                        answer: self.answer_text(index).to_string(),
+                    });
+                }
+            }
+            Some(Step::Cleared) if self.best_of.as_ref().is_some_and(|best| !best.finished()) => {
+                self.next_round(now);
+            }
+            Some(Step::Charging) => {
+                tracing::debug!("charging the oracle");
+                self.report_progress(None);
+            }
+            Some(Step::Cleared) | None => {}
+        }
+        // After settling, so a failed write shows now rather than on the next
+        // wakeup, which may be a long way off.
+        if let Some(err) = self.engine.webhook_failure() {
+            self.show_notice(format!("Webhook failed: {err}"));
+        }
+        if let Some(err) = self.engine.history.take_write_error() {
+            self.show_notice(format!("Could not save history: {err}"));
+        }
+    }
+
+    /// Animations of the screen shown: the gallery's preview, the
+    /// bracket's duels and the settings' demo board.
+    fn tick_screen(&mut self, now: Instant) {
+        match &mut self.screen {
+            Screen::Gallery(gallery) => self.dirty |= gallery.tick(now),
+            Screen::Bracket(bracket) => {
+                if let Some(step) = bracket.tick(now) {
+                    self.dirty = true;
+                    let champion = bracket.champion.clone().filter(|_| step == Step::Cleared);
+                    self.beep(step);
+                    if let Some(champion) = champion {
+                        self.show_notice(format!("{champion} wins the tournament!"));
+                    }
+                }
+            }
+            Screen::Settings(settings) => {
+                if let Some(step) = settings.demo.tick(now) {
+                    self.dirty = true;
+                    if rings(settings.sound(), step) {
+                        self.output.push(b'\x07');
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn show_banner(&mut self, title: &'static str, lines: Vec<String>) {
+        let until = self.clock.now() + Duration::from_millis(BANNER_MS);
+        self.banner = Some((title, lines, until));
+        self.dirty = true;
+    }
+
+    pub fn show_notice(&mut self, message: impl Into<String>) {
+        let until = self.clock.now() + Duration::from_millis(NOTICE_MS);
+        self.notice = Some((message.into(), until));
+        self.dirty = true;
+    }
+
+    /// Real time until [`App::tick`] has something to do: the next light
+    /// switch, the end of a flash, or a notice expiring. `None` when idle.
+    pub fn time_to_next_deadline(&self) -> Option<Duration> {
+        let deadline = [
+            self.decision.next_deadline(),
+            self.notice.as_ref().map(|(_, until)| *until),
+            self.banner.as_ref().map(|(_, _, until)| *until),
+            self.streak
+                .as_ref()
+                .map(|_| self.clock.now() + Duration::from_millis(SPARKLE_FRAME_MS)),
+            match &self.screen {
+                Screen::Gallery(gallery) => Some(gallery.next_deadline()),
+                Screen::Settings(settings) => settings.demo.next_deadline(),
+                Screen::Bracket(bracket) => bracket.duel.next_deadline(),
+                _ => None,
+            },
+            self.fading()
+                .then(|| self.clock.now() + Duration::from_millis(GLOW_FRAME_MS)),
+            self.charging()
+                .then(|| self.clock.now() + Duration::from_millis(CHARGE_FRAME_MS)),
+            self.demo.as_ref().map(Demo::next_deadline),
+            self.elimination
+                .as_ref()
+                .and_then(|elimination| elimination.next_round_at),
+            self.spinner_frame()
+                .map(|_| self.clock.now() + Duration::from_millis(rationale::SPINNER_FRAME_MS)),
+            // The countdown's next whole second.
+            self.countdown()
+                .zip(self.pending)
+                .map(|(left, (_, until))| until - Duration::from_secs(left.saturating_sub(1))),
+        ]
+        .into_iter()
+        .flatten()
+        .min()?;
+        let span = deadline.saturating_duration_since(self.clock.now());
+        Some(self.clock.wait_for(span))
+    }
+
+    /// Which sparkle frame the streak celebration is on, while one shows.
+    pub fn sparkle_frame(&self) -> Option<usize> {
+        let (_, shown_at) = self.streak.as_ref()?;
+        let elapsed = self.clock.now().saturating_duration_since(*shown_at);
+        Some((elapsed.as_millis() / u128::from(SPARKLE_FRAME_MS)) as usize)
+    }
+
+    /// Brightness of the lit button's gradient, on terminals with 24-bit
+    /// colour; `None` keeps the theme's flat highlight.
+    pub fn glow(&self) -> Option<f64> {
+        if self.color_depth != ColorDepth::TrueColor {
+            return None;
+        }
+        self.decision.glow(self.clock.now())
+    }
+
+    /// Whether the suspense bar is filling, which takes a frame every
+    /// [`CHARGE_FRAME_MS`].
+    fn charging(&self) -> bool {
+        matches!(self.decision.state(), State::Charging { .. }) && !self.decision.is_paused()
+    }
+
+    /// How full the suspense bar is, while the board is dark.
+    pub fn charge(&self) -> Option<f64> {
+        self.decision.charge(self.clock.now())
+    }
+
+    /// Whether the glow is fading, which takes a frame every
+    /// [`GLOW_FRAME_MS`] instead of one at the end of the flash.
+    fn fading(&self) -> bool {
+        self.color_depth == ColorDepth::TrueColor
+            && matches!(self.decision.state(), State::Showing { .. })
+            && !self.decision.is_paused()
+    }
+
+    /// Whether the screen is out of date, clearing the flag. The debug
+    /// overlay's timings change every frame, so it keeps the screen live.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty) || self.debug_visible
+    }
+
+    fn copy_answer(&mut self) {
+        let Some(index) = self.last_answer else {
+            self.show_notice("Nothing to copy yet.");
+            return;
+        };
+        let text = match self.question() {
+            Some(question) => format!("{question} → {}", self.answer_text(index)),
+            None => self.answer_text(index).to_string(),
+        };
+        match self.clipboard.copy(&text, &mut self.output) {
+            Ok(()) => self.show_notice(format!("Copied \"{text}\" to clipboard.")),
+            Err(err) => self.show_notice(format!("Copy failed: {err}")),
+        }
+    }
+
+    /// Show the last decision as a QR code.
+    fn open_qr(&mut self) {
+        let id = self
+            .decision_id
+            .as_deref()
+            .filter(|_| self.last_answer.is_some());
+        let Some(decision) = id.and_then(|id| {
+            self.engine
+                .history
+                .recent()
+                .find(|decision| decision.key() == id)
+        }) else {
+            self.show_notice("Nothing to share yet.");
+            return;
+        };
+        match qr::render(&qr::payload(decision)) {
+            Ok(rows) => self.qr = Some(rows),
+            Err(err) => self.show_notice(format!("No QR code: {err}")),
+        }
+    }
+
+    /// Write `frame`, the one drawn after `S`, to the working directory as
+    /// SVG or, with `screenshot_html`, HTML. `None` when the front end
+    /// can't hand one over.
+    pub fn save_screenshot(&mut self, frame: Option<&Buffer>) {
+        self.screenshot_requested = false;
+        let Some(frame) = frame else {
+            self.show_notice("Screenshots need the terminal front end.");
+            return;
+        };
+        let html = self.config.screenshot_html;
+        let text = if html {
+            screenshot::html(frame)
+        } else {
+            screenshot::svg(frame)
+        };
+        let name = screenshot::file_name(self.clock.local_now(), html);
+        let path = std::env::current_dir()
+            .map(|dir| dir.join(&name))
+            .unwrap_or_else(|_| PathBuf::from(&name));
+        match std::fs::write(&path, text) {
+            Ok(()) => {
+                tracing::info!(path = %path.display(), "screenshot saved");
+                self.show_notice(format!("Screenshot saved to {}", path.display()));
+            }
+            Err(err) => self.show_notice(format!("Could not save screenshot: {err}")),
+        }
+    }
+
+    /// While the QR code is shown, any key puts it away.
+    fn handle_qr(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => return true,
+            Action::Focus(focused) => self.set_focus(focused),
+            Action::Resize => {}
+            _ => self.qr = None,
+        }
+        false
+    }
+
+    pub fn open_gallery(&mut self) {
+        let (packs, _) = answers::installed_packs(&self.pack_dirs);
+        self.screen = Screen::Gallery(Gallery::new(packs, &self.engine.pack.id, self.clock.now()));
+    }
+
+    /// Switch to the pack highlighted in the gallery and remember it as the
+    /// default for future launches.
+    fn choose_gallery_pack(&mut self) {
+        let Screen::Gallery(gallery) = std::mem::replace(&mut self.screen, Screen::Oracle) else {
+            return;
+        };
+        self.accept();
+        self.matrix_return = None;
+        self.engine.pack = gallery.current().clone();
+        if let Some(weights) = self.config.weights.get(&self.engine.pack.id) {
+            self.engine.pack.apply_weights(weights);
+        }
+        self.decision.reset();
+        self.last_answer = None;
+        self.config.default_pack = Some(self.engine.pack.id.clone());
+        match self.save_config() {
+            Ok(()) => self.show_notice(format!(
+                "{} is now your default pack.",
+                self.engine.pack.title
+            )),
+            Err(err) => self.show_notice(format!("Could not save config: {err}")),
+        }
+    }
+
+    /// Write the config back, if it came from a file.
+    fn save_config(&self) -> io::Result<()> {
+        match &self.config_path {
+            Some(path) => self.config.save(path).inspect_err(|err| {
+                tracing::warn!(path = %path.display(), %err, "could not save config");
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Use the tuned weights and remember them for this pack.
+    fn keep_weights(&mut self) {
+        let Screen::Weights(editor) = std::mem::replace(&mut self.screen, Screen::Oracle) else {
+            return;
+        };
+        let tuned: BTreeMap<String, u32> = editor
+            .pack
+            .answers
+            .iter()
+            .filter(|answer| answer.weight != 1)
+            .map(|answer| (answer.text.clone(), answer.weight))
+            .collect();
+        if tuned.is_empty() {
+            self.config.weights.remove(&editor.pack.id);
+        } else {
+            self.config.weights.insert(editor.pack.id.clone(), tuned);
+        }
+        self.engine.pack = editor.pack;
+        match self.save_config() {
+            Ok(()) => self.show_notice(format!("Weights saved for {}.", self.engine.pack.title)),
+            Err(err) => self.show_notice(format!("Could not save config: {err}")),
+        }
+    }
+
+    /// Switch to the edited pack and write it to its file in `packs/`. The
+    /// file now holds the weights, so the config's table for the pack goes.
+    fn save_pack(&mut self) {
+        let Screen::PackEditor(editor) = std::mem::replace(&mut self.screen, Screen::Oracle) else {
+            return;
+        };
+        if !editor.changed {
+            return;
+        }
+        self.accept();
+        self.engine.pack = editor.pack;
+        self.decision.reset();
+        self.last_answer = None;
+        self.second_opinion = None;
+        let Some(dir) = self.packs_dir() else {
+            self.show_notice(format!(
+                "{} changed for this session.",
+                self.engine.pack.title
+            ));
+            return;
+        };
+        let path = answers::pack_file(&dir, &self.engine.pack.id);
+        let previous = answers::find_pack_file(&self.pack_dirs, &self.engine.pack.id);
+        let saved = PackFile::from_pack(&self.engine.pack, previous.map(|(_, file)| file).as_ref())
+            .save(&path)
+            .inspect_err(|err| {
+                tracing::warn!(path = %path.display(), %err, "could not save pack");
+            });
+        if let Err(err) = saved {
+            self.show_notice(format!("Could not save pack: {err}"));
+            return;
+        }
+        if self.config.weights.remove(&self.engine.pack.id).is_some() {
+            if let Err(err) = self.save_config() {
+                self.show_notice(format!("Could not save config: {err}"));
+                return;
+            }
+        }
+        self.show_notice(format!("Saved {}.", path.display()));
+    }
+
+    fn open_profiles(&mut self) {
+        if self.profiles.len() < 2 {
+            self.show_notice("Start edm with --profile NAME to add a profile.");
+            return;
+        }
+        self.screen = Screen::Profiles(ProfilePicker::new(self.profiles.clone(), &self.profile));
+    }
+
+    /// Quit to start over on the profile highlighted in the picker. Returns
+    /// whether the app should terminate.
+    fn choose_profile(&mut self) -> bool {
+        let Screen::Profiles(picker) = std::mem::replace(&mut self.screen, Screen::Oracle) else {
+            return false;
+        };
+        if picker.current() == self.profile {
+            return false;
+        }
+        tracing::info!(profile = picker.current(), "switching profile");
+        self.switch_profile = Some(picker.current().to_string());
+        true
+    }
+
+    /// Directory holding user pack files, next to the config file.
+    fn packs_dir(&self) -> Option<PathBuf> {
+        self.config_path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(|dir| dir.join("packs"))
+    }
+
+    /// Directory holding saved decision matrices, next to the config file.
+    fn matrices_dir(&self) -> Option<PathBuf> {
+        self.config_path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(|dir| dir.join("matrices"))
+    }
+
+    /// Directory holding user theme files, next to the config file.
+    fn themes_dir(&self) -> Option<PathBuf> {
+        self.config_path
+            .as_deref()
+            .and_then(Path::parent)
+            .map(|dir| dir.join("themes"))
+    }
+
+    /// Switch to the high-contrast theme, or back to the configured one.
+    fn toggle_contrast(&mut self) {
+        self.config.high_contrast = !self.config.high_contrast;
+        let theme = if self.config.high_contrast {
+            theme::high_contrast()
+        } else {
+            let (themes, _) = theme::installed_themes(self.themes_dir().as_deref());
+            let name = self.config.theme.as_deref().unwrap_or(theme::DEFAULT_THEME);
+            themes
+                .iter()
+                .find(|theme| theme.name == name)
+                .unwrap_or(&themes[0])
+                .clone()
+        };
+        self.set_theme(theme);
+        self.dirty = true;
+        match self.save_config() {
+            Ok(()) => self.show_notice(format!("Theme set to {}.", self.theme.name)),
+            Err(err) => self.show_notice(format!("Could not save config: {err}")),
+        }
+    }
+
+    /// Start the live theme preview, re-reading the theme directory so new or
+    /// edited files show up without a restart.
+    fn open_theme_preview(&mut self) {
+        let (themes, errors) = theme::installed_themes(self.themes_dir().as_deref());
+        if !errors.is_empty() {
+            self.show_notice(format!("Skipped broken theme: {}", errors.join("; ")));
+        }
+        let preview = ThemePreview::new(themes, self.theme.clone());
+        self.set_theme(preview.current().clone());
+        self.theme_preview = Some(preview);
+    }
+
+    fn cycle_theme(&mut self, delta: isize) {
+        if let Some(preview) = self.theme_preview.as_mut() {
+            preview.cycle(delta);
+            let theme = preview.current().clone();
+            self.set_theme(theme);
+        }
+    }
+
+    /// Draw with `theme`, the config's answer styles laid over it.
+    fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme.with_styles(&self.answer_styles);
+    }
+
+    /// Keep the previewed theme and store it in the config.
+    fn keep_theme(&mut self) {
+        self.theme_preview = None;
+        self.config.theme = Some(self.theme.name.clone());
+        self.config.high_contrast = false;
+        match self.save_config() {
+            Ok(()) => self.show_notice(format!("Theme set to {}.", self.theme.name)),
+            Err(err) => self.show_notice(format!("Could not save config: {err}")),
+        }
+    }
+
+    fn revert_theme(&mut self) {
+        if let Some(preview) = self.theme_preview.take() {
+            self.theme = preview.original;
+        }
+    }
+
+    /// A chord was pressed while recording a binding.
+    fn record_chord(&mut self, chord: Chord) {
+        let Screen::Settings(settings) = &mut self.screen else {
+            return;
+        };
+        if !settings.recording {
+            return;
+        }
+        match settings.record(chord, &mut self.keymap) {
+            Recorded::Bound => self.save_keys(chord),
+            // The settings screen shows the warning dialog.
+            Recorded::Conflict => {}
+        }
+    }
+
+    /// Move the conflicting chord over to the selected action.
+    fn confirm_chord(&mut self) {
+        let chord = match &mut self.screen {
+            Screen::Settings(settings) => settings.confirm(&mut self.keymap),
+            _ => None,
+        };
+        if let Some(chord) = chord {
+            self.save_keys(chord);
+        }
+    }
+
+    /// Store the keymap after `chord` was bound to the selected action.
+    fn save_keys(&mut self, chord: Chord) {
+        let label = match &self.screen {
+            Screen::Settings(settings) => settings.label(),
+            _ => "",
+        };
+        let message = format!("{chord} now means {label}.");
+        self.config.keys = self.keymap.to_config();
+        match self.save_config() {
+            Ok(()) => self.show_notice(message),
+            Err(err) => self.show_notice(format!("Could not save config: {err}")),
+        }
+    }
+
+    fn open_settings(&mut self) {
+        let (themes, errors) = theme::installed_themes(self.themes_dir().as_deref());
+        if !errors.is_empty() {
+            self.show_notice(format!("Skipped broken theme: {}", errors.join("; ")));
+        }
+        let count = self.engine.pack.answers.len();
+        let mut settings = Settings::new(&self.config, &self.theme, themes, count);
+        settings.restart_demo(self.clock.now());
+        self.screen = Screen::Settings(Box::new(settings));
+    }
+
+    /// Esc on the settings screen: close the dialog, stop recording, or
+    /// leave the screen (dropping unsaved preferences), in that order.
+    fn settings_back(&mut self) {
+        let Screen::Settings(settings) = &mut self.screen else {
+            return;
+        };
+        if settings.conflict.is_some() {
+            settings.conflict = None;
+        } else if settings.recording {
+            settings.recording = false;
+        } else {
+            self.close_screen();
+        }
+    }
+
+    /// Enter on the settings screen: save the preferences, confirm the
+    /// dialog, toggle the selected experiment, or start recording.
+    fn settings_enter(&mut self) {
+        let Screen::Settings(settings) = &mut self.screen else {
+            return;
+        };
+        if settings.tab == settings::Tab::Preferences {
+            settings.save(&mut self.config);
+            self.engine.optimism = self.config.optimism();
+            tracing::info!("preferences saved");
+            match self.save_config() {
+                Ok(()) => self.show_notice("Preferences saved."),
+                Err(err) => self.show_notice(format!("Could not save config: {err}")),
+            }
+        } else if settings.conflict.is_some() {
+            self.confirm_chord();
+        } else if let Some(experiment) = settings.experiment() {
+            self.toggle_experiment(experiment.name);
+        } else {
+            settings.recording = true;
+        }
+    }
+
+    fn toggle_experiment(&mut self, name: &str) {
+        let on = !self.config.experiment(name);
+        self.config.experimental.insert(name.to_string(), on);
+        tracing::info!(name, on, "experiment toggled");
+        match self.save_config() {
+            Ok(()) => self.show_notice(format!(
+                "Experiment {name} switched {}.",
+                if on { "on" } else { "off" }
+            )),
+            Err(err) => self.show_notice(format!("Could not save config: {err}")),
+        }
+    }
+
+    fn toggle_help(&mut self) {
+        if matches!(self.screen, Screen::Help { .. }) {
+            self.show_tab(screen::Tab::Oracle);
+        } else {
+            self.show_tab(screen::Tab::Help);
+        }
+    }
+
+    fn lock(&mut self) {
+        if self.config.lock_passphrase.is_none() {
+            self.show_notice("Set lock_passphrase in the config to lock the screen.");
+            return;
+        }
+        if matches!(self.screen, Screen::Help { .. }) {
+            self.screen = Screen::Oracle;
+        }
+        self.notice = None;
+        self.lock = Some(String::new());
+    }
+
+    /// While locked, keys only go into the passphrase. Ctrl+C still quits.
+    fn handle_locked(&mut self, action: Action) -> bool {
+        let Some(entry) = self.lock.as_mut() else {
+            return false;
+        };
+        match action {
+            Action::Quit => return true,
+            Action::Type(c) => entry.push(c),
+            Action::Erase => {
+                entry.pop();
+            }
+            Action::Back => entry.clear(),
+            Action::Ask => {
+                if self.config.lock_passphrase.as_deref() == Some(entry.as_str()) {
+                    self.lock = None;
+                    self.notice = None;
+                } else {
+                    entry.clear();
+                    self.show_notice("Wrong passphrase.");
+                }
+            }
+            Action::Focus(focused) => self.set_focus(focused),
+            _ => {}
+        }
+        false
+    }
+
+    /// Text pasted in one go, which terminals with bracketed paste send as
+    /// a whole instead of key by key. It goes in as typed wherever text is
+    /// typed, with line breaks kept only in a note; on the board it starts
+    /// a question, so a pasted space can't ask. Lists and prompts, where
+    /// letters are commands, ignore it.
+    pub fn paste(&mut self, text: &str) {
+        let text = match self.input_mode() {
+            Mode::Note => text.replace("\r\n", "\n"),
+            Mode::Typing if self.recall.is_none() => {
+                text.split_whitespace().collect::<Vec<_>>().join(" ")
+            }
+            Mode::Commands => {
+                let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if text.is_empty() {
+                    return;
+                }
+                self.handle(Action::EditQuestion);
+                if !self.typing {
+                    return;
+                }
+                text
+            }
+            Mode::Typing | Mode::Editing | Mode::Recording => return,
+        };
+        tracing::debug!(chars = text.chars().count(), "pasted");
+        for c in text.chars() {
+            self.handle(Action::Type(c));
+        }
+    }
+
+    /// Start a note on the last decision, or carry on with the one it has.
+    fn open_note(&mut self) {
+        let Some(id) = self
+            .decision_id
+            .clone()
+            .filter(|_| self.last_answer.is_some())
+        else {
+            self.show_notice("Ask first, then write a note on the answer.");
+            return;
+        };
+        let text = self
+            .engine
+            .history
+            .recent()
+            .find(|decision| decision.key() == id)
+            .and_then(|decision| decision.note.as_deref())
+            .unwrap_or_default();
+        self.note = Some((id, TextArea::new(text)));
+    }
+
+    /// While writing a note, keys go into it: Ctrl+S (as Ask) saves, Esc
+    /// discards. Ctrl+C still quits.
+    fn handle_note(&mut self, action: Action) -> bool {
+        let Some((id, area)) = self.note.as_mut() else {
+            return false;
+        };
+        match action {
+            Action::Quit => return true,
+            Action::Type(c) => area.insert(c),
+            Action::Erase => area.erase(),
+            Action::Up => area.move_line(-1),
+            Action::Down => area.move_line(1),
+            Action::Decrease => area.move_by(-1),
+            Action::Increase => area.move_by(1),
+            Action::Back => self.note = None,
+            Action::Ask => {
+                let (id, text) = (id.clone(), area.text());
+                self.note = None;
+                if !self
+                    .engine
+                    .history
+                    .set_note(&id, &text, self.clock.local_now())
+                {
+                    self.show_notice("That decision is no longer in the history.");
+                } else if text.trim().is_empty() {
+                    self.show_notice("Note removed.");
+                } else {
+                    self.show_notice("Note saved.");
+                }
+            }
+            Action::Focus(focused) => self.set_focus(focused),
+            _ => {}
+        }
+        false
+    }
+
+    /// Start retagging the last decision.
+    fn open_tagging(&mut self) {
+        let Some(id) = self
+            .decision_id
+            .clone()
+            .filter(|_| self.last_answer.is_some())
+        else {
+            self.show_notice("Ask first, then tag the answer.");
+            return;
+        };
+        let current = self
+            .engine
+            .history
+            .recent()
+            .find(|decision| decision.key() == id)
+            .map(|decision| tags::format(&decision.tags))
+            .unwrap_or_default();
+        let text = if current.is_empty() {
+            "#".to_string()
+        } else {
+            format!("{current} ")
+        };
+        self.tagging = Some((id, text));
+    }
+
+    /// While retagging, keys go into the tag list: Enter saves, Esc cancels.
+    /// Ctrl+C still quits.
+    fn handle_tagging(&mut self, action: Action) -> bool {
+        let Some((id, text)) = self.tagging.as_mut() else {
+            return false;
+        };
+        match action {
+            Action::Quit => return true,
+            Action::Type(c) => text.push(c),
+            Action::Erase => {
+                text.pop();
+            }
+            Action::Back => self.tagging = None,
+            Action::Ask => match tags::parse_list(text) {
+                Ok(tags) => {
+                    let id = id.clone();
+                    self.tagging = None;
+                    if self.decision_id.as_ref() == Some(&id) {
+                        self.tags = tags.clone();
+                    }
+                    let message = match tags.as_slice() {
+                        [] => "Tags removed.".to_string(),
+                        tags => format!("Tagged {}.", tags::format(tags)),
+                    };
+                    if self
+                        .engine
+                        .history
+                        .set_tags(&id, tags, self.clock.local_now())
+                    {
+                        self.show_notice(message);
+                    } else {
+                        self.show_notice("That decision is no longer in the history.");
+                    }
+                }
+                Err(bad) => self.show_notice(format!("Not a tag: {bad}")),
+            },
+            Action::Focus(focused) => self.set_focus(focused),
+            _ => {}
+        }
+        false
+    }
+
+    /// Keys while a theme is previewed, over whatever screen is shown.
+    /// `None` leaves the key to the screen.
+    fn handle_theme_preview(&mut self, action: Action) -> Option<bool> {
+        match action {
+            Action::Back => self.revert_theme(),
+            Action::Ask => self.keep_theme(),
+            Action::Up => self.cycle_theme(-1),
+            Action::Down | Action::CycleTheme => self.cycle_theme(1),
+            // The preview decides the theme, and optimism waits for it.
+            Action::ToggleContrast | Action::Increase | Action::Decrease => {}
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the board. `None` leaves the key to
+    /// [`Self::handle_global`].
+    fn handle_oracle(&mut self, action: Action) -> Option<bool> {
+        if self.elimination.is_some() && !self.typing {
+            if let Some(quit) = self.handle_elimination(action) {
+                return Some(quit);
+            }
+        }
+        let animating = self.decision.is_animating();
+        match action {
+            Action::Back => {
+                if self.typing && self.suggestion.is_some() {
+                    self.suggestion = None;
+                } else if self.typing {
+                    self.typing = false;
+                    self.question.clear();
+                    self.suggestions.clear();
+                } else if self.matrix_return.is_some() && !animating {
+                    self.leave_matrix();
+                } else {
+                    return Some(true);
+                }
+            }
+            Action::Ask => {
+                if let Some(index) = self.suggestion.filter(|_| self.typing) {
+                    self.accept_suggestion(index);
+                } else if self.pending.is_some() && !self.typing {
+                    self.accept();
+                } else {
+                    self.ask_or_recall();
+                }
+            }
+            Action::Up | Action::Down => {
+                if self.typing {
+                    self.pick_suggestion(if action == Action::Up { -1 } else { 1 });
+                }
+            }
+            Action::Increase => self.adjust_optimism(1),
+            Action::Decrease => self.adjust_optimism(-1),
+            Action::EditWeights if !animating => {
+                self.screen = Screen::Weights(WeightEditor::new(&self.engine.pack));
+            }
+            Action::EditPack if !animating => {
+                self.screen = Screen::PackEditor(PackEditor::new(&self.engine.pack));
+            }
+            Action::OpenProfiles if !animating => self.open_profiles(),
+            Action::OpenWorksheet if !animating => {
+                let sheet = Worksheet::new(self.question());
+                self.screen = Screen::Worksheet(sheet);
+            }
+            Action::OpenBracket if !animating => {
+                self.screen = Screen::Bracket(self.last_bracket.take().unwrap_or_default());
+            }
+            Action::OpenMatrix if !animating => self.open_matrix(),
+            Action::OpenAchievements => self.screen = Screen::Achievements,
+            Action::OpenGallery => self.open_gallery(),
+            Action::EditNote if !animating => self.open_note(),
+            Action::EditTags if !animating => self.open_tagging(),
+            Action::CycleTagFilter => self.cycle_tag_filter(),
+            Action::ShowQr if !animating => self.open_qr(),
+            Action::EditQuestion if !animating => {
+                self.question.clear();
+                self.tags.clear();
+                self.suggestions.clear();
+                self.suggestion = None;
+                self.valid_for = None;
+                self.timebox = self.config.timebox();
+                self.typing = true;
+            }
+            Action::CycleValidity if self.typing => {
+                self.valid_for = match self.valid_for {
+                    None => Some(VALIDITY_DAYS[0]),
+                    Some(days) => VALIDITY_DAYS.iter().copied().find(|&choice| choice > days),
+                };
+            }
+            Action::CycleTimebox if self.typing => {
+                self.timebox = match self.timebox {
+                    None => Some(TIMEBOX_SECS[0]),
+                    Some(secs) => TIMEBOX_SECS.iter().copied().find(|&choice| choice > secs),
+                };
+            }
+            Action::Eliminate if !animating && self.elimination.is_none() => {
+                self.start_elimination();
+            }
+            Action::Veto if !animating => self.veto(),
+            Action::Revalidate if !animating => self.revalidate(),
+            Action::MarkGood | Action::MarkBad if !animating => {
+                self.rate(action == Action::MarkGood);
+            }
+            Action::Type(c) if self.typing => {
+                self.question.push(c);
+                self.refresh_suggestions();
+            }
+            Action::Erase if self.typing => {
+                self.question.pop();
+                self.refresh_suggestions();
+            }
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the board while answers are knocked out: Esc stops, and
+    /// Enter waits for the last one standing.
+    fn handle_elimination(&mut self, action: Action) -> Option<bool> {
+        match action {
+            Action::Back => self.stop_elimination(),
+            Action::Ask | Action::Eliminate => {}
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the history screen.
+    fn handle_history(&mut self, action: Action) -> Option<bool> {
+        let Screen::History(view) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back => {
+                if view.searching || !view.query.is_empty() {
+                    view.clear(&self.engine.history);
+                } else {
+                    self.screen = Screen::Oracle;
+                }
+            }
+            Action::Ask => view.searching = false,
+            Action::Up => view.move_by(-1),
+            Action::Down => view.move_by(1),
+            Action::Select(index) if index < view.matches.len() => view.selected = index,
+            Action::Search => view.searching = true,
+            Action::Type(c) if view.searching => view.type_char(c, &self.engine.history),
+            Action::Erase if view.searching => view.erase(&self.engine.history),
+            Action::MarkGood | Action::MarkBad if !view.searching => {
+                self.rate(action == Action::MarkGood);
+            }
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the stats screen, which only has Esc of its own.
+    fn handle_stats(&mut self, action: Action) -> Option<bool> {
+        match action {
+            Action::Back => self.screen = Screen::Oracle,
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the settings screen.
+    fn handle_settings(&mut self, action: Action) -> Option<bool> {
+        let Screen::Settings(settings) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back => self.settings_back(),
+            Action::Ask => self.settings_enter(),
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                match settings.tab {
+                    settings::Tab::Preferences => settings.form.move_by(delta),
+                    settings::Tab::Keys => settings.move_by(delta),
+                }
+            }
+            Action::Select(index) if settings.conflict.is_none() && !settings.recording => {
+                match settings.tab {
+                    settings::Tab::Preferences => settings.form.select(index),
+                    settings::Tab::Keys => settings.select(index),
+                }
+            }
+            Action::NextTab => {
+                settings.next_tab();
+                if settings.tab == settings::Tab::Preferences {
+                    settings.restart_demo(self.clock.now());
+                }
+            }
+            Action::Increase | Action::Decrease => {
+                let delta = if action == Action::Increase { 1 } else { -1 };
+                if settings.tab == settings::Tab::Preferences && settings.form.adjust(delta) {
+                    settings.restart_demo(self.clock.now());
+                    let theme = settings.theme().clone();
+                    self.set_theme(theme);
+                }
+            }
+            Action::Chord(chord) => self.record_chord(chord),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the help screen: the arrows scroll, Esc or Enter go back to
+    /// the board.
+    fn handle_help(&mut self, action: Action) -> Option<bool> {
+        let Screen::Help { scroll } = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back | Action::Ask => self.screen = Screen::Oracle,
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                let last = crate::ui::help_lines(&self.strings).len().saturating_sub(1);
+                *scroll = scroll
+                    .saturating_add_signed(delta)
+                    .min(u16::try_from(last).unwrap_or(u16::MAX));
+            }
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys in the pack gallery.
+    fn handle_gallery(&mut self, action: Action) -> Option<bool> {
+        let Screen::Gallery(gallery) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back => self.screen = Screen::Oracle,
+            Action::Ask => self.choose_gallery_pack(),
+            Action::Up => gallery.move_by(-1, self.clock.now()),
+            Action::Down => gallery.move_by(1, self.clock.now()),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys in the weight editor.
+    fn handle_weights(&mut self, action: Action) -> Option<bool> {
+        let Screen::Weights(editor) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back => self.screen = Screen::Oracle,
+            Action::Ask => self.keep_weights(),
+            Action::Up => editor.move_by(-1),
+            Action::Down => editor.move_by(1),
+            Action::Increase => editor.adjust(1),
+            Action::Decrease => editor.adjust(-1),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys in the pack editor: `a` adds an answer and `r` renames one.
+    fn handle_pack_editor(&mut self, action: Action) -> Option<bool> {
+        let Screen::PackEditor(editor) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back if editor.entry.is_some() => editor.cancel_entry(),
+            Action::Back => self.screen = Screen::Oracle,
+            Action::Ask if editor.entry.is_some() => editor.confirm_entry(),
+            Action::Ask => self.save_pack(),
+            Action::Up => editor.move_by(-1),
+            Action::Down => editor.move_by(1),
+            Action::Increase => editor.adjust(1),
+            Action::Decrease => editor.adjust(-1),
+            Action::Type(c) if editor.entry.is_some() => editor.type_char(c),
+            Action::Type('a' | 'A') => editor.start_add(),
+            Action::Type('r' | 'R') => editor.start_rename(),
+            Action::Erase if editor.entry.is_some() => editor.erase(),
+            Action::Erase => editor.remove(),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the worksheet: `a` adds a pro or con, `r` renames one, and
+    /// Tab switches columns.
+    fn handle_worksheet(&mut self, action: Action) -> Option<bool> {
+        let Screen::Worksheet(sheet) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back if sheet.entry.is_some() => sheet.cancel_entry(),
+            Action::Back => self.screen = Screen::Oracle,
+            Action::Ask if sheet.entry.is_some() => sheet.confirm_entry(),
+            Action::Ask => self.weigh_worksheet(),
+            Action::Up => sheet.move_by(-1),
+            Action::Down => sheet.move_by(1),
+            Action::NextTab => sheet.switch_column(),
+            Action::Increase => sheet.adjust(1),
+            Action::Decrease => sheet.adjust(-1),
+            Action::Type(c) if sheet.entry.is_some() => sheet.type_char(c),
+            Action::Type('a' | 'A') => sheet.start_add(),
+            Action::Type('r' | 'R') => sheet.start_rename(),
+            Action::Erase if sheet.entry.is_some() => sheet.erase(),
+            Action::Erase => sheet.remove(),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys in the matrix editor: `o` and `c` add an option or criterion,
+    /// `r` renames, `t` retitles, `s` saves and `l` loads.
+    fn handle_matrix(&mut self, action: Action) -> Option<bool> {
+        let Screen::Matrix(editor) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back if editor.entry.is_some() => editor.cancel_entry(),
+            Action::Back => self.close_screen(),
+            Action::Ask if editor.entry.is_some() => editor.confirm_entry(),
+            Action::Ask => self.run_matrix(),
+            Action::Up => editor.move_by(-1),
+            Action::Down => editor.move_by(1),
+            Action::NextTab => editor.next_column(),
+            Action::Increase => editor.adjust(1),
+            Action::Decrease => editor.adjust(-1),
+            Action::Type(c) if editor.entry.is_some() => editor.type_char(c),
+            Action::Type('o' | 'O') => editor.start(Target::Option(None)),
+            Action::Type('c' | 'C') => editor.start(Target::Criterion(None)),
+            Action::Type('r' | 'R') => editor.start_rename(),
+            Action::Type('t' | 'T') => editor.start(Target::Title),
+            Action::Type('s' | 'S') => self.save_matrix(),
+            Action::Type('l' | 'L') => self.load_matrix(),
+            Action::Erase if editor.entry.is_some() => editor.erase(),
+            Action::Erase => editor.remove(),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the bracket: `a` adds an entrant until the tournament runs.
+    fn handle_bracket(&mut self, action: Action) -> Option<bool> {
+        let Screen::Bracket(bracket) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back if bracket.entry.is_some() => bracket.cancel_entry(),
+            Action::Back if bracket.running() => bracket.stop(),
+            Action::Back => self.close_screen(),
+            Action::Ask if bracket.entry.is_some() => bracket.confirm_entry(),
+            Action::Ask => self.start_bracket(),
+            Action::Up => bracket.move_by(-1),
+            Action::Down => bracket.move_by(1),
+            Action::Type(c) if bracket.entry.is_some() => bracket.type_char(c),
+            Action::Type('a' | 'A') if !bracket.running() => bracket.start_add(),
+            Action::Erase if bracket.entry.is_some() => bracket.erase(),
+            Action::Erase if !bracket.running() => bracket.remove(),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys in the profile picker.
+    fn handle_profiles(&mut self, action: Action) -> Option<bool> {
+        let Screen::Profiles(picker) = &mut self.screen else {
+            return None;
+        };
+        match action {
+            Action::Back => self.screen = Screen::Oracle,
+            Action::Ask => return Some(self.choose_profile()),
+            Action::Up => picker.move_by(-1),
+            Action::Down => picker.move_by(1),
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Keys on the achievements screen, which only has Esc of its own.
+    fn handle_achievements(&mut self, action: Action) -> Option<bool> {
+        match action {
+            Action::Back => self.screen = Screen::Oracle,
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    fn handle_palette(&mut self, action: Action) -> bool {
+        let Some(palette) = self.palette.as_mut() else {
+            return false;
+        };
+        match action {
+            Action::Quit => return true,
+            Action::Type(c) => palette.type_char(c),
+            Action::Erase => palette.erase(),
+            Action::Up => palette.move_by(-1),
+            Action::Down => palette.move_by(1),
+            Action::Back => self.palette = None,
+            Action::Ask => {
+                let chosen = palette.chosen();
+                self.palette = None;
+                match chosen {
+                    Some(Command::Action(action, _)) => {
+                        tracing::debug!(?action, "run from the palette");
+                        return self.handle(action);
+                    }
+                    Some(Command::CycleSound) => self.cycle_sound(),
+                    None => {}
+                }
+            }
+            Action::Focus(focused) => self.set_focus(focused),
+            _ => {}
+        }
+        false
+    }
+
+    /// Ring the bell on every light, then only for answers, then never,
+    /// and keep the choice in the config.
+    fn cycle_sound(&mut self) {
+        let next = Sound::ALL
+            .iter()
+            .position(|&sound| sound == self.config.sound)
+            .map_or(0, |index| (index + 1) % Sound::ALL.len());
+        self.config.sound = Sound::ALL[next];
+        let name = match self.config.sound {
+            Sound::Lights => "every light",
+            Sound::Answer => "answers only",
+            Sound::Off => "off",
+        };
+        match self.save_config() {
+            Ok(()) => self.show_notice(format!("Sound: {name}.")),
+            Err(err) => self.show_notice(format!("Could not save config: {err}")),
+        }
+    }
+
+    /// Show only the next tag's decisions in the recent list and tally, and
+    /// every decision again after the last tag.
+    fn cycle_tag_filter(&mut self) {
+        let tags = self.engine.history.tags();
+        if tags.is_empty() {
+            self.tag_filter = None;
+            self.show_notice("No tagged decisions yet: add #tags to a question.");
+            return;
+        }
+        self.tag_filter = match &self.tag_filter {
+            None => Some(tags[0].clone()),
+            Some(current) => tags.iter().find(|tag| *tag > current).cloned(),
+        };
+        match &self.tag_filter {
+            Some(tag) => self.show_notice(format!("Showing #{tag} only.")),
+            None => self.show_notice("Showing every decision."),
+        }
+    }
+
+    /// The terminal gained or lost focus. Unless `animate_unfocused` is on,
+    /// the lights stop while nobody is looking.
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+        let now = self.clock.now();
+        if focused {
+            self.decision.resume(now);
+        } else if !self.config.animate_unfocused {
+            self.decision.pause(now);
+        }
+    }
+
+    /// Returns true if the app should terminate.
+    pub fn handle(&mut self, action: Action) -> bool {
+        self.dirty = true;
+        // Any key dismisses the launch banner and a streak on its way
+        // through.
+        if !matches!(action, Action::Focus(_)) {
+            self.banner = None;
+            self.streak = None;
+        }
+        if self.lock.is_some() {
+            // Not logged: the keys are the passphrase.
+            return self.handle_locked(action);
+        }
+        if self.recall.is_some() {
+            return self.handle_recall(action);
+        }
+        if self.note.is_some() {
+            // Not logged: the keys are the note.
+            return self.handle_note(action);
+        }
+        if self.tagging.is_some() {
+            return self.handle_tagging(action);
+        }
+        if self.palette.is_some() {
+            return self.handle_palette(action);
+        }
+        if self.qr.is_some() {
+            return self.handle_qr(action);
+        }
+        if !matches!(action, Action::Type(_) | Action::Erase) {
+            tracing::debug!(?action, "action");
+        }
+        if self.theme_preview.is_some() {
+            if let Some(quit) = self.handle_theme_preview(action) {
+                return quit;
+            }
+        }
+        let handled = match self.screen {
+            Screen::Oracle => self.handle_oracle(action),
+            Screen::History(_) => self.handle_history(action),
+            Screen::Stats => self.handle_stats(action),
+            Screen::Settings(_) => self.handle_settings(action),
+            Screen::Help { .. } => self.handle_help(action),
+            Screen::Gallery(_) => self.handle_gallery(action),
+            Screen::Weights(_) => self.handle_weights(action),
+            Screen::PackEditor(_) => self.handle_pack_editor(action),
+            Screen::Worksheet(_) => self.handle_worksheet(action),
+            Screen::Matrix(_) => self.handle_matrix(action),
+            Screen::Bracket(_) => self.handle_bracket(action),
+            Screen::Profiles(_) => self.handle_profiles(action),
+            Screen::Achievements => self.handle_achievements(action),
+        };
+        match handled {
+            Some(quit) => quit,
+            None => self.handle_global(action),
+        }
+    }
+
+    /// Keys that mean the same on every screen. Returns true if the app
+    /// should terminate.
+    fn handle_global(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => return true,
+            Action::ToggleHelp => self.toggle_help(),
+            Action::OpenPalette => {
+                if matches!(self.screen, Screen::Help { .. }) {
+                    self.screen = Screen::Oracle;
+                }
+                self.palette = Some(CommandPalette::new());
+            }
+            Action::CycleTheme => self.open_theme_preview(),
+            Action::OpenSettings => self.show_tab(screen::Tab::Settings),
+            Action::OpenHistory => self.show_tab(screen::Tab::History),
+            Action::ShowScreen(tab) => self.show_tab(tab),
+            Action::NextScreen | Action::PrevScreen => {
+                if let Some(tab) = self.screen.tab() {
+                    let delta = if action == Action::NextScreen { 1 } else { -1 };
+                    self.show_tab(tab.step(delta));
+                }
+            }
+            Action::ToggleDebug => self.debug_visible = !self.debug_visible,
+            Action::ToggleContrast => self.toggle_contrast(),
+            Action::Lock => self.lock(),
+            Action::CopyAnswer => self.copy_answer(),
+            Action::Screenshot => self.screenshot_requested = true,
+            Action::Focus(focused) => self.set_focus(focused),
+            // Keys of another screen, or of the board while it is covered.
+            // Resize is already marked dirty above, and the event loop
+            // stops the process on Suspend; see `frontend::suspend`.
+            _ => {}
+        }
+        false
+    }
+
+    /// Append `event` to the event log, if there is one. A failing log is
+    /// dropped rather than retried every tick.
+    fn emit(&mut self, event: Event) {
+        let Some(log) = self.event_log.as_mut() else {
+            return;
+        };
+        if let Err(err) = writeln!(log, "{}", event.to_json()).and_then(|()| log.flush()) {
+            tracing::warn!(%err, "event log stopped");
+            self.event_log = None;
+            self.show_notice(format!("Event log stopped: {err}"));
+        }
+    }
+
+    /// Update the terminal's progress bar, if enabled.
+    fn report_progress(&mut self, percent: Option<u8>) {
+        if self.config.osc_progress {
+            self.output.extend(notify::progress(percent));
+        }
+    }
+
+    /// Ring the bell for `step`, as far as the `sound` setting allows.
+    fn beep(&mut self, step: Step) {
+        if rings(self.config.sound, step) {
+            self.output.push(b'\x07');
+        }
+    }
+
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+}
+
+/// Whether the bell rings for `step` with `sound`.
+fn rings(sound: Sound, step: Step) -> bool {
+    match (sound, step) {
+        (_, Step::Cleared | Step::Charging) | (Sound::Off, _) => false,
+        (Sound::Answer, step) => matches!(step, Step::Landed(_)),
+        (Sound::Lights, _) => true,
+    }
+}
@@ -0,0 +1,129 @@
+//! Best-of-N: with `best_of` set, the oracle is consulted that many times in
+//! a row, each round with a shorter shuffle, and the answer drawn most often
+//! stands. Once one answer has a majority of the rounds the rest could not
+//! change that, so they are skipped. Only the verdict goes into the history.
+
+use std::{cmp::Reverse, time::Duration};
+
+/// More rounds than this are cut down to it.
+pub const MAX_ROUNDS: usize = 9;
+/// The shortest shuffle a round gets, however many rounds there are.
+pub const ROUND_MIN_MS: u64 = 600;
+/// How long each round's answer stays lit before the next shuffle.
+pub const ROUND_FLASH_MS: u64 = 700;
+
+/// The rounds of one best-of-N ask.
+#[derive(Clone, Debug)]
+pub struct BestOf {
+    rounds: usize,
+    /// Answer index drawn in each round so far.
+    draws: Vec<usize>,
+}
+
+impl BestOf {
+    pub fn new(rounds: usize) -> Self {
+        Self {
+            rounds,
+            draws: Vec::with_capacity(rounds),
+        }
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Rounds drawn so far.
+    pub fn drawn(&self) -> usize {
+        self.draws.len()
+    }
+
+    pub fn record(&mut self, index: usize) {
+        self.draws.push(index);
+    }
+
+    /// Every round is drawn, or one answer already has a majority.
+    pub fn finished(&self) -> bool {
+        let leading = self.tally().first().map_or(0, |&(_, votes)| votes);
+        self.draws.len() >= self.rounds || leading * 2 > self.rounds
+    }
+
+    /// The answer drawn most often; a tie goes to the one that got there
+    /// first.
+    pub fn verdict(&self) -> Option<usize> {
+        let mut votes = vec![0; self.draws.iter().max()? + 1];
+        let mut leader = (self.draws[0], 0);
+        for &index in &self.draws {
+            votes[index] += 1;
+            if votes[index] > leader.1 {
+                leader = (index, votes[index]);
+            }
+        }
+        Some(leader.0)
+    }
+
+    /// Votes per answer index, most first, ties in the order drawn.
+    pub fn tally(&self) -> Vec<(usize, usize)> {
+        let mut tally: Vec<(usize, usize)> = Vec::new();
+        for &index in &self.draws {
+            match tally.iter_mut().find(|(drawn, _)| *drawn == index) {
+                Some((_, votes)) => *votes += 1,
+                None => tally.push((index, 1)),
+            }
+        }
+        // Stable, so equal counts keep the order they were drawn in.
+        tally.sort_by_key(|&(_, votes)| Reverse(votes));
+        tally
+    }
+
+    /// How long each round shuffles: a share of the usual `shuffle`.
+    pub fn round_time(&self, shuffle: Duration) -> Duration {
+        (shuffle / self.rounds.max(1) as u32).max(Duration::from_millis(ROUND_MIN_MS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drawn(rounds: usize, draws: &[usize]) -> BestOf {
+        let mut best = BestOf::new(rounds);
+        for &index in draws {
+            best.record(index);
+        }
+        best
+    }
+
+    #[test]
+    fn the_majority_stands() {
+        let best = drawn(5, &[2, 0, 2, 1, 2]);
+        assert!(best.finished());
+        assert_eq!(best.verdict(), Some(2));
+        assert_eq!(best.tally(), [(2, 3), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn a_tie_goes_to_the_first_to_get_there() {
+        let best = drawn(4, &[1, 3, 3, 1]);
+        assert!(best.finished());
+        assert_eq!(best.verdict(), Some(3));
+        assert_eq!(best.tally(), [(1, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn a_majority_ends_the_rounds_early() {
+        let mut best = drawn(5, &[4, 4]);
+        assert!(!best.finished());
+        best.record(4);
+        assert!(best.finished());
+        assert_eq!(best.drawn(), 3);
+        assert_eq!(best.verdict(), Some(4));
+
+        // Half is not a majority.
+        assert!(!drawn(4, &[0, 0]).finished());
+    }
+
+    #[test]
+    fn no_rounds_no_verdict() {
+        assert_eq!(BestOf::new(3).verdict(), None);
+    }
+}
@@ -0,0 +1,253 @@
+//! Tournament bracket, opened with `b`: list the options, then Enter seeds
+//! them at random and plays head-to-head matchups on a two-button board
+//! until one is left. An odd one out in a round gets a bye.
+
+use crate::answers::{Answer, AnswerPack};
+use crate::sentiment::Sentiment;
+use crate::theme::AnswerStyle;
+use crate::widget::{DecisionState, Step};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// Fewest options a tournament needs.
+pub const MIN_OPTIONS: usize = 2;
+/// Most options a bracket takes.
+pub const MAX_OPTIONS: usize = 32;
+
+pub struct Bracket {
+    /// The entrants, in the order typed.
+    pub options: Vec<String>,
+    pub selected: usize,
+    /// Open while an option is typed.
+    pub entry: Option<String>,
+    /// Entrants of each round so far, the first seeded at random; the last
+    /// fills with winners as its matchups are played.
+    pub rounds: Vec<Vec<String>>,
+    /// The matchup being played, as a two-answer pack for the board.
+    pub matchup: Option<AnswerPack>,
+    pub duel: DecisionState,
+    /// The last one standing, once the final is played.
+    pub champion: Option<String>,
+}
+
+impl Bracket {
+    pub fn new() -> Self {
+        Self {
+            options: Vec::new(),
+            selected: 0,
+            entry: None,
+            rounds: Vec::new(),
+            matchup: None,
+            duel: DecisionState::default(),
+            champion: None,
+        }
+    }
+
+    /// Whether the tournament has started and isn't over.
+    pub fn running(&self) -> bool {
+        !self.rounds.is_empty() && self.champion.is_none()
+    }
+
+    /// Why the tournament can't start yet, if it can't.
+    pub fn problem(&self) -> Option<String> {
+        (self.options.len() < MIN_OPTIONS)
+            .then(|| format!("Add at least {MIN_OPTIONS} options first (a)."))
+    }
+
+    /// Seed the options at random and play the first matchup, each shuffle
+    /// taking `shuffle` and each winner flashing for `flash`.
+    pub fn start(&mut self, shuffle: Duration, flash: Duration, now: Instant) {
+        let mut seeded = self.options.clone();
+        seeded.shuffle(&mut rand::thread_rng());
+        tracing::info!(entrants = seeded.len(), "tournament started");
+        self.rounds = vec![seeded, Vec::new()];
+        self.champion = None;
+        self.duel.reset();
+        self.duel.set_timings(shuffle, flash);
+        self.next_matchup(now);
+    }
+
+    /// Stop the tournament and go back to the list.
+    pub fn stop(&mut self) {
+        self.rounds.clear();
+        self.matchup = None;
+        self.champion = None;
+        self.duel.reset();
+    }
+
+    /// The round being played and the one it fills with winners.
+    fn current(&self) -> (&[String], &[String]) {
+        let played = self.rounds.len() - 2;
+        (&self.rounds[played], &self.rounds[played + 1])
+    }
+
+    /// Set up the next matchup, passing byes through and opening new rounds
+    /// as old ones finish, or crown the champion.
+    fn next_matchup(&mut self, now: Instant) {
+        self.matchup = None;
+        loop {
+            let (entrants, winners) = self.current();
+            let (entrants, winners) = (entrants.len(), winners.len());
+            if winners * 2 >= entrants {
+                if winners == 1 {
+                    let champion = self.rounds[self.rounds.len() - 1][0].clone();
+                    tracing::info!(%champion, "tournament won");
+                    self.champion = Some(champion);
+                    return;
+                }
+                self.rounds.push(Vec::new());
+                continue;
+            }
+            let (entrants, _) = self.current();
+            let first = entrants[winners * 2].clone();
+            let Some(second) = entrants.get(winners * 2 + 1).cloned() else {
+                self.winners().push(first);
+                continue;
+            };
+            let pack = AnswerPack {
+                id: "bracket".to_string(),
+                title: "Tournament".to_string(),
+                description: String::new(),
+                answers: [first, second].map(entrant).into(),
+            };
+            self.matchup = Some(pack);
+            let winner = rand::thread_rng().gen_range(0..2);
+            self.duel.start(winner, 2, now);
+            return;
+        }
+    }
+
+    fn winners(&mut self) -> &mut Vec<String> {
+        let last = self.rounds.len() - 1;
+        &mut self.rounds[last]
+    }
+
+    /// Advance the matchup on show: a winner goes through when the lights
+    /// land, and the next matchup starts when it stops flashing.
+    pub fn tick(&mut self, now: Instant) -> Option<Step> {
+        let step = self.duel.tick(now)?;
+        match step {
+            Step::Landed(index) => {
+                if let Some(matchup) = &self.matchup {
+                    let winner = matchup.answers[index].text.clone();
+                    tracing::debug!(%winner, "matchup won");
+                    self.winners().push(winner);
+                }
+            }
+            Step::Cleared if self.running() => self.next_matchup(now),
+            _ => {}
+        }
+        Some(step)
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.options.len().saturating_sub(1));
+    }
+
+    pub fn start_add(&mut self) {
+        if self.options.len() < MAX_OPTIONS {
+            self.entry = Some(String::new());
+        }
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        if let Some(entry) = self.entry.as_mut() {
+            entry.push(c);
+        }
+    }
+
+    pub fn erase(&mut self) {
+        if let Some(entry) = self.entry.as_mut() {
+            entry.pop();
+        }
+    }
+
+    pub fn cancel_entry(&mut self) {
+        self.entry = None;
+    }
+
+    /// Add the typed option, unless it is empty or already entered.
+    pub fn confirm_entry(&mut self) {
+        let Some(text) = self.entry.take() else {
+            return;
+        };
+        let text = text.trim().to_string();
+        if text.is_empty()
+            || self
+                .options
+                .iter()
+                .any(|option| option.eq_ignore_ascii_case(&text))
+        {
+            return;
+        }
+        self.options.push(text);
+        self.selected = self.options.len() - 1;
+    }
+
+    pub fn remove(&mut self) {
+        if self.selected < self.options.len() {
+            self.options.remove(self.selected);
+        }
+        self.move_by(0);
+    }
+}
+
+impl Default for Bracket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn entrant(text: String) -> Answer {
+    Answer {
+        text,
+        sentiment: Sentiment::Neutral,
+        weight: 1,
+        style: AnswerStyle::default(),
+        icon: None,
+        opposite: None,
+        flavor: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_odd_one_out_gets_a_bye() {
+        let mut bracket = Bracket::new();
+        for option in ["Tacos", "Salad", "Ramen"] {
+            bracket.start_add();
+            option.chars().for_each(|c| bracket.type_char(c));
+            bracket.confirm_entry();
+        }
+        let mut now = Instant::now();
+        let tick = Duration::from_millis(10);
+        bracket.start(tick * 5, tick * 5, now);
+        let seeded = bracket.rounds[0].clone();
+
+        let mut matchups = 0;
+        while bracket.running() {
+            now += tick;
+            if let Some(Step::Landed(_)) = bracket.tick(now) {
+                matchups += 1;
+            }
+            assert!(matchups <= 2, "a three-way tournament has two matchups");
+        }
+
+        // The first two play, the third goes straight to the final.
+        assert_eq!(matchups, 2);
+        assert_eq!(bracket.rounds.len(), 3);
+        assert_eq!(bracket.rounds[1].len(), 2);
+        assert_eq!(bracket.rounds[1][1], seeded[2]);
+        assert!(seeded[..2].contains(&bracket.rounds[1][0]));
+        let champion = bracket.champion.clone().unwrap();
+        assert!(bracket.rounds[1].contains(&champion));
+        assert_eq!(bracket.rounds[2], [champion]);
+    }
+}
@@ -0,0 +1,143 @@
+//! Session recordings, made with `--record session.cast`: everything drawn
+//! to the terminal, with its timing, in the asciicast v2 format that
+//! `asciinema play` and the asciinema web player replay.
+//!
+//! [`Output`] stands in for stdout under the ratatui backend, so frames,
+//! bells and the escape sequences sent with [`crate::frontend::Frontend::write_raw`]
+//! all land in the recording exactly as the terminal got them.
+
+use chrono::Utc;
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::Instant,
+};
+
+/// The first line of a cast file.
+#[derive(Serialize)]
+struct Header<'a> {
+    version: u8,
+    width: u16,
+    height: u16,
+    timestamp: i64,
+    title: &'a str,
+    env: Env,
+}
+
+#[derive(Serialize)]
+struct Env {
+    #[serde(rename = "TERM", skip_serializing_if = "Option::is_none")]
+    term: Option<String>,
+}
+
+/// A cast file being written: one event line per flush of the terminal.
+pub struct Recorder {
+    file: BufWriter<File>,
+    started: Instant,
+    /// The terminal size last recorded, to notice resizes.
+    size: (u16, u16),
+    /// Bytes written to the terminal since the last event.
+    pending: Vec<u8>,
+}
+
+impl Recorder {
+    /// Create (or truncate) the cast file at `path` and write its header,
+    /// sized like the terminal is now.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let size = crossterm::terminal::size().unwrap_or((80, 24));
+        let header = Header {
+            version: 2,
+            width: size.0,
+            height: size.1,
+            timestamp: Utc::now().timestamp(),
+            title: "Executive Decision Maker",
+            env: Env {
+                term: std::env::var("TERM").ok(),
+            },
+        };
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, "{}", serde_json::to_string(&header)?)?;
+        file.flush()?;
+        Ok(Self {
+            file,
+            started: Instant::now(),
+            size,
+            pending: Vec::new(),
+        })
+    }
+
+    fn record(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+    }
+
+    /// One event line: seconds since the start, its code and its data.
+    fn event(&mut self, code: &str, data: &str) -> io::Result<()> {
+        // Microseconds, like asciinema itself writes.
+        let elapsed = (self.started.elapsed().as_secs_f64() * 1e6).round() / 1e6;
+        writeln!(
+            self.file,
+            "{}",
+            serde_json::to_string(&(elapsed, code, data))?
+        )
+    }
+
+    /// Write what the terminal got since the last flush as an output event,
+    /// after a resize event if the terminal changed size. A character cut
+    /// in half by the end of the buffer waits for the next flush.
+    fn flush(&mut self) -> io::Result<()> {
+        if let Ok(size) = crossterm::terminal::size() {
+            if size != self.size {
+                self.size = size;
+                self.event("r", &format!("{}x{}", size.0, size.1))?;
+            }
+        }
+        let end = match std::str::from_utf8(&self.pending) {
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            _ => self.pending.len(),
+        };
+        if end > 0 {
+            let text = String::from_utf8_lossy(&self.pending[..end]).into_owned();
+            self.pending.drain(..end);
+            self.event("o", &text)?;
+        }
+        self.file.flush()
+    }
+}
+
+/// Stdout, copied into a [`Recorder`] when there is one.
+pub struct Output {
+    stdout: io::Stdout,
+    recorder: Option<Recorder>,
+}
+
+impl Output {
+    pub fn new(recorder: Option<Recorder>) -> Self {
+        Self {
+            stdout: io::stdout(),
+            recorder,
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.stdout.write(buf)?;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    /// A recording that can't be written is given up on, with a warning in
+    /// the log; the session itself carries on.
+    fn flush(&mut self) -> io::Result<()> {
+        self.stdout.flush()?;
+        if let Some(Err(err)) = self.recorder.as_mut().map(Recorder::flush) {
+            tracing::warn!(%err, "recording stopped");
+            self.recorder = None;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+//! Chaos week: for scheduled stretches of days the oracle drops its manners.
+//! Weights are ignored, sealed answers and the "asked before?" check are
+//! off, and a CHAOS WEEK banner says so. Schedule one in the config:
+//!
+//! ```toml
+//! [[chaos_week]]
+//! start = "2026-11-02"
+//! end = "2026-11-06"
+//! ```
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// A scheduled chaos week. Both ends are included.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ChaosWeek {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl ChaosWeek {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// The rules a draw follows: the configured ones, overridden wholesale
+/// during a chaos week.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rules {
+    /// Honour answer weights.
+    pub weighted: bool,
+    /// Skew the weights toward positive (above 0) or negative answers.
+    pub optimism: i32,
+    /// Same question, same day, same answer.
+    pub daily_seal: bool,
+    /// Offer the earlier verdict when a question is asked again.
+    pub recall: bool,
+    /// A chaos week is on.
+    pub chaos: bool,
+}
+
+impl Rules {
+    pub fn new(daily_seal: bool, optimism: i32, schedule: &[ChaosWeek], date: NaiveDate) -> Self {
+        if schedule.iter().any(|week| week.contains(date)) {
+            Self {
+                weighted: false,
+                optimism: 0,
+                daily_seal: false,
+                recall: false,
+                chaos: true,
+            }
+        } else {
+            Self {
+                weighted: true,
+                optimism,
+                daily_seal,
+                recall: true,
+                chaos: false,
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Slack and Discord incoming-webhook formatting and delivery.
+
+use crate::webhook::post_json;
+use clap::ValueEnum;
+use serde_json::json;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ChatService {
+    Slack,
+    Discord,
+}
+
+impl fmt::Display for ChatService {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChatService::Slack => "Slack",
+            ChatService::Discord => "Discord",
+        })
+    }
+}
+
+/// The announcement posted to the channel.
+pub fn format_message(answer: &str) -> String {
+    format!("🎱 The Executive Decision Maker says: {answer}")
+}
+
+/// Post `message` to the service's incoming webhook at `url`.
+pub fn post(service: ChatService, url: &str, message: &str) -> Result<(), String> {
+    let payload = match service {
+        ChatService::Slack => json!({ "text": message }),
+        ChatService::Discord => json!({ "content": message }),
+    };
+    post_json(url, &payload)
+}
@@ -0,0 +1,60 @@
+//! Copying the verdict to the system clipboard.
+//!
+//! The text is always sent as an OSC 52 escape sequence, which the terminal
+//! itself forwards to the clipboard, so it works over SSH too. When running
+//! locally with the `system-clipboard` feature, `arboard` is used as well for
+//! terminals that ignore OSC 52. The sequence is written to whatever output
+//! the caller's front end drains, not straight to stdout.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use std::io::{self, Write};
+
+pub struct Clipboard {
+    #[cfg(feature = "system-clipboard")]
+    native: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "system-clipboard")]
+            native: if is_remote_session() {
+                None
+            } else {
+                arboard::Clipboard::new().ok()
+            },
+        }
+    }
+
+    /// OSC 52 only, for sessions whose terminal is not on this machine (the
+    /// `edm ssh` server).
+    pub fn terminal_only() -> Self {
+        Self {
+            #[cfg(feature = "system-clipboard")]
+            native: None,
+        }
+    }
+
+    /// Copy `text`, writing the OSC 52 sequence to `terminal`.
+    pub fn copy(&mut self, text: &str, terminal: &mut impl Write) -> io::Result<()> {
+        write!(terminal, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+
+        #[cfg(feature = "system-clipboard")]
+        if let Some(native) = self.native.as_mut() {
+            // OSC 52 already went out; a failure here is not worth reporting.
+            let _ = native.set_text(text.to_owned());
+        }
+        Ok(())
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "system-clipboard")]
+fn is_remote_session() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
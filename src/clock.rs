@@ -0,0 +1,235 @@
+//! Time sources for the animation state machine.
+//!
+//! `App` never calls `Instant::now()` or `Local::now()` directly; it asks its
+//! [`Clock`]. This keeps the timers and cooldowns swappable, e.g. for the
+//! `--simulate-speed` debug flag, or a [`ManualClock`] that a test steps by
+//! hand:
+//!
+//! ```
+//! use executive_decision_maker::{
+//!     answers, clock::ManualClock, config::Config, history::History, theme, App, Engine,
+//! };
+//!
+//! let config = Config::default();
+//! let pack = answers::builtin("classic").unwrap();
+//! let engine = Engine::new(pack, None, History::default(), &config);
+//! let theme = theme::builtin_themes().remove(0);
+//! let clock = ManualClock::new();
+//! let mut app = App::new(engine, theme, config, None, Box::new(clock.clone()));
+//! app.ask();
+//! clock.advance(app.config.animation());
+//! app.tick();
+//! assert!(app.last_answer.is_some());
+//! ```
+
+use chrono::{DateTime, Local};
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Anything that can tell the current time.
+pub trait Clock {
+    fn now(&self) -> Instant;
+
+    /// The date and time on the wall, moving along with [`Clock::now`]: for
+    /// cooldowns, expiry, and anything else measured in hours or days.
+    fn local_now(&self) -> DateTime<Local>;
+
+    /// How long to wait in real time for `span` of this clock to pass.
+    fn wait_for(&self, span: Duration) -> Duration {
+        span
+    }
+}
+
+/// The real wall clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn local_now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+/// A clock that runs `factor` times faster than real time, starting from the
+/// moment it was created.
+pub struct ScaledClock {
+    origin: Instant,
+    local_origin: DateTime<Local>,
+    factor: f64,
+}
+
+impl ScaledClock {
+    pub fn new(factor: f64) -> Self {
+        Self {
+            origin: Instant::now(),
+            local_origin: Local::now(),
+            factor,
+        }
+    }
+}
+
+impl Clock for ScaledClock {
+    fn now(&self) -> Instant {
+        self.origin + self.origin.elapsed().mul_f64(self.factor)
+    }
+
+    fn local_now(&self) -> DateTime<Local> {
+        after(self.local_origin, self.now() - self.origin)
+    }
+
+    fn wait_for(&self, span: Duration) -> Duration {
+        span.div_f64(self.factor)
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so one
+/// can be handed to the `App` and the other kept to step it.
+#[derive(Clone)]
+pub struct ManualClock {
+    origin: Instant,
+    local_origin: DateTime<Local>,
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Start at the current instant.
+    pub fn new() -> Self {
+        Self::at(Local::now())
+    }
+
+    /// Start with the wall clock showing `local`.
+    pub fn at(local: DateTime<Local>) -> Self {
+        let origin = Instant::now();
+        Self {
+            origin,
+            local_origin: local,
+            now: Arc::new(Mutex::new(origin)),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner) += by;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.now.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn local_now(&self) -> DateTime<Local> {
+        after(self.local_origin, self.now() - self.origin)
+    }
+}
+
+/// `local` moved on by `span`.
+fn after(local: DateTime<Local>, span: Duration) -> DateTime<Local> {
+    local + chrono::Duration::from_std(span).unwrap_or(chrono::Duration::MAX)
+}
+
+/// Parse a speed factor such as `10x`, `2.5x`, or plain `4`.
+pub fn parse_speed(raw: &str) -> Result<f64, String> {
+    let trimmed = raw.trim();
+    let number = trimmed
+        .strip_suffix('x')
+        .or_else(|| trimmed.strip_suffix('X'))
+        .unwrap_or(trimmed);
+    match number.parse::<f64>() {
+        Ok(factor) if factor.is_finite() && factor > 0.0 => Ok(factor),
+        _ => Err(format!("invalid speed `{raw}` (expected e.g. `10x`)")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers, config::Config, history::History, theme, widget::State, App, Engine};
+
+    fn app_on(clock: &ManualClock) -> App {
+        let config = Config::default();
+        let pack = answers::builtin("classic").expect("classic pack");
+        let engine = Engine::new(pack, None, History::default(), &config);
+        let theme = theme::builtin_themes().remove(0);
+        App::new(engine, theme, config, None, Box::new(clock.clone()))
+    }
+
+    #[test]
+    fn manual_clock_only_moves_when_told() {
+        let clock = ManualClock::new();
+        let other = clock.clone();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+        let local = clock.local_now();
+        other.advance(Duration::from_millis(250));
+        assert_eq!(clock.now() - start, Duration::from_millis(250));
+        assert_eq!(
+            clock.local_now() - local,
+            chrono::Duration::milliseconds(250)
+        );
+        assert_eq!(
+            clock.wait_for(Duration::from_secs(1)),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn ask_tick_and_land_on_a_manual_clock() {
+        let clock = ManualClock::new();
+        let mut app = app_on(&clock);
+        let (shuffle, flash) = (app.config.animation(), app.config.flash());
+
+        app.ask();
+        app.tick();
+        assert!(matches!(app.decision.state(), State::Animating { .. }));
+        assert_eq!(app.last_answer, None);
+
+        clock.advance(shuffle - Duration::from_millis(1));
+        app.tick();
+        assert!(matches!(app.decision.state(), State::Animating { .. }));
+
+        clock.advance(Duration::from_millis(1));
+        app.tick();
+        let State::Showing { index, .. } = app.decision.state() else {
+            panic!(
+                "expected the answer to land, got {:?}",
+                app.decision.state()
+            );
+        };
+        assert_eq!(app.last_answer, Some(index));
+        assert_eq!(app.engine.history.session().len(), 1);
+
+        clock.advance(flash);
+        app.tick();
+        assert_eq!(app.decision.state(), State::Idle);
+        assert_eq!(app.last_answer, Some(index));
+    }
+
+    #[test]
+    fn scaled_clock_waits_less_in_real_time() {
+        let clock = ScaledClock::new(4.0);
+        assert_eq!(
+            clock.wait_for(Duration::from_secs(2)),
+            Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn speeds_parse_with_or_without_x() {
+        assert_eq!(parse_speed("10x"), Ok(10.0));
+        assert_eq!(parse_speed(" 2.5X "), Ok(2.5));
+        assert_eq!(parse_speed("4"), Ok(4.0));
+        assert!(parse_speed("0x").is_err());
+        assert!(parse_speed("fast").is_err());
+    }
+}
@@ -0,0 +1,123 @@
+//! Command palette, opened with Ctrl+P: every action by name, narrowed down
+//! by a fuzzy match as you type, with its keys alongside. Enter runs the
+//! selected one as if its key had been pressed.
+
+use crate::input::Action;
+use crate::keymap::BINDABLE;
+
+/// Something the palette can run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// A bindable action; the index into [`BINDABLE`] gives its keys.
+    Action(Action, usize),
+    /// Go to the next bell setting: every light, answers only, off.
+    CycleSound,
+}
+
+/// Bindable actions that only make sense on a particular screen, or that
+/// would just reopen the palette, are left out.
+const HIDDEN: [Action; 7] = [
+    Action::Back,
+    Action::Up,
+    Action::Down,
+    Action::Increase,
+    Action::Decrease,
+    Action::NextTab,
+    Action::OpenPalette,
+];
+
+/// Every command, in the order listed before anything is typed.
+pub fn commands() -> Vec<(Command, &'static str)> {
+    BINDABLE
+        .iter()
+        .enumerate()
+        .filter(|(_, (action, _, _))| !HIDDEN.contains(action))
+        .map(|(slot, &(action, _, label))| (Command::Action(action, slot), label))
+        .chain([(Command::CycleSound, "Cycle sound")])
+        .collect()
+}
+
+pub struct CommandPalette {
+    pub query: String,
+    /// The commands matching the query, best first.
+    pub matches: Vec<(Command, &'static str)>,
+    /// Index into `matches`.
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            matches: commands(),
+            selected: 0,
+        }
+    }
+
+    fn refresh(&mut self) {
+        let mut scored: Vec<_> = commands()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(order, (command, label))| {
+                fuzzy_score(label, &self.query).map(|score| (score, order, command, label))
+            })
+            .collect();
+        scored.sort_by_key(|&(score, order, _, _)| (std::cmp::Reverse(score), order));
+        self.matches = scored
+            .into_iter()
+            .map(|(_, _, command, label)| (command, label))
+            .collect();
+        self.selected = 0;
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        self.query.push(c);
+        self.refresh();
+    }
+
+    pub fn erase(&mut self) {
+        self.query.pop();
+        self.refresh();
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.matches.len().saturating_sub(1));
+    }
+
+    /// The command Enter would run.
+    pub fn chosen(&self) -> Option<Command> {
+        self.matches.get(self.selected).map(|&(command, _)| command)
+    }
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How well `query` matches `label`, ignoring case and spaces in the query:
+/// `None` unless its letters all appear in order. Letters starting a word
+/// and runs of consecutive letters score higher; gaps cost a little.
+pub fn fuzzy_score(label: &str, query: &str) -> Option<i32> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + label[position..].iter().position(|&c| c == wanted)?;
+        let word_start = found == 0 || !label[found - 1].is_alphanumeric();
+        score += match (word_start, previous) {
+            (true, _) => 10,
+            (false, Some(previous)) if previous + 1 == found => 5,
+            _ => 1,
+        };
+        score -= (found - position).min(5) as i32;
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
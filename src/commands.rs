@@ -0,0 +1,454 @@
+//! Non-interactive subcommands.
+
+use crate::{
+    answers::{self, AnswerPack, PackFile, EXPORT_EXTENSION},
+    chaos::Rules,
+    chat::{self, ChatService},
+    config::{self, Config},
+    config_check, download,
+    error::{EdmError, Result},
+    events,
+    export::Exporter,
+    fairness, heatmap,
+    history::{Decision, History},
+    pack_check::{self, Level},
+    paths, seal,
+    stats::Summary,
+    tags,
+};
+use chrono::{Local, NaiveDate};
+use clap_complete::{env::Shells, CompletionCandidate, Shell};
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// `edm ask`: print a single verdict, optionally announcing it in chat.
+pub fn ask(
+    pack: &AnswerPack,
+    config: &Config,
+    question: Option<&str>,
+    post: Option<ChatService>,
+) -> Result<()> {
+    let rules = Rules::new(
+        config.daily_seal,
+        config.optimism(),
+        &config.chaos_weeks,
+        Local::now().date_naive(),
+    );
+    let draw = seal::draw(pack, question, rules);
+    let answer = &pack.answers[draw.index];
+    println!("{}", answer.text);
+    if draw.sealed {
+        eprintln!("(sealed: this question gets the same answer all day)");
+    }
+
+    if let Some(service) = post {
+        let url = match service {
+            ChatService::Slack => config.slack_webhook_url.as_deref(),
+            ChatService::Discord => config.discord_webhook_url.as_deref(),
+        }
+        .ok_or_else(|| {
+            EdmError::Network(format!(
+                "no {} webhook configured (set `{}_webhook_url` in the config file)",
+                service,
+                service.to_string().to_lowercase()
+            ))
+        })?;
+        chat::post(service, url, &chat::format_message(&answer.text))
+            .map_err(|err| EdmError::Network(format!("posting to {service} failed: {err}")))?;
+    }
+    Ok(())
+}
+
+/// `edm history`: the last `limit` decisions tagged `tag` (all of them for
+/// `None`), newest first, then today's tally.
+pub fn history(history: &History, tag: Option<&str>, limit: usize) -> Result<()> {
+    let mut out = io::stdout().lock();
+    for decision in history.tagged(tag).take(limit) {
+        let mut line = format!(
+            "{}  {}",
+            decision.decided_at.format("%Y-%m-%d %H:%M"),
+            decision.answer
+        );
+        if let Some(question) = &decision.question {
+            line.push_str(&format!("  “{question}”"));
+        }
+        if !decision.tags.is_empty() {
+            line.push_str(&format!("  {}", tags::format(&decision.tags)));
+        }
+        writeln!(out, "{line}")?;
+        for note in decision.note.iter().flat_map(|note| note.lines()) {
+            writeln!(out, "    {note}")?;
+        }
+    }
+    let now = Local::now();
+    let (agreed, asked) = history.today_agreement(tag, now);
+    let mut summary = format!(
+        "{} decision(s); today: {}",
+        history.tagged(tag).count(),
+        history.today_tally(tag, now)
+    );
+    if asked > 0 {
+        summary.push_str(&format!("; second opinions agreed {agreed}/{asked}"));
+    }
+    writeln!(out, "{summary}")?;
+    Ok(())
+}
+
+/// The decisions `edm history export` writes: those tagged `tag` (all of
+/// them for `None`) from `since` on, oldest first.
+pub fn exported<'a>(
+    history: &'a History,
+    tag: Option<&'a str>,
+    since: Option<NaiveDate>,
+) -> Vec<&'a Decision> {
+    let mut decisions: Vec<_> = history
+        .tagged(tag)
+        .filter(|decision| since.is_none_or(|since| decision.decided_at.date_naive() >= since))
+        .collect();
+    decisions.reverse();
+    decisions
+}
+
+/// `edm stats`: a [`Summary`] of `decisions`, oldest first, and a heatmap
+/// of the last `weeks` weeks.
+pub fn stats(decisions: &[&Decision], weeks: usize) -> Result<()> {
+    let today = Local::now().date_naive();
+    let mut out = io::stdout().lock();
+    write!(out, "{}", Summary::new(decisions, today))?;
+    writeln!(out)?;
+    let counts = heatmap::counts(decisions.iter().copied());
+    for line in heatmap::render(&counts, today, weeks) {
+        writeln!(out, "{line}")?;
+    }
+    Ok(())
+}
+
+/// `edm stats --fairness`: audit the draws from `pack` among `decisions`,
+/// oldest first; see [`fairness`].
+pub fn fairness(pack: &AnswerPack, decisions: &[&Decision]) -> Result<()> {
+    print!("{}", fairness::audit(pack, decisions));
+    Ok(())
+}
+
+/// `edm history merge <file>`: fold another machine's copy of the history
+/// into this one.
+pub fn history_merge(history: &mut History, file: &Path, passphrase: Option<&str>) -> Result<()> {
+    let added = history.merge(file, passphrase)?;
+    println!("Merged {added} new decision(s) from {}.", file.display());
+    Ok(())
+}
+
+/// `edm history export`: `decisions` in `exporter`'s format, to `path`
+/// or, for `-`, standard output.
+pub fn history_export(decisions: &[&Decision], exporter: &dyn Exporter, path: &Path) -> Result<()> {
+    let text = exporter.render(decisions);
+    if path == Path::new("-") {
+        io::stdout().lock().write_all(text.as_bytes())?;
+        return Ok(());
+    }
+    fs::write(path, text).map_err(|source| EdmError::Export {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    eprintln!(
+        "Wrote {} decision(s) to {}.",
+        decisions.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// `edm pack test <file>`: run the contributor checks and fail on any
+/// failing one.
+pub fn pack_test(path: &Path) -> Result<()> {
+    let pack = PackFile::load(path)?;
+    println!(
+        "Checking {} ({}, {} answers)",
+        path.display(),
+        pack.title,
+        pack.answers.len()
+    );
+
+    let findings = pack_check::check(&pack);
+    for finding in &findings {
+        let label = match finding.level {
+            Level::Ok => "ok  ",
+            Level::Warn => "warn",
+            Level::Fail => "FAIL",
+        };
+        println!("  {label}  {}: {}", finding.check, finding.message);
+    }
+
+    let count = |level| findings.iter().filter(|f| f.level == level).count();
+    let (failures, warnings) = (count(Level::Fail), count(Level::Warn));
+    println!("{failures} failure(s), {warnings} warning(s)");
+    if failures > 0 {
+        return Err(EdmError::PackChecks(path.to_path_buf()));
+    }
+    Ok(())
+}
+
+/// `edm pack list`: every pack installed from `dirs`, and where it comes
+/// from.
+pub fn pack_list(dirs: &[PathBuf]) -> Result<()> {
+    let (packs, errors) = answers::installed_packs(dirs);
+    let id_width = packs.iter().map(|pack| pack.id.len()).max().unwrap_or(0);
+    let title_width = packs
+        .iter()
+        .map(|pack| pack.title.chars().count())
+        .max()
+        .unwrap_or(0);
+    for pack in &packs {
+        let source = match answers::find_pack_file(dirs, &pack.id) {
+            Some((path, file)) => match &file.author {
+                Some(author) => format!("{} (by {author})", path.display()),
+                None => path.display().to_string(),
+            },
+            None => "built-in".to_string(),
+        };
+        println!(
+            "{:<id_width$}  {:<title_width$}  {:>3} answers  {source}",
+            pack.id,
+            pack.title,
+            pack.answers.len()
+        );
+    }
+    for error in &errors {
+        eprintln!("skipped {error}");
+    }
+    Ok(())
+}
+
+/// `edm pack export <id>`: write `pack` as an `.edmpack` to share. `dirs`
+/// are searched for the pack's file, whose author and translations are
+/// kept.
+pub fn pack_export(
+    pack: &AnswerPack,
+    dirs: &[PathBuf],
+    output: Option<&Path>,
+    author: Option<&str>,
+) -> Result<()> {
+    let previous = answers::find_pack_file(dirs, &pack.id).map(|(_, file)| file);
+    let mut file = PackFile::from_pack(pack, previous.as_ref());
+    if let Some(author) = author {
+        file.author = Some(author.to_string());
+    }
+    let path = output
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(format!("{}.{EXPORT_EXTENSION}", pack.id)));
+    file.save(&path).map_err(|source| EdmError::PackWrite {
+        path: path.clone(),
+        source,
+    })?;
+    println!("Exported {} to {}", pack.title, path.display());
+    Ok(())
+}
+
+/// `edm pack import <file>`: install a shared pack into `dir`, refusing
+/// broken packs and, unless `force` is on, ids already installed from
+/// `dirs`.
+pub fn pack_import(path: &Path, dir: &Path, dirs: &[PathBuf], force: bool) -> Result<()> {
+    let file = PackFile::load(path)?;
+    check_installable(&file, path, dirs, force)?;
+    let target = install(&file, dir)?;
+    println!(
+        "Imported {} as `{}` into {}",
+        file.title,
+        file.id,
+        target.display()
+    );
+    Ok(())
+}
+
+/// `edm pack install <url>`: download a pack into `dir`, showing its
+/// SHA-256 before installing. With `sha256` the download must match it;
+/// otherwise the user confirms, unless `yes` is on.
+pub fn pack_install(
+    url: &str,
+    dir: &Path,
+    dirs: &[PathBuf],
+    sha256: Option<&str>,
+    force: bool,
+    yes: bool,
+) -> Result<()> {
+    let download = download::fetch(url)?;
+    println!("Downloaded {} bytes from {url}", download.bytes.len());
+    println!("SHA-256: {}", download.sha256);
+    if let Some(expected) = sha256 {
+        if !expected.trim().eq_ignore_ascii_case(&download.sha256) {
+            return Err(EdmError::Checksum {
+                expected: expected.trim().to_string(),
+                actual: download.sha256,
+            });
+        }
+    }
+
+    let origin = Path::new(url);
+    let file = PackFile::parse(&String::from_utf8_lossy(&download.bytes), origin)?;
+    check_installable(&file, origin, dirs, force)?;
+    match &file.author {
+        Some(author) => println!("{} by {author}", file.title),
+        None => println!("{}", file.title),
+    }
+    println!("  id `{}`, {} answers", file.id, file.answers.len());
+    if sha256.is_none() && !yes && !confirm("Install it?")? {
+        println!("Not installed.");
+        return Ok(());
+    }
+    let target = install(&file, dir)?;
+    println!("Installed into {}", target.display());
+    Ok(())
+}
+
+/// Fail on a pack that would not load, or whose id is already installed
+/// from `dirs` unless `force` is on.
+fn check_installable(file: &PackFile, origin: &Path, dirs: &[PathBuf], force: bool) -> Result<()> {
+    let failures: Vec<_> = pack_check::structure(file)
+        .into_iter()
+        .filter(|finding| finding.level == Level::Fail)
+        .collect();
+    for finding in &failures {
+        println!("  FAIL  {}: {}", finding.check, finding.message);
+    }
+    if !failures.is_empty() {
+        return Err(EdmError::PackChecks(origin.to_path_buf()));
+    }
+    let (installed, _) = answers::installed_packs(dirs);
+    if !force && installed.iter().any(|pack| pack.id == file.id) {
+        return Err(EdmError::PackExists(file.id.clone()));
+    }
+    Ok(())
+}
+
+/// Write `file` into `dir`, over any file there with the same id.
+fn install(file: &PackFile, dir: &Path) -> Result<PathBuf> {
+    let target = answers::pack_file(dir, &file.id);
+    file.save(&target).map_err(|source| EdmError::PackWrite {
+        path: target.clone(),
+        source,
+    })?;
+    Ok(target)
+}
+
+/// Ask `question` on the terminal; only `y` or `yes` agrees.
+fn confirm(question: &str) -> Result<bool> {
+    print!("{question} [y/N] ");
+    io::stdout().flush()?;
+    let mut reply = String::new();
+    io::stdin().read_line(&mut reply)?;
+    Ok(matches!(reply.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// `edm config init`: write the commented default config to `path`, unless
+/// something is there already and `force` is off.
+pub fn config_init(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        return Err(EdmError::ConfigExists(path.to_path_buf()));
+    }
+    let write = || {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, config::TEMPLATE)
+    };
+    write().map_err(|source| EdmError::ConfigWrite {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// `edm config check`: report what in the config at `path` would be
+/// ignored or rejected, failing if anything would.
+pub fn config_check(path: &Path, pack_dirs: &[PathBuf]) -> Result<()> {
+    let text = fs::read_to_string(path).map_err(|source| EdmError::ConfigRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let problems = config_check::check(&text, path.parent(), pack_dirs);
+    for problem in &problems {
+        let label = match problem.level {
+            Level::Ok => continue,
+            Level::Warn => "warning",
+            Level::Fail => "error",
+        };
+        match problem.line {
+            Some(line) => println!("{}:{line}: {label}: {}", path.display(), problem.message),
+            None => println!("{}: {label}: {}", path.display(), problem.message),
+        }
+        if let Some(help) = &problem.help {
+            println!("  help: {help}");
+        }
+    }
+
+    let count = |level| problems.iter().filter(|p| p.level == level).count();
+    let (errors, warnings) = (count(Level::Fail), count(Level::Warn));
+    println!("{errors} error(s), {warnings} warning(s)");
+    if errors > 0 {
+        return Err(EdmError::ConfigChecks(path.to_path_buf()));
+    }
+    Ok(())
+}
+
+/// `edm completions <shell>`: the script that hooks `edm` into `shell`'s
+/// completion. The shell calls back into `edm` on every Tab, so pack ids
+/// and profile names are always the ones installed right then; see
+/// [`pack_candidates`] and [`profile_candidates`].
+pub fn completions(shell: Shell) -> Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell.to_string())
+        .ok_or_else(|| EdmError::UnsupportedShell(shell.to_string()))?;
+    completer.write_registration(COMPLETE_VAR, "edm", "edm", "edm", &mut io::stdout())?;
+    Ok(())
+}
+
+/// The variable that asks `edm` for completions instead of running.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// The config file and data directory completion looks in: the profile's,
+/// unless `EDM_CONFIG` or `EDM_DATA_DIR` say otherwise. Flags on the line
+/// being completed aren't seen.
+fn completion_paths() -> (Option<PathBuf>, Option<PathBuf>) {
+    let profile = std::env::var("EDM_PROFILE")
+        .ok()
+        .filter(|profile| paths::valid_profile(profile))
+        .unwrap_or_else(|| paths::DEFAULT_PROFILE.to_string());
+    let data = std::env::var_os("EDM_DATA_DIR").map(PathBuf::from);
+    let config = std::env::var_os("EDM_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| paths::config_file(&profile));
+    (config, paths::profile_data_dir(data.as_deref(), &profile))
+}
+
+/// The ids of the installed packs, with their titles, for completing
+/// `--pack` and friends.
+pub fn pack_candidates() -> Vec<CompletionCandidate> {
+    let (config, data) = completion_paths();
+    let (packs, _) =
+        answers::installed_packs(&paths::pack_dirs(config.as_deref(), data.as_deref()));
+    packs
+        .into_iter()
+        .map(|pack| CompletionCandidate::new(pack.id).help(Some(pack.title.into())))
+        .collect()
+}
+
+/// The names of the existing profiles, for completing `--profile`.
+pub fn profile_candidates() -> Vec<CompletionCandidate> {
+    let data = std::env::var_os("EDM_DATA_DIR").map(PathBuf::from);
+    paths::profiles(data.as_deref())
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// `edm events schema`: the JSON Schema of the event stream.
+pub fn events_schema() -> Result<()> {
+    let schema = serde_json::to_string_pretty(&events::schema()).expect("the schema serializes");
+    println!("{schema}");
+    Ok(())
+}
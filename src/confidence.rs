@@ -0,0 +1,28 @@
+//! The confidence meter: how sure the oracle claims to be, shown as a gauge
+//! under the verdict and kept in the history. Pure theatre: a random
+//! percentage, nudged up for answers the pack weights heavily and down for
+//! neutral ones.
+
+use crate::{answers::AnswerPack, sentiment::Sentiment};
+use rand::Rng;
+
+/// How confident the oracle is in the answer at `index`, in percent.
+pub fn draw(pack: &AnswerPack, index: usize) -> u8 {
+    let heaviest = pack
+        .answers
+        .iter()
+        .map(|answer| answer.weight)
+        .max()
+        .unwrap_or(1);
+    let answer = &pack.answers[index];
+    // 1 for the most likely answers, less for long shots.
+    let share = f64::from(answer.weight) / f64::from(heaviest.max(1));
+    let hedge = if answer.sentiment == Sentiment::Neutral {
+        20.0
+    } else {
+        0.0
+    };
+    let low = 50.0 + 20.0 * share - hedge;
+    let high = 80.0 + 19.0 * share - hedge;
+    rand::thread_rng().gen_range(low..=high).round() as u8
+}
@@ -0,0 +1,632 @@
+//! User configuration, stored as TOML.
+//!
+//! Every plain setting can also come from an `EDM_<SETTING>` environment
+//! variable (`EDM_THEME=Ocean`, `EDM_DAILY_SEAL=1`), which beats the file;
+//! command-line flags beat both. `EDM_KEYS_<ACTION>` takes a comma-separated
+//! list of keys and `EDM_EXPERIMENTAL_<NAME>` switches an experiment. Values
+//! from the environment are never written back to the file.
+
+use crate::{
+    best_of,
+    chaos::ChaosWeek,
+    encryption,
+    error::{EdmError, Result},
+    theme::StyleEntry,
+    widget::{ANIMATION_DURATION_MS, ANSWER_FLASH_MS},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap, fmt, fs, io, ops::RangeInclusive, path::Path, str::FromStr,
+    time::Duration,
+};
+
+/// The fully commented default config written by `edm config init`.
+pub const TEMPLATE: &str = include_str!("../config.example.toml");
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Answer pack used at startup. Unset until the onboarding gallery (or
+    /// the user) picks one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_pack: Option<String>,
+
+    /// Every completed decision is POSTed here as JSON.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+
+    /// Slack incoming webhook used by `edm ask --post slack`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slack_webhook_url: Option<String>,
+
+    /// Discord webhook used by `edm ask --post discord`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discord_webhook_url: Option<String>,
+
+    /// Colour theme, by name. Built-ins or files in `themes/` next to this
+    /// config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+
+    /// The high-contrast theme is switched on (F2), overriding `theme`
+    /// until it is switched off again.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub high_contrast: bool,
+
+    /// Show a Latin line under answers in another script, using this
+    /// scheme; see `transliteration.rs`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transliteration: Option<String>,
+
+    /// Pack consulted for a second opinion after every answer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub second_opinion_pack: Option<String>,
+
+    /// The same question asked on the same day always gets the same answer.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub daily_seal: bool,
+
+    /// Date ranges when weights, seals, and recall are switched off; see
+    /// `chaos.rs`.
+    #[serde(rename = "chaos_week", skip_serializing_if = "Vec::is_empty")]
+    pub chaos_weeks: Vec<ChaosWeek>,
+
+    /// Printed after the TUI exits instead of the last decision; empty for
+    /// nothing. See `exit_summary.rs` for placeholders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_summary: Option<String>,
+
+    /// Answer weights per pack id, by answer text. Answers not listed have
+    /// weight 1.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub weights: BTreeMap<String, BTreeMap<String, u32>>,
+
+    /// Button colours by answer text, in any pack; they beat the theme's and
+    /// the pack's.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub styles: BTreeMap<String, StyleEntry>,
+
+    /// Key bindings that differ from the built-in ones; see `keymap.rs`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, Vec<String>>,
+
+    /// How long the lights shuffle, in milliseconds; see [`TIMING_MS`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub animation_ms: Option<u64>,
+
+    /// How long the answer stays lit afterwards, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flash_ms: Option<u64>,
+
+    /// Drumroll reveal: the board goes dark for this many milliseconds
+    /// after the shuffle while a bar charges up. Unset reveals at once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspense_ms: Option<u64>,
+
+    /// When the terminal bell rings.
+    #[serde(skip_serializing_if = "Sound::is_default")]
+    pub sound: Sound,
+
+    /// Keep shuffling while the terminal is in the background instead of
+    /// pausing until it comes back. Needed for background notifications.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub animate_unfocused: bool,
+
+    /// The terminal reorders right-to-left text itself (GNOME Terminal,
+    /// Konsole, mlterm), so `edm` must not; see `locale.rs`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub terminal_bidi: bool,
+
+    /// Announce landed answers with an OSC 9 terminal notification.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub osc_notifications: bool,
+
+    /// Show the shuffle as a ConEmu / Windows Terminal progress bar.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub osc_progress: bool,
+
+    /// Capture the mouse for scrolling and clicks; see `mouse.rs`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub mouse: bool,
+
+    /// Save screenshots (`S`) as HTML instead of SVG.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub screenshot_html: bool,
+
+    /// Experiments switched on or off by name; see `experiments.rs`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub experimental: BTreeMap<String, bool>,
+
+    /// Typed to unlock the screen after `L`. Stored as plain text: it keeps
+    /// passers-by out, not anyone with access to this file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_passphrase: Option<String>,
+
+    /// Keep the history file encrypted under a passphrase asked for at
+    /// startup; see `encryption.rs`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub encrypt_history: bool,
+
+    /// How many answers `r` may veto and re-roll per day; see
+    /// [`DEFAULT_VETO_BUDGET`]. 0 turns vetoes off.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub veto_budget: Option<u32>,
+
+    /// Show the answer's opposite dimly after the reveal, when the pack
+    /// names one.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub devils_advocate: bool,
+
+    /// Commitment mode: asking nearly the same question again within this
+    /// many hours shows the earlier answer instead of re-rolling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment_hours: Option<u32>,
+
+    /// Consult the oracle this many times per ask and go with the answer
+    /// drawn most often; see `best_of.rs`. 1 (the default) asks once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_of: Option<u32>,
+
+    /// Decision deadline: an answer waits this many seconds for Enter, then
+    /// commits itself to the history. Unset commits at once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timebox_secs: Option<u32>,
+
+    /// API key for the rationale panel (`llm` feature): with one set, each
+    /// answer gets a short justification from a language model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_api_key: Option<String>,
+
+    /// OpenAI-compatible chat completions endpoint; see
+    /// [`rationale::DEFAULT_URL`](crate::rationale::DEFAULT_URL).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_url: Option<String>,
+
+    /// Model asked for the rationale; see
+    /// [`rationale::DEFAULT_MODEL`](crate::rationale::DEFAULT_MODEL).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_model: Option<String>,
+
+    /// Skews draws toward positive answers (up to [`MAX_OPTIMISM`]) or
+    /// negative ones (down to its negative); 0 leaves the weights alone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimism: Option<i32>,
+
+    /// Let answers whose decisions were marked as having worked out come up
+    /// more often; see [`adapt`](crate::adapt).
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub adapt_weights: bool,
+
+    /// Settings taken from the environment: key, the file's value, and the
+    /// environment's, so saving can put the file's own value back.
+    #[serde(skip)]
+    from_env: Vec<(String, Setting, Setting)>,
+}
+
+/// The shortest and longest `animation_ms`, `flash_ms`, and `suspense_ms`
+/// honoured; others are clamped.
+pub const TIMING_MS: RangeInclusive<u64> = 250..=10_000;
+
+/// Vetoes per day unless `veto_budget` says otherwise.
+pub const DEFAULT_VETO_BUDGET: u32 = 3;
+
+/// How far `optimism` goes either way.
+pub const MAX_OPTIMISM: i32 = 3;
+
+/// When the terminal bell rings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sound {
+    /// On every light, like the original.
+    #[default]
+    Lights,
+    /// Once, when the answer lands.
+    Answer,
+    Off,
+}
+
+impl Sound {
+    pub const ALL: [Sound; 3] = [Sound::Lights, Sound::Answer, Sound::Off];
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for Sound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Sound::Lights => "lights",
+            Sound::Answer => "answer",
+            Sound::Off => "off",
+        })
+    }
+}
+
+impl FromStr for Sound {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|sound| sound.to_string().eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| format!("expected lights, answer, or off, got `{text}`"))
+    }
+}
+
+/// Prefix of the environment variables read by [`Config::apply_env`].
+pub const ENV_PREFIX: &str = "EDM_";
+
+/// A setting's value, as it can be read from or put back into a [`Config`].
+#[derive(Clone, Debug, PartialEq)]
+enum Setting {
+    Text(Option<String>),
+    Flag(bool),
+    Millis(Option<u64>),
+    Count(Option<u32>),
+    Signed(Option<i32>),
+    Sound(Sound),
+    Keys(Option<Vec<String>>),
+    Experiment(Option<bool>),
+}
+
+impl Config {
+    /// How long the lights shuffle.
+    pub fn animation(&self) -> Duration {
+        timing(self.animation_ms.unwrap_or(ANIMATION_DURATION_MS))
+    }
+
+    /// How long the answer flashes.
+    pub fn flash(&self) -> Duration {
+        timing(self.flash_ms.unwrap_or(ANSWER_FLASH_MS))
+    }
+
+    /// How long the board stays dark before the reveal, if it does.
+    pub fn suspense(&self) -> Option<Duration> {
+        self.suspense_ms.map(timing)
+    }
+
+    /// How many answers may be vetoed per day.
+    pub fn veto_budget(&self) -> u32 {
+        self.veto_budget.unwrap_or(DEFAULT_VETO_BUDGET)
+    }
+
+    /// The optimism bias, within [`MAX_OPTIMISM`] either way.
+    pub fn optimism(&self) -> i32 {
+        self.optimism
+            .unwrap_or(0)
+            .clamp(-MAX_OPTIMISM, MAX_OPTIMISM)
+    }
+
+    /// How many rounds each ask takes, at most [`best_of::MAX_ROUNDS`].
+    pub fn best_of(&self) -> usize {
+        self.best_of
+            .map_or(1, |rounds| rounds as usize)
+            .clamp(1, best_of::MAX_ROUNDS)
+    }
+
+    /// How long an answer waits to be accepted; `None` (or 0) commits at
+    /// once.
+    pub fn timebox(&self) -> Option<u32> {
+        self.timebox_secs.filter(|&secs| secs > 0)
+    }
+
+    /// Whether the experiment called `name` is switched on.
+    pub fn experiment(&self, name: &str) -> bool {
+        self.experimental.get(name).copied().unwrap_or(false)
+    }
+
+    /// Load the config at `path`; a missing file yields the defaults.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|source| EdmError::ConfigParse {
+                path: path.to_path_buf(),
+                source: Box::new(source),
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(EdmError::ConfigRead {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        tracing::debug!(path = %path.display(), "saving config");
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = self.clone();
+        for (key, from_file, from_env) in self.from_env.iter().rev() {
+            // A setting changed in the app since startup is the user's
+            // choice now, so it is kept.
+            if file.get(key).as_ref() == Some(from_env) {
+                file.set(key, from_file.clone());
+            }
+        }
+        let text = toml::to_string_pretty(&file).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    /// Layer the `EDM_*` ones of the environment variables `vars`, such as
+    /// [`std::env::vars`], over the loaded file. Variables naming no setting
+    /// are logged and skipped; values that don't parse are an error.
+    pub fn apply_env(&mut self, vars: impl IntoIterator<Item = (String, String)>) -> Result<()> {
+        let mut vars: Vec<_> = vars
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect();
+        vars.sort();
+        for (name, value) in vars {
+            let key = name[ENV_PREFIX.len()..].to_ascii_lowercase();
+            let Some(current) = self.get(&key) else {
+                // `EDM_CONFIG` and friends belong to the command line.
+                if !ENV_FLAGS.contains(&name.as_str()) {
+                    tracing::warn!(%name, "no such setting; ignored");
+                }
+                continue;
+            };
+            let invalid = || EdmError::Env {
+                name: name.clone(),
+                message: format!("expected true or false, got `{value}`"),
+            };
+            let parsed = match current {
+                Setting::Text(_) => Setting::Text((!value.is_empty()).then(|| value.clone())),
+                Setting::Flag(_) => Setting::Flag(flag(&value).ok_or_else(invalid)?),
+                Setting::Millis(_) if value.trim().is_empty() => Setting::Millis(None),
+                Setting::Millis(_) => {
+                    Setting::Millis(Some(value.trim().parse().map_err(|_| EdmError::Env {
+                        name: name.clone(),
+                        message: format!("expected milliseconds, got `{value}`"),
+                    })?))
+                }
+                Setting::Count(_) if value.trim().is_empty() => Setting::Count(None),
+                Setting::Count(_) => {
+                    Setting::Count(Some(value.trim().parse().map_err(|_| EdmError::Env {
+                        name: name.clone(),
+                        message: format!("expected a whole number, got `{value}`"),
+                    })?))
+                }
+                Setting::Signed(_) if value.trim().is_empty() => Setting::Signed(None),
+                Setting::Signed(_) => {
+                    Setting::Signed(Some(value.trim().parse().map_err(|_| EdmError::Env {
+                        name: name.clone(),
+                        message: format!("expected a whole number, got `{value}`"),
+                    })?))
+                }
+                Setting::Sound(_) => {
+                    Setting::Sound(value.parse().map_err(|message| EdmError::Env {
+                        name: name.clone(),
+                        message,
+                    })?)
+                }
+                Setting::Keys(_) => Setting::Keys(Some(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|chord| !chord.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                )),
+                Setting::Experiment(_) => {
+                    Setting::Experiment(Some(flag(&value).ok_or_else(invalid)?))
+                }
+            };
+            tracing::debug!(%name, "setting taken from the environment");
+            self.set(&key, parsed.clone());
+            self.from_env.push((key, current, parsed));
+        }
+        Ok(())
+    }
+
+    /// The plain setting called `key` (as in the file), if there is one.
+    fn get(&self, key: &str) -> Option<Setting> {
+        if let Some(action) = key.strip_prefix("keys_") {
+            return Some(Setting::Keys(self.keys.get(action).cloned()));
+        }
+        if let Some(name) = key.strip_prefix("experimental_") {
+            return Some(Setting::Experiment(self.experimental.get(name).copied()));
+        }
+        let text = |value: &Option<String>| Some(Setting::Text(value.clone()));
+        match key {
+            "default_pack" => text(&self.default_pack),
+            "webhook_url" => text(&self.webhook_url),
+            "slack_webhook_url" => text(&self.slack_webhook_url),
+            "discord_webhook_url" => text(&self.discord_webhook_url),
+            "theme" => text(&self.theme),
+            "transliteration" => text(&self.transliteration),
+            "second_opinion_pack" => text(&self.second_opinion_pack),
+            "exit_summary" => text(&self.exit_summary),
+            "lock_passphrase" => text(&self.lock_passphrase),
+            "llm_api_key" => text(&self.llm_api_key),
+            "llm_url" => text(&self.llm_url),
+            "llm_model" => text(&self.llm_model),
+            "high_contrast" => Some(Setting::Flag(self.high_contrast)),
+            "daily_seal" => Some(Setting::Flag(self.daily_seal)),
+            "animate_unfocused" => Some(Setting::Flag(self.animate_unfocused)),
+            "terminal_bidi" => Some(Setting::Flag(self.terminal_bidi)),
+            "osc_notifications" => Some(Setting::Flag(self.osc_notifications)),
+            "osc_progress" => Some(Setting::Flag(self.osc_progress)),
+            "mouse" => Some(Setting::Flag(self.mouse)),
+            "screenshot_html" => Some(Setting::Flag(self.screenshot_html)),
+            "encrypt_history" => Some(Setting::Flag(self.encrypt_history)),
+            "devils_advocate" => Some(Setting::Flag(self.devils_advocate)),
+            "adapt_weights" => Some(Setting::Flag(self.adapt_weights)),
+            "animation_ms" => Some(Setting::Millis(self.animation_ms)),
+            "flash_ms" => Some(Setting::Millis(self.flash_ms)),
+            "suspense_ms" => Some(Setting::Millis(self.suspense_ms)),
+            "veto_budget" => Some(Setting::Count(self.veto_budget)),
+            "commitment_hours" => Some(Setting::Count(self.commitment_hours)),
+            "best_of" => Some(Setting::Count(self.best_of)),
+            "timebox_secs" => Some(Setting::Count(self.timebox_secs)),
+            "optimism" => Some(Setting::Signed(self.optimism)),
+            "sound" => Some(Setting::Sound(self.sound)),
+            _ => None,
+        }
+    }
+
+    /// Store `value` in the setting `key`; see [`Config::get`].
+    fn set(&mut self, key: &str, value: Setting) {
+        match value {
+            Setting::Keys(chords) => {
+                let action = key["keys_".len()..].to_string();
+                match chords {
+                    Some(chords) => self.keys.insert(action, chords),
+                    None => self.keys.remove(&action),
+                };
+            }
+            Setting::Experiment(on) => {
+                let name = key["experimental_".len()..].to_string();
+                match on {
+                    Some(on) => self.experimental.insert(name, on),
+                    None => self.experimental.remove(&name),
+                };
+            }
+            Setting::Text(text) => {
+                let field = match key {
+                    "default_pack" => &mut self.default_pack,
+                    "webhook_url" => &mut self.webhook_url,
+                    "slack_webhook_url" => &mut self.slack_webhook_url,
+                    "discord_webhook_url" => &mut self.discord_webhook_url,
+                    "theme" => &mut self.theme,
+                    "transliteration" => &mut self.transliteration,
+                    "second_opinion_pack" => &mut self.second_opinion_pack,
+                    "exit_summary" => &mut self.exit_summary,
+                    "lock_passphrase" => &mut self.lock_passphrase,
+                    "llm_api_key" => &mut self.llm_api_key,
+                    "llm_url" => &mut self.llm_url,
+                    "llm_model" => &mut self.llm_model,
+                    _ => return,
+                };
+                *field = text;
+            }
+            Setting::Millis(millis) => {
+                let field = match key {
+                    "animation_ms" => &mut self.animation_ms,
+                    "flash_ms" => &mut self.flash_ms,
+                    "suspense_ms" => &mut self.suspense_ms,
+                    _ => return,
+                };
+                *field = millis;
+            }
+            Setting::Count(count) => {
+                let field = match key {
+                    "veto_budget" => &mut self.veto_budget,
+                    "commitment_hours" => &mut self.commitment_hours,
+                    "best_of" => &mut self.best_of,
+                    "timebox_secs" => &mut self.timebox_secs,
+                    _ => return,
+                };
+                *field = count;
+            }
+            Setting::Signed(value) => {
+                if key == "optimism" {
+                    self.optimism = value;
+                }
+            }
+            Setting::Sound(sound) => self.sound = sound,
+            Setting::Flag(on) => {
+                let field = match key {
+                    "high_contrast" => &mut self.high_contrast,
+                    "daily_seal" => &mut self.daily_seal,
+                    "animate_unfocused" => &mut self.animate_unfocused,
+                    "terminal_bidi" => &mut self.terminal_bidi,
+                    "osc_notifications" => &mut self.osc_notifications,
+                    "osc_progress" => &mut self.osc_progress,
+                    "mouse" => &mut self.mouse,
+                    "screenshot_html" => &mut self.screenshot_html,
+                    "encrypt_history" => &mut self.encrypt_history,
+                    "devils_advocate" => &mut self.devils_advocate,
+                    "adapt_weights" => &mut self.adapt_weights,
+                    _ => return,
+                };
+                *field = on;
+            }
+        }
+    }
+}
+
+/// Environment variables read by the command line rather than the config.
+const ENV_FLAGS: [&str; 6] = [
+    "EDM_CONFIG",
+    "EDM_DATA_DIR",
+    "EDM_HISTORY_FILE",
+    "EDM_LOG_LEVEL",
+    "EDM_PROFILE",
+    encryption::PASSPHRASE_ENV,
+];
+
+fn timing(millis: u64) -> Duration {
+    Duration::from_millis(millis.clamp(*TIMING_MS.start(), *TIMING_MS.end()))
+}
+
+/// `1`/`true`/`yes`/`on` or `0`/`false`/`no`/`off`, any case.
+fn flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|&(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn variables_override_the_file() {
+        let mut config = Config {
+            theme: Some("dark".to_string()),
+            best_of: Some(5),
+            ..Config::default()
+        };
+        config
+            .apply_env(vars(&[
+                ("EDM_THEME", "solarized"),
+                ("EDM_DAILY_SEAL", "yes"),
+                ("EDM_ANIMATION_MS", " 1500 "),
+                ("EDM_BEST_OF", ""),
+                ("EDM_OPTIMISM", "-2"),
+                ("EDM_SOUND", "off"),
+                ("EDM_KEYS_ASK", "enter, space"),
+                ("EDM_EXPERIMENTAL_ROULETTE", "on"),
+                ("EDM_NO_SUCH_SETTING", "whatever"),
+                ("HOME", "/root"),
+            ]))
+            .unwrap();
+        assert_eq!(config.theme.as_deref(), Some("solarized"));
+        assert!(config.daily_seal);
+        assert_eq!(config.animation_ms, Some(1500));
+        assert_eq!(config.best_of, None);
+        assert_eq!(config.optimism, Some(-2));
+        assert_eq!(config.sound, Sound::Off);
+        assert_eq!(config.keys["ask"], ["enter", "space"]);
+        assert_eq!(config.experimental.get("roulette"), Some(&true));
+    }
+
+    #[test]
+    fn invalid_values_name_the_variable() {
+        for (name, value) in [
+            ("EDM_DAILY_SEAL", "maybe"),
+            ("EDM_FLASH_MS", "fast"),
+            ("EDM_VETO_BUDGET", "-1"),
+            ("EDM_OPTIMISM", "1.5"),
+            ("EDM_SOUND", "loud"),
+            ("EDM_EXPERIMENTAL_ROULETTE", "sometimes"),
+        ] {
+            let err = Config::default()
+                .apply_env(vars(&[(name, value)]))
+                .unwrap_err();
+            assert!(
+                matches!(&err, EdmError::Env { name: named, .. } if named == name),
+                "{name}: {err}"
+            );
+        }
+    }
+}
@@ -0,0 +1,511 @@
+//! `edm config check`: what in a config file would be ignored or rejected at
+//! startup. A file can parse and still name a pack that doesn't exist, weight
+//! an answer the pack doesn't have, or bind one key to two actions; each of
+//! those is a [`Problem`] with the line it sits on, when it can be found, and
+//! a suggestion.
+
+use crate::{
+    answers::{self, AnswerPack},
+    config::{Config, TIMING_MS},
+    experiments::EXPERIMENTS,
+    keymap::{Chord, Keymap, BINDABLE},
+    pack_check::Level,
+    recall, theme, transliteration,
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+use serde::de::IgnoredAny;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Every top-level key [`Config`] reads.
+const KEYS: [&str; 37] = [
+    "default_pack",
+    "webhook_url",
+    "slack_webhook_url",
+    "discord_webhook_url",
+    "theme",
+    "high_contrast",
+    "transliteration",
+    "second_opinion_pack",
+    "daily_seal",
+    "chaos_week",
+    "exit_summary",
+    "weights",
+    "styles",
+    "keys",
+    "animation_ms",
+    "flash_ms",
+    "suspense_ms",
+    "sound",
+    "animate_unfocused",
+    "terminal_bidi",
+    "osc_notifications",
+    "osc_progress",
+    "mouse",
+    "screenshot_html",
+    "experimental",
+    "lock_passphrase",
+    "encrypt_history",
+    "veto_budget",
+    "devils_advocate",
+    "commitment_hours",
+    "best_of",
+    "timebox_secs",
+    "llm_api_key",
+    "llm_url",
+    "llm_model",
+    "optimism",
+    "adapt_weights",
+];
+
+#[derive(Debug)]
+pub struct Problem {
+    pub level: Level,
+    /// 1-based line of the offending setting, if it could be found.
+    pub line: Option<usize>,
+    pub message: String,
+    /// What to do about it, e.g. "did you mean `classic`?".
+    pub help: Option<String>,
+}
+
+impl Problem {
+    fn error(line: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            level: Level::Fail,
+            line,
+            message: message.into(),
+            help: None,
+        }
+    }
+
+    fn warning(line: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            level: Level::Warn,
+            ..Self::error(line, message)
+        }
+    }
+
+    fn help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+}
+
+/// Check the config file contents `text`. `dir` is the directory holding
+/// it, where `themes/` and `transliterations/` are looked up; packs are
+/// installed from `pack_dirs`. A file that
+/// doesn't parse yields just the parse error.
+pub fn check(text: &str, dir: Option<&Path>, pack_dirs: &[PathBuf]) -> Vec<Problem> {
+    let config: Config = match toml::from_str(text) {
+        Ok(config) => config,
+        Err(err) => {
+            let line = err.span().map(|span| line_at(text, span.start));
+            return vec![Problem::error(line, err.message())];
+        }
+    };
+    let (installed, _) = answers::installed_packs(pack_dirs);
+    let mut problems = unknown_keys(text);
+    problems.extend(packs(text, &config, &installed));
+    problems.extend(weights(text, &config, &installed));
+    problems.extend(styles(text, &config, &installed));
+    problems.extend(timings(text, &config));
+    problems.extend(looks(text, &config, dir));
+    problems.extend(keys(text, &config));
+    problems.extend(experiments(text, &config));
+    problems.extend(chaos_weeks(text, &config));
+    problems
+}
+
+/// Top-level keys no setting reads: usually a typo.
+fn unknown_keys(text: &str) -> Vec<Problem> {
+    let Ok(table) = toml::from_str::<BTreeMap<String, IgnoredAny>>(text) else {
+        return Vec::new();
+    };
+    table
+        .keys()
+        .filter(|key| !KEYS.contains(&key.as_str()))
+        .map(|key| {
+            Problem::error(line_of(text, None, key), format!("unknown setting `{key}`"))
+                .help(suggest(key, KEYS))
+        })
+        .collect()
+}
+
+/// The startup and second-opinion packs exist.
+fn packs(text: &str, config: &Config, installed: &[AnswerPack]) -> Vec<Problem> {
+    let ids: Vec<&str> = installed.iter().map(|pack| pack.id.as_str()).collect();
+    [
+        ("default_pack", &config.default_pack),
+        ("second_opinion_pack", &config.second_opinion_pack),
+    ]
+    .into_iter()
+    .filter_map(|(key, id)| Some((key, id.as_deref()?)))
+    .filter(|(_, id)| !ids.contains(id))
+    .map(|(key, id)| {
+        Problem::error(
+            line_of(text, None, key),
+            format!("unknown pack `{id}` in `{key}`"),
+        )
+        .help(suggest(id, ids.iter().copied()))
+    })
+    .collect()
+}
+
+/// Weights name real packs and answers, and leave something to draw.
+fn weights(text: &str, config: &Config, packs: &[AnswerPack]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for (id, weights) in &config.weights {
+        let table = format!("weights.{id}");
+        let Some(pack) = packs.iter().find(|pack| &pack.id == id) else {
+            problems.push(
+                Problem::error(
+                    header_line(text, &table, 0),
+                    format!("unknown pack `{id}` in `weights`"),
+                )
+                .help(suggest(id, packs.iter().map(|pack| pack.id.as_str()))),
+            );
+            continue;
+        };
+        let texts = || pack.answers.iter().map(|answer| answer.text.as_str());
+        for answer in weights.keys() {
+            if !texts().any(|text| text == answer) {
+                problems.push(
+                    Problem::error(
+                        line_of(text, Some(&table), answer),
+                        format!("`{answer}` is not an answer in {}", pack.title),
+                    )
+                    .help(suggest(answer, texts())),
+                );
+            }
+        }
+        let weight_of = |text| weights.get(text).copied().unwrap_or(1);
+        if texts().all(|text| weight_of(text) == 0) {
+            problems.push(
+                Problem::error(
+                    header_line(text, &table, 0),
+                    format!(
+                        "every answer in {} weighs 0, so the weights are ignored",
+                        pack.title
+                    ),
+                )
+                .help("give at least one answer a weight above 0"),
+            );
+        } else if texts().filter(|&text| weight_of(text) > 0).count() == 1 {
+            problems.push(Problem::warning(
+                header_line(text, &table, 0),
+                format!("only one answer in {} can ever be drawn", pack.title),
+            ));
+        }
+    }
+    problems
+}
+
+/// Answer styles use real colours and name an answer some pack has.
+fn styles(text: &str, config: &Config, packs: &[AnswerPack]) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for (answer, entry) in &config.styles {
+        let line = header_line(text, &format!("styles.{answer}"), 0)
+            .or_else(|| line_of(text, Some("styles"), answer));
+        if let Err(err) = entry.parse() {
+            problems.push(
+                Problem::error(line, format!("{err} in the style for `{answer}`"))
+                    .help("use a colour name like `lightgreen`, `#rrggbb`, or 0-255"),
+            );
+        }
+        let texts = || {
+            packs
+                .iter()
+                .flat_map(|pack| pack.answers.iter().map(|answer| answer.text.as_str()))
+        };
+        if !texts().any(|known| known == answer) {
+            problems.push(
+                Problem::warning(line, format!("no installed pack has the answer `{answer}`"))
+                    .help(suggest(answer, texts())),
+            );
+        }
+    }
+    problems
+}
+
+/// Timings within what the board honours.
+fn timings(text: &str, config: &Config) -> Vec<Problem> {
+    [
+        ("animation_ms", config.animation_ms),
+        ("flash_ms", config.flash_ms),
+    ]
+    .into_iter()
+    .filter_map(|(key, millis)| Some((key, millis?)))
+    .filter(|(_, millis)| !TIMING_MS.contains(millis))
+    .map(|(key, millis)| {
+        let clamped = millis.clamp(*TIMING_MS.start(), *TIMING_MS.end());
+        Problem::warning(
+            line_of(text, None, key),
+            format!("`{key}` of {millis} ms is out of range and will be {clamped} ms"),
+        )
+        .help(format!(
+            "use {} to {} ms",
+            TIMING_MS.start(),
+            TIMING_MS.end()
+        ))
+    })
+    .collect()
+}
+
+/// The theme and transliteration exist, and every theme file (colour names
+/// included) parses.
+fn looks(text: &str, config: &Config, dir: Option<&Path>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let (themes, errors) = theme::installed_themes(dir.map(|dir| dir.join("themes")).as_deref());
+    for error in errors {
+        problems.push(Problem::error(None, format!("theme file {error}")));
+    }
+    if let Some(name) = &config.theme {
+        if !themes.iter().any(|theme| &theme.name == name) {
+            problems.push(
+                Problem::error(
+                    line_of(text, None, "theme"),
+                    format!("unknown theme `{name}`"),
+                )
+                .help(suggest(
+                    name,
+                    themes.iter().map(|theme| theme.name.as_str()),
+                )),
+            );
+        }
+    }
+    if let Some(name) = &config.transliteration {
+        let dir = dir.map(|dir| dir.join("transliterations"));
+        let line = line_of(text, None, "transliteration");
+        match transliteration::find(name, dir.as_deref()) {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                let schemes = transliteration::builtin_schemes();
+                problems.push(
+                    Problem::error(line, format!("unknown transliteration `{name}`")).help(
+                        suggest(name, schemes.iter().map(|scheme| scheme.name.as_str())),
+                    ),
+                );
+            }
+            Err(err) => problems.push(Problem::error(line, err)),
+        }
+    }
+    problems
+}
+
+/// Actions and chords in `[keys]` parse, and no chord ends up on two
+/// actions.
+fn keys(text: &str, config: &Config) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let reserved = ['c', 'z'].map(|c| Chord::new(KeyCode::Char(c), KeyModifiers::CONTROL));
+    let actions = || BINDABLE.iter().map(|(_, name, _)| *name);
+    for (name, chords) in &config.keys {
+        let line = line_of(text, Some("keys"), name);
+        if !actions().any(|action| action == name) {
+            problems.push(
+                Problem::error(line, format!("unknown action `{name}` in `[keys]`"))
+                    .help(suggest(name, actions())),
+            );
+            continue;
+        }
+        for chord in chords {
+            match chord.parse::<Chord>() {
+                Err(err) => problems.push(
+                    Problem::error(line, err)
+                        .help("write keys like `q`, `Space`, `F5`, or `Ctrl+Alt+Up`"),
+                ),
+                Ok(parsed) if reserved.contains(&parsed) => {
+                    problems.push(Problem::warning(
+                        line,
+                        format!("`{chord}` always quits or suspends, so `{name}` never sees it"),
+                    ));
+                }
+                Ok(_) => {}
+            }
+        }
+    }
+
+    let (keymap, _) = Keymap::from_config(&config.keys);
+    for (slot, (_, name, _)) in BINDABLE.iter().enumerate() {
+        for chord in keymap.chords(slot) {
+            let Some((_, other, _)) = BINDABLE[..slot]
+                .iter()
+                .enumerate()
+                .find(|(earlier, _)| keymap.chords(*earlier).contains(chord))
+                .map(|(_, entry)| entry)
+            else {
+                continue;
+            };
+            // Clashes among the built-in keys are the app's business.
+            if !config.keys.contains_key(*name) && !config.keys.contains_key(*other) {
+                continue;
+            }
+            let configured = if config.keys.contains_key(*name) {
+                name
+            } else {
+                other
+            };
+            problems.push(
+                Problem::error(
+                    line_of(text, Some("keys"), configured),
+                    format!("`{chord}` is bound to both `{other}` and `{name}`; only `{other}` will get it"),
+                )
+                .help(format!("bind `{other}` or `{name}` to another key")),
+            );
+        }
+    }
+    problems
+}
+
+/// Experiments switched on or off by a name this build knows.
+fn experiments(text: &str, config: &Config) -> Vec<Problem> {
+    let names = || EXPERIMENTS.iter().map(|experiment| experiment.name);
+    config
+        .experimental
+        .keys()
+        .filter(|name| !names().any(|known| known == name.as_str()))
+        .map(|name| {
+            Problem::warning(
+                line_of(text, Some("experimental"), name),
+                format!("unknown experiment `{name}` (finished or removed?)"),
+            )
+            .help(suggest(name, names()))
+        })
+        .collect()
+}
+
+/// Chaos weeks end on or after the day they start.
+fn chaos_weeks(text: &str, config: &Config) -> Vec<Problem> {
+    config
+        .chaos_weeks
+        .iter()
+        .enumerate()
+        .filter(|(_, week)| week.end < week.start)
+        .map(|(nth, week)| {
+            Problem::error(
+                header_line(text, "chaos_week", nth),
+                format!(
+                    "chaos week ends ({}) before it starts ({})",
+                    week.end, week.start
+                ),
+            )
+            .help("swap `start` and `end`")
+        })
+        .collect()
+}
+
+/// "did you mean" the closest of `known`, or the full list when nothing is
+/// close.
+fn suggest<'a>(name: &str, known: impl IntoIterator<Item = &'a str>) -> String {
+    let known: Vec<&str> = known.into_iter().collect();
+    let closest = known
+        .iter()
+        .map(|candidate| {
+            let distance = recall::edit_distance(&name.to_lowercase(), &candidate.to_lowercase());
+            (distance, candidate)
+        })
+        .min();
+    match closest {
+        Some((distance, candidate)) if distance <= 2.max(name.chars().count() / 3) => {
+            format!("did you mean `{candidate}`?")
+        }
+        _ => format!("expected one of: {}", known.join(", ")),
+    }
+}
+
+/// The 1-based line holding byte `offset` of `text`.
+fn line_at(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+/// The line assigning `key`, at the top level or in `[table]`.
+fn line_of(text: &str, table: Option<&str>, key: &str) -> Option<usize> {
+    let mut current = None;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            current = Some(line.trim_matches(['[', ']']).trim().to_string());
+            continue;
+        }
+        let rest = match line.strip_prefix('"') {
+            Some(quoted) => quoted
+                .strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix('"')),
+            None => line.strip_prefix(key),
+        };
+        let assigns = rest.is_some_and(|rest| rest.trim_start().starts_with('='));
+        if assigns && current.as_deref() == table {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+/// The line of the `nth` `[table]` or `[[table]]` header.
+fn header_line(text: &str, table: &str, nth: usize) -> Option<usize> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            line.starts_with('[') && line.trim_matches(['[', ']']).trim() == table
+        })
+        .nth(nth)
+        .map(|(index, _)| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"daily_seal = true
+animaton_ms = 500
+
+[keys]
+ask = ["F5"]
+aks = ["a"]
+copy = ["F5"]
+"#;
+
+    #[test]
+    fn problems_point_at_their_lines() {
+        let problems = check(CONFIG, None, &[]);
+        let found: Vec<(Option<usize>, &str, Option<&str>)> = problems
+            .iter()
+            .map(|problem| {
+                (
+                    problem.line,
+                    problem.message.as_str(),
+                    problem.help.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    Some(2),
+                    "unknown setting `animaton_ms`",
+                    Some("did you mean `animation_ms`?")
+                ),
+                (
+                    Some(6),
+                    "unknown action `aks` in `[keys]`",
+                    Some("did you mean `ask`?")
+                ),
+                (
+                    Some(7),
+                    "`F5` is bound to both `ask` and `copy`; only `ask` will get it",
+                    Some("bind `ask` or `copy` to another key")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_parse_error_points_at_its_line() {
+        let problems = check("daily_seal = true\nbest_of = \"three\"\n", None, &[]);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(2));
+    }
+}
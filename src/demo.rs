@@ -0,0 +1,58 @@
+//! Demo mode (`--demo`): the board asks one canned question after another,
+//! forever, for a booth or an office TV. Nothing it decides goes into the
+//! history.
+
+use std::time::{Duration, Instant};
+
+/// Asked in turn, starting over after the last.
+pub const QUESTIONS: &[&str] = &[
+    "Should we ship it on a Friday?",
+    "Is it time for coffee?",
+    "Should the meeting have been an email?",
+    "Do we rewrite it in Rust?",
+    "Should I take the afternoon off?",
+    "Is this a good idea?",
+    "Pizza for lunch?",
+    "Should we add another microservice?",
+];
+
+/// When the next canned question is due, and which one it is.
+#[derive(Clone, Debug)]
+pub struct Demo {
+    every: Duration,
+    next_at: Instant,
+    asked: usize,
+}
+
+impl Demo {
+    /// Ask every `every`, the first time right away.
+    pub fn new(every: Duration, now: Instant) -> Self {
+        Self {
+            every,
+            next_at: now,
+            asked: 0,
+        }
+    }
+
+    pub fn next_deadline(&self) -> Instant {
+        self.next_at
+    }
+
+    /// Someone is using the board: try again a full interval from `now`.
+    pub fn postpone(&mut self, now: Instant) {
+        if now >= self.next_at {
+            self.next_at = now + self.every;
+        }
+    }
+
+    /// The question to ask if one is due at `now`, scheduling the next.
+    pub fn due(&mut self, now: Instant) -> Option<&'static str> {
+        if now < self.next_at {
+            return None;
+        }
+        let question = QUESTIONS[self.asked % QUESTIONS.len()];
+        self.asked += 1;
+        self.next_at = now + self.every;
+        Some(question)
+    }
+}
@@ -0,0 +1,48 @@
+//! Downloading shared packs for `edm pack install`, with the SHA-256 of what
+//! arrived so it can be checked before anything is installed.
+
+use crate::error::{EdmError, Result};
+
+/// Largest pack accepted; real packs are a few kilobytes.
+#[cfg(feature = "http")]
+const MAX_PACK_BYTES: u64 = 1024 * 1024;
+
+pub struct Download {
+    pub bytes: Vec<u8>,
+    /// Lowercase hex.
+    pub sha256: String,
+}
+
+/// GET `url`, blocking until it has all arrived.
+#[cfg(feature = "http")]
+pub fn fetch(url: &str) -> Result<Download> {
+    use sha2::{Digest, Sha256};
+    use std::io::Read;
+
+    tracing::info!(%url, "downloading pack");
+    let response = ureq::get(url)
+        .timeout(std::time::Duration::from_secs(30))
+        .call()
+        .map_err(|err| EdmError::Network(format!("downloading {url} failed: {err}")))?;
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(MAX_PACK_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|err| EdmError::Network(format!("downloading {url} failed: {err}")))?;
+    if bytes.len() as u64 > MAX_PACK_BYTES {
+        return Err(EdmError::Network(format!(
+            "{url} is larger than {MAX_PACK_BYTES} bytes; not a pack"
+        )));
+    }
+    let sha256 = Sha256::digest(&bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Ok(Download { bytes, sha256 })
+}
+
+#[cfg(not(feature = "http"))]
+pub fn fetch(_url: &str) -> Result<Download> {
+    Err(EdmError::FeatureDisabled("http"))
+}
@@ -0,0 +1,101 @@
+//! Elimination mode, started with `x`: instead of picking a winner, each
+//! shuffle picks an answer to knock out, greyed out on the board, until the
+//! last one standing is the verdict. Rounds are a short pause apart.
+//!
+//! Answers weighted 0 go out first, as they could never be drawn; after
+//! that every answer still in is equally likely to go next.
+
+use crate::answers::AnswerPack;
+use crate::widget::DecisionState;
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// The pause between one answer going out and the next shuffle.
+pub const PAUSE_MS: u64 = 1_000;
+
+/// Where an elimination run stands.
+#[derive(Clone, Debug, Default)]
+pub struct Elimination {
+    /// The answer the lights landed on, greyed out once its flash ends.
+    pub landed: Option<usize>,
+    /// When the next shuffle starts, between rounds.
+    pub next_round_at: Option<Instant>,
+}
+
+impl Elimination {
+    /// Wait out the pause before the next round.
+    pub fn pause(&mut self, now: Instant) {
+        self.landed = None;
+        self.next_round_at = Some(now + Duration::from_millis(PAUSE_MS));
+    }
+
+    /// Whether the next round is due at `now`, clearing it if so.
+    pub fn due(&mut self, now: Instant) -> bool {
+        if self.next_round_at.is_some_and(|at| now >= at) {
+            self.next_round_at = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// Answers of `pack` still in on `board`.
+pub fn remaining(pack: &AnswerPack, board: &DecisionState) -> Vec<usize> {
+    (0..pack.answers.len())
+        .filter(|&index| !board.is_disabled(index))
+        .collect()
+}
+
+/// The answer to knock out next: one weighted 0 if any is left, otherwise
+/// any of those still in.
+pub fn victim(pack: &AnswerPack, board: &DecisionState) -> usize {
+    let left = remaining(pack, board);
+    if let Some(&hopeless) = left.iter().find(|&&index| pack.answers[index].weight == 0) {
+        return hopeless;
+    }
+    left[rand::thread_rng().gen_range(0..left.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::{builtin, DEFAULT_PACK};
+
+    #[test]
+    fn knock_outs_leave_one_standing() {
+        let mut pack = builtin(DEFAULT_PACK).unwrap();
+        pack.answers[2].weight = 0;
+        pack.answers[4].weight = 0;
+        let mut board = DecisionState::default();
+        let mut out = Vec::new();
+        while remaining(&pack, &board).len() > 1 {
+            let index = victim(&pack, &board);
+            assert!(!board.is_disabled(index), "{index} is already out");
+            board.disable(index);
+            out.push(index);
+        }
+
+        // The hopeless go first, then everyone else but the last.
+        assert_eq!(out[..2], [2, 4]);
+        assert_eq!(out.len(), pack.answers.len() - 1);
+        let standing = remaining(&pack, &board);
+        assert_eq!(standing.len(), 1);
+        assert!(!out.contains(&standing[0]));
+        assert!(pack.answers[standing[0]].weight > 0);
+    }
+
+    #[test]
+    fn the_next_round_waits_out_the_pause() {
+        let now = Instant::now();
+        let mut elimination = Elimination {
+            landed: Some(3),
+            ..Elimination::default()
+        };
+        elimination.pause(now);
+        assert_eq!(elimination.landed, None);
+        assert!(!elimination.due(now));
+        let later = now + Duration::from_millis(PAUSE_MS);
+        assert!(elimination.due(later));
+        assert!(!elimination.due(later), "a round is due only once");
+    }
+}
@@ -0,0 +1,181 @@
+//! At-rest encryption of the history file, with `encrypt_history` on. The
+//! key comes from a passphrase through Argon2id, and each line is sealed on
+//! its own with XChaCha20-Poly1305, so the file stays append-only.
+//!
+//! The first line of an encrypted file is a [`Header`] with the salt and a
+//! sealed check value, which tells a wrong passphrase from a damaged line.
+//! Every later line is `base64(nonce ‖ ciphertext)` of one plain line.
+
+use crate::error::{EdmError, Result};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+/// Read instead of prompting, for scripts and services.
+pub const PASSPHRASE_ENV: &str = "EDM_PASSPHRASE";
+/// The [`Header::encryption`] this module writes and reads.
+const SCHEME: &str = "argon2id-xchacha20poly1305";
+/// Sealed into the header to check the passphrase against.
+const CHECK: &str = "edm";
+const NONCE_LEN: usize = 24;
+
+/// The first line of an encrypted history file.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Header {
+    pub encryption: String,
+    salt: String,
+    check: String,
+}
+
+pub struct Cipher {
+    aead: XChaCha20Poly1305,
+}
+
+impl Cipher {
+    fn derive(passphrase: &str, salt: &[u8]) -> Result<Self> {
+        let mut key = [0; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|err| EdmError::Encryption(err.to_string()))?;
+        Ok(Self {
+            aead: XChaCha20Poly1305::new(Key::from_slice(&key)),
+        })
+    }
+
+    /// A key for `passphrase` with a fresh salt, and the header recording it.
+    pub fn create(passphrase: &str) -> Result<(Self, Header)> {
+        let salt: [u8; 16] = rand::random();
+        let cipher = Self::derive(passphrase, &salt)?;
+        let header = Header {
+            encryption: SCHEME.to_string(),
+            salt: STANDARD.encode(salt),
+            check: cipher.seal(CHECK),
+        };
+        Ok((cipher, header))
+    }
+
+    /// The key `header` was made with, if `passphrase` is the right one.
+    pub fn unlock(header: &Header, passphrase: &str) -> Result<Option<Self>> {
+        if header.encryption != SCHEME {
+            return Err(EdmError::Encryption(format!(
+                "unknown encryption `{}`",
+                header.encryption
+            )));
+        }
+        let salt = STANDARD
+            .decode(&header.salt)
+            .map_err(|_| EdmError::Encryption("damaged header".to_string()))?;
+        let cipher = Self::derive(passphrase, &salt)?;
+        let right = cipher.open(&header.check).as_deref() == Some(CHECK);
+        Ok(right.then_some(cipher))
+    }
+
+    /// `plain` encrypted under a fresh nonce, as one line of base64.
+    pub fn seal(&self, plain: &str) -> String {
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let mut sealed = nonce.to_vec();
+        sealed.extend(
+            self.aead
+                .encrypt(XNonce::from_slice(&nonce), plain.as_bytes())
+                .expect("encrypting to memory cannot fail"),
+        );
+        STANDARD.encode(sealed)
+    }
+
+    /// The plain text of a [`Cipher::seal`]ed line; `None` if it is damaged
+    /// or was sealed with another key.
+    pub fn open(&self, sealed: &str) -> Option<String> {
+        let sealed = STANDARD.decode(sealed.trim()).ok()?;
+        if sealed.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plain = self
+            .aead
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .ok()?;
+        String::from_utf8(plain).ok()
+    }
+}
+
+/// `line` as a header, if it is one.
+pub fn header(line: &str) -> Option<Header> {
+    serde_json::from_str(line).ok()
+}
+
+/// Whether the file at `path` starts with a [`Header`]. A missing file is
+/// not encrypted.
+pub fn is_encrypted(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let mut first = String::new();
+    BufReader::new(file).read_line(&mut first).is_ok() && header(&first).is_some()
+}
+
+/// The passphrase from [`PASSPHRASE_ENV`], else asked for on the terminal;
+/// twice when `confirm`, for a new one.
+pub fn passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    let ask = |prompt: &str| rpassword::prompt_password(prompt).map_err(EdmError::Passphrase);
+    let passphrase = ask("History passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err(EdmError::Passphrase(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the passphrase may not be empty",
+        )));
+    }
+    if confirm && ask("Again, to confirm: ")? != passphrase {
+        return Err(EdmError::Passphrase(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the passphrases differ",
+        )));
+    }
+    Ok(passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_lines_open_with_the_same_key_only() {
+        let (cipher, header) = Cipher::create("correct horse").unwrap();
+        let sealed = cipher.seal(r#"{"answer":"YES"}"#);
+        assert!(!sealed.contains("YES"));
+        assert_eq!(cipher.open(&sealed).as_deref(), Some(r#"{"answer":"YES"}"#));
+        // A fresh nonce each time.
+        assert_ne!(cipher.seal("same"), cipher.seal("same"));
+
+        let unlocked = Cipher::unlock(&header, "correct horse").unwrap().unwrap();
+        assert_eq!(
+            unlocked.open(&sealed).as_deref(),
+            Some(r#"{"answer":"YES"}"#)
+        );
+        let (other, _) = Cipher::create("correct horse").unwrap();
+        assert_eq!(other.open(&sealed), None);
+        assert_eq!(cipher.open("not base64 at all"), None);
+    }
+
+    #[test]
+    fn a_wrong_passphrase_does_not_unlock() {
+        let (_, header) = Cipher::create("correct horse").unwrap();
+        assert!(Cipher::unlock(&header, "battery staple").unwrap().is_none());
+
+        let line = serde_json::to_string(&header).unwrap();
+        let mut header = self::header(&line).unwrap();
+        header.encryption = "rot13".to_string();
+        assert!(Cipher::unlock(&header, "correct horse").is_err());
+    }
+}
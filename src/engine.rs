@@ -0,0 +1,163 @@
+//! The decision engine shared by every front end: draws verdicts, asks for
+//! second opinions, records history, unlocks achievements, and fires the
+//! webhook. The TUI animates
+//! around it; `edm serve` calls it directly.
+
+use crate::{
+    achievements::{Achievement, Achievements},
+    adapt,
+    answers::AnswerPack,
+    chaos::{ChaosWeek, Rules},
+    confidence,
+    config::Config,
+    history::History,
+    opinion::{self, SecondOpinion},
+    seal::{self, Draw},
+    webhook::{self, Webhook},
+};
+use chrono::{DateTime, Local};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+pub struct Engine {
+    pub pack: AnswerPack,
+    /// Pack asked for a second opinion, if enabled.
+    pub second_opinion_pack: Option<AnswerPack>,
+    pub history: History,
+    /// Same question, same day, same answer.
+    pub daily_seal: bool,
+    /// Skews draws toward positive or negative answers; see
+    /// [`Config::optimism`].
+    pub optimism: i32,
+    /// Tune the weights to rated outcomes; see [`adapt`].
+    pub adapt_weights: bool,
+    /// Scheduled chaos weeks, which override the rules above.
+    pub chaos_weeks: Vec<ChaosWeek>,
+    /// Session-only unless a front end loads them from the data directory.
+    pub achievements: Achievements,
+    /// Unlocked by recent decisions and not yet announced.
+    unlocked: Vec<&'static Achievement>,
+    webhook: Option<Webhook>,
+    /// Notified when a background task (webhook, history write) has
+    /// something to report.
+    wake: Arc<Notify>,
+}
+
+impl Engine {
+    pub fn new(
+        pack: AnswerPack,
+        second_opinion_pack: Option<AnswerPack>,
+        mut history: History,
+        config: &Config,
+    ) -> Self {
+        let wake = Arc::new(Notify::new());
+        history.write_in_background(Arc::clone(&wake));
+        Self {
+            pack,
+            second_opinion_pack,
+            history,
+            daily_seal: config.daily_seal,
+            optimism: config.optimism(),
+            adapt_weights: config.adapt_weights,
+            chaos_weeks: config.chaos_weeks.clone(),
+            achievements: Achievements::default(),
+            unlocked: Vec::new(),
+            webhook: config
+                .webhook_url
+                .clone()
+                .map(|url| Webhook::spawn(url, Arc::clone(&wake))),
+            wake,
+        }
+    }
+
+    /// The rules in force today.
+    pub fn rules(&self) -> Rules {
+        Rules::new(
+            self.daily_seal,
+            self.optimism,
+            &self.chaos_weeks,
+            Local::now().date_naive(),
+        )
+    }
+
+    /// Pick the next verdict without committing to it yet.
+    pub fn draw(&self, question: Option<&str>) -> Draw {
+        if self.adapt_weights {
+            let pack = adapt::tuned(&self.pack, &self.history);
+            return seal::draw(&pack, question, self.rules());
+        }
+        seal::draw(&self.pack, question, self.rules())
+    }
+
+    /// Commit to the verdict at `index`, decided at `now`: consult the second
+    /// opinion, record it with `tags` and a confidence, check achievements,
+    /// and notify the webhook. Past `expires_at` it needs re-validation.
+    pub fn settle(
+        &mut self,
+        index: usize,
+        question: Option<&str>,
+        expires_at: Option<DateTime<Local>>,
+        tags: &[String],
+        now: DateTime<Local>,
+    ) -> Option<SecondOpinion> {
+        let answer = &self.pack.answers[index];
+        let opinion = self
+            .second_opinion_pack
+            .as_ref()
+            .map(|pack| opinion::consult(pack, answer));
+        let agreed = opinion.as_ref().map(|opinion| opinion.agrees);
+        let confidence = confidence::draw(&self.pack, index);
+        let decision = self.history.record(
+            &self.pack.id,
+            question,
+            answer,
+            agreed,
+            expires_at,
+            tags,
+            confidence,
+            now,
+        );
+        if let Some(webhook) = &self.webhook {
+            webhook.send(webhook::Payload {
+                question: decision.question.clone(),
+                answer: decision.answer.clone(),
+                sentiment: decision.sentiment,
+                pack: decision.pack.clone(),
+                timestamp: decision.decided_at.to_rfc3339(),
+            });
+        }
+        let unlocked = self.achievements.update(&self.history);
+        self.unlocked.extend(unlocked);
+        opinion
+    }
+
+    /// Draw and settle in one go, for front ends without an animation.
+    pub fn decide(
+        &mut self,
+        question: Option<&str>,
+        tags: &[String],
+        now: DateTime<Local>,
+    ) -> (Draw, Option<SecondOpinion>) {
+        let draw = self.draw(question);
+        (draw, self.settle(draw.index, question, None, tags, now))
+    }
+
+    /// Achievements unlocked since the last call, for the front end to
+    /// announce.
+    pub fn take_unlocked(&mut self) -> Vec<&'static Achievement> {
+        std::mem::take(&mut self.unlocked)
+    }
+
+    /// Wakes an event loop when [`Engine::webhook_failure`] or
+    /// [`History::take_write_error`] has news.
+    pub fn wake(&self) -> &Arc<Notify> {
+        &self.wake
+    }
+
+    /// The most recent webhook delivery error since the last call.
+    pub fn webhook_failure(&self) -> Option<String> {
+        self.webhook
+            .as_ref()
+            .and_then(|webhook| webhook.failures().last())
+    }
+}
@@ -0,0 +1,162 @@
+//! The crate's error type. Anything that can stop `edm` before or after the
+//! TUI runs ends up as an [`EdmError`]; `main` prints its message, not its
+//! `Debug` form, once the terminal has been restored.
+
+use std::{io, path::PathBuf};
+use thiserror::Error;
+
+pub type Result<T, E = EdmError> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum EdmError {
+    /// Raw mode, the alternate screen, or drawing failed.
+    #[error("terminal error: {0}")]
+    Terminal(#[source] io::Error),
+
+    #[error("could not read config {}: {source}", path.display())]
+    ConfigRead {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("invalid config {}: {source}", path.display())]
+    ConfigParse {
+        path: PathBuf,
+        #[source]
+        source: Box<toml::de::Error>,
+    },
+
+    #[error("could not write config {}: {source}", path.display())]
+    ConfigWrite {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// An `EDM_*` variable holds a value its setting can't take.
+    #[error("invalid {name}: {message}")]
+    Env { name: String, message: String },
+
+    /// `edm config check` found problems.
+    #[error("{} has problems", .0.display())]
+    ConfigChecks(PathBuf),
+
+    /// `edm config init` would overwrite an existing file.
+    #[error("{} already exists; pass --force to overwrite it", .0.display())]
+    ConfigExists(PathBuf),
+
+    /// No home directory to put the config in, and no `--config`.
+    #[error("no config directory found; pass --config FILE")]
+    NoConfigDir,
+
+    #[error("no data directory found; pass --data-dir DIR")]
+    NoDataDir,
+
+    #[error("could not read history {}: {source}", path.display())]
+    History {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("wrong passphrase for {}", .0.display())]
+    WrongPassphrase(PathBuf),
+
+    #[error("could not read the passphrase: {0}")]
+    Passphrase(#[source] io::Error),
+
+    #[error("history encryption: {0}")]
+    Encryption(String),
+
+    #[error("the history is encrypted; pass --decrypt to export it in plain text")]
+    EncryptedExport,
+
+    #[error("could not open event log {}: {source}", path.display())]
+    EventLog {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("could not create recording {}: {source}", path.display())]
+    Recording {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("unknown answer pack `{0}`")]
+    UnknownPack(String),
+
+    #[error("profile name `{0}` may only use letters, digits, - and _")]
+    InvalidProfile(String),
+
+    #[error("unknown transliteration scheme `{0}`")]
+    UnknownTransliteration(String),
+
+    #[error("invalid transliteration scheme {0}")]
+    TransliterationFile(String),
+
+    #[error("could not read pack {}: {source}", path.display())]
+    PackRead {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("invalid pack {}: {source}", path.display())]
+    PackParse {
+        path: PathBuf,
+        #[source]
+        source: Box<toml::de::Error>,
+    },
+
+    /// `edm pack test` found failing checks.
+    #[error("{} failed its checks", .0.display())]
+    PackChecks(PathBuf),
+
+    #[error("could not write pack {}: {source}", path.display())]
+    PackWrite {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("could not write export {}: {source}", path.display())]
+    Export {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("could not read template {}: {source}", path.display())]
+    TemplateRead {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("a pack with id `{0}` is already installed (use --force to replace it)")]
+    PackExists(String),
+
+    #[error("checksum mismatch: expected {expected}, downloaded {actual}")]
+    Checksum { expected: String, actual: String },
+
+    /// A webhook, chat post, or listening socket failed.
+    #[error("{0}")]
+    Network(String),
+
+    /// The log file or filter could not be set up.
+    #[error("logging: {0}")]
+    Logging(String),
+
+    #[error("built without the `{0}` feature")]
+    FeatureDisabled(&'static str),
+
+    #[error("no completion script for shell `{0}`")]
+    UnsupportedShell(String),
+
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
@@ -0,0 +1,153 @@
+//! The event stream: what happens during an ask, as versioned JSON that
+//! scripts can rely on across refactors.
+//!
+//! Every event is one JSON object with a `version` and a `type`. The same
+//! events go to the `--event-log` file of the TUI and to the `/events`
+//! WebSocket of `edm serve`. `edm events schema` prints a JSON Schema for
+//! them, generated from [`KINDS`].
+//!
+//! Bump [`VERSION`] when a field is removed, renamed, or changes meaning.
+//! Adding fields or event types is not a breaking change.
+
+use crate::{achievements::Achievement, sentiment::Sentiment};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+pub const VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    AskStarted {
+        question: Option<String>,
+        pack: String,
+    },
+    LightChanged {
+        index: usize,
+        answer: String,
+    },
+    AnswerFinal {
+        index: usize,
+        answer: String,
+        sentiment: Sentiment,
+        sealed: bool,
+    },
+    AchievementUnlocked {
+        id: String,
+        title: String,
+    },
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    version: u32,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+impl Event {
+    /// The event as a single line of JSON, version included.
+    pub fn to_json(&self) -> String {
+        let envelope = Envelope {
+            version: VERSION,
+            event: self,
+        };
+        serde_json::to_string(&envelope).expect("events always serialize")
+    }
+}
+
+impl From<&Achievement> for Event {
+    fn from(achievement: &Achievement) -> Self {
+        Event::AchievementUnlocked {
+            id: achievement.id.to_string(),
+            title: achievement.title.to_string(),
+        }
+    }
+}
+
+/// One event type, for the schema. Keep in step with [`Event`].
+pub struct Kind {
+    pub name: &'static str,
+    pub doc: &'static str,
+    /// Name, JSON type, description.
+    pub fields: &'static [(&'static str, &'static str, &'static str)],
+}
+
+pub const KINDS: [Kind; 4] = [
+    Kind {
+        name: "ask_started",
+        doc: "Someone asked; the lights start shuffling.",
+        fields: &[
+            ("question", "string|null", "The question, if one was typed."),
+            ("pack", "string", "Id of the answer pack in use."),
+        ],
+    },
+    Kind {
+        name: "light_changed",
+        doc: "Another light came on while shuffling.",
+        fields: &[
+            (
+                "index",
+                "integer",
+                "Position of the lit answer in the pack.",
+            ),
+            ("answer", "string", "Text of the lit answer."),
+        ],
+    },
+    Kind {
+        name: "answer_final",
+        doc: "The lights stopped on the verdict.",
+        fields: &[
+            ("index", "integer", "Position of the answer in the pack."),
+            ("answer", "string", "Text of the answer."),
+            (
+                "sentiment",
+                "string",
+                "`positive`, `neutral`, or `negative`.",
+            ),
+            (
+                "sealed",
+                "boolean",
+                "The answer was sealed for the day rather than random.",
+            ),
+        ],
+    },
+    Kind {
+        name: "achievement_unlocked",
+        doc: "The decision just made unlocked an achievement.",
+        fields: &[
+            ("id", "string", "Stable id of the achievement."),
+            ("title", "string", "Its name as shown in the TUI."),
+        ],
+    },
+];
+
+/// A JSON Schema (draft 2020-12) describing every event.
+pub fn schema() -> Value {
+    let variants: Vec<Value> = KINDS.iter().map(kind_schema).collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "edm event",
+        "description": format!("Events emitted by edm, version {VERSION}."),
+        "oneOf": variants,
+    })
+}
+
+fn kind_schema(kind: &Kind) -> Value {
+    let mut properties = Map::new();
+    properties.insert("version".to_string(), json!({ "const": VERSION }));
+    properties.insert("type".to_string(), json!({ "const": kind.name }));
+    let mut required = vec!["version", "type"];
+    for (name, ty, doc) in kind.fields {
+        let ty: Vec<&str> = ty.split('|').collect();
+        properties.insert(name.to_string(), json!({ "type": ty, "description": doc }));
+        required.push(name);
+    }
+    json!({
+        "title": kind.name,
+        "description": kind.doc,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
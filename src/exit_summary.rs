@@ -0,0 +1,56 @@
+//! The line printed after the TUI exits, so the verdict survives the
+//! alternate-screen wipe. Without a template it is the last decision, e.g.
+//! `Ship on Friday? → NO (16:05)`; a template such as
+//! `"{answer} after {asks} asks"` replaces it, and an empty one turns it off.
+//!
+//! Placeholders: `{answer}`, `{question}`, `{time}` (when the last answer was
+//! given), `{pack}`, `{asks}`, `{streak}` (how many answers in a row ended on
+//! the last answer's sentiment), `{sentiment}`, `{positive}`, `{neutral}`,
+//! `{negative}`. Anything else is printed as written.
+
+use crate::{history::Decision, sentiment::SentimentTally};
+
+pub const DEFAULT_TEMPLATE: &str = "{answer} ({asks} asks this session)";
+
+/// The line printed when no template is set: the last decision, with its
+/// question if one was typed.
+pub fn last_decision(session: &[Decision]) -> Option<String> {
+    let template = match session.last()?.question {
+        Some(_) => "{question} → {answer} ({time})",
+        None => "{answer} ({time})",
+    };
+    render(template, session)
+}
+
+/// Fill in `template` from this session's decisions, oldest first. `None`
+/// when nothing was asked.
+pub fn render(template: &str, session: &[Decision]) -> Option<String> {
+    let last = session.last()?;
+    let streak = session
+        .iter()
+        .rev()
+        .take_while(|decision| decision.sentiment == last.sentiment)
+        .count();
+    let mut tally = SentimentTally::default();
+    for decision in session {
+        tally.add(decision.sentiment);
+    }
+
+    let values = [
+        ("answer", last.answer.clone()),
+        ("question", last.question.clone().unwrap_or_default()),
+        ("time", last.decided_at.format("%H:%M").to_string()),
+        ("pack", last.pack.clone()),
+        ("asks", session.len().to_string()),
+        ("streak", streak.to_string()),
+        ("sentiment", last.sentiment.to_string()),
+        ("positive", tally.positive.to_string()),
+        ("neutral", tally.neutral.to_string()),
+        ("negative", tally.negative.to_string()),
+    ];
+    let mut out = template.to_string();
+    for (name, value) in values {
+        out = out.replace(&format!("{{{name}}}"), &value);
+    }
+    Some(out)
+}
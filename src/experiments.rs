@@ -0,0 +1,24 @@
+//! Experiments: animations and modes still in development. They ship in
+//! every build but stay off until switched on by name, either on the
+//! settings screen (`,`) or in the config:
+//!
+//! ```toml
+//! [experimental]
+//! roulette = true
+//! ```
+//!
+//! Experiments may change or disappear between releases. Names not listed
+//! in [`EXPERIMENTS`] are ignored.
+
+pub struct Experiment {
+    pub name: &'static str,
+    pub summary: &'static str,
+}
+
+/// Every experiment in this build, in settings-screen order.
+pub const EXPERIMENTS: &[Experiment] = &[Experiment {
+    name: ROULETTE,
+    summary: "Lights run round the board in order and slow to a stop",
+}];
+
+pub const ROULETTE: &str = "roulette";
@@ -0,0 +1,66 @@
+//! Formats for `edm history export`. Each one is an [`Exporter`]: CSV here,
+//! Markdown in [`crate::markdown`], and org-mode in [`crate::org`].
+
+use crate::history::Decision;
+
+pub trait Exporter {
+    /// `decisions`, oldest first, as the whole file.
+    fn render(&self, decisions: &[&Decision]) -> String;
+}
+
+/// One row per decision with `timestamp` (RFC 3339), `question`, `answer`,
+/// `sentiment`, `tags`, and `note` columns, for a spreadsheet.
+pub struct Csv;
+
+impl Exporter for Csv {
+    fn render(&self, decisions: &[&Decision]) -> String {
+        let mut text = String::from("timestamp,question,answer,sentiment,tags,note\r\n");
+        for decision in decisions {
+            let fields = [
+                decision.decided_at.to_rfc3339(),
+                decision.question.clone().unwrap_or_default(),
+                decision.answer.clone(),
+                decision.sentiment.to_string(),
+                decision.tags.join(" "),
+                decision.note.clone().unwrap_or_default(),
+            ];
+            let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            text.push_str(&row.join(","));
+            text.push_str("\r\n");
+        }
+        text
+    }
+}
+
+/// `field` for a CSV file (RFC 4180): quoted when it holds a comma, quote,
+/// line break, or edge spaces, with quotes doubled.
+fn csv_field(field: &str) -> String {
+    let quote =
+        field.contains([',', '"', '\n', '\r']) || field.starts_with(' ') || field.ends_with(' ');
+    if quote {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_rows_carry_sentiment_and_quote_what_needs_it() {
+        let decision: Decision = serde_json::from_str(
+            r#"{"question":"Ship it, today?","answer":"WHY NOT","pack":"classic","sentiment":"positive","decided_at":"2026-10-16T09:00:00+02:00","tags":["deploy","friday"],"note":"said \"fine\""}"#,
+        )
+        .unwrap();
+        let row = "\"Ship it, today?\",WHY NOT,positive,deploy friday,\"said \"\"fine\"\"\"";
+        assert_eq!(
+            Csv.render(&[&decision]),
+            format!(
+                "timestamp,question,answer,sentiment,tags,note\r\n{},{row}\r\n",
+                decision.decided_at.to_rfc3339()
+            )
+        );
+    }
+}
@@ -0,0 +1,370 @@
+//! `edm stats --fairness`: whether the recorded draws of a pack look like
+//! what its weights promise. Observed counts are set against expected ones
+//! with Pearson's chi-square test, and the longest runs of one answer are
+//! listed, for anyone who suspects the oracle of playing favourites.
+//!
+//! Every recorded decision counts as a draw, sealed answers and chaos weeks
+//! included, and the expected counts use the weights as they are today.
+
+use crate::{
+    answers::{probabilities, AnswerPack},
+    history::Decision,
+};
+use std::fmt;
+
+/// Below this expected count for any answer, the chi-square test says
+/// little; the report says so.
+const MIN_EXPECTED: f64 = 5.0;
+/// p-values below this are reported as unlikely to be chance.
+const SIGNIFICANCE: f64 = 0.05;
+
+pub struct Row {
+    pub answer: String,
+    pub weight: u32,
+    pub expected: f64,
+    pub observed: usize,
+    /// Most times in a row this answer came up.
+    pub longest_run: usize,
+}
+
+pub struct Report {
+    pub pack: String,
+    pub draws: usize,
+    /// One per answer, in pack order.
+    pub rows: Vec<Row>,
+    /// Recorded answers the pack no longer has; left out of the test.
+    pub unknown: usize,
+    pub chi_square: f64,
+    pub degrees_of_freedom: usize,
+    /// How often a fair oracle would spread at least this unevenly. `None`
+    /// with fewer than two drawable answers.
+    pub p_value: Option<f64>,
+}
+
+/// Audit the draws from `pack` among `decisions`, oldest first.
+pub fn audit(pack: &AnswerPack, decisions: &[&Decision]) -> Report {
+    let draws: Vec<&str> = decisions
+        .iter()
+        .filter(|decision| decision.pack == pack.id)
+        .map(|decision| decision.answer.as_str())
+        .collect();
+    let mut rows: Vec<Row> = pack
+        .answers
+        .iter()
+        .map(|answer| Row {
+            answer: answer.text.clone(),
+            weight: answer.weight,
+            expected: 0.0,
+            observed: 0,
+            longest_run: 0,
+        })
+        .collect();
+
+    let mut unknown = 0;
+    let mut run = 0;
+    for (index, draw) in draws.iter().enumerate() {
+        run = if index > 0 && draws[index - 1] == *draw {
+            run + 1
+        } else {
+            1
+        };
+        match rows.iter_mut().find(|row| row.answer == *draw) {
+            Some(row) => {
+                row.observed += 1;
+                row.longest_run = row.longest_run.max(run);
+            }
+            None => unknown += 1,
+        }
+    }
+
+    let counted = draws.len() - unknown;
+    for (row, percent) in rows.iter_mut().zip(probabilities(&pack.weights())) {
+        row.expected = counted as f64 * percent / 100.0;
+    }
+    let chi_square = rows
+        .iter()
+        .filter(|row| row.expected > 0.0)
+        .map(|row| (row.observed as f64 - row.expected).powi(2) / row.expected)
+        .sum();
+    let drawable = rows.iter().filter(|row| row.weight > 0).count();
+    let degrees_of_freedom = drawable.saturating_sub(1);
+    let p_value = (degrees_of_freedom > 0 && counted > 0)
+        .then(|| chi_square_p(chi_square, degrees_of_freedom));
+
+    Report {
+        pack: pack.id.clone(),
+        draws: draws.len(),
+        rows,
+        unknown,
+        chi_square,
+        degrees_of_freedom,
+        p_value,
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Fairness of `{}` over {} draw(s)", self.pack, self.draws)?;
+        writeln!(f)?;
+        let width = self
+            .rows
+            .iter()
+            .map(|row| row.answer.chars().count())
+            .chain(["Answer".len()])
+            .max()
+            .unwrap_or_default();
+        writeln!(
+            f,
+            "{:<width$}  Weight  Expected  Observed  Longest run",
+            "Answer"
+        )?;
+        for row in &self.rows {
+            writeln!(
+                f,
+                "{:<width$}  {:>6}  {:>8.1}  {:>8}  {:>11}",
+                row.answer, row.weight, row.expected, row.observed, row.longest_run
+            )?;
+        }
+        writeln!(f)?;
+        if self.unknown > 0 {
+            writeln!(
+                f,
+                "{} draw(s) of answers the pack no longer has are left out.",
+                self.unknown
+            )?;
+        }
+        let Some(p_value) = self.p_value else {
+            return writeln!(f, "Nothing to test: too few draws or drawable answers.");
+        };
+        writeln!(
+            f,
+            "Chi-square {:.2} with {} degree(s) of freedom, p = {:.3}.",
+            self.chi_square, self.degrees_of_freedom, p_value
+        )?;
+        if p_value < SIGNIFICANCE {
+            writeln!(
+                f,
+                "Unlikely to be chance: a fair oracle spreads this unevenly only {:.1}% of the time.",
+                p_value * 100.0
+            )?;
+        } else {
+            writeln!(
+                f,
+                "No sign of bias: a fair oracle spreads at least this unevenly {:.0}% of the time.",
+                p_value * 100.0
+            )?;
+        }
+        if let Some(row) = self.rows.iter().max_by_key(|row| row.longest_run) {
+            if row.longest_run > 1 {
+                writeln!(f, "Longest run: {} ×{}.", row.answer, row.longest_run)?;
+            }
+        }
+        if self
+            .rows
+            .iter()
+            .any(|row| row.weight > 0 && row.expected < MIN_EXPECTED)
+        {
+            writeln!(
+                f,
+                "Some answers expect fewer than {MIN_EXPECTED} draws, so take the test with a grain of salt."
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The chance of a chi-square statistic of at least `statistic` with
+/// `degrees` degrees of freedom.
+pub fn chi_square_p(statistic: f64, degrees: usize) -> f64 {
+    gamma_q(degrees as f64 / 2.0, statistic / 2.0)
+}
+
+/// The upper regularized incomplete gamma function Q(a, x), by its series
+/// below `a + 1` and its continued fraction above.
+fn gamma_q(a: f64, x: f64) -> f64 {
+    const ITERATIONS: usize = 300;
+    const EPSILON: f64 = 1e-14;
+    const TINY: f64 = 1e-300;
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefix = (-x + a * x.ln() - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..ITERATIONS {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        return (1.0 - sum * prefix).clamp(0.0, 1.0);
+    }
+    // Lentz's method.
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..=ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let step = d * c;
+        h *= step;
+        if (step - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    (prefix * h).clamp(0.0, 1.0)
+}
+
+/// ln Γ(x) for x > 0, by the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+    let mut series = 1.000_000_000_190_015;
+    let mut y = x;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    let t = x + 5.5;
+    (x + 0.5) * t.ln() - t + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::{builtin, DEFAULT_PACK};
+
+    /// The classic pack: DEFINITELY, FORGET IT, ASK AGAIN, NEVER, POSSIBLY
+    /// and WHY NOT, each of weight 1.
+    fn classic() -> AnswerPack {
+        builtin(DEFAULT_PACK).unwrap()
+    }
+
+    /// One decision per answer, in order, from the pack `pack`.
+    fn decisions(pack: &str, answers: &[&str]) -> Vec<Decision> {
+        answers
+            .iter()
+            .map(|answer| {
+                serde_json::from_value(serde_json::json!({
+                    "answer": answer,
+                    "pack": pack,
+                    "sentiment": "neutral",
+                    "decided_at": "2026-10-16T09:00:00+02:00",
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 5e-4, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn p_values_match_the_chi_square_table() {
+        // Critical values at the 5% and 1% levels.
+        for (statistic, degrees, p) in [
+            (3.841, 1, 0.05),
+            (6.635, 1, 0.01),
+            (5.991, 2, 0.05),
+            (9.210, 2, 0.01),
+            (11.070, 5, 0.05),
+            (15.086, 5, 0.01),
+        ] {
+            assert_close(chi_square_p(statistic, degrees), p);
+        }
+        assert_eq!(chi_square_p(0.0, 3), 1.0);
+    }
+
+    #[test]
+    fn audit_sets_observed_against_expected() {
+        let classic = classic();
+        let mut answers = Vec::new();
+        for (answer, count) in classic.answers.iter().zip([20, 10, 10, 10, 5, 5]) {
+            answers.extend(std::iter::repeat_n(answer.text.as_str(), count));
+        }
+        let decisions = decisions(&classic.id, &answers);
+        let report = audit(&classic, &decisions.iter().collect::<Vec<_>>());
+        assert_eq!(report.draws, 60);
+        for row in &report.rows {
+            assert_close(row.expected, 10.0);
+        }
+        // (10² + 0 + 0 + 0 + 5² + 5²) / 10
+        assert_close(report.chi_square, 15.0);
+        assert_eq!(report.degrees_of_freedom, 5);
+        // Just above the 1% critical value of 15.086.
+        assert_close(report.p_value.unwrap(), 0.0104);
+        assert_eq!(report.rows[0].longest_run, 20);
+    }
+
+    #[test]
+    fn zero_weight_answers_are_not_degrees_of_freedom() {
+        let mut classic = classic();
+        classic.answers[4].weight = 0;
+        classic.answers[5].weight = 0;
+        let decisions = decisions(
+            &classic.id,
+            &["DEFINITELY", "FORGET IT", "ASK AGAIN", "NEVER"],
+        );
+        let report = audit(&classic, &decisions.iter().collect::<Vec<_>>());
+        assert_eq!(report.degrees_of_freedom, 3);
+        assert_eq!(report.rows[4].expected, 0.0);
+        assert_eq!(report.rows[5].expected, 0.0);
+        assert_close(report.chi_square, 0.0);
+        assert_close(report.p_value.unwrap(), 1.0);
+    }
+
+    #[test]
+    fn unknown_answers_are_left_out_but_break_runs() {
+        let classic = classic();
+        let mut decisions = decisions(
+            &classic.id,
+            &["DEFINITELY", "DEFINITELY", "RETIRED", "DEFINITELY"],
+        );
+        decisions.extend(self::decisions("lunch", &["DEFINITELY"]));
+        let report = audit(&classic, &decisions.iter().collect::<Vec<_>>());
+        assert_eq!(report.draws, 4);
+        assert_eq!(report.unknown, 1);
+        assert_eq!(report.rows[0].observed, 3);
+        assert_eq!(report.rows[0].longest_run, 2);
+        let expected: f64 = report.rows.iter().map(|row| row.expected).sum();
+        assert_close(expected, 3.0);
+    }
+
+    #[test]
+    fn nothing_to_test_without_draws_or_a_choice() {
+        let classic = classic();
+        let report = audit(&classic, &[]);
+        assert_eq!(report.p_value, None);
+        assert!(report.to_string().contains("Nothing to test"));
+
+        let mut rigged = classic;
+        for answer in &mut rigged.answers[1..] {
+            answer.weight = 0;
+        }
+        let decisions = decisions(&rigged.id, &["DEFINITELY", "DEFINITELY"]);
+        let report = audit(&rigged, &decisions.iter().collect::<Vec<_>>());
+        assert_eq!(report.degrees_of_freedom, 0);
+        assert_eq!(report.p_value, None);
+    }
+}
@@ -0,0 +1,66 @@
+//! Packs to test against: small builders for [`PackFile`]s, so a test only
+//! spells out the part of a pack it is about.
+
+use crate::{
+    answers::{AnswerEntry, PackFile},
+    sentiment::Sentiment,
+};
+use std::{fs, path::Path};
+
+/// An answer with weight 1 and nothing else set.
+pub fn entry(text: &str, sentiment: Sentiment) -> AnswerEntry {
+    AnswerEntry {
+        text: text.to_string(),
+        sentiment,
+        weight: 1,
+        color: None,
+        idle_color: None,
+        icon: None,
+        opposite: None,
+        flavor: Vec::new(),
+        translations: Default::default(),
+    }
+}
+
+/// Like [`entry`], with `weight`.
+pub fn weighted(text: &str, sentiment: Sentiment, weight: u32) -> AnswerEntry {
+    AnswerEntry {
+        weight,
+        ..entry(text, sentiment)
+    }
+}
+
+/// A pack with id `test` holding `answers`.
+pub fn pack(answers: Vec<AnswerEntry>) -> PackFile {
+    PackFile {
+        id: "test".to_string(),
+        title: "Test".to_string(),
+        description: String::new(),
+        author: None,
+        answers,
+    }
+}
+
+/// One answer of each sentiment, equally weighted: passes every check.
+pub fn balanced() -> PackFile {
+    pack(vec![
+        entry("YES", Sentiment::Positive),
+        entry("MAYBE", Sentiment::Neutral),
+        entry("NO", Sentiment::Negative),
+    ])
+}
+
+/// Every pack file shipped in the repo's `packs/` directory.
+pub fn shipped() -> Vec<PackFile> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("packs");
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .expect("packs/ is readable")
+        .map(|entry| entry.expect("packs/ entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| PackFile::load(path).expect("shipped pack parses"))
+        .collect()
+}
@@ -0,0 +1,168 @@
+//! Forms for screens that edit values in place: a list of labelled
+//! [`Field`]s, one of them selected, each stepped with ←/→ (or `+`/`-`) and
+//! drawn by [`FormWidget`] as `label  ‹ value ›`.
+
+use crate::theme::Theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Debug)]
+pub struct Form {
+    pub fields: Vec<Field>,
+    pub selected: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Field {
+    pub label: &'static str,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    /// A number stepped within `range`, shown with its unit.
+    Number {
+        value: u64,
+        range: RangeInclusive<u64>,
+        step: u64,
+        unit: &'static str,
+    },
+    /// One of `options`, wrapping round at either end.
+    Choice { options: Vec<String>, index: usize },
+}
+
+impl Value {
+    /// Step `delta` times; whether the value changed.
+    fn adjust(&mut self, delta: i32) -> bool {
+        match self {
+            Value::Number {
+                value, range, step, ..
+            } => {
+                let moved = u64::from(delta.unsigned_abs()) * *step;
+                let new = if delta < 0 {
+                    value.saturating_sub(moved)
+                } else {
+                    value.saturating_add(moved)
+                }
+                .clamp(*range.start(), *range.end());
+                std::mem::replace(value, new) != new
+            }
+            Value::Choice { options, index } if !options.is_empty() => {
+                let len = options.len() as i64;
+                let new = (*index as i64 + i64::from(delta)).rem_euclid(len) as usize;
+                std::mem::replace(index, new) != new
+            }
+            Value::Choice { .. } => false,
+        }
+    }
+
+    /// The number, for a [`Value::Number`].
+    pub fn number(&self) -> Option<u64> {
+        match self {
+            Value::Number { value, .. } => Some(*value),
+            Value::Choice { .. } => None,
+        }
+    }
+
+    /// The chosen option's index, for a [`Value::Choice`].
+    pub fn choice(&self) -> Option<usize> {
+        match self {
+            Value::Choice { index, .. } => Some(*index),
+            Value::Number { .. } => None,
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Value::Number { value, unit, .. } => format!("{value} {unit}"),
+            Value::Choice { options, index } => options.get(*index).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+impl Form {
+    pub fn new(fields: Vec<Field>) -> Self {
+        Self {
+            fields,
+            selected: 0,
+        }
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.fields.len().saturating_sub(1));
+    }
+
+    /// Select the field on `row`, if there is one.
+    pub fn select(&mut self, row: usize) {
+        if row < self.fields.len() {
+            self.selected = row;
+        }
+    }
+
+    /// Step the selected field; whether its value changed.
+    pub fn adjust(&mut self, delta: i32) -> bool {
+        self.fields
+            .get_mut(self.selected)
+            .is_some_and(|field| field.value.adjust(delta))
+    }
+
+    /// The value of the field at `row`.
+    pub fn value(&self, row: usize) -> &Value {
+        &self.fields[row].value
+    }
+}
+
+/// Draws a [`Form`], the selected row in the theme's highlight.
+pub struct FormWidget<'a> {
+    form: &'a Form,
+    theme: &'a Theme,
+}
+
+impl<'a> FormWidget<'a> {
+    pub fn new(form: &'a Form, theme: &'a Theme) -> Self {
+        Self { form, theme }
+    }
+}
+
+impl Widget for FormWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let label_width = self
+            .form
+            .fields
+            .iter()
+            .map(|field| field.label.len())
+            .max()
+            .unwrap_or(0)
+            + 2;
+        let lines: Vec<Line> = self
+            .form
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(row, field)| {
+                let text = format!("{:<label_width$}‹ {} ›", field.label, field.value.text());
+                if row == self.form.selected {
+                    Line::from(Span::styled(
+                        format!("> {text}"),
+                        Style::default()
+                            .fg(self.theme.active_fg)
+                            .bg(self.theme.active_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::raw(format!("  {text}"))
+                }
+            })
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+}
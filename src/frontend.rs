@@ -0,0 +1,135 @@
+//! Front-end abstraction.
+//!
+//! The engine loop in [`run`] owns the timing; a [`Frontend`] only has to
+//! draw the current [`App`] and translate its own input into [`Action`]s.
+//! The ratatui front end in [`crate::tui`] is the default implementation;
+//! alternatives (a plain REPL, a web page, GPIO buttons) plug in the same way.
+//! `edm` itself drives it with the async loop in [`crate::tui::run`], which
+//! shares [`frame`] with the blocking loop here.
+
+use crate::{
+    app::{App, TICK_RATE_MS},
+    input::Action,
+};
+use ratatui::buffer::Buffer;
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+pub trait Frontend {
+    /// Prepare the output device (raw mode, sockets, pins, ...).
+    fn init(&mut self) -> io::Result<()>;
+
+    /// Present the current state.
+    fn render(&mut self, app: &App) -> io::Result<()>;
+
+    /// Pass raw bytes (bell, OSC 52) through to the user's terminal.
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()>;
+
+    /// Wait at most `timeout` for the next user action, or until there is
+    /// one when `timeout` is `None`.
+    fn handle_input(&mut self, timeout: Option<Duration>) -> io::Result<Option<Action>>;
+
+    /// The frame drawn last, for a screenshot; asked for after a render
+    /// while [`App::screenshot_requested`] is set. Front ends that don't
+    /// draw to a ratatui buffer have none.
+    fn last_frame(&mut self) -> Option<Buffer> {
+        None
+    }
+
+    /// Input received but not handed out yet, if the front end can tell.
+    /// Shown on the debug overlay.
+    fn queued_input(&self) -> Option<usize> {
+        None
+    }
+
+    /// Stop the process for job control (Ctrl+Z) and return once it is
+    /// continued, with the output device set up again. Front ends without a
+    /// local terminal ignore it.
+    fn suspend(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Restore the output device. Called once after the loop ends, even when
+    /// it ends with an error.
+    fn shutdown(&mut self) -> io::Result<()>;
+}
+
+/// Run the engine loop until the user quits. It sleeps until input arrives
+/// or the app's next deadline ([`App::time_to_next_deadline`]) and only
+/// renders when [`App::take_dirty`] reports a change, so an idle board does
+/// not wake up at all.
+pub fn run(app: &mut App, frontend: &mut dyn Frontend) -> io::Result<()> {
+    frontend.init()?;
+    tracing::debug!("event loop started");
+    let result = drive(app, frontend);
+    let cleanup = frontend.shutdown();
+    tracing::debug!(ok = result.is_ok(), "event loop stopped");
+    result.and(cleanup)
+}
+
+fn drive(app: &mut App, frontend: &mut dyn Frontend) -> io::Result<()> {
+    // When the wait for input should have ended, if it had a deadline.
+    let mut due: Option<Instant> = None;
+    loop {
+        let timeout = frame(app, frontend, due)?;
+        due = timeout.map(|timeout| Instant::now() + timeout);
+        if let Some(action) = frontend.handle_input(timeout)? {
+            if action == Action::Suspend {
+                suspend(app, frontend)?;
+            } else if app.handle(action) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Ctrl+Z: the board pauses as if the window lost focus while the shell has
+/// the terminal, then redraws in full once the job is back in the
+/// foreground.
+pub fn suspend(app: &mut App, frontend: &mut dyn Frontend) -> io::Result<()> {
+    tracing::debug!("suspending");
+    app.handle(Action::Focus(false));
+    frontend.suspend()?;
+    tracing::debug!("resumed");
+    app.handle(Action::Focus(true));
+    Ok(())
+}
+
+/// One turn of an engine loop: tick, redraw if anything changed, and pass on
+/// raw output. `due` is when the previous wait was meant to end. Returns how
+/// long to wait for input before the next turn (`None`: until there is some).
+pub fn frame(
+    app: &mut App,
+    frontend: &mut dyn Frontend,
+    due: Option<Instant>,
+) -> io::Result<Option<Duration>> {
+    // Wall-clock timings for the debug overlay, whatever the app's clock.
+    let now = Instant::now();
+    app.frame_stats.tick_latency =
+        due.map_or(Duration::ZERO, |due| now.saturating_duration_since(due));
+    app.tick();
+    // Idle frames are skipped: only state changes and input redraw.
+    if app.take_dirty() {
+        frontend.render(app)?;
+        app.frame_stats.render = now.elapsed();
+        app.frame_stats.queued_input = frontend.queued_input();
+    }
+    if app.screenshot_requested {
+        let frame = frontend.last_frame();
+        app.save_screenshot(frame.as_ref());
+    }
+    let output = app.take_output();
+    if !output.is_empty() {
+        frontend.write_raw(&output)?;
+    }
+
+    // Sleep until the next deadline or input; the debug overlay keeps its
+    // timings live at the old fixed rate.
+    Ok(if app.debug_visible {
+        Some(Duration::from_millis(TICK_RATE_MS))
+    } else {
+        app.time_to_next_deadline()
+    })
+}
@@ -0,0 +1,59 @@
+//! Pack gallery: browse the installed packs with a live preview of each
+//! board and pick one as the default. Shown automatically on first launch.
+
+use crate::answers::AnswerPack;
+use crate::widget::random_index_except;
+use std::time::{Duration, Instant};
+
+const PREVIEW_STEP_MS: u64 = 250;
+
+pub struct Gallery {
+    pub packs: Vec<AnswerPack>,
+    pub selected: usize,
+    /// Index of the lit button in the preview board.
+    pub lit: usize,
+    next_switch: Instant,
+}
+
+impl Gallery {
+    /// Open the gallery on `packs` with `current` (a pack id) preselected.
+    pub fn new(packs: Vec<AnswerPack>, current: &str, now: Instant) -> Self {
+        let selected = packs
+            .iter()
+            .position(|pack| pack.id == current)
+            .unwrap_or(0);
+        Self {
+            packs,
+            selected,
+            lit: 0,
+            next_switch: now,
+        }
+    }
+
+    pub fn current(&self) -> &AnswerPack {
+        &self.packs[self.selected]
+    }
+
+    /// Move the selection by `delta`, wrapping at either end.
+    pub fn move_by(&mut self, delta: isize, now: Instant) {
+        let len = self.packs.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+        self.lit = 0;
+        self.next_switch = now;
+    }
+
+    /// When the preview light moves next.
+    pub fn next_deadline(&self) -> Instant {
+        self.next_switch
+    }
+
+    /// Move the preview light when it is due. Returns whether it moved.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if now < self.next_switch {
+            return false;
+        }
+        self.lit = random_index_except(self.current().answers.len(), self.lit);
+        self.next_switch = now + Duration::from_millis(PREVIEW_STEP_MS);
+        true
+    }
+}
@@ -0,0 +1,144 @@
+//! Headless driver for checking layouts and state transitions without a real
+//! terminal. A [`Harness`] owns an [`App`] on a [`ManualClock`] and renders it
+//! into a ratatui `TestBackend`:
+//!
+//! ```
+//! use executive_decision_maker::{answers, config::Config, harness::Harness};
+//!
+//! let pack = answers::builtin("classic").unwrap();
+//! let mut harness = Harness::new(pack, Config::default(), 80, 24)?;
+//! harness.press("Space");
+//! harness.advance(harness.app.config.animation());
+//! assert!(harness.app.last_answer.is_some());
+//! assert!(harness.snapshot()?.contains("EXECUTIVE DECISION MAKER"));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Keys go through the same [`input::map`] as in the TUI, so custom bindings
+//! and typing mode apply. Nothing is written to disk: history stays in
+//! memory and the config has no path.
+
+use crate::{
+    answers::AnswerPack,
+    app::{App, TICK_RATE_MS},
+    clipboard::Clipboard,
+    clock::ManualClock,
+    config::Config,
+    engine::Engine,
+    history::History,
+    input,
+    keymap::Chord,
+    theme, ui,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::{io, time::Duration};
+
+pub struct Harness {
+    pub app: App,
+    /// Shared with `app`; only [`Harness::advance`] moves it.
+    pub clock: ManualClock,
+    terminal: Terminal<TestBackend>,
+    /// Bytes the app asked to send to the terminal (bell, OSC 52).
+    output: Vec<u8>,
+    quit: bool,
+}
+
+impl Harness {
+    /// An app on `pack` with the default theme, sized `width` x `height`.
+    pub fn new(pack: AnswerPack, config: Config, width: u16, height: u16) -> io::Result<Self> {
+        let clock = ManualClock::new();
+        let engine = Engine::new(pack, None, History::default(), &config);
+        let theme = theme::builtin_themes().remove(0);
+        let mut app = App::new(engine, theme, config, None, Box::new(clock.clone()));
+        app.clipboard = Clipboard::terminal_only();
+        Ok(Self {
+            app,
+            clock,
+            terminal: Terminal::new(TestBackend::new(width, height))?,
+            output: Vec::new(),
+            quit: false,
+        })
+    }
+
+    /// Press each chord in `keys`, separated by spaces, e.g. `"? Esc Ctrl+G"`.
+    /// Panics on a chord that doesn't parse.
+    pub fn press(&mut self, keys: &str) {
+        for name in keys.split_whitespace() {
+            let chord: Chord = name.parse().unwrap_or_else(|err| panic!("{err}"));
+            self.key(KeyEvent {
+                code: chord.code,
+                modifiers: chord.modifiers,
+                kind: KeyEventKind::Press,
+                state: KeyEventState::NONE,
+            });
+        }
+    }
+
+    /// Type `text` character by character, as into the question field.
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    /// Feed one key event, mapped the way the TUI maps it.
+    pub fn key(&mut self, key: KeyEvent) {
+        if let Some(action) = input::map(self.app.input_mode(), &self.app.keymap, key) {
+            self.quit |= self.app.handle(action);
+        }
+        self.output.extend(self.app.take_output());
+    }
+
+    /// Let `by` pass, ticking the app once per frame as the real loop does.
+    pub fn advance(&mut self, by: Duration) {
+        let frame = Duration::from_millis(TICK_RATE_MS);
+        let mut left = by;
+        while !left.is_zero() {
+            let step = left.min(frame);
+            self.clock.advance(step);
+            self.app.tick();
+            left -= step;
+        }
+        self.output.extend(self.app.take_output());
+    }
+
+    /// Whether a key press asked the app to quit.
+    pub fn quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Everything the app wanted written to the terminal so far.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.terminal.backend_mut().resize(width, height);
+    }
+
+    /// Draw the current state and return the rendered buffer.
+    pub fn render(&mut self) -> io::Result<&Buffer> {
+        self.terminal.draw(|f| {
+            ui::draw(f, &self.app);
+        })?;
+        Ok(self.terminal.backend().buffer())
+    }
+
+    /// Draw the current state and return it as plain text, one line per row
+    /// with trailing spaces trimmed. Styles are left out; use
+    /// [`Harness::render`] to inspect them.
+    pub fn snapshot(&mut self) -> io::Result<String> {
+        let buffer = self.render()?;
+        let width = usize::from(buffer.area.width.max(1));
+        let lines: Vec<String> = buffer
+            .content
+            .chunks(width)
+            .map(|row| {
+                let line: String = row.iter().map(|cell| cell.symbol()).collect();
+                line.trim_end().to_string()
+            })
+            .collect();
+        Ok(lines.join("\n"))
+    }
+}
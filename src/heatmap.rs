@@ -0,0 +1,95 @@
+//! The calendar heatmap `edm stats` prints: a column per week and a row per
+//! weekday, each day shaded by how many decisions were made on it, like a
+//! contribution graph.
+//!
+//! ```text
+//!     Jan     Feb      Mar
+//! Mon ·░··▒·░··█··░·····
+//!     ···░··▒··▓·░······
+//! Wed ░·▒···░·········
+//! ```
+
+use crate::history::Decision;
+use chrono::{Datelike, Duration, NaiveDate};
+use std::collections::HashMap;
+
+/// How many weeks `edm stats` shows by default: a year.
+pub const DEFAULT_WEEKS: usize = 52;
+/// From no decisions to the busiest days.
+const SHADES: [char; 5] = ['·', '░', '▒', '▓', '█'];
+/// Room for the weekday labels.
+pub const LABEL_WIDTH: usize = 4;
+
+/// Decisions per day.
+pub fn counts<'a>(decisions: impl Iterator<Item = &'a Decision>) -> HashMap<NaiveDate, usize> {
+    let mut counts = HashMap::new();
+    for decision in decisions {
+        *counts.entry(decision.decided_at.date_naive()).or_default() += 1;
+    }
+    counts
+}
+
+/// The last `weeks` weeks up to `today` as lines of text: month names, a
+/// row per weekday from Monday, and a legend. Days after `today` are blank.
+pub fn render(counts: &HashMap<NaiveDate, usize>, today: NaiveDate, weeks: usize) -> Vec<String> {
+    let weeks = weeks.max(1);
+    let monday = today - Duration::days(today.weekday().num_days_from_monday().into());
+    let first = monday - Duration::weeks(weeks as i64 - 1);
+    let busiest = (0..weeks * 7)
+        .map(|day| first + Duration::days(day as i64))
+        .filter_map(|date| counts.get(&date))
+        .copied()
+        .max()
+        .unwrap_or_default();
+
+    let mut months = " ".repeat(LABEL_WIDTH);
+    let mut month = None;
+    for week in 0..weeks {
+        let start = first + Duration::weeks(week as i64);
+        let column = LABEL_WIDTH + week;
+        if month != Some(start.month()) {
+            month = Some(start.month());
+            // Only where it fits after the previous name.
+            if months.chars().count() <= column && week + 3 <= weeks {
+                months.push_str(&" ".repeat(column - months.chars().count()));
+                months.push_str(&start.format("%b").to_string());
+            }
+        }
+    }
+
+    let mut lines = vec![months.trim_end().to_string()];
+    for weekday in 0..7 {
+        let label = match weekday {
+            0 => "Mon",
+            2 => "Wed",
+            4 => "Fri",
+            _ => "",
+        };
+        let mut line = format!("{label:<LABEL_WIDTH$}");
+        for week in 0..weeks {
+            let date = first + Duration::days((week * 7 + weekday) as i64);
+            line.push(if date > today {
+                ' '
+            } else {
+                shade(counts.get(&date).copied().unwrap_or_default(), busiest)
+            });
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    let legend: Vec<String> = SHADES.iter().map(char::to_string).collect();
+    lines.push(format!(
+        "{}Less {} More (busiest day: {busiest})",
+        " ".repeat(LABEL_WIDTH),
+        legend.join(" ")
+    ));
+    lines
+}
+
+/// The shade for `count` decisions when the busiest day had `busiest`.
+fn shade(count: usize, busiest: usize) -> char {
+    if count == 0 {
+        return SHADES[0];
+    }
+    let steps = SHADES.len() - 1;
+    SHADES[(count * steps).div_ceil(busiest).clamp(1, steps)]
+}
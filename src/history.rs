@@ -0,0 +1,841 @@
+//! Decisions made during this session, optionally backed by a JSON Lines
+//! file (one decision per line) that new decisions are appended to. A note,
+//! tags, or a veto added later are appended as a line of their own naming
+//! the decision's time; the newest line for a decision wins. Every line carries
+//! its time and every decision an id, so [`History::merge`] can fold in
+//! another machine's copy without duplicates. With
+//! [`History::write_in_background`] the appends happen on a writer thread,
+//! so a slow disk never holds up a frame. With [`History::open_encrypted`]
+//! every line is sealed first; see [`crate::encryption`].
+
+use crate::answers::Answer;
+use crate::encryption::{self, Cipher};
+use crate::error::{EdmError, Result};
+use crate::seal;
+use crate::sentiment::{Sentiment, SentimentTally};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+};
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Decision {
+    /// A UUID, the same on every machine the decision is synced to. Empty
+    /// for decisions made before ids, which go by `decided_at` instead.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+    pub answer: String,
+    /// Id of the pack the answer came from.
+    pub pack: String,
+    pub sentiment: Sentiment,
+    pub decided_at: DateTime<Local>,
+    /// Whether the second opinion agreed, when one was asked for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agreed: Option<bool>,
+    /// After this the decision should be asked again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Local>>,
+    /// Journal note added after the reveal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Lower case, without the `#`; see [`crate::tags`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// How sure the oracle claimed to be, in percent; see
+    /// [`crate::confidence`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    /// When the answer was vetoed and asked again; see [`History::veto`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vetoed_at: Option<DateTime<Local>>,
+    /// Whether acting on it worked out, once rated; see [`History::rate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worked_out: Option<bool>,
+}
+
+impl Decision {
+    /// What tells this decision apart from every other, on any machine:
+    /// its id, or its time for decisions made before ids.
+    pub fn key(&self) -> String {
+        if self.id.is_empty() {
+            self.decided_at.to_rfc3339()
+        } else {
+            self.id.clone()
+        }
+    }
+
+    /// Whether the decision carries `tag`; any decision does for `None`.
+    pub fn has_tag(&self, tag: Option<&str>) -> bool {
+        match tag {
+            Some(tag) => self.tags.iter().any(|own| own == tag),
+            None => true,
+        }
+    }
+}
+
+/// A note added to the decision `id` (see [`Decision::key`]). An empty
+/// note removes the earlier one.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Note {
+    /// Older files point at the decision's time under `note_for`.
+    #[serde(alias = "note_for")]
+    id: String,
+    note: String,
+    /// When it was written; missing from older files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    noted_at: Option<DateTime<Local>>,
+}
+
+/// The tags of the decision `id`, replacing the earlier ones.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Tags {
+    #[serde(alias = "tags_for")]
+    id: String,
+    tags: Vec<String>,
+    /// When they were set; missing from older files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tagged_at: Option<DateTime<Local>>,
+}
+
+/// The decision `id` was vetoed and re-rolled.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Veto {
+    #[serde(alias = "veto_for")]
+    id: String,
+    vetoed_at: DateTime<Local>,
+}
+
+/// How acting on the decision `id` worked out, replacing the earlier
+/// rating.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Outcome {
+    #[serde(alias = "outcome_for")]
+    id: String,
+    worked_out: bool,
+    rated_at: DateTime<Local>,
+}
+
+/// One line of the history file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+enum Line {
+    Decision(Decision),
+    Note(Note),
+    Tags(Tags),
+    Veto(Veto),
+    Outcome(Outcome),
+}
+
+impl Line {
+    /// The decision an edit applies to and what kind of edit it is; none
+    /// for a decision.
+    fn edit(&self) -> Option<(&str, &'static str)> {
+        match self {
+            Line::Decision(_) => None,
+            Line::Note(note) => Some((&note.id, "note")),
+            Line::Tags(tags) => Some((&tags.id, "tags")),
+            Line::Veto(veto) => Some((&veto.id, "veto")),
+            Line::Outcome(outcome) => Some((&outcome.id, "outcome")),
+        }
+    }
+
+    /// When an edit was made; missing for notes and tags from older files.
+    fn edited_at(&self) -> Option<DateTime<Local>> {
+        match self {
+            Line::Decision(_) => None,
+            Line::Note(note) => note.noted_at,
+            Line::Tags(tags) => tags.tagged_at,
+            Line::Veto(veto) => Some(veto.vetoed_at),
+            Line::Outcome(outcome) => Some(outcome.rated_at),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct History {
+    entries: Vec<Decision>,
+    /// How many entries came from the file rather than this session.
+    loaded: usize,
+    /// Where new decisions are appended, if anywhere.
+    file: Option<PathBuf>,
+    /// The last failed append, until someone picks it up.
+    write_error: Option<io::Error>,
+    /// Background appender, once started.
+    writer: Option<Writer>,
+    /// Seals appended lines when the file is encrypted.
+    cipher: Option<Arc<Cipher>>,
+}
+
+/// Appends lines to the history file on its own thread.
+struct Writer {
+    jobs: Option<Sender<Line>>,
+    failures: Receiver<io::Error>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Writer {
+    fn spawn(path: PathBuf, cipher: Option<Arc<Cipher>>, wake: Arc<Notify>) -> Self {
+        let (jobs, queue) = mpsc::channel::<Line>();
+        let (report, failures) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            for line in queue {
+                if let Err(err) = History::append(&path, cipher.as_deref(), &line) {
+                    tracing::warn!(path = %path.display(), %err, "could not append to history");
+                    let _ = report.send(err);
+                    wake.notify_one();
+                }
+            }
+        });
+        Self {
+            jobs: Some(jobs),
+            failures,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for Writer {
+    /// Finish the queued appends before exiting, so `edm` never loses the
+    /// last decision of a session.
+    fn drop(&mut self) {
+        drop(self.jobs.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl History {
+    /// Load the decisions stored at `path` (none if it doesn't exist yet) and
+    /// keep appending to it. Lines that don't parse are skipped.
+    pub fn open(path: &Path) -> Result<Self> {
+        let text = Self::read(path)?;
+        if text.lines().next().and_then(encryption::header).is_some() {
+            return Err(EdmError::Encryption(format!(
+                "{} is encrypted; turn on encrypt_history",
+                path.display()
+            )));
+        }
+        Ok(Self::load(path, text.lines(), None))
+    }
+
+    /// Like [`History::open`] for a file encrypted with `passphrase`. A plain
+    /// file is encrypted first, and a new one starts out encrypted.
+    pub fn open_encrypted(path: &Path, passphrase: &str) -> Result<Self> {
+        let text = Self::read(path)?;
+        let mut lines = text.lines();
+        let Some(header) = text.lines().next().and_then(encryption::header) else {
+            let (cipher, header) = Cipher::create(passphrase)?;
+            let mut sealed = serde_json::to_string(&header)
+                .map_err(|err| EdmError::Encryption(err.to_string()))?;
+            sealed.push('\n');
+            for line in text.lines() {
+                sealed.push_str(&cipher.seal(line));
+                sealed.push('\n');
+            }
+            replace(path, &sealed)?;
+            tracing::info!(path = %path.display(), "history encrypted");
+            return Ok(Self::load(path, text.lines(), Some(cipher)));
+        };
+        lines.next();
+        let cipher = Cipher::unlock(&header, passphrase)?
+            .ok_or_else(|| EdmError::WrongPassphrase(path.to_path_buf()))?;
+        let plain: Vec<String> = lines.filter_map(|line| cipher.open(line)).collect();
+        Ok(Self::load(
+            path,
+            plain.iter().map(String::as_str),
+            Some(cipher),
+        ))
+    }
+
+    /// The text at `path`; empty if there is no file yet.
+    fn read(path: &Path) -> Result<String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+            Err(source) => Err(EdmError::History {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    /// The history in the plain `lines` of the file at `path`.
+    fn load<'a>(path: &Path, lines: impl Iterator<Item = &'a str>, cipher: Option<Cipher>) -> Self {
+        let entries = entries(lines.filter_map(|line| serde_json::from_str(line).ok()));
+        tracing::debug!(path = %path.display(), entries = entries.len(), "history loaded");
+        Self {
+            loaded: entries.len(),
+            entries,
+            file: Some(path.to_path_buf()),
+            write_error: None,
+            writer: None,
+            cipher: cipher.map(Arc::new),
+        }
+    }
+
+    /// Fold in `other`, another machine's copy of this history, decrypted
+    /// with `passphrase` if it is encrypted: decisions this file lacks, and
+    /// edits newer than its own of the same kind on the same decision, are
+    /// appended. Returns how many decisions were new.
+    pub fn merge(&mut self, other: &Path, passphrase: Option<&str>) -> Result<usize> {
+        let path = self.file.clone().ok_or(EdmError::NoDataDir)?;
+        let text = Self::read(&path)?;
+        let mut own = text.lines();
+        if self.cipher.is_some() {
+            own.next();
+        }
+        let own: Vec<String> = match &self.cipher {
+            Some(cipher) => own.filter_map(|line| cipher.open(line)).collect(),
+            None => own.map(str::to_string).collect(),
+        };
+        let mut lines = parse(&own);
+        let theirs = parse(&Self::read_plain(other, passphrase)?);
+
+        let mut known: HashSet<String> = HashSet::new();
+        let mut decisions: Vec<Decision> = Vec::new();
+        for line in lines.iter().chain(&theirs) {
+            if let Line::Decision(decision) = line {
+                if known.insert(decision.key()) {
+                    decisions.push(decision.clone());
+                }
+            }
+        }
+        // The latest edit of each kind on each decision, by the decision's
+        // key so that edits pointing at its time count too.
+        let edit_key = |line: &Line| {
+            line.edit().map(|(id, kind)| {
+                let key = position(&decisions, id)
+                    .map_or_else(|| id.to_string(), |found| decisions[found].key());
+                (key, kind)
+            })
+        };
+        let mut latest: HashMap<(String, &str), Option<DateTime<Local>>> = HashMap::new();
+        let mut decided: HashSet<String> = HashSet::new();
+        for line in &lines {
+            match line {
+                Line::Decision(decision) => {
+                    decided.insert(decision.key());
+                }
+                edit => {
+                    let edited_at = edit.edited_at();
+                    let newest = latest.entry(edit_key(edit).expect("an edit")).or_default();
+                    *newest = (*newest).max(edited_at);
+                }
+            }
+        }
+
+        let mut new_decisions = Vec::new();
+        let mut new_edits = Vec::new();
+        for line in theirs {
+            match &line {
+                Line::Decision(decision) => {
+                    if decided.insert(decision.key()) {
+                        new_decisions.push(line);
+                    }
+                }
+                edit => {
+                    let edited_at = edit.edited_at();
+                    let key = edit_key(edit).expect("an edit");
+                    let newer = latest.get(&key).is_none_or(|&newest| edited_at > newest);
+                    if newer {
+                        latest.insert(key, edited_at);
+                        new_edits.push(line);
+                    }
+                }
+            }
+        }
+
+        // Decisions first, so every appended edit finds its decision.
+        let added = new_decisions.len();
+        for line in new_decisions.into_iter().chain(new_edits) {
+            Self::append(&path, self.cipher.as_deref(), &line).map_err(|source| {
+                EdmError::History {
+                    path: path.clone(),
+                    source,
+                }
+            })?;
+            lines.push(line);
+        }
+        self.entries = entries(lines.into_iter());
+        self.loaded = self.entries.len();
+        tracing::info!(path = %path.display(), other = %other.display(), added, "history merged");
+        Ok(added)
+    }
+
+    /// The plain lines of the history file at `path`, decrypted with
+    /// `passphrase` if it is encrypted.
+    fn read_plain(path: &Path, passphrase: Option<&str>) -> Result<Vec<String>> {
+        let text = Self::read(path)?;
+        let mut lines = text.lines();
+        let Some(header) = text.lines().next().and_then(encryption::header) else {
+            return Ok(lines.map(str::to_string).collect());
+        };
+        lines.next();
+        let cipher = passphrase
+            .map(|passphrase| Cipher::unlock(&header, passphrase))
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| EdmError::WrongPassphrase(path.to_path_buf()))?;
+        Ok(lines.filter_map(|line| cipher.open(line)).collect())
+    }
+
+    /// Whether the file is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// Append new decisions on a background thread from now on. `wake` is
+    /// notified when an append fails.
+    pub fn write_in_background(&mut self, wake: Arc<Notify>) {
+        if let (Some(path), None) = (&self.file, &self.writer) {
+            self.writer = Some(Writer::spawn(path.clone(), self.cipher.clone(), wake));
+        }
+    }
+
+    /// The last error hit while saving a decision, if any.
+    pub fn take_write_error(&mut self) -> Option<io::Error> {
+        let reported = self
+            .writer
+            .as_ref()
+            .and_then(|writer| writer.failures.try_iter().last());
+        reported.or_else(|| self.write_error.take())
+    }
+
+    fn append(path: &Path, cipher: Option<&Cipher>, line: &Line) -> io::Result<()> {
+        let mut text = serde_json::to_string(line)?;
+        if let Some(cipher) = cipher {
+            text = cipher.seal(&text);
+        }
+        text.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(text.as_bytes())
+    }
+
+    /// Save `line` to the file, if there is one.
+    fn write(&mut self, line: Line) {
+        if let Some(writer) = &self.writer {
+            if let Some(jobs) = &writer.jobs {
+                // The thread only stops once `jobs` is dropped.
+                let _ = jobs.send(line);
+            }
+        } else if let Some(path) = &self.file {
+            if let Err(err) = Self::append(path, self.cipher.as_deref(), &line) {
+                tracing::warn!(path = %path.display(), %err, "could not append to history");
+                self.write_error = Some(err);
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn record(
+        &mut self,
+        pack: &str,
+        question: Option<&str>,
+        answer: &Answer,
+        agreed: Option<bool>,
+        expires_at: Option<DateTime<Local>>,
+        tags: &[String],
+        confidence: u8,
+        now: DateTime<Local>,
+    ) -> &Decision {
+        self.entries.push(Decision {
+            id: new_id(),
+            question: question.map(str::to_string),
+            answer: answer.text.clone(),
+            pack: pack.to_string(),
+            sentiment: answer.sentiment,
+            decided_at: now,
+            agreed,
+            expires_at,
+            note: None,
+            tags: tags.to_vec(),
+            confidence: Some(confidence),
+            vetoed_at: None,
+            worked_out: None,
+        });
+        let decision = self.entries[self.entries.len() - 1].clone();
+        self.write(Line::Decision(decision));
+        &self.entries[self.entries.len() - 1]
+    }
+
+    /// Set the note of the decision `id` (see [`Decision::key`]); an empty
+    /// `note` removes it. Returns whether there was such a decision.
+    pub fn set_note(&mut self, id: &str, note: &str, now: DateTime<Local>) -> bool {
+        let Some(entry) = find(&mut self.entries, id) else {
+            return false;
+        };
+        let id = entry.key();
+        let note = note.trim_end();
+        entry.note = Some(note.to_string()).filter(|note| !note.is_empty());
+        self.write(Line::Note(Note {
+            id,
+            note: note.to_string(),
+            noted_at: Some(now),
+        }));
+        true
+    }
+
+    /// Replace the tags of the decision `id`. Returns whether there was
+    /// such a decision.
+    pub fn set_tags(&mut self, id: &str, tags: Vec<String>, now: DateTime<Local>) -> bool {
+        let Some(entry) = find(&mut self.entries, id) else {
+            return false;
+        };
+        let id = entry.key();
+        entry.tags = tags.clone();
+        self.write(Line::Tags(Tags {
+            id,
+            tags,
+            tagged_at: Some(now),
+        }));
+        true
+    }
+
+    /// Mark the decision `id` as vetoed. Returns whether there was such a
+    /// decision.
+    pub fn veto(&mut self, id: &str, now: DateTime<Local>) -> bool {
+        let Some(entry) = find(&mut self.entries, id) else {
+            return false;
+        };
+        let id = entry.key();
+        entry.vetoed_at = Some(now);
+        self.write(Line::Veto(Veto { id, vetoed_at: now }));
+        true
+    }
+
+    /// Rate how acting on the decision `id` worked out. Returns whether
+    /// there was such a decision.
+    pub fn rate(&mut self, id: &str, worked_out: bool, now: DateTime<Local>) -> bool {
+        let Some(entry) = find(&mut self.entries, id) else {
+            return false;
+        };
+        let id = entry.key();
+        entry.worked_out = Some(worked_out);
+        self.write(Line::Outcome(Outcome {
+            id,
+            worked_out,
+            rated_at: now,
+        }));
+        true
+    }
+
+    /// How many answers were vetoed on the day of `now`.
+    pub fn vetoes_today(&self, now: DateTime<Local>) -> usize {
+        let today = now.date_naive();
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.vetoed_at)
+            .filter(|vetoed_at| vetoed_at.date_naive() == today)
+            .count()
+    }
+
+    /// Every tag in use, in alphabetical order.
+    pub fn tags(&self) -> Vec<String> {
+        let tags: BTreeSet<&String> = self.entries.iter().flat_map(|entry| &entry.tags).collect();
+        tags.into_iter().cloned().collect()
+    }
+
+    /// Decisions made since startup, oldest first.
+    pub fn session(&self) -> &[Decision] {
+        &self.entries[self.loaded..]
+    }
+
+    /// Newest first.
+    pub fn recent(&self) -> impl Iterator<Item = &Decision> {
+        self.entries.iter().rev()
+    }
+
+    /// Decisions tagged `tag` (all of them for `None`), newest first.
+    pub fn tagged<'a>(&'a self, tag: Option<&'a str>) -> impl Iterator<Item = &'a Decision> {
+        self.recent().filter(move |entry| entry.has_tag(tag))
+    }
+
+    /// Questions whose latest decision has expired, oldest first. Asking one
+    /// again takes it off the list.
+    pub fn needs_revalidation(&self, now: DateTime<Local>) -> Vec<&Decision> {
+        let mut seen = HashSet::new();
+        let mut expired: Vec<&Decision> = self
+            .recent()
+            .filter(|entry| {
+                let question = entry.question.as_deref().map(seal::normalize);
+                question.is_some_and(|question| seen.insert(question))
+            })
+            .filter(|entry| entry.expires_at.is_some_and(|expires_at| expires_at <= now))
+            .collect();
+        expired.reverse();
+        expired
+    }
+
+    /// Sentiment counts for decisions tagged `tag` made on the day of `now`.
+    pub fn today_tally(&self, tag: Option<&str>, now: DateTime<Local>) -> SentimentTally {
+        let mut tally = SentimentTally::default();
+        for entry in self.today(now).filter(|entry| entry.has_tag(tag)) {
+            tally.add(entry.sentiment);
+        }
+        tally
+    }
+
+    /// How many of the second opinions on decisions tagged `tag` made on the
+    /// day of `now` agreed, out of how many asked.
+    pub fn today_agreement(&self, tag: Option<&str>, now: DateTime<Local>) -> (usize, usize) {
+        self.today(now)
+            .filter(|entry| entry.has_tag(tag))
+            .filter_map(|entry| entry.agreed)
+            .fold((0, 0), |(agreed, asked), agrees| {
+                (agreed + usize::from(agrees), asked + 1)
+            })
+    }
+
+    fn today(&self, now: DateTime<Local>) -> impl Iterator<Item = &Decision> {
+        let today = now.date_naive();
+        self.entries
+            .iter()
+            .filter(move |entry| entry.decided_at.date_naive() == today)
+    }
+}
+
+/// The decisions `lines` describe, oldest first, with their latest notes,
+/// tags, vetoes, and outcomes.
+fn entries(lines: impl Iterator<Item = Line>) -> Vec<Decision> {
+    let (decisions, edits): (Vec<Line>, Vec<Line>) =
+        lines.partition(|line| matches!(line, Line::Decision(_)));
+    let mut entries: Vec<Decision> = decisions
+        .into_iter()
+        .filter_map(|line| match line {
+            Line::Decision(decision) => Some(decision),
+            _ => None,
+        })
+        .collect();
+    // Merged decisions are appended after newer local ones.
+    entries.sort_by_key(|entry| entry.decided_at);
+    for edit in edits {
+        match edit {
+            Line::Decision(_) => {}
+            Line::Note(note) => {
+                if let Some(entry) = find(&mut entries, &note.id) {
+                    entry.note = Some(note.note).filter(|note| !note.is_empty());
+                }
+            }
+            Line::Tags(tags) => {
+                if let Some(entry) = find(&mut entries, &tags.id) {
+                    entry.tags = tags.tags;
+                }
+            }
+            Line::Veto(veto) => {
+                if let Some(entry) = find(&mut entries, &veto.id) {
+                    entry.vetoed_at = Some(veto.vetoed_at);
+                }
+            }
+            Line::Outcome(outcome) => {
+                if let Some(entry) = find(&mut entries, &outcome.id) {
+                    entry.worked_out = Some(outcome.worked_out);
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// The lines of `text` that parse.
+fn parse(text: &[String]) -> Vec<Line> {
+    text.iter()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// A new random decision id.
+fn new_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Write `text` over the file at `path` through a temporary file, so a
+/// crash never leaves half of each.
+fn replace(path: &Path, text: &str) -> Result<()> {
+    let temporary = path.with_extension("jsonl.tmp");
+    fs::write(&temporary, text)
+        .and_then(|()| fs::rename(&temporary, path))
+        .map_err(|source| EdmError::History {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Where the newest entry `id` points at is: the one with that key, or
+/// for edits from before ids, the one made at that time.
+fn position(entries: &[Decision], id: &str) -> Option<usize> {
+    let decided_at = DateTime::parse_from_rfc3339(id).ok();
+    entries.iter().rposition(|entry| {
+        entry.key() == id || decided_at.is_some_and(|decided_at| entry.decided_at == decided_at)
+    })
+}
+
+fn find<'a>(entries: &'a mut [Decision], id: &str) -> Option<&'a mut Decision> {
+    position(entries, id).map(|found| &mut entries[found])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST: &str = r#"{"id":"a","answer":"YES","pack":"classic","sentiment":"positive","decided_at":"2026-10-16T09:00:00+02:00"}"#;
+    const SECOND: &str = r#"{"id":"b","answer":"NO","pack":"classic","sentiment":"negative","decided_at":"2026-10-16T10:00:00+02:00"}"#;
+
+    /// A fresh directory for one test's files.
+    fn scratch() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("edm-history-{}", new_id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_lines(path: &Path, lines: &[&str]) {
+        fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn edits_name_the_decision_by_id() {
+        let dir = scratch();
+        let path = dir.join("history.jsonl");
+        write_lines(&path, &[FIRST]);
+        let mut history = History::open(&path).unwrap();
+        let now = Local::now();
+        assert!(history.set_note("a", "went with it", now));
+        assert!(history.set_tags("a", vec!["work".to_string()], now));
+        assert!(history.rate("a", true, now));
+        assert!(!history.veto("missing", now));
+
+        let text = fs::read_to_string(&path).unwrap();
+        let note = text.lines().nth(1).unwrap();
+        assert!(
+            note.starts_with(r#"{"id":"a","note":"went with it""#),
+            "{note}"
+        );
+
+        let history = History::open(&path).unwrap();
+        let decision = history.recent().next().unwrap();
+        assert_eq!(decision.note.as_deref(), Some("went with it"));
+        assert_eq!(decision.tags, ["work"]);
+        assert_eq!(decision.worked_out, Some(true));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn edits_from_before_ids_still_find_their_decision() {
+        let dir = scratch();
+        let path = dir.join("history.jsonl");
+        // The same moment as FIRST, written with another offset.
+        write_lines(
+            &path,
+            &[
+                FIRST,
+                r#"{"note_for":"2026-10-16T07:00:00+00:00","note":"old"}"#,
+            ],
+        );
+        let history = History::open(&path).unwrap();
+        assert_eq!(
+            history.recent().next().unwrap().note.as_deref(),
+            Some("old")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn merge_appends_new_decisions_and_newer_edits() {
+        let dir = scratch();
+        let (own, other) = (dir.join("history.jsonl"), dir.join("laptop.jsonl"));
+        write_lines(
+            &own,
+            &[
+                FIRST,
+                r#"{"id":"a","note":"mine","noted_at":"2026-10-16T09:05:00+02:00"}"#,
+            ],
+        );
+        write_lines(
+            &other,
+            &[
+                FIRST,
+                r#"{"id":"a","note":"older","noted_at":"2026-10-16T09:01:00+02:00"}"#,
+                SECOND,
+                r#"{"id":"a","tags":["home"],"tagged_at":"2026-10-16T10:30:00+02:00"}"#,
+            ],
+        );
+        let before = fs::read_to_string(&own).unwrap();
+        let mut history = History::open(&own).unwrap();
+        assert_eq!(history.merge(&other, None).unwrap(), 1);
+
+        let after = fs::read_to_string(&own).unwrap();
+        assert!(after.starts_with(&before), "the file was rewritten");
+        assert_eq!(after.lines().count(), 4);
+        let answers: Vec<&str> = history.recent().map(|d| d.answer.as_str()).collect();
+        assert_eq!(answers, ["NO", "YES"]);
+        let first = history.recent().nth(1).unwrap();
+        assert_eq!(first.note.as_deref(), Some("mine"));
+        assert_eq!(first.tags, ["home"]);
+
+        assert_eq!(history.merge(&other, None).unwrap(), 0);
+        assert_eq!(fs::read_to_string(&own).unwrap(), after);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn merge_sees_through_timezone_offsets() {
+        let dir = scratch();
+        let (own, other) = (dir.join("history.jsonl"), dir.join("laptop.jsonl"));
+        write_lines(
+            &own,
+            &[
+                FIRST,
+                r#"{"tags_for":"2026-10-16T09:00:00+02:00","tags":["x"]}"#,
+            ],
+        );
+        write_lines(
+            &other,
+            &[FIRST, r#"{"tags_for":"2026-10-16T07:00:00Z","tags":["x"]}"#],
+        );
+        let before = fs::read_to_string(&own).unwrap();
+        let mut history = History::open(&own).unwrap();
+        assert_eq!(history.merge(&other, None).unwrap(), 0);
+        assert_eq!(fs::read_to_string(&own).unwrap(), before);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn opening_encrypted_converts_a_plain_file() {
+        let dir = scratch();
+        let path = dir.join("history.jsonl");
+        write_lines(&path, &[FIRST, SECOND]);
+        let history = History::open_encrypted(&path, "correct horse").unwrap();
+        assert_eq!(history.recent().count(), 2);
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(encryption::is_encrypted(&path));
+        assert!(!text.contains("YES"), "{text}");
+        assert_eq!(text.lines().count(), 3);
+        assert!(matches!(History::open(&path), Err(EdmError::Encryption(_))));
+        assert!(matches!(
+            History::open_encrypted(&path, "battery staple"),
+            Err(EdmError::WrongPassphrase(_))
+        ));
+
+        let history = History::open_encrypted(&path, "correct horse").unwrap();
+        let answers: Vec<_> = history.recent().map(|d| d.answer.as_str()).collect();
+        assert_eq!(answers, ["NO", "YES"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+//! History screen, opened with `H`: every decision, newest first, with `/`
+//! searching question, answer, and tags as the query is typed.
+
+use crate::history::{Decision, History};
+
+pub struct HistoryView {
+    /// The search typed so far; empty shows everything.
+    pub query: String,
+    /// Keys go into the query.
+    pub searching: bool,
+    /// Positions in [`History::recent`] of the decisions shown.
+    pub matches: Vec<usize>,
+    /// Index into `matches`.
+    pub selected: usize,
+}
+
+impl HistoryView {
+    pub fn new(history: &History) -> Self {
+        let mut view = Self {
+            query: String::new(),
+            searching: false,
+            matches: Vec::new(),
+            selected: 0,
+        };
+        view.refresh(history);
+        view
+    }
+
+    /// Filter `history` by the query again.
+    pub fn refresh(&mut self, history: &History) {
+        self.matches = history
+            .recent()
+            .enumerate()
+            .filter(|(_, decision)| matches(decision, &self.query))
+            .map(|(position, _)| position)
+            .collect();
+        self.selected = 0;
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.matches.len().saturating_sub(1));
+    }
+
+    pub fn type_char(&mut self, c: char, history: &History) {
+        self.query.push(c);
+        self.refresh(history);
+    }
+
+    pub fn erase(&mut self, history: &History) {
+        self.query.pop();
+        self.refresh(history);
+    }
+
+    /// Drop the query and show everything again.
+    pub fn clear(&mut self, history: &History) {
+        self.query.clear();
+        self.searching = false;
+        self.refresh(history);
+    }
+}
+
+/// Whether `decision`'s question, answer, or one of its tags contains
+/// `query`, ignoring case. A leading `#` only looks at tags.
+pub fn matches(decision: &Decision, query: &str) -> bool {
+    let query = query.trim();
+    if query.is_empty() {
+        return true;
+    }
+    if let Some(tag) = query.strip_prefix('#') {
+        let tag = tag.to_lowercase();
+        return decision.tags.iter().any(|own| own.contains(&tag));
+    }
+    let query = query.to_lowercase();
+    !highlight(&decision.answer, &query).is_empty()
+        || decision
+            .question
+            .as_deref()
+            .is_some_and(|question| !highlight(question, &query).is_empty())
+        || decision.tags.iter().any(|tag| tag.contains(&query))
+}
+
+/// Where `query` occurs in `text`, ignoring case, as character ranges.
+pub fn highlight(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let text: Vec<char> = text.chars().map(fold).collect();
+    let query: Vec<char> = query.trim().chars().map(fold).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut found = Vec::new();
+    let mut start = 0;
+    while start + query.len() <= text.len() {
+        if text[start..start + query.len()] == query[..] {
+            found.push((start, start + query.len()));
+            start += query.len();
+        } else {
+            start += 1;
+        }
+    }
+    found
+}
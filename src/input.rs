@@ -0,0 +1,223 @@
+//! Front-end independent input: the [`Action`]s an [`crate::app::App`]
+//! understands, and how the local terminal and SSH front ends turn crossterm
+//! key events into them.
+
+use crate::keymap::{Chord, Keymap};
+use crate::screen::Tab;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Front-end independent user input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Consult the oracle (or close the help screen).
+    Ask,
+    ToggleHelp,
+    /// Close the topmost overlay, or quit when there is none.
+    Back,
+    /// Quit immediately.
+    Quit,
+    /// Ctrl+Z: hand the terminal back to the shell until `fg`. Handled by
+    /// the event loop, not the app.
+    Suspend,
+    CopyAnswer,
+    /// Show the last decision as a QR code.
+    ShowQr,
+    /// Save the frame on screen as SVG or HTML.
+    Screenshot,
+    /// Move a selection (lists, galleries).
+    Up,
+    Down,
+    OpenGallery,
+    /// Start or advance the live theme preview.
+    CycleTheme,
+    /// Start typing a question.
+    EditQuestion,
+    OpenSettings,
+    /// Open the live weight editor for the current pack.
+    EditWeights,
+    /// Open the answer editor for the current pack.
+    EditPack,
+    /// Pick another profile to continue in.
+    OpenProfiles,
+    /// Browse past decisions.
+    OpenHistory,
+    /// List achievements, unlocked or not.
+    OpenAchievements,
+    /// Open the pros and cons worksheet.
+    OpenWorksheet,
+    /// Open the decision matrix.
+    OpenMatrix,
+    /// Open the tournament bracket.
+    OpenBracket,
+    /// Knock answers out one shuffle at a time until one is left.
+    Eliminate,
+    /// Start typing a search on the history screen.
+    Search,
+    /// Write a journal note on the last decision.
+    EditNote,
+    /// Change the tags of the last decision.
+    EditTags,
+    /// Narrow the recent list and tally down to the next tag.
+    CycleTagFilter,
+    /// Veto the last answer and ask again, within the daily budget.
+    Veto,
+    /// Mark the last decision, or the one selected in the history, as
+    /// having worked out.
+    MarkGood,
+    /// Mark it as not having worked out.
+    MarkBad,
+    /// Re-ask the oldest decision that has expired.
+    Revalidate,
+    /// Change how long the question being typed stays decided.
+    CycleValidity,
+    /// Change how long the next answer waits before it commits itself.
+    CycleTimebox,
+    /// Blank the screen until the passphrase is typed.
+    Lock,
+    /// List every action by name, to run one.
+    OpenPalette,
+    /// Show or hide the frame timing overlay.
+    ToggleDebug,
+    /// Switch the high-contrast theme on or off.
+    ToggleContrast,
+    /// Show the next tab of a tabbed screen (settings).
+    NextTab,
+    /// Step along the tab bar of screens: oracle, history, stats,
+    /// settings, help.
+    NextScreen,
+    PrevScreen,
+    /// A click on a tab of the tab bar.
+    ShowScreen(Tab),
+    /// A click on a row of the list on show: the item's index.
+    Select(usize),
+    /// Nudge a value (weights) up or down.
+    Increase,
+    Decrease,
+    /// A character typed into the question.
+    Type(char),
+    /// Delete the last typed character.
+    Erase,
+    /// A key chord captured while recording a binding.
+    Chord(Chord),
+    /// The window gained (`true`) or lost (`false`) focus.
+    Focus(bool),
+    /// The terminal changed size; only redraws.
+    Resize,
+}
+
+/// How key presses are read, depending on what the app is doing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Keys trigger their bound actions.
+    #[default]
+    Commands,
+    /// Keys are typed into the question.
+    Typing,
+    /// The next key is captured as a chord for the settings screen.
+    Recording,
+    /// A list on an editing screen (pack editor, worksheet, matrix,
+    /// bracket): arrows and a few keys are commands, letters are passed on
+    /// as typed.
+    Editing,
+    /// A note is written: Enter starts a new line and Ctrl+S saves.
+    Note,
+}
+
+/// Map a key press to an action in the given mode.
+pub fn map(mode: Mode, keymap: &Keymap, key: KeyEvent) -> Option<Action> {
+    match mode {
+        Mode::Commands => keymap.action(Chord::from(key)),
+        Mode::Typing => map_typing_key(key),
+        Mode::Recording => map_recording_key(key),
+        Mode::Editing => map_editing_key(key),
+        Mode::Note => map_note_key(key),
+    }
+}
+
+/// Map a key press to an action while a question is being typed: plain
+/// characters are text, only a few keys keep their meaning.
+fn map_typing_key(key: KeyEvent) -> Option<Action> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return match key.code {
+            KeyCode::Char('c') | KeyCode::Char('C') => Some(Action::Quit),
+            KeyCode::Char('z') | KeyCode::Char('Z') => Some(Action::Suspend),
+            KeyCode::Char('t') | KeyCode::Char('T') => Some(Action::CycleTimebox),
+            _ => None,
+        };
+    }
+
+    match key.code {
+        KeyCode::Esc => Some(Action::Back),
+        KeyCode::Enter => Some(Action::Ask),
+        KeyCode::Backspace => Some(Action::Erase),
+        KeyCode::Tab => Some(Action::CycleValidity),
+        KeyCode::Up => Some(Action::Up),
+        KeyCode::Down => Some(Action::Down),
+        KeyCode::Char(c) => Some(Action::Type(c)),
+        _ => None,
+    }
+}
+
+/// Map a key press to an action in an editing screen's list. The screen
+/// gives the letters their meaning.
+fn map_editing_key(key: KeyEvent) -> Option<Action> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return match key.code {
+            KeyCode::Char('c') | KeyCode::Char('C') => Some(Action::Quit),
+            KeyCode::Char('z') | KeyCode::Char('Z') => Some(Action::Suspend),
+            _ => None,
+        };
+    }
+
+    match key.code {
+        KeyCode::Esc => Some(Action::Back),
+        KeyCode::Enter => Some(Action::Ask),
+        KeyCode::Up => Some(Action::Up),
+        KeyCode::Down => Some(Action::Down),
+        KeyCode::Tab => Some(Action::NextTab),
+        KeyCode::Right | KeyCode::Char('+') => Some(Action::Increase),
+        KeyCode::Left | KeyCode::Char('-') => Some(Action::Decrease),
+        KeyCode::Backspace | KeyCode::Delete => Some(Action::Erase),
+        KeyCode::Char(c) => Some(Action::Type(c)),
+        _ => None,
+    }
+}
+
+/// Map a key press to an action in the note editor: text goes in as typed,
+/// Enter included, and the arrows move the cursor.
+fn map_note_key(key: KeyEvent) -> Option<Action> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return match key.code {
+            KeyCode::Char('c') | KeyCode::Char('C') => Some(Action::Quit),
+            KeyCode::Char('z') | KeyCode::Char('Z') => Some(Action::Suspend),
+            KeyCode::Char('s') | KeyCode::Char('S') => Some(Action::Ask),
+            _ => None,
+        };
+    }
+
+    match key.code {
+        KeyCode::Esc => Some(Action::Back),
+        KeyCode::Enter => Some(Action::Type('\n')),
+        KeyCode::Up => Some(Action::Up),
+        KeyCode::Down => Some(Action::Down),
+        KeyCode::Right => Some(Action::Increase),
+        KeyCode::Left => Some(Action::Decrease),
+        KeyCode::Backspace => Some(Action::Erase),
+        KeyCode::Char(c) => Some(Action::Type(c)),
+        _ => None,
+    }
+}
+
+/// While recording, every chord is captured except Esc, which cancels,
+/// Ctrl+C, which still quits, and Ctrl+Z, which still suspends.
+fn map_recording_key(key: KeyEvent) -> Option<Action> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Char('c') | KeyCode::Char('C') if ctrl => Some(Action::Quit),
+        KeyCode::Char('z') | KeyCode::Char('Z') if ctrl => Some(Action::Suspend),
+        KeyCode::Esc => Some(Action::Back),
+        // Lone modifier presses (with keyboard enhancement) are not chords.
+        KeyCode::Modifier(_) => None,
+        _ => Some(Action::Chord(Chord::from(key))),
+    }
+}
@@ -0,0 +1,314 @@
+//! Configurable key bindings.
+//!
+//! A [`Keymap`] maps key chords to [`Action`]s. It starts from the built-in
+//! bindings and applies the `[keys]` table of the config, where each action
+//! lists the chords that trigger it:
+//!
+//! ```toml
+//! [keys]
+//! ask = ["Enter", "Space"]
+//! gallery = ["Ctrl+G"]
+//! ```
+//!
+//! An action listed there loses its built-in chords. Ctrl+C always quits
+//! and Ctrl+Z always suspends, whatever the config says.
+
+use crate::input::Action;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// A key plus the modifiers held with it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Chord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Chord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // A character already carries its case, so Shift would only make
+        // `Q` and `Shift+Q` two different chords.
+        let modifiers = match code {
+            KeyCode::Char(_) => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self {
+            code,
+            modifiers: modifiers
+                & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT),
+        }
+    }
+
+    fn key(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+}
+
+impl From<KeyEvent> for Chord {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            code => {
+                let name = NAMED_KEYS
+                    .iter()
+                    .find(|(_, named)| *named == code)
+                    .map_or("?", |(name, _)| name);
+                f.write_str(name)
+            }
+        }
+    }
+}
+
+const NAMED_KEYS: [(&str, KeyCode); 14] = [
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+impl FromStr for Chord {
+    type Err = String;
+
+    /// Parse names like `q`, `Space`, `Ctrl+H` or `Alt+Shift+Up`.
+    fn from_str(text: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        // `+` on its own (or after a modifier) is the plus key.
+        while let Some((prefix, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier `{prefix}` in `{text}`")),
+            };
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ if rest.eq_ignore_ascii_case("space") => KeyCode::Char(' '),
+            _ => match NAMED_KEYS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(rest))
+            {
+                Some((_, code)) => *code,
+                None => match rest.strip_prefix(['F', 'f']).map(str::parse::<u8>) {
+                    Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key `{rest}` in `{text}`")),
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+/// Actions that can be bound, with their config names and labels.
+pub const BINDABLE: [(Action, &str, &str); 39] = [
+    (Action::Ask, "ask", "Ask"),
+    (Action::Back, "back", "Back / quit"),
+    (Action::Quit, "quit", "Quit now"),
+    (Action::ToggleHelp, "help", "Help"),
+    (Action::OpenPalette, "palette", "Command palette"),
+    (Action::CopyAnswer, "copy", "Copy answer"),
+    (Action::ShowQr, "qr", "QR code"),
+    (Action::Screenshot, "screenshot", "Screenshot"),
+    (Action::EditQuestion, "question", "Type a question"),
+    (Action::OpenGallery, "gallery", "Pack gallery"),
+    (Action::CycleTheme, "theme", "Themes"),
+    (Action::OpenSettings, "settings", "Settings"),
+    (Action::EditWeights, "weights", "Tune weights"),
+    (Action::EditPack, "pack", "Edit answers"),
+    (Action::OpenProfiles, "profiles", "Switch profile"),
+    (Action::OpenHistory, "history", "History"),
+    (Action::OpenAchievements, "achievements", "Achievements"),
+    (Action::OpenWorksheet, "worksheet", "Pros & cons"),
+    (Action::OpenMatrix, "matrix", "Decision matrix"),
+    (Action::OpenBracket, "bracket", "Tournament"),
+    (Action::Search, "search", "Search history"),
+    (Action::EditNote, "note", "Note on answer"),
+    (Action::EditTags, "tags", "Tag answer"),
+    (Action::CycleTagFilter, "filter", "Filter by tag"),
+    (Action::Veto, "veto", "Veto and re-roll"),
+    (Action::Eliminate, "eliminate", "Elimination"),
+    (Action::Revalidate, "revalidate", "Re-ask expired"),
+    (Action::MarkGood, "good", "Worked out"),
+    (Action::MarkBad, "bad", "Didn't work out"),
+    (Action::Lock, "lock", "Lock screen"),
+    (Action::ToggleDebug, "debug", "Debug overlay"),
+    (Action::ToggleContrast, "contrast", "High contrast"),
+    (Action::NextScreen, "next_screen", "Next screen"),
+    (Action::PrevScreen, "prev_screen", "Previous screen"),
+    (Action::NextTab, "tab", "Next tab"),
+    (Action::Increase, "increase", "Increase"),
+    (Action::Decrease, "decrease", "Decrease"),
+    (Action::Up, "up", "Up"),
+    (Action::Down, "down", "Down"),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keymap {
+    /// Chords per entry of [`BINDABLE`], in the same order.
+    chords: Vec<Vec<Chord>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let chords = BINDABLE
+            .iter()
+            .map(|(action, _, _)| default_chords(*action))
+            .collect();
+        Self { chords }
+    }
+}
+
+fn default_chords(action: Action) -> Vec<Chord> {
+    let keys = |codes: &[KeyCode]| codes.iter().map(|&code| Chord::key(code)).collect();
+    let chars = |chars: &str| {
+        chars
+            .chars()
+            .map(|c| Chord::key(KeyCode::Char(c)))
+            .collect()
+    };
+    match action {
+        Action::Ask => keys(&[KeyCode::Enter, KeyCode::Char(' ')]),
+        Action::Back => keys(&[KeyCode::Esc, KeyCode::Char('q'), KeyCode::Char('Q')]),
+        Action::Quit => vec![Chord::ctrl('c'), Chord::ctrl('C')],
+        Action::ToggleHelp => vec![Chord::ctrl('h'), Chord::ctrl('H')],
+        Action::OpenPalette => vec![Chord::ctrl('p'), Chord::ctrl('P')],
+        Action::CopyAnswer => chars("yc"),
+        Action::ShowQr => chars("s"),
+        Action::Screenshot => chars("S"),
+        Action::EditQuestion => chars("i"),
+        Action::OpenGallery => chars("g"),
+        Action::CycleTheme => chars("T"),
+        Action::OpenSettings => chars(","),
+        Action::EditWeights => chars("W"),
+        Action::EditPack => chars("E"),
+        Action::OpenProfiles => chars("P"),
+        Action::OpenHistory => chars("H"),
+        Action::OpenAchievements => chars("A"),
+        Action::OpenWorksheet => chars("w"),
+        Action::OpenMatrix => chars("m"),
+        Action::OpenBracket => chars("b"),
+        Action::Search => chars("/"),
+        Action::EditNote => chars("n"),
+        Action::EditTags => chars("#"),
+        Action::CycleTagFilter => chars("t"),
+        Action::Veto => chars("r"),
+        Action::Eliminate => chars("x"),
+        Action::Revalidate => chars("R"),
+        Action::MarkGood => chars("u"),
+        Action::MarkBad => chars("d"),
+        Action::Lock => chars("L"),
+        Action::ToggleDebug => keys(&[KeyCode::F(12)]),
+        Action::ToggleContrast => keys(&[KeyCode::F(2)]),
+        Action::NextScreen => chars("]"),
+        Action::PrevScreen => chars("["),
+        Action::NextTab => keys(&[KeyCode::Tab]),
+        Action::Increase => keys(&[KeyCode::Char('+'), KeyCode::Char('='), KeyCode::Right]),
+        Action::Decrease => keys(&[KeyCode::Char('-'), KeyCode::Left]),
+        Action::Up => keys(&[KeyCode::Up, KeyCode::Char('k')]),
+        Action::Down => keys(&[KeyCode::Down, KeyCode::Char('j')]),
+        _ => Vec::new(),
+    }
+}
+
+impl Keymap {
+    /// The built-in bindings with the config's `[keys]` table applied.
+    /// Unknown actions and unparsable chords are skipped and reported.
+    pub fn from_config(keys: &BTreeMap<String, Vec<String>>) -> (Self, Vec<String>) {
+        let mut keymap = Self::default();
+        let mut errors = Vec::new();
+        for (name, chords) in keys {
+            let Some(slot) = BINDABLE.iter().position(|(_, id, _)| id == name) else {
+                errors.push(format!("unknown action `{name}`"));
+                continue;
+            };
+            let mut parsed = Vec::new();
+            for chord in chords {
+                match chord.parse() {
+                    Ok(chord) => parsed.push(chord),
+                    Err(err) => errors.push(err),
+                }
+            }
+            keymap.chords[slot] = parsed;
+        }
+        (keymap, errors)
+    }
+
+    /// The `[keys]` table for this keymap: only actions whose chords differ
+    /// from the built-in ones.
+    pub fn to_config(&self) -> BTreeMap<String, Vec<String>> {
+        let defaults = Self::default();
+        BINDABLE
+            .iter()
+            .zip(self.chords.iter().zip(&defaults.chords))
+            .filter(|(_, (chords, default))| chords != default)
+            .map(|((_, name, _), (chords, _))| {
+                let names = chords.iter().map(Chord::to_string).collect();
+                (name.to_string(), names)
+            })
+            .collect()
+    }
+
+    /// The action bound to `chord`, if any.
+    pub fn action(&self, chord: Chord) -> Option<Action> {
+        if chord == Chord::ctrl('c') {
+            return Some(Action::Quit);
+        }
+        if chord == Chord::ctrl('z') {
+            return Some(Action::Suspend);
+        }
+        BINDABLE
+            .iter()
+            .zip(&self.chords)
+            .find(|(_, chords)| chords.contains(&chord))
+            .map(|((action, _, _), _)| *action)
+    }
+
+    /// The chords bound to the [`BINDABLE`] entry at `slot`.
+    pub fn chords(&self, slot: usize) -> &[Chord] {
+        &self.chords[slot]
+    }
+
+    /// Make `chord` the only binding of the entry at `slot`, taking it away
+    /// from any other action.
+    pub fn bind(&mut self, slot: usize, chord: Chord) {
+        for chords in &mut self.chords {
+            chords.retain(|bound| *bound != chord);
+        }
+        self.chords[slot] = vec![chord];
+    }
+}
@@ -0,0 +1,91 @@
+//! The Executive Decision Maker as a library: the decision [`Engine`], the
+//! [`App`] state machine around it, and the ratatui rendering in [`ui`].
+//!
+//! The `edm` binary is a thin driver over this crate. Other programs can
+//! embed the oracle by building an [`App`] and running it with their own
+//! [`frontend::Frontend`], or use the [`Engine`] alone without any UI.
+
+pub mod accessible;
+pub mod achievements;
+pub mod adapt;
+pub mod answers;
+pub mod app;
+pub mod best_of;
+pub mod bracket;
+pub mod cast;
+pub mod chaos;
+pub mod chat;
+pub mod clipboard;
+pub mod clock;
+pub mod command_palette;
+pub mod commands;
+pub mod confidence;
+pub mod config;
+pub mod config_check;
+pub mod demo;
+pub mod download;
+pub mod elimination;
+pub mod encryption;
+pub mod engine;
+pub mod error;
+pub mod events;
+pub mod exit_summary;
+pub mod experiments;
+pub mod export;
+pub mod fairness;
+#[cfg(test)]
+mod fixtures;
+pub mod form;
+pub mod frontend;
+pub mod gallery;
+pub mod harness;
+pub mod heatmap;
+pub mod history;
+pub mod history_view;
+pub mod input;
+pub mod keymap;
+pub mod locale;
+pub mod logging;
+pub mod markdown;
+pub mod matrix;
+pub mod mouse;
+pub mod notify;
+pub mod opinion;
+pub mod org;
+pub mod pack_check;
+pub mod pack_editor;
+pub mod palette;
+pub mod paths;
+pub mod profile;
+pub mod qr;
+pub mod rationale;
+pub mod recall;
+pub mod screen;
+pub mod screenshot;
+pub mod seal;
+pub mod sentiment;
+pub mod server;
+pub mod settings;
+pub mod ssh;
+pub mod stats;
+pub mod streaks;
+pub mod suggest;
+pub mod tags;
+pub mod terminal;
+pub mod text_area;
+pub mod theme;
+pub mod transliteration;
+pub mod tui;
+pub mod ui;
+pub mod webhook;
+pub mod weights;
+pub mod widget;
+pub mod width;
+pub mod worksheet;
+pub mod workspace;
+
+pub use answers::{Answer, AnswerPack};
+pub use app::App;
+pub use engine::Engine;
+pub use input::Action;
+pub use widget::{DecisionMakerWidget, DecisionState};
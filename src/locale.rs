@@ -0,0 +1,149 @@
+//! Interface strings, kept in [Fluent](https://projectfluent.org) files under
+//! `locales/` and compiled in.
+//!
+//! The language comes from `LC_ALL`, `LC_MESSAGES` or `LANG`, the first one
+//! set, the way gettext picks it. English covers whatever a translation
+//! leaves out, and any locale without a file at all.
+//!
+//! Right-to-left languages mirror the layout, and since most terminals
+//! print cells strictly left to right, [`Strings::visual`] puts their text
+//! in display order first. Terminals with their own bidi support (GNOME
+//! Terminal, Konsole, mlterm) set `terminal_bidi` in the config instead.
+
+use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use std::{borrow::Cow, env};
+use unic_langid::{CharacterDirection, LanguageIdentifier};
+use unicode_bidi::{BidiInfo, Level};
+
+/// Languages with a translation, by primary language subtag. English comes
+/// first: it is the fallback.
+const LOCALES: [(&str, &str); 3] = [
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+    ("he", include_str!("../locales/he.ftl")),
+];
+
+/// The interface strings for one language.
+pub struct Strings {
+    /// The chosen language, then English.
+    bundles: Vec<FluentBundle<FluentResource>>,
+    /// The chosen language is written right to left.
+    rtl: bool,
+    /// The terminal lays out right-to-left text itself, so
+    /// [`Strings::visual`] leaves it alone.
+    pub terminal_bidi: bool,
+}
+
+impl Strings {
+    /// Strings for the locale in the environment.
+    pub fn detect() -> Self {
+        let tag = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty());
+        Self::for_locale(tag.as_deref().unwrap_or("en"))
+    }
+
+    /// Strings for a POSIX locale name like `de_DE.UTF-8` or a language tag
+    /// like `de-AT`. Unknown languages get English.
+    pub fn for_locale(tag: &str) -> Self {
+        // `de_DE.UTF-8@euro` → `de-DE`; `C` and `POSIX` parse as nothing.
+        let tag = tag.split(['.', '@']).next().unwrap_or_default();
+        let language = tag
+            .replace('_', "-")
+            .parse::<LanguageIdentifier>()
+            .ok()
+            .map(|id| id.language.as_str().to_string());
+        let mut bundles = Vec::new();
+        let mut rtl = false;
+        if let Some((code, source)) = LOCALES
+            .iter()
+            .skip(1)
+            .find(|(code, _)| Some(*code) == language.as_deref())
+        {
+            bundles.push(bundle(code, source));
+            rtl = code
+                .parse::<LanguageIdentifier>()
+                .is_ok_and(|id| id.character_direction() == CharacterDirection::RTL);
+        }
+        bundles.push(bundle(LOCALES[0].0, LOCALES[0].1));
+        Self {
+            bundles,
+            rtl,
+            terminal_bidi: false,
+        }
+    }
+
+    /// Whether the interface reads right to left, so alignment and the
+    /// answer grid are mirrored.
+    pub fn is_rtl(&self) -> bool {
+        self.rtl
+    }
+
+    /// `text` in the order its characters appear on screen: as is for
+    /// left-to-right languages, reordered by the Unicode bidi algorithm with
+    /// a right-to-left base direction otherwise. Runs of Latin text, key
+    /// names and numbers keep their own order within the line.
+    pub fn visual<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.rtl || self.terminal_bidi {
+            return Cow::Borrowed(text);
+        }
+        let info = BidiInfo::new(text, Some(Level::rtl()));
+        match info.paragraphs.first() {
+            Some(paragraph) => info.reorder_line(paragraph, paragraph.range.clone()),
+            None => Cow::Borrowed(text),
+        }
+    }
+
+    /// The message `id`, without arguments.
+    pub fn get(&self, id: &str) -> String {
+        self.format(id, &[])
+    }
+
+    /// The message `id` with `args` filled in. A message missing from every
+    /// bundle comes out as its id, so the gap is visible on screen.
+    pub fn format(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        for bundle in &self.bundles {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+            if !errors.is_empty() {
+                tracing::warn!(id, ?errors, "could not format message");
+            }
+            return text.into_owned();
+        }
+        tracing::warn!(id, "missing message");
+        id.to_string()
+    }
+}
+
+impl Default for Strings {
+    /// English.
+    fn default() -> Self {
+        Self::for_locale("en")
+    }
+}
+
+/// A bundle of the compiled-in file for `code`.
+fn bundle(code: &str, source: &str) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = code.parse().expect("locale codes are valid");
+    let mut bundle = FluentBundle::new(vec![language]);
+    // Unicode isolation marks around placeables show up as stray cells in
+    // some terminals.
+    bundle.set_use_isolating(false);
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+            tracing::warn!(code, ?errors, "errors in locale file");
+            resource
+        });
+    if let Err(errors) = bundle.add_resource(resource) {
+        tracing::warn!(code, ?errors, "could not load locale file");
+    }
+    bundle
+}
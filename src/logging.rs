@@ -0,0 +1,52 @@
+//! Diagnostic logging with `tracing`.
+//!
+//! Off unless `--log-level` or `RUST_LOG` asks for it; the flag wins when
+//! both are set. Logs go to `edm.<date>.log` in [`paths::log_dir`], rotated
+//! daily with the last [`KEEP_FILES`] kept, and never to stdout or stderr:
+//! the TUI owns the screen.
+
+use crate::{
+    error::{EdmError, Result},
+    paths,
+};
+use std::path::Path;
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::EnvFilter;
+
+/// Rotated log files kept around.
+pub const KEEP_FILES: usize = 7;
+
+/// Start logging at `level` (anything `RUST_LOG` accepts, e.g. `debug` or
+/// `edm=trace`). Returns `None` when logging stays off; otherwise keep the
+/// guard alive until exit so buffered lines get written. `data` is
+/// `--data-dir`, if given.
+pub fn init(level: Option<&str>, data: Option<&Path>) -> Result<Option<WorkerGuard>> {
+    let filter = match level {
+        Some(level) => EnvFilter::try_new(level)
+            .map_err(|err| EdmError::Logging(format!("invalid log level `{level}`: {err}")))?,
+        None => match EnvFilter::try_from_default_env() {
+            Ok(filter) => filter,
+            Err(_) => return Ok(None),
+        },
+    };
+    let dir = paths::log_dir(data)
+        .ok_or_else(|| EdmError::Logging("no home directory for the log files".to_string()))?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("edm")
+        .filename_suffix("log")
+        .max_log_files(KEEP_FILES)
+        .build(&dir)
+        .map_err(|err| EdmError::Logging(format!("{}: {err}", dir.display())))?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false)
+        .try_init()
+        .map_err(|err| EdmError::Logging(err.to_string()))?;
+    Ok(Some(guard))
+}
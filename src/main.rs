@@ -2,441 +2,777 @@
 //! ------------------------------------------------
 //! - Press Enter or Space (or click the "ASK" prompt) to get a random answer.
 //! - The chosen answer lights up for 1.5 s.
+//! - Browse and pick answer packs with `g` (shown on first launch).
+//! - Type your question first with `i`; with `daily_seal` the same question
+//!   gets the same answer all day.
+//! - Optionally get a second opinion from another pack after each answer.
+//! - Preview and switch colour themes live with `T`.
+//! - Keep work and play apart with `--profile NAME`; switch with `P`.
+//! - Copy the last answer to the clipboard with `y` or `c`.
 //! - Quit with `q`, `Esc`, or Ctrl+C.
-
-use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ExecutableCommand,
-};
-use rand::Rng;
-use ratatui::{
-    backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
-    Terminal,
+//! - `--simulate-speed 10x` runs every timer faster (debug aid).
+
+use chrono::{Duration, Local, NaiveDate};
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::{ArgValueCandidates, CompleteEnv};
+use executive_decision_maker::{
+    accessible::LinearFrontend,
+    achievements::Achievements,
+    answers::{self, AnswerPack},
+    cast,
+    chat::ChatService,
+    clipboard::Clipboard,
+    clock::{self, Clock, ScaledClock, SystemClock},
+    commands,
+    config::Config,
+    encryption,
+    error::{EdmError, Result},
+    exit_summary,
+    export::{Csv, Exporter},
+    frontend,
+    history::History,
+    locale::Strings,
+    logging,
+    markdown::Markdown,
+    org::Org,
+    palette::ColorDepth,
+    paths, server, ssh, tags,
+    theme::{self, Theme},
+    transliteration::{self, Scheme},
+    tui, workspace, App, Engine,
 };
 use std::{
+    fs,
     io::{self, Write},
-    time::{Duration, Instant},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
 };
 
-/// The six possible answers (exactly as on the original device)
-const ANSWERS: [&str; 6] = [
-    "DEFINITELY",
-    "FORGET IT",
-    "ASK AGAIN",
-    "NEVER",
-    "POSSIBLY",
-    "WHY NOT",
-];
-
-const ANIMATION_DURATION_MS: u64 = 2_000;
-const ANIMATION_STEP_MS: u64 = 120;
-const ANSWER_FLASH_MS: u64 = 1_500;
-const TICK_RATE_MS: u64 = 50;
-
-#[derive(Clone, Copy, PartialEq)]
-enum State {
-    Idle,
-    Animating {
-        final_index: usize,
-        current_index: usize,
-        end_at: Instant,
-        next_switch: Instant,
+/// Command-line options
+#[derive(Parser)]
+#[command(name = "edm", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Run all timers faster than real time, e.g. `10x` (debug aid)
+    #[arg(long, value_name = "FACTOR", value_parser = clock::parse_speed)]
+    simulate_speed: Option<f64>,
+
+    /// Answer pack to use for this session (see the `g` gallery for ids)
+    #[arg(long, value_name = "ID", global = true)]
+    #[arg(add = ArgValueCandidates::new(commands::pack_candidates))]
+    pack: Option<String>,
+
+    /// Also ask this pack for a second opinion after each answer
+    #[arg(long, value_name = "ID", global = true)]
+    #[arg(add = ArgValueCandidates::new(commands::pack_candidates))]
+    second_opinion: Option<String>,
+
+    /// Print this template instead of the last decision when the TUI exits,
+    /// e.g. "{answer} after {asks} asks"
+    #[arg(long, value_name = "TEMPLATE", num_args = 0..=1)]
+    #[arg(default_missing_value = exit_summary::DEFAULT_TEMPLATE)]
+    exit_summary: Option<String>,
+
+    /// Print plain status lines instead of drawing the full-screen board, for
+    /// screen readers and braille displays
+    #[arg(long)]
+    accessible: bool,
+
+    /// Ask a canned question every SECONDS (default 10), forever, for a booth
+    /// or office TV; nothing is written to the history
+    #[arg(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "10")]
+    #[arg(value_parser = parse_demo_interval)]
+    demo: Option<u64>,
+
+    /// Append every ask's events to FILE as JSON lines (see `edm events schema`)
+    #[arg(long, value_name = "FILE")]
+    event_log: Option<PathBuf>,
+
+    /// Record the session to FILE as an asciinema cast, for `asciinema play`
+    /// or a web page
+    #[arg(long, value_name = "FILE", conflicts_with = "accessible")]
+    record: Option<PathBuf>,
+
+    /// Write diagnostics at LEVEL (e.g. `debug`, `edm=trace`) to the log
+    /// directory; overrides `RUST_LOG`
+    #[arg(long, value_name = "LEVEL", global = true, env = "EDM_LOG_LEVEL")]
+    log_level: Option<String>,
+
+    /// Config file (defaults to `config.toml` in the platform's config
+    /// directory, e.g. `~/.config/edm/` on Linux)
+    #[arg(long, value_name = "FILE", global = true, env = "EDM_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Keep history and logs in DIR instead of the platform's data directory
+    #[arg(long, value_name = "DIR", global = true, env = "EDM_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Keep history in FILE, inside a workspace or not
+    #[arg(long, value_name = "FILE", global = true, env = "EDM_HISTORY_FILE")]
+    history_file: Option<PathBuf>,
+
+    /// Count only decisions tagged TAG in the recent list and tally (`t`
+    /// cycles through the tags in the app)
+    #[arg(long, value_name = "TAG", value_parser = parse_tag)]
+    tag: Option<String>,
+
+    /// Use the config, packs, and history of profile NAME, e.g. `work`
+    #[arg(long, value_name = "NAME", global = true, env = "EDM_PROFILE")]
+    #[arg(default_value = paths::DEFAULT_PROFILE)]
+    #[arg(add = ArgValueCandidates::new(commands::profile_candidates))]
+    profile: String,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a single answer without starting the TUI
+    Ask {
+        /// The question being decided
+        question: Option<String>,
+
+        /// Also announce the answer via the configured chat webhook
+        #[arg(long, value_name = "SERVICE")]
+        post: Option<ChatService>,
+    },
+    /// List past decisions, newest first, with today's tally
+    History {
+        #[command(subcommand)]
+        command: Option<HistoryCommand>,
+
+        /// Only decisions tagged TAG
+        #[arg(long, value_name = "TAG", value_parser = parse_tag, global = true)]
+        tag: Option<String>,
+
+        /// How many decisions to list
+        #[arg(short = 'n', long, value_name = "N", default_value_t = 20)]
+        limit: usize,
+    },
+    /// Count decisions by sentiment and answer
+    Stats {
+        /// Test the pack's recorded draws against its weights (chi-square
+        /// and longest runs)
+        #[arg(long)]
+        fairness: bool,
+
+        /// Only decisions tagged TAG
+        #[arg(long, value_name = "TAG", value_parser = parse_tag)]
+        tag: Option<String>,
+
+        /// Only decisions from DATE on: `2026-01-31`, or `7d` / `4w` ago
+        #[arg(long, value_name = "DATE", value_parser = parse_since)]
+        since: Option<NaiveDate>,
+
+        /// How many weeks the activity heatmap covers
+        #[arg(long, value_name = "N", default_value_t = executive_decision_maker::heatmap::DEFAULT_WEEKS)]
+        weeks: usize,
     },
-    Showing {
-        index: usize,
-        until: Instant,
+    /// Serve the decision engine over HTTP (`POST /ask`, `GET /history`, `GET /stats`, a journal page at `/`)
+    Serve {
+        /// Address to listen on
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:7878")]
+        addr: SocketAddr,
+    },
+    /// Work with answer pack files
+    Pack {
+        #[command(subcommand)]
+        command: PackCommand,
+    },
+    /// Manage the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Describe the event stream sent to `--event-log` and `/events`
+    Events {
+        #[command(subcommand)]
+        command: EventsCommand,
+    },
+    /// Host the interactive oracle over SSH, one session per connection
+    Ssh {
+        /// Address to listen on; give e.g. 0.0.0.0:2222 to reach the
+        /// rest of the network
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:2222")]
+        addr: SocketAddr,
+        /// Let clients in without offering a password or key
+        #[arg(long)]
+        allow_anonymous: bool,
+    },
+    /// Print a completion script for SHELL that also completes the
+    /// installed packs and profiles
+    Completions {
+        /// bash, zsh, fish, powershell, or elvish
+        shell: clap_complete::Shell,
     },
 }
 
-struct App {
-    state: State,
-    help_visible: bool,
-    last_answer: Option<usize>,
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Report unknown settings, packs, answers, themes, and clashing keys
+    Check,
+    /// Write a fully commented default config to the config path
+    Init {
+        /// Replace an existing config file
+        #[arg(long)]
+        force: bool,
+    },
 }
 
-impl App {
-    fn new() -> Self {
-        Self {
-            state: State::Idle,
-            help_visible: false,
-            last_answer: None,
-        }
-    }
+#[derive(Subcommand)]
+enum HistoryCommand {
+    /// Write every decision, oldest first, to a file for a spreadsheet or notes app
+    #[command(group(clap::ArgGroup::new("format").required(true).args(["csv", "markdown", "org"])))]
+    Export {
+        /// Write CSV with timestamp, question, answer, sentiment, tags, and
+        /// note columns
+        /// to FILE (`-` for standard output)
+        #[arg(long, value_name = "FILE")]
+        csv: Option<PathBuf>,
+
+        /// Write a Markdown journal grouped by day to FILE (`-` for standard
+        /// output)
+        #[arg(long, value_name = "FILE")]
+        markdown: Option<PathBuf>,
+
+        /// Write an org-mode file with a DONE heading per decision to FILE
+        /// (`-` for standard output)
+        #[arg(long, value_name = "FILE")]
+        org: Option<PathBuf>,
+
+        /// Only decisions from DATE on: `2026-01-31`, or `7d` / `4w` ago
+        #[arg(long, value_name = "DATE", value_parser = parse_since)]
+        since: Option<NaiveDate>,
+
+        /// Lay out each Markdown entry by the template in FILE
+        #[arg(long, value_name = "FILE", requires = "markdown")]
+        template: Option<PathBuf>,
+
+        /// Export an encrypted history in plain text
+        #[arg(long)]
+        decrypt: bool,
+    },
+    /// Add the decisions and edits of another machine's history file
+    Merge {
+        /// The other history file, e.g. a sync conflict copy
+        file: PathBuf,
+    },
+}
 
-    fn ask(&mut self) {
-        let mut rng = rand::thread_rng();
-        let final_idx = rng.gen_range(0..ANSWERS.len());
-        let mut current_idx = final_idx;
-        if ANSWERS.len() > 1 {
-            while current_idx == final_idx {
-                current_idx = rng.gen_range(0..ANSWERS.len());
-            }
-        }
+#[derive(Subcommand)]
+enum EventsCommand {
+    /// Print the JSON Schema of every event, with its version
+    Schema,
+}
 
-        let now = Instant::now();
-        self.last_answer = None;
-        self.state = State::Animating {
-            final_index: final_idx,
-            current_index: current_idx,
-            end_at: now + Duration::from_millis(ANIMATION_DURATION_MS),
-            next_switch: now,
-        };
-        Self::beep();
-    }
+#[derive(Subcommand)]
+enum PackCommand {
+    /// Check a pack file: distribution, widths, sentiment, translations
+    Test {
+        /// The pack's `.toml` or `.edmpack` file
+        file: PathBuf,
+    },
+    /// List the built-in and installed packs
+    List,
+    /// Write an installed pack to an `.edmpack` file to share
+    Export {
+        /// The pack's id
+        #[arg(add = ArgValueCandidates::new(commands::pack_candidates))]
+        id: String,
+
+        /// Where to write it [default: <ID>.edmpack]
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Credit this author in the file
+        #[arg(long, value_name = "NAME")]
+        author: Option<String>,
+    },
+    /// Install a shared `.edmpack` (or `.toml`) pack into `packs/` next to
+    /// the config
+    Import {
+        file: PathBuf,
+
+        /// Replace an installed pack with the same id
+        #[arg(long)]
+        force: bool,
+    },
+    /// Download a pack into the data directory, showing its SHA-256 first
+    Install {
+        url: String,
 
-    fn tick(&mut self) {
-        let now = Instant::now();
-        match self.state {
-            State::Idle => {}
-            State::Animating {
-                final_index,
-                current_index,
-                end_at,
-                next_switch,
-            } => {
-                if now >= end_at {
-                    self.last_answer = Some(final_index);
-                    self.state = State::Showing {
-                        index: final_index,
-                        until: now + Duration::from_millis(ANSWER_FLASH_MS),
-                    };
-                    Self::beep();
-                } else if now >= next_switch {
-                    let mut rng = rand::thread_rng();
-                    let mut next_index = rng.gen_range(0..ANSWERS.len());
-                    if ANSWERS.len() > 1 {
-                        while next_index == current_index {
-                            next_index = rng.gen_range(0..ANSWERS.len());
-                        }
-                    }
-                    self.state = State::Animating {
-                        final_index,
-                        current_index: next_index,
-                        end_at,
-                        next_switch: now + Duration::from_millis(ANIMATION_STEP_MS),
-                    };
-                    Self::beep();
-                }
-            }
-            State::Showing { until, .. } => {
-                if now >= until {
-                    self.state = State::Idle;
-                }
-            }
+        /// Only install if the download has this SHA-256 (hex)
+        #[arg(long, value_name = "HEX")]
+        sha256: Option<String>,
+
+        /// Replace an installed pack with the same id
+        #[arg(long)]
+        force: bool,
+
+        /// Install without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    // Answers the shell's completion requests, then exits.
+    CompleteEnv::with_factory(Cli::command)
+        .var(commands::COMPLETE_VAR)
+        .complete();
+    let cli = Cli::parse();
+    // Held until exit so buffered log lines are flushed.
+    let _log_guard = match logging::init(cli.log_level.as_deref(), cli.data_dir.as_deref()) {
+        Ok(guard) => guard,
+        Err(err) => {
+            eprintln!("edm: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // By the time an error gets here the terminal has been restored, so the
+    // message lands on a normal screen.
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            tracing::error!(%err, "exiting");
+            eprintln!("edm: {err}");
+            ExitCode::FAILURE
         }
     }
+}
 
-    fn toggle_help(&mut self) {
-        self.help_visible = !self.help_visible;
+fn run(mut cli: Cli) -> Result<()> {
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), profile = %cli.profile, "starting");
+    if !paths::valid_profile(&cli.profile) {
+        return Err(EdmError::InvalidProfile(cli.profile));
     }
-
-    /// Returns true if the app should terminate.
-    fn on_key(&mut self, key: KeyEvent) -> bool {
-        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
-        if ctrl {
-            match key.code {
-                KeyCode::Char('c') | KeyCode::Char('C') => return true,
-                KeyCode::Char('h') | KeyCode::Char('H') => {
-                    self.toggle_help();
-                    return false;
-                }
-                _ => {}
+    let config_path = cli
+        .config
+        .clone()
+        .or_else(|| paths::config_file(&cli.profile));
+    let data_dir = paths::profile_data_dir(cli.data_dir.as_deref(), &cli.profile);
+    // Before loading: the existing file may be the broken one being replaced.
+    if let Some(Command::Config { command }) = &cli.command {
+        let path = config_path.ok_or(EdmError::NoConfigDir)?;
+        return match *command {
+            ConfigCommand::Init { force } => commands::config_init(&path, force),
+            ConfigCommand::Check => {
+                commands::config_check(&path, &paths::pack_dirs(Some(&path), data_dir.as_deref()))
             }
+        };
+    }
+    // Nor does it take a broken config to complete the command line.
+    if let Some(Command::Completions { shell }) = cli.command {
+        return commands::completions(shell);
+    }
+    let mut config = match &config_path {
+        Some(path) => Config::load(path)?,
+        None => Config::default(),
+    };
+    config.apply_env(std::env::vars())?;
+
+    let pack_id = cli
+        .pack
+        .as_deref()
+        .or(config.default_pack.as_deref())
+        .unwrap_or(answers::DEFAULT_PACK);
+    let pack_dirs = paths::pack_dirs(config_path.as_deref(), data_dir.as_deref());
+    let pack = installed_pack(pack_id, &config, &pack_dirs)?;
+    let history_path = history_path(cli.history_file.as_deref(), data_dir.as_deref())?;
+    let encrypted = history_path
+        .as_deref()
+        .is_some_and(|path| config.encrypt_history || encryption::is_encrypted(path));
+    if encrypted
+        && matches!(
+            cli.command,
+            Some(Command::History {
+                command: Some(HistoryCommand::Export { decrypt: false, .. }),
+                ..
+            })
+        )
+    {
+        return Err(EdmError::EncryptedExport);
+    }
+    // Only ask for the passphrase when the command reads the history. A
+    // demo's asks are nobody's decisions, so it doesn't.
+    let uses_history = cli.demo.is_none()
+        && matches!(
+            cli.command,
+            None | Some(
+                Command::History { .. }
+                    | Command::Stats { .. }
+                    | Command::Serve { .. }
+                    | Command::Ssh { .. }
+            )
+        );
+    let passphrase = match &history_path {
+        Some(path) if encrypted && uses_history => {
+            Some(encryption::passphrase(!encryption::is_encrypted(path))?)
         }
-
-        match key.code {
-            KeyCode::Esc => {
-                if self.help_visible {
-                    self.help_visible = false;
-                    false
-                } else {
-                    true
-                }
+        _ => None,
+    };
+    let mut history = if uses_history {
+        open_history(history_path.as_deref(), passphrase.as_deref())?
+    } else {
+        History::default()
+    };
+    let second_opinion_pack = cli
+        .second_opinion
+        .as_deref()
+        .or(config.second_opinion_pack.as_deref())
+        .map(|id| installed_pack(id, &config, &pack_dirs))
+        .transpose()?;
+
+    match cli.command {
+        Some(Command::Ask { ref question, post }) => {
+            commands::ask(&pack, &config, question.as_deref(), post)
+        }
+        Some(Command::Pack { ref command }) => match command {
+            PackCommand::Test { file } => commands::pack_test(file),
+            PackCommand::List => commands::pack_list(&pack_dirs),
+            PackCommand::Export { id, output, author } => {
+                let pack = installed_pack(id, &config, &pack_dirs)?;
+                commands::pack_export(&pack, &pack_dirs, output.as_deref(), author.as_deref())
             }
-            KeyCode::Char('q') | KeyCode::Char('Q') => {
-                if self.help_visible {
-                    self.help_visible = false;
-                    false
-                } else {
-                    true
-                }
+            PackCommand::Import { file, force } => {
+                let dir = config_path
+                    .as_deref()
+                    .and_then(Path::parent)
+                    .map(|dir| dir.join("packs"))
+                    .ok_or(EdmError::NoConfigDir)?;
+                commands::pack_import(file, &dir, &pack_dirs, *force)
             }
-            KeyCode::Enter | KeyCode::Char(' ') => {
-                if self.help_visible {
-                    self.help_visible = false;
-                } else {
-                    self.ask();
-                }
-                false
+            PackCommand::Install {
+                url,
+                sha256,
+                force,
+                yes,
+            } => {
+                let dir =
+                    paths::downloaded_packs(data_dir.as_deref()).ok_or(EdmError::NoDataDir)?;
+                commands::pack_install(url, &dir, &pack_dirs, sha256.as_deref(), *force, *yes)
+            }
+        },
+        Some(Command::History {
+            ref command,
+            ref tag,
+            limit,
+        }) => match command {
+            None => commands::history(&history, tag.as_deref(), limit),
+            Some(HistoryCommand::Merge { file }) => {
+                // Another machine's copy is encrypted with the same passphrase.
+                let passphrase = match passphrase {
+                    None if encryption::is_encrypted(file) => Some(encryption::passphrase(false)?),
+                    passphrase => passphrase,
+                };
+                commands::history_merge(&mut history, file, passphrase.as_deref())
+            }
+            Some(HistoryCommand::Export {
+                csv,
+                markdown,
+                org,
+                since,
+                template,
+                decrypt: _,
+            }) => {
+                let decisions = commands::exported(&history, tag.as_deref(), *since);
+                let (exporter, path): (Box<dyn Exporter>, _) = match (csv, markdown, org) {
+                    (Some(path), _, _) => (Box::new(Csv), path),
+                    (_, Some(path), _) => (Box::new(Markdown::load(template.as_deref())?), path),
+                    (_, _, Some(path)) => (Box::new(Org), path),
+                    _ => unreachable!("clap requires one format"),
+                };
+                commands::history_export(&decisions, exporter.as_ref(), path)
+            }
+        },
+        Some(Command::Stats {
+            fairness,
+            ref tag,
+            since,
+            weeks,
+        }) => {
+            let decisions = commands::exported(&history, tag.as_deref(), since);
+            if fairness {
+                commands::fairness(&pack, &decisions)
+            } else {
+                commands::stats(&decisions, weeks)
             }
-            _ => false,
         }
-    }
-
-    fn beep() {
-        let mut stdout = io::stdout();
-        let _ = stdout.write_all(b"\x07");
-        let _ = stdout.flush();
-    }
-}
-
-type TerminalBackend = CrosstermBackend<io::Stdout>;
-type AppTerminal = Terminal<TerminalBackend>;
-
-fn setup_terminal() -> io::Result<AppTerminal> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    stdout.execute(EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    match Terminal::new(backend) {
-        Ok(mut terminal) => {
-            terminal.hide_cursor()?;
-            terminal.clear()?;
-            Ok(terminal)
+        Some(Command::Events {
+            command: EventsCommand::Schema,
+        }) => commands::events_schema(),
+        Some(Command::Config { .. } | Command::Completions { .. }) => {
+            unreachable!("handled before the config is loaded")
         }
-        Err(err) => {
-            let _ = disable_raw_mode();
-            let _ = io::stdout().execute(LeaveAlternateScreen);
-            Err(err)
+        Some(Command::Serve { addr }) => {
+            let mut engine = Engine::new(pack, second_opinion_pack, history, &config);
+            if let Some(path) = paths::achievements_file(data_dir.as_deref()) {
+                engine.achievements = Achievements::load(&path);
+            }
+            server::serve(engine, addr)
+        }
+        Some(Command::Ssh {
+            addr,
+            allow_anonymous,
+        }) => {
+            let theme = startup_theme(&config, config_path.as_deref());
+            let scheme = startup_transliteration(&config, config_path.as_deref())?;
+            let profile = cli.profile.clone();
+            // Opened above already, so a bad file or passphrase stops startup;
+            // a session that can't reopen it later is refused.
+            let make_app = move || {
+                // Sessions share the file but not the in-memory copy.
+                let history = open_history(history_path.as_deref(), passphrase.as_deref())?;
+                let engine =
+                    Engine::new(pack.clone(), second_opinion_pack.clone(), history, &config);
+                // Sessions must not rewrite the host's config or clipboard.
+                let mut app = App::new(
+                    engine,
+                    theme.clone(),
+                    config.clone(),
+                    None,
+                    Box::new(SystemClock),
+                );
+                app.clipboard = Clipboard::terminal_only();
+                app.transliteration = scheme.clone();
+                app.pack_dirs = pack_dirs.clone();
+                app.profile = profile.clone();
+                Ok(app)
+            };
+            ssh::serve(addr, allow_anonymous, Arc::new(make_app))
+        }
+        None => {
+            let engine = if cli.demo.is_some() {
+                let mut quiet = config.clone();
+                quiet.webhook_url = None;
+                Engine::new(pack, second_opinion_pack, history, &quiet)
+            } else {
+                Engine::new(pack, second_opinion_pack, history, &config)
+            };
+            let Some(profile) = run_tui(&cli, engine, config, config_path, data_dir)? else {
+                return Ok(());
+            };
+            // Packs, history, and tags named on the command line belong to
+            // the profile being left.
+            cli.profile = profile;
+            cli.pack = None;
+            cli.second_opinion = None;
+            cli.history_file = None;
+            cli.tag = None;
+            run(cli)
         }
     }
 }
 
-fn cleanup_terminal(terminal: &mut AppTerminal) -> io::Result<()> {
-    terminal.show_cursor()?;
-    terminal.backend_mut().execute(LeaveAlternateScreen)?;
-    disable_raw_mode()
+/// A tag given on the command line, `#` optional.
+fn parse_tag(text: &str) -> std::result::Result<String, String> {
+    tags::normalize(text).ok_or_else(|| format!("`{text}` is not a tag"))
 }
 
-fn run_app(terminal: &mut AppTerminal) -> io::Result<()> {
-    let mut app = App::new();
+/// Seconds between demo asks: at least 3, so each answer gets seen.
+fn parse_demo_interval(text: &str) -> std::result::Result<u64, String> {
+    match text.parse::<u64>() {
+        Ok(secs) if secs >= 3 => Ok(secs),
+        _ => Err(format!("`{text}` is not a number of seconds, 3 or more")),
+    }
+}
 
-    loop {
-        app.tick();
-        terminal.draw(|f| ui(f, &app))?;
+fn parse_since(text: &str) -> std::result::Result<NaiveDate, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    let days = match (text.strip_suffix('d'), text.strip_suffix('w')) {
+        (Some(days), _) => days.parse::<u32>().ok(),
+        (_, Some(weeks)) => weeks
+            .parse::<u32>()
+            .ok()
+            .map(|weeks| weeks.saturating_mul(7)),
+        _ => None,
+    }
+    .ok_or_else(|| format!("`{text}` is not a date such as 2026-01-31 or 7d"))?;
+    Ok(Local::now().date_naive() - Duration::days(days.into()))
+}
 
-        if event::poll(Duration::from_millis(TICK_RATE_MS))? {
-            if let Event::Key(key) = event::read()? {
-                if app.on_key(key) {
-                    break;
-                }
-            }
+/// `file` if given, else the workspace's history when started inside one,
+/// otherwise the one in the data directory, with its directory created.
+/// Without any of those, history lasts for this session only.
+fn history_path(file: Option<&Path>, data: Option<&Path>) -> Result<Option<PathBuf>> {
+    let path = file
+        .map(Path::to_path_buf)
+        .or_else(|| {
+            std::env::current_dir()
+                .ok()
+                .and_then(|dir| workspace::history_path(&dir))
+        })
+        .or_else(|| paths::history_file(data));
+    if let Some(path) = &path {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|source| EdmError::History {
+                path: path.clone(),
+                source,
+            })?;
         }
     }
-
-    Ok(())
+    Ok(path)
 }
 
-fn main() -> io::Result<()> {
-    let mut terminal = setup_terminal()?;
-    let result = run_app(&mut terminal);
-    cleanup_terminal(&mut terminal)?;
-    result
+/// The history at `path`, decrypted with `passphrase` when one is given.
+fn open_history(path: Option<&Path>, passphrase: Option<&str>) -> Result<History> {
+    match (path, passphrase) {
+        (None, _) => Ok(History::default()),
+        (Some(path), None) => History::open(path),
+        (Some(path), Some(passphrase)) => History::open_encrypted(path, passphrase),
+    }
 }
 
-/// Render the whole UI
-fn ui(f: &mut ratatui::Frame, app: &App) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(5),
-            Constraint::Min(7),
-            Constraint::Length(3),
-        ])
-        .margin(2)
-        .split(f.area());
-
-    render_header(f, chunks[0], app);
-    render_buttons(f, chunks[1], app);
-    render_footer(f, chunks[2], app);
-    if app.help_visible {
-        render_help_overlay(f);
+/// A pack installed from `pack_dirs` or a built-in one, with the weights
+/// tuned in the config.
+fn installed_pack(id: &str, config: &Config, pack_dirs: &[PathBuf]) -> Result<AnswerPack> {
+    let mut pack =
+        answers::installed(id, pack_dirs).ok_or_else(|| EdmError::UnknownPack(id.to_string()))?;
+    if let Some(weights) = config.weights.get(id) {
+        pack.apply_weights(weights);
     }
+    Ok(pack)
 }
 
-/// Draw the six answer “buttons”
-fn render_buttons(f: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) {
-    let rows = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Min(1),
-        ])
-        .split(area);
-
-    let row_chunks = |rect| {
-        Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(34),
-            ])
-            .split(rect)
+/// Returns the profile to start over on, if one was picked.
+fn run_tui(
+    cli: &Cli,
+    mut engine: Engine,
+    config: Config,
+    config_path: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+) -> Result<Option<String>> {
+    let clock: Box<dyn Clock> = match cli.simulate_speed {
+        Some(factor) => Box::new(ScaledClock::new(factor)),
+        None => Box::new(SystemClock),
     };
 
-    let active_index = match app.state {
-        State::Animating { current_index, .. } => Some(current_index),
-        State::Showing { index, .. } => Some(index),
-        State::Idle => None,
-    };
-
-    // Row 1
-    let top_row = row_chunks(rows[0]);
-    draw_button(f, top_row[0], ANSWERS[0], active_index == Some(0));
-    draw_button(f, top_row[1], ANSWERS[1], active_index == Some(1));
-    draw_button(f, top_row[2], ANSWERS[2], active_index == Some(2));
-
-    // Row 2
-    let bottom_row = row_chunks(rows[1]);
-    draw_button(f, bottom_row[0], ANSWERS[3], active_index == Some(3));
-    draw_button(f, bottom_row[1], ANSWERS[4], active_index == Some(4));
-    draw_button(f, bottom_row[2], ANSWERS[5], active_index == Some(5));
-}
-
-/// Render a single answer button
-fn draw_button(f: &mut ratatui::Frame, area: ratatui::layout::Rect, text: &str, active: bool) {
-    let style = if active {
-        Style::default()
-            .fg(Color::Black)
-            .bg(Color::LightGreen)
-            .add_modifier(Modifier::BOLD)
+    // First launch: nothing chosen anywhere yet, so show the gallery. The
+    // accessible front end has no gallery and sticks to the default pack.
+    let onboarding = cli.pack.is_none()
+        && config.default_pack.is_none()
+        && !cli.accessible
+        && cli.demo.is_none();
+    let theme = startup_theme(&config, config_path.as_deref());
+    let scheme = startup_transliteration(&config, config_path.as_deref())?;
+
+    let pack_dirs = paths::pack_dirs(config_path.as_deref(), data_dir.as_deref());
+    if let Some(path) = paths::achievements_file(data_dir.as_deref()).filter(|_| cli.demo.is_none())
+    {
+        engine.achievements = Achievements::load(&path);
+    }
+    let mut app = App::new(engine, theme, config, config_path, clock);
+    app.pack_dirs = pack_dirs;
+    app.profile = cli.profile.clone();
+    app.tag_filter = cli.tag.clone();
+    app.profiles = paths::profiles(cli.data_dir.as_deref());
+    if !app.profiles.contains(&app.profile) {
+        app.profiles.push(app.profile.clone());
+    }
+    app.transliteration = scheme;
+    if let Some(secs) = cli.demo {
+        app.start_demo(std::time::Duration::from_secs(secs));
+    }
+    app.color_depth = ColorDepth::detect();
+    app.strings = Strings::detect();
+    app.strings.terminal_bidi = app.config.terminal_bidi;
+    if let Some(path) = &cli.event_log {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|source| EdmError::EventLog {
+                path: path.clone(),
+                source,
+            })?;
+        app.event_log = Some(Box::new(file));
+    }
+    let in_workspace = cli.history_file.is_none()
+        && std::env::current_dir()
+            .ok()
+            .and_then(|dir| workspace::history_path(&dir))
+            .is_some();
+    if onboarding {
+        app.open_gallery();
+    } else if let Some(lines) =
+        workspace::summary(&app.engine.history, Local::now()).filter(|_| in_workspace)
+    {
+        app.show_banner(" This project ", lines);
+    }
+    let recorder = cli
+        .record
+        .as_deref()
+        .map(|path| {
+            cast::Recorder::create(path).map_err(|source| EdmError::Recording {
+                path: path.to_path_buf(),
+                source,
+            })
+        })
+        .transpose()?;
+    if cli.accessible {
+        frontend::run(&mut app, &mut LinearFrontend::new())
     } else {
-        Style::default().fg(Color::White).bg(Color::DarkGray)
-    };
-
-    let widget = Paragraph::new(Span::styled(text, style))
-        .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL));
-    f.render_widget(widget, area);
-}
-
-fn render_header(f: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) {
-    let title_style = Style::default()
-        .fg(Color::Yellow)
-        .add_modifier(Modifier::BOLD);
-    let mut lines = vec![
-        Line::from(Span::styled("EXECUTIVE DECISION MAKER", title_style)),
-        Line::raw(""),
-    ];
-    lines.push(Line::raw(
-        "Think of your question, then press Enter or Space to consult the oracle.",
-    ));
-    match app.state {
-        State::Animating { .. } => {
-            lines.push(Line::raw("Lights are shuffling... hold tight!"));
-        }
-        State::Showing { .. } => {
-            lines.push(Line::raw("Final answer locked in. Ask again any time."));
-        }
-        State::Idle => {
-            if app.last_answer.is_none() {
-                lines.push(Line::raw("Need instructions? Press Ctrl+H for help."));
-            } else {
-                lines.push(Line::raw(
-                    "Ready for another? Press Enter or Space to ask again.",
-                ));
-            }
-        }
+        tui::run(&mut app, recorder)
+    }
+    .map_err(EdmError::Terminal)?;
+    // Quitting doesn't get an answer out of its timebox.
+    app.accept();
+    if app.switch_profile.is_some() {
+        return Ok(app.switch_profile);
     }
 
-    let paragraph = Paragraph::new(lines).alignment(Alignment::Center).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(" Radio Shack "),
-    );
-    f.render_widget(paragraph, area);
-}
-
-fn render_footer(f: &mut ratatui::Frame, area: ratatui::layout::Rect, app: &App) {
-    let (status_line, help_line) = match app.state {
-        State::Animating { .. } => (
-            "Consulting the oracle...".to_string(),
-            "Lights flash in random order before the final answer appears.",
-        ),
-        State::Showing { index, .. } => (
-            format!("Answer: {}", ANSWERS[index]),
-            "Highlight stays on briefly so you can see the result.",
-        ),
-        State::Idle => match app.last_answer {
-            Some(idx) => (
-                format!("Final Answer: {}", ANSWERS[idx]),
-                "Press Enter/Space to ask again · Ctrl+H for help · q/Esc to quit",
-            ),
-            None => (
-                "Ready when you are.".to_string(),
-                "Press Enter/Space to ask · Ctrl+H for help · q/Esc to quit",
-            ),
-        },
+    // The alternate screen is gone by now, so this stays in the scrollback.
+    let template = cli
+        .exit_summary
+        .as_ref()
+        .or(app.config.exit_summary.as_ref());
+    let session = app.engine.history.session();
+    let line = match template {
+        Some(template) if template.is_empty() => None,
+        Some(template) => exit_summary::render(template, session),
+        None => exit_summary::last_decision(session),
     };
-
-    let content = vec![Line::from(status_line), Line::raw(""), Line::raw(help_line)];
-    let paragraph = Paragraph::new(content)
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(Color::Cyan))
-        .block(Block::default().borders(Borders::ALL).title(" Status "));
-
-    f.render_widget(paragraph, area);
+    if let Some(line) = line {
+        // After a hangup there is nowhere to print to, which is no reason to
+        // panic.
+        let _ = writeln!(io::stdout(), "{line}");
+    }
+    Ok(None)
 }
 
-fn render_help_overlay(f: &mut ratatui::Frame) {
-    let area = centered_rect(60, 50, f.area());
-
-    let help = [
-        "EXECUTIVE DECISION MAKER",
-        "",
-        "How to play:",
-        "  - Press Enter or Space to light up a random answer.",
-        "  - The highlighted answer stays on for about 1.5 s.",
-        "",
-        "Controls:",
-        "  Enter / Space    Ask (or close this help)",
-        "  Ctrl+H           Toggle help",
-        "  q / Esc          Quit (Esc closes help first)",
-        "  Ctrl+C           Quit immediately",
-    ]
-    .join("\n");
-
-    let block = Block::default()
-        .title(" Help ")
-        .borders(Borders::ALL)
-        .style(Style::default().fg(Color::White));
-
-    let paragraph = Paragraph::new(help)
-        .style(Style::default().fg(Color::Yellow))
-        .alignment(Alignment::Left)
-        .block(block);
-
-    f.render_widget(Clear, area);
-    f.render_widget(paragraph, area);
+/// The configured theme, or the default one if it is not installed.
+fn startup_theme(config: &Config, config_path: Option<&Path>) -> Theme {
+    if config.high_contrast {
+        return theme::high_contrast();
+    }
+    let themes_dir = config_path
+        .and_then(Path::parent)
+        .map(|dir| dir.join("themes"));
+    let theme_name = config.theme.as_deref().unwrap_or(theme::DEFAULT_THEME);
+    let (themes, _) = theme::installed_themes(themes_dir.as_deref());
+    themes
+        .iter()
+        .find(|theme| theme.name == theme_name)
+        .unwrap_or(&themes[0])
+        .clone()
 }
 
-fn centered_rect(
-    percent_x: u16,
-    percent_y: u16,
-    area: ratatui::layout::Rect,
-) -> ratatui::layout::Rect {
-    let vertical = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(area);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(vertical[1])[1]
+/// The configured transliteration scheme, looked up in `transliterations/`
+/// next to the config and then among the built-ins.
+fn startup_transliteration(config: &Config, config_path: Option<&Path>) -> Result<Option<Scheme>> {
+    let Some(name) = &config.transliteration else {
+        return Ok(None);
+    };
+    let dir = config_path
+        .and_then(Path::parent)
+        .map(|dir| dir.join("transliterations"));
+    match transliteration::find(name, dir.as_deref()) {
+        Ok(Some(scheme)) => Ok(Some(scheme)),
+        Ok(None) => Err(EdmError::UnknownTransliteration(name.clone())),
+        Err(err) => Err(EdmError::TransliterationFile(err)),
+    }
 }
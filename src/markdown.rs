@@ -0,0 +1,196 @@
+//! Decisions as a Markdown journal for a notes app such as Obsidian: a
+//! `## YYYY-MM-DD` heading per day, then each decision laid out by a
+//! template such as `"- {time} {question} → **{answer}**"`.
+//!
+//! Placeholders: `{date}`, `{time}`, `{question}`, `{answer}`, `{pack}`,
+//! `{sentiment}`, `{tags}` (as `#deploy #personal`), `{note}`. Anything else
+//! is written as is. A template line whose placeholders are all empty is
+//! left out, so a decision without a note gets no empty note line.
+
+use crate::{
+    error::{EdmError, Result},
+    export::Exporter,
+    history::Decision,
+    tags,
+};
+use std::{fs, path::Path};
+
+pub const DEFAULT_TEMPLATE: &str = "### {time}\n\n> {question}\n\n**{answer}** {tags}\n\n{note}\n";
+
+pub struct Markdown {
+    pub template: String,
+}
+
+impl Markdown {
+    /// With the entry template in `file`, or [`DEFAULT_TEMPLATE`] for `None`.
+    pub fn load(file: Option<&Path>) -> Result<Self> {
+        let template = match file {
+            Some(file) => fs::read_to_string(file).map_err(|source| EdmError::TemplateRead {
+                path: file.to_path_buf(),
+                source,
+            })?,
+            None => DEFAULT_TEMPLATE.to_string(),
+        };
+        Ok(Self { template })
+    }
+}
+
+impl Exporter for Markdown {
+    /// One document, grouped by day.
+    fn render(&self, decisions: &[&Decision]) -> String {
+        let mut out = String::from("# Decisions\n");
+        let mut day = None;
+        for decision in decisions {
+            let date = decision.decided_at.date_naive();
+            if day != Some(date) {
+                day = Some(date);
+                out.push_str(&format!("\n## {}\n", date.format("%Y-%m-%d")));
+            }
+            out.push('\n');
+            out.push_str(&entry(decision, &self.template));
+        }
+        out
+    }
+}
+
+/// One decision laid out by `template`, ending in a single line break.
+fn entry(decision: &Decision, template: &str) -> String {
+    let values = [
+        ("date", decision.decided_at.format("%Y-%m-%d").to_string()),
+        ("time", decision.decided_at.format("%H:%M").to_string()),
+        ("question", decision.question.clone().unwrap_or_default()),
+        ("answer", decision.answer.clone()),
+        ("pack", decision.pack.clone()),
+        ("sentiment", decision.sentiment.to_string()),
+        ("tags", tags::format(&decision.tags)),
+        ("note", decision.note.clone().unwrap_or_default()),
+    ];
+    let mut lines: Vec<String> = Vec::new();
+    for line in template.lines() {
+        let used: Vec<&str> = values
+            .iter()
+            .filter(|(name, _)| line.contains(&format!("{{{name}}}")))
+            .map(|(_, value)| value.as_str())
+            .collect();
+        if !used.is_empty() && used.iter().all(|value| value.trim().is_empty()) {
+            continue;
+        }
+        let mut filled = line.to_string();
+        for (name, value) in &values {
+            filled = filled.replace(&format!("{{{name}}}"), value);
+        }
+        // Blank lines left by dropped ones collapse into one.
+        let filled = filled.trim_end().to_string();
+        if filled.is_empty() && lines.last().is_none_or(String::is_empty) {
+            continue;
+        }
+        lines.push(filled);
+    }
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers, commands, history::History};
+    use chrono::{Local, NaiveDate, TimeZone};
+
+    /// A positive `answer` from the classic pack, decided at `hour`:00 on
+    /// October `day`, 2026, local time.
+    fn decision(day: u32, hour: u32, answer: &str) -> Decision {
+        let mut decision: Decision = serde_json::from_value(serde_json::json!({
+            "answer": answer,
+            "pack": "classic",
+            "sentiment": "positive",
+            "decided_at": "2026-10-16T09:00:00+00:00",
+        }))
+        .unwrap();
+        decision.decided_at = Local.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+        decision
+    }
+
+    #[test]
+    fn default_template_drops_the_missing_note_and_tags() {
+        let mut asked = decision(16, 9, "WHY NOT");
+        asked.question = Some("Ship it?".to_string());
+        assert_eq!(
+            entry(&asked, DEFAULT_TEMPLATE),
+            "### 09:00\n\n> Ship it?\n\n**WHY NOT**\n"
+        );
+        let unasked = decision(16, 9, "WHY NOT");
+        assert_eq!(
+            entry(&unasked, DEFAULT_TEMPLATE),
+            "### 09:00\n\n**WHY NOT**\n"
+        );
+    }
+
+    #[test]
+    fn default_template_keeps_the_note_and_tags() {
+        let mut decision = decision(16, 9, "WHY NOT");
+        decision.tags = vec!["deploy".to_string(), "friday".to_string()];
+        decision.note = Some("Went fine.".to_string());
+        assert_eq!(
+            entry(&decision, DEFAULT_TEMPLATE),
+            "### 09:00\n\n**WHY NOT** #deploy #friday\n\nWent fine.\n"
+        );
+    }
+
+    #[test]
+    fn a_custom_template_fills_every_placeholder() {
+        let mut decision = decision(16, 9, "WHY NOT");
+        decision.tags = vec!["deploy".to_string()];
+        let template = "{date} {time} [{pack}] {answer} ({sentiment}) {tags} {mood}\n- {note}";
+        assert_eq!(
+            entry(&decision, template),
+            "2026-10-16 09:00 [classic] WHY NOT (positive) #deploy {mood}\n"
+        );
+    }
+
+    #[test]
+    fn decisions_are_grouped_by_day() {
+        let decisions = [
+            decision(16, 9, "WHY NOT"),
+            decision(16, 17, "NEVER"),
+            decision(17, 8, "POSSIBLY"),
+        ];
+        let markdown = Markdown {
+            template: "- {time} **{answer}**".to_string(),
+        };
+        assert_eq!(
+            markdown.render(&decisions.iter().collect::<Vec<_>>()),
+            "# Decisions\n\n## 2026-10-16\n\n- 09:00 **WHY NOT**\n\n- 17:00 **NEVER**\n\n\
+             ## 2026-10-17\n\n- 08:00 **POSSIBLY**\n"
+        );
+    }
+
+    #[test]
+    fn since_leaves_out_earlier_days() {
+        let pack = answers::builtin(answers::DEFAULT_PACK).unwrap();
+        let mut history = History::default();
+        for (day, answer) in [(15, 0), (16, 5), (17, 3)] {
+            let at = Local.with_ymd_and_hms(2026, 10, day, 9, 0, 0).unwrap();
+            history.record(
+                &pack.id,
+                None,
+                &pack.answers[answer],
+                None,
+                None,
+                &[],
+                50,
+                at,
+            );
+        }
+        let since = NaiveDate::from_ymd_opt(2026, 10, 16);
+        let decisions = commands::exported(&history, None, since);
+        let markdown = Markdown {
+            template: "- **{answer}**".to_string(),
+        };
+        assert_eq!(
+            markdown.render(&decisions),
+            "# Decisions\n\n## 2026-10-16\n\n- **WHY NOT**\n\n## 2026-10-17\n\n- **NEVER**\n"
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! Decision matrix, opened with `m`: options down the side, weighted
+//! criteria across the top, and a score in every cell. An option's total is
+//! the sum of its scores times the criteria's weights, and Enter puts the
+//! options on the board for a shuffle biased by those totals.
+//!
+//! Matrices are saved as TOML in `matrices/` next to the config, named
+//! after their title, so recurring decisions can be loaded again.
+
+use crate::answers::{Answer, AnswerPack};
+use crate::sentiment::Sentiment;
+use crate::theme::AnswerStyle;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Highest score a cell can have.
+pub const MAX_SCORE: u8 = 5;
+/// Highest weight a criterion can have.
+pub const MAX_WEIGHT: u8 = 5;
+/// Score of a new cell and weight of a new criterion.
+const DEFAULT: u8 = 3;
+/// Fewest options the board can shuffle over.
+pub const MIN_OPTIONS: usize = 2;
+/// Prefix of the pack id the options are drawn as.
+pub const PACK_PREFIX: &str = "matrix-";
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Criterion {
+    pub name: String,
+    pub weight: u8,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Matrix {
+    pub title: String,
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub criteria: Vec<Criterion>,
+    /// Per option, a score per criterion.
+    #[serde(default)]
+    pub scores: Vec<Vec<u8>>,
+}
+
+impl Matrix {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let mut matrix: Self = toml::from_str(&text).map_err(io::Error::other)?;
+        matrix.fill();
+        Ok(matrix)
+    }
+
+    /// Write the matrix to `path`, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        tracing::debug!(path = %path.display(), "saving matrix");
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let text = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    /// Give every option a score for every criterion, as a hand-edited file
+    /// may not.
+    fn fill(&mut self) {
+        self.scores.resize(self.options.len(), Vec::new());
+        for row in &mut self.scores {
+            row.resize(self.criteria.len(), DEFAULT);
+            for score in row.iter_mut() {
+                *score = (*score).min(MAX_SCORE);
+            }
+        }
+        for criterion in &mut self.criteria {
+            criterion.weight = criterion.weight.clamp(1, MAX_WEIGHT);
+        }
+    }
+
+    /// Each option's scores times the criteria's weights, summed.
+    pub fn totals(&self) -> Vec<u32> {
+        self.scores
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&self.criteria)
+                    .map(|(&score, criterion)| u32::from(score) * u32::from(criterion.weight))
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// The options as a pack, each weighted by its total. An option scoring
+    /// 0 is kept at weight 1 so the board can still land on it, rarely.
+    pub fn to_pack(&self) -> AnswerPack {
+        AnswerPack {
+            id: format!("{PACK_PREFIX}{}", slug(&self.title)),
+            title: self.title.clone(),
+            description: "Options from a decision matrix".to_string(),
+            answers: self
+                .options
+                .iter()
+                .zip(self.totals())
+                .map(|(option, total)| Answer {
+                    text: option.clone(),
+                    sentiment: Sentiment::Neutral,
+                    weight: total.max(1),
+                    style: AnswerStyle::default(),
+                    icon: None,
+                    opposite: None,
+                    flavor: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// `title` lowercased with runs of anything but letters and digits turned
+/// into single dashes, for file names and pack ids.
+pub fn slug(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "untitled".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Where the matrix titled `title` is saved in `dir`.
+pub fn matrix_file(dir: &Path, title: &str) -> PathBuf {
+    dir.join(format!("{}.toml", slug(title)))
+}
+
+/// Saved matrices in `dir`, by file name.
+pub fn matrix_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// What the text being typed will become.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Title,
+    /// A new option, or the option at the index.
+    Option(Option<usize>),
+    /// A new criterion, or the criterion at the index.
+    Criterion(Option<usize>),
+}
+
+/// Text being typed for a title, option, or criterion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub target: Target,
+    pub text: String,
+}
+
+pub struct MatrixEditor {
+    pub matrix: Matrix,
+    /// 0 is the weights row, then one row per option.
+    pub row: usize,
+    /// Index into the criteria.
+    pub column: usize,
+    /// Open while a name is typed.
+    pub entry: Option<Entry>,
+    /// Whether there are changes that aren't saved.
+    pub changed: bool,
+    /// The saved matrix loaded last, to load the one after it next.
+    loaded: Option<PathBuf>,
+}
+
+impl MatrixEditor {
+    pub fn new(matrix: Matrix) -> Self {
+        Self {
+            matrix,
+            row: 0,
+            column: 0,
+            entry: None,
+            changed: false,
+            loaded: None,
+        }
+    }
+
+    /// The option on the selected row, if it isn't the weights row.
+    pub fn option(&self) -> Option<usize> {
+        self.row
+            .checked_sub(1)
+            .filter(|&option| option < self.matrix.options.len())
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        self.row = self
+            .row
+            .saturating_add_signed(delta)
+            .min(self.matrix.options.len());
+    }
+
+    /// Move to the next criterion, wrapping around.
+    pub fn next_column(&mut self) {
+        let count = self.matrix.criteria.len();
+        if count > 0 {
+            self.column = (self.column + 1) % count;
+        }
+    }
+
+    /// Change the selected cell's score, or on the weights row the
+    /// criterion's weight.
+    pub fn adjust(&mut self, delta: i32) {
+        let column = self.column;
+        let (value, min, max): (&mut u8, u8, u8) = match self.option() {
+            Some(option) => match self.matrix.scores[option].get_mut(column) {
+                Some(score) => (score, 0, MAX_SCORE),
+                None => return,
+            },
+            None => match self.matrix.criteria.get_mut(column) {
+                Some(criterion) => (&mut criterion.weight, 1, MAX_WEIGHT),
+                None => return,
+            },
+        };
+        let new = (i32::from(*value) + delta).clamp(i32::from(min), i32::from(max)) as u8;
+        if new != *value {
+            *value = new;
+            self.changed = true;
+        }
+    }
+
+    pub fn start(&mut self, target: Target) {
+        let text = match target {
+            Target::Title => self.matrix.title.clone(),
+            Target::Option(Some(index)) => self.matrix.options[index].clone(),
+            Target::Criterion(Some(index)) => self.matrix.criteria[index].name.clone(),
+            Target::Option(None) | Target::Criterion(None) => String::new(),
+        };
+        self.entry = Some(Entry { target, text });
+    }
+
+    /// Start renaming the selected option, or on the weights row the
+    /// selected criterion.
+    pub fn start_rename(&mut self) {
+        match self.option() {
+            Some(option) => self.start(Target::Option(Some(option))),
+            None if self.column < self.matrix.criteria.len() => {
+                self.start(Target::Criterion(Some(self.column)));
+            }
+            None => {}
+        }
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        if let Some(entry) = self.entry.as_mut() {
+            entry.text.push(c);
+        }
+    }
+
+    pub fn erase(&mut self) {
+        if let Some(entry) = self.entry.as_mut() {
+            entry.text.pop();
+        }
+    }
+
+    pub fn cancel_entry(&mut self) {
+        self.entry = None;
+    }
+
+    /// Use the typed text; an empty one is dropped.
+    pub fn confirm_entry(&mut self) {
+        let Some(entry) = self.entry.take() else {
+            return;
+        };
+        let text = entry.text.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let matrix = &mut self.matrix;
+        match entry.target {
+            Target::Title => matrix.title = text,
+            Target::Option(Some(index)) => matrix.options[index] = text,
+            Target::Criterion(Some(index)) => matrix.criteria[index].name = text,
+            Target::Option(None) => {
+                matrix.options.push(text);
+                matrix.scores.push(vec![DEFAULT; matrix.criteria.len()]);
+                self.row = matrix.options.len();
+            }
+            Target::Criterion(None) => {
+                matrix.criteria.push(Criterion {
+                    name: text,
+                    weight: DEFAULT,
+                });
+                for row in &mut matrix.scores {
+                    row.push(DEFAULT);
+                }
+                self.column = matrix.criteria.len() - 1;
+            }
+        }
+        self.changed = true;
+    }
+
+    /// Remove the selected option, or on the weights row the selected
+    /// criterion.
+    pub fn remove(&mut self) {
+        let option = self.option();
+        let matrix = &mut self.matrix;
+        match option {
+            Some(option) => {
+                matrix.options.remove(option);
+                matrix.scores.remove(option);
+                self.row = self.row.min(matrix.options.len());
+            }
+            None if self.column < matrix.criteria.len() => {
+                matrix.criteria.remove(self.column);
+                for row in &mut matrix.scores {
+                    row.remove(self.column);
+                }
+                self.column = self.column.min(matrix.criteria.len().saturating_sub(1));
+            }
+            None => return,
+        }
+        self.changed = true;
+    }
+
+    /// Save to `dir`, named after the title.
+    pub fn save(&mut self, dir: &Path) -> io::Result<PathBuf> {
+        let path = matrix_file(dir, &self.matrix.title);
+        self.matrix.save(&path)?;
+        self.changed = false;
+        self.loaded = Some(path.clone());
+        Ok(path)
+    }
+
+    /// Load the saved matrix after the one loaded last, wrapping around.
+    /// `Ok(None)` when there are none.
+    pub fn load_next(&mut self, dir: &Path) -> io::Result<Option<PathBuf>> {
+        let files = matrix_files(dir);
+        let next = match &self.loaded {
+            Some(loaded) => files
+                .iter()
+                .position(|path| path == loaded)
+                .map_or(0, |index| (index + 1) % files.len()),
+            None => 0,
+        };
+        let Some(path) = files.get(next) else {
+            return Ok(None);
+        };
+        self.matrix = Matrix::load(path)?;
+        self.row = 0;
+        self.column = 0;
+        self.changed = false;
+        self.loaded = Some(path.clone());
+        Ok(Some(path.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lunch() -> Matrix {
+        Matrix {
+            title: "Lunch?".to_string(),
+            options: vec!["Tacos".to_string(), "Salad".to_string(), "Skip".to_string()],
+            criteria: vec![
+                Criterion {
+                    name: "Taste".to_string(),
+                    weight: 3,
+                },
+                Criterion {
+                    name: "Health".to_string(),
+                    weight: 1,
+                },
+            ],
+            scores: vec![vec![5, 1], vec![2, 5], vec![0, 0]],
+        }
+    }
+
+    #[test]
+    fn totals_weigh_each_score_by_its_criterion() {
+        assert_eq!(lunch().totals(), [5 * 3 + 1, 2 * 3 + 5, 0]);
+    }
+
+    #[test]
+    fn the_pack_draws_by_total_and_keeps_zero_scores_drawable() {
+        let pack = lunch().to_pack();
+        assert_eq!(pack.id, "matrix-lunch");
+        assert_eq!(pack.weights(), [16, 11, 1]);
+    }
+
+    #[test]
+    fn hand_edited_files_are_filled_and_clamped() {
+        let mut matrix = lunch();
+        matrix.criteria[0].weight = 9;
+        matrix.criteria[1].weight = 0;
+        matrix.scores = vec![vec![7], vec![2, 5]];
+        matrix.fill();
+        assert_eq!(
+            matrix.scores,
+            [vec![MAX_SCORE, DEFAULT], vec![2, 5], vec![DEFAULT; 2]]
+        );
+        assert_eq!(matrix.totals(), [5 * 5 + 3, 2 * 5 + 5, 3 * 5 + 3]);
+    }
+}
@@ -0,0 +1,50 @@
+//! Mouse support, switched on with `mouse = true`: the wheel scrolls the
+//! list on show, a click on a row selects it, and a click on a tab switches
+//! screens. [`crate::ui::draw`] records where it drew tabs and lists as
+//! [`HitAreas`], and the front end maps mouse events through the ones from
+//! the last frame.
+
+use crate::{input::Action, screen::Tab};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+/// Where the last frame put what a click can hit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HitAreas {
+    /// Each tab of the tab bar.
+    pub tabs: Vec<(Rect, Tab)>,
+    /// Lists with one item per row: the rows, and the index of the item on
+    /// the first of them.
+    pub lists: Vec<(Rect, usize)>,
+}
+
+impl HitAreas {
+    pub fn tab(&mut self, area: Rect, tab: Tab) {
+        self.tabs.push((area, tab));
+    }
+
+    pub fn list(&mut self, area: Rect, first: usize) {
+        self.lists.push((area, first));
+    }
+
+    /// What `event` means on the last frame: the wheel moves the selection
+    /// like the arrows, a left click picks the tab or row under it.
+    pub fn action(&self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        match event.kind {
+            MouseEventKind::ScrollUp => Some(Action::Up),
+            MouseEventKind::ScrollDown => Some(Action::Down),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(&(_, tab)) = self.tabs.iter().find(|(area, _)| area.contains(position))
+                {
+                    return Some(Action::ShowScreen(tab));
+                }
+                self.lists
+                    .iter()
+                    .find(|(area, _)| area.contains(position))
+                    .map(|&(area, first)| Action::Select(first + usize::from(event.row - area.y)))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Notifications for answers that land while the terminal is in the
+//! background, and terminal escapes that report the oracle's state.
+//!
+//! Desktop notifications need the `notifications` feature. The escapes need
+//! nothing but a terminal that understands them and are opt-in through the
+//! config:
+//!
+//! - `osc_notifications`: OSC 9 (`ESC ] 9 ; message BEL`), shown natively by
+//!   iTerm2, WezTerm, Windows Terminal, kitty, and others.
+//! - `osc_progress`: the ConEmu progress sequence (`ESC ] 9 ; 4 ; state ;
+//!   percent BEL`), which Windows Terminal and ConEmu show on the tab and
+//!   taskbar while the lights shuffle.
+
+/// Show `answer` as a desktop notification. Sending it can block on the
+/// notification daemon, so it goes from a detached thread of its own.
+#[cfg(feature = "notifications")]
+pub fn answer_landed(answer: &str) {
+    let answer = answer.to_string();
+    std::thread::spawn(move || {
+        // Best effort: a missing notification daemon must not disturb the TUI.
+        if let Err(err) = notify_rust::Notification::new()
+            .appname("Executive Decision Maker")
+            .summary("The oracle has spoken")
+            .body(&answer)
+            .show()
+        {
+            tracing::debug!(%err, "desktop notification failed");
+        }
+    });
+}
+
+#[cfg(not(feature = "notifications"))]
+pub fn answer_landed(_answer: &str) {}
+
+/// An OSC 9 notification carrying `message`. Control characters are dropped
+/// so the text cannot end the sequence early.
+pub fn osc9(message: &str) -> Vec<u8> {
+    let message: String = message.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]9;{message}\x07").into_bytes()
+}
+
+/// A ConEmu progress sequence: `Some(percent)` shows the bar, `None` removes
+/// it.
+pub fn progress(percent: Option<u8>) -> Vec<u8> {
+    match percent {
+        Some(percent) => format!("\x1b]9;4;1;{}\x07", percent.min(100)).into_bytes(),
+        None => b"\x1b]9;4;0;0\x07".to_vec(),
+    }
+}
@@ -0,0 +1,22 @@
+//! "Second opinion": what another pack would have said about the same
+//! question.
+
+use crate::answers::{Answer, AnswerPack};
+
+pub struct SecondOpinion {
+    /// Title of the pack that was consulted.
+    pub pack: String,
+    pub answer: Answer,
+    /// Whether both verdicts lean the same way (same sentiment).
+    pub agrees: bool,
+}
+
+/// Draw an answer from `pack` and compare it with the main verdict.
+pub fn consult(pack: &AnswerPack, verdict: &Answer) -> SecondOpinion {
+    let answer = pack.answers[pack.draw_index()].clone();
+    SecondOpinion {
+        pack: pack.title.clone(),
+        agrees: answer.sentiment == verdict.sentiment,
+        answer,
+    }
+}
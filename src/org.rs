@@ -0,0 +1,144 @@
+//! Decisions as an Emacs org-mode file: one `DONE` heading per decision,
+//! closed at the time it was made, with its tags, answer, and note.
+//!
+//! ```org
+//! * DONE Ship on Friday? → Absolutely not                        :deploy:
+//!   CLOSED: [2026-10-16 Fri 16:45] DEADLINE: <2026-11-16 Mon>
+//!   :PROPERTIES:
+//!   :ANSWER:    Absolutely not
+//!   :PACK:      classic
+//!   :SENTIMENT: negative
+//!   :END:
+//!   Asked again on Monday.
+//! ```
+//!
+//! An expiring decision's expiry is its `DEADLINE`, so it shows up in the
+//! agenda when it is time to ask again.
+
+use crate::{export::Exporter, history::Decision};
+
+/// Org timestamp layouts: inactive for when it happened, active for dates
+/// the agenda should show.
+const INACTIVE: &str = "[%Y-%m-%d %a %H:%M]";
+const ACTIVE: &str = "<%Y-%m-%d %a>";
+
+pub struct Org;
+
+impl Exporter for Org {
+    fn render(&self, decisions: &[&Decision]) -> String {
+        let mut out = String::from("#+TITLE: Decisions\n");
+        for decision in decisions {
+            out.push('\n');
+            out.push_str(&entry(decision));
+        }
+        out
+    }
+}
+
+fn entry(decision: &Decision) -> String {
+    let answer = one_line(&decision.answer);
+    let mut heading = match &decision.question {
+        Some(question) => format!("* DONE {} → {answer}", one_line(question)),
+        None => format!("* DONE {answer}"),
+    };
+    if !decision.tags.is_empty() {
+        let tags: Vec<String> = decision.tags.iter().map(|tag| tag_name(tag)).collect();
+        heading.push_str(&format!(" :{}:", tags.join(":")));
+    }
+
+    let mut planning = format!("CLOSED: {}", decision.decided_at.format(INACTIVE));
+    if let Some(expires_at) = decision.expires_at {
+        planning.push_str(&format!(" DEADLINE: {}", expires_at.format(ACTIVE)));
+    }
+    let mut lines = vec![
+        heading,
+        format!("  {planning}"),
+        "  :PROPERTIES:".to_string(),
+        format!("  :ANSWER:    {answer}"),
+        format!("  :PACK:      {}", decision.pack),
+        format!("  :SENTIMENT: {}", decision.sentiment),
+    ];
+    if let Some(agreed) = decision.agreed {
+        lines.push(format!(
+            "  :AGREED:    {}",
+            if agreed { "yes" } else { "no" }
+        ));
+    }
+    lines.push("  :END:".to_string());
+    // Indented, so a note line starting with `*` is not taken for a heading.
+    for line in decision.note.iter().flat_map(|note| note.lines()) {
+        lines.push(format!("  {line}").trim_end().to_string());
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Neither a heading nor a property may span lines.
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Org tags allow letters, digits, `_`, `@`, `#`, and `%`, but not the `-`
+/// ours may have.
+fn tag_name(tag: &str) -> String {
+    tag.replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, TimeZone};
+
+    /// A negative answer from the classic pack, decided on Friday, October
+    /// 16, 2026 at 16:45 local time.
+    fn decision(answer: &str) -> Decision {
+        let mut decision: Decision = serde_json::from_value(serde_json::json!({
+            "answer": answer,
+            "pack": "classic",
+            "sentiment": "negative",
+            "decided_at": "2026-10-16T16:45:00+00:00",
+        }))
+        .unwrap();
+        decision.decided_at = Local.with_ymd_and_hms(2026, 10, 16, 16, 45, 0).unwrap();
+        decision
+    }
+
+    #[test]
+    fn the_heading_holds_question_and_answer_on_one_line() {
+        let mut asked = decision("FORGET\nIT");
+        asked.question = Some("Ship  on\nFriday?".to_string());
+        let text = entry(&asked);
+        assert_eq!(
+            text.lines().next(),
+            Some("* DONE Ship on Friday? → FORGET IT")
+        );
+        assert!(text.contains("\n  :ANSWER:    FORGET IT\n"), "{text}");
+
+        let unasked = decision("NEVER");
+        assert!(entry(&unasked).starts_with("* DONE NEVER\n"));
+    }
+
+    #[test]
+    fn tags_end_the_heading_without_dashes() {
+        let mut decision = decision("NEVER");
+        decision.tags = vec!["deploy".to_string(), "on-call".to_string()];
+        assert!(entry(&decision).starts_with("* DONE NEVER :deploy:on_call:\n"));
+    }
+
+    #[test]
+    fn an_expiry_is_the_deadline() {
+        let mut decision = decision("NEVER");
+        assert!(entry(&decision).contains("\n  CLOSED: [2026-10-16 Fri 16:45]\n"));
+        decision.expires_at = Local.with_ymd_and_hms(2026, 11, 16, 16, 45, 0).single();
+        assert!(entry(&decision)
+            .contains("\n  CLOSED: [2026-10-16 Fri 16:45] DEADLINE: <2026-11-16 Mon>\n"));
+    }
+
+    #[test]
+    fn note_lines_are_indented_under_the_drawer() {
+        let mut decision = decision("NEVER");
+        decision.note = Some("* not a heading\n\nAsked again on Monday.".to_string());
+        assert!(
+            entry(&decision).ends_with("  :END:\n  * not a heading\n\n  Asked again on Monday.\n")
+        );
+    }
+}
@@ -0,0 +1,443 @@
+//! `edm pack test <file>`: the checks a contributed pack should pass before
+//! it lands in the repo. Every check is a plain function from a [`PackFile`]
+//! to [`Finding`]s, so they can be reused outside the command.
+
+use crate::{
+    answers::{probabilities, PackFile},
+    sentiment::{Sentiment, SentimentTally},
+    ui::{MIN_WIDTH, UI_MARGIN},
+    widget::board_columns,
+    width,
+};
+use ratatui::style::Color;
+use std::{
+    collections::{BTreeMap, HashSet},
+    str::FromStr,
+};
+
+/// Draws simulated by [`distribution`].
+const DRAWS: usize = 10_000;
+/// How far (in percentage points) an answer's share may stray from fair.
+const TOLERANCE: f64 = 2.0;
+/// One sentiment making up more than this share of a pack is worth a warning.
+const LOPSIDED_SHARE: f64 = 0.7;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug)]
+pub struct Finding {
+    pub level: Level,
+    /// Short name of the check, e.g. `width`.
+    pub check: String,
+    pub message: String,
+}
+
+impl Finding {
+    fn new(level: Level, check: &str, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            check: check.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Run every check.
+pub fn check(pack: &PackFile) -> Vec<Finding> {
+    let mut findings = structure(pack);
+    if pack.answers.len() >= 2 && pack.answers.iter().any(|entry| entry.weight > 0) {
+        findings.extend(distribution(pack));
+    }
+    findings.extend(sentiment(pack));
+    findings.extend(width(pack));
+    findings.extend(localization(pack));
+    findings
+}
+
+/// A usable id, enough answers, none empty, none repeated, colours and
+/// opposites that exist, no blank flavor lines.
+pub fn structure(pack: &PackFile) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let id_chars = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if pack.id.is_empty() || !pack.id.chars().all(id_chars) {
+        findings.push(Finding::new(
+            Level::Fail,
+            "structure",
+            format!("id `{}` may only use letters, digits, - and _", pack.id),
+        ));
+    }
+    if pack.answers.len() < 2 {
+        findings.push(Finding::new(
+            Level::Fail,
+            "structure",
+            format!("{} answer(s); a pack needs at least 2", pack.answers.len()),
+        ));
+    }
+    if pack
+        .answers
+        .iter()
+        .any(|entry| entry.text.trim().is_empty())
+    {
+        findings.push(Finding::new(Level::Fail, "structure", "empty answer text"));
+    }
+    if !pack.answers.is_empty() && pack.answers.iter().all(|entry| entry.weight == 0) {
+        findings.push(Finding::new(
+            Level::Fail,
+            "structure",
+            "every weight is 0, so nothing can be drawn",
+        ));
+    }
+    for entry in &pack.answers {
+        for color in entry.color.iter().chain(&entry.idle_color) {
+            if Color::from_str(color).is_err() {
+                findings.push(Finding::new(
+                    Level::Fail,
+                    "structure",
+                    format!("unknown colour `{color}` on \"{}\"", entry.text),
+                ));
+            }
+        }
+    }
+    for entry in &pack.answers {
+        let Some(opposite) = &entry.opposite else {
+            continue;
+        };
+        if !pack.answers.iter().any(|other| &other.text == opposite) {
+            findings.push(Finding::new(
+                Level::Fail,
+                "structure",
+                format!(
+                    "opposite \"{opposite}\" of \"{}\" is not an answer",
+                    entry.text
+                ),
+            ));
+        }
+    }
+    for entry in &pack.answers {
+        if entry.flavor.iter().any(|line| line.trim().is_empty()) {
+            findings.push(Finding::new(
+                Level::Fail,
+                "structure",
+                format!("empty flavor line on \"{}\"", entry.text),
+            ));
+        }
+    }
+    let mut seen = HashSet::new();
+    for entry in &pack.answers {
+        if !seen.insert(entry.text.to_lowercase()) {
+            findings.push(Finding::new(
+                Level::Fail,
+                "structure",
+                format!("duplicate answer \"{}\"", entry.text),
+            ));
+        }
+    }
+    if findings.is_empty() {
+        findings.push(Finding::new(
+            Level::Ok,
+            "structure",
+            format!("{} distinct answers", pack.answers.len()),
+        ));
+    }
+    findings
+}
+
+/// Simulate draws and check every answer comes up about as often as its
+/// weight says.
+pub fn distribution(pack: &PackFile) -> Vec<Finding> {
+    let answers = pack.to_pack();
+    let mut counts = vec![0usize; answers.answers.len()];
+    for _ in 0..DRAWS {
+        counts[answers.draw_index()] += 1;
+    }
+
+    let expected = probabilities(&answers.weights());
+    let (worst, share, fair) = counts
+        .iter()
+        .map(|&count| count as f64 * 100.0 / DRAWS as f64)
+        .zip(&expected)
+        .enumerate()
+        .map(|(index, (share, &fair))| (index, share, fair))
+        .max_by(|a, b| (a.1 - a.2).abs().total_cmp(&(b.1 - b.2).abs()))
+        .expect("at least two answers");
+    let drift = (share - fair).abs();
+    let level = if drift <= TOLERANCE {
+        Level::Ok
+    } else {
+        Level::Fail
+    };
+    vec![Finding::new(
+        level,
+        "distribution",
+        format!(
+            "{DRAWS} draws; furthest off is \"{}\" at {share:.1}% (expected {fair:.1}%)",
+            pack.answers[worst].text
+        ),
+    )]
+}
+
+/// Every sentiment present, none dominating.
+pub fn sentiment(pack: &PackFile) -> Vec<Finding> {
+    let mut tally = SentimentTally::default();
+    for entry in &pack.answers {
+        tally.add(entry.sentiment);
+    }
+    let counts = [
+        (Sentiment::Positive, tally.positive),
+        (Sentiment::Neutral, tally.neutral),
+        (Sentiment::Negative, tally.negative),
+    ];
+
+    let mut findings = Vec::new();
+    for (sentiment, count) in counts {
+        if count == 0 {
+            findings.push(Finding::new(
+                Level::Warn,
+                "sentiment",
+                format!("no {sentiment} answers"),
+            ));
+        } else if count as f64 > pack.answers.len() as f64 * LOPSIDED_SHARE {
+            findings.push(Finding::new(
+                Level::Warn,
+                "sentiment",
+                format!("{count} of {} answers are {sentiment}", pack.answers.len()),
+            ));
+        }
+    }
+    if findings.is_empty() {
+        findings.push(Finding::new(Level::Ok, "sentiment", tally.to_string()));
+    }
+    findings
+}
+
+/// Every answer fits its button on the smallest supported terminal.
+pub fn width(pack: &PackFile) -> Vec<Finding> {
+    let columns = board_columns(pack.answers.len()) as u16;
+    // Screen margins on both sides, then a border on each side of a button.
+    let room = ((MIN_WIDTH - 2 * UI_MARGIN) / columns).saturating_sub(2) as usize;
+
+    let too_wide: Vec<Finding> = pack
+        .answers
+        .iter()
+        .map(|entry| entry.label())
+        .filter(|label| width::of(label) > room)
+        .map(|label| {
+            Finding::new(
+                Level::Fail,
+                "width",
+                format!(
+                    "\"{label}\" is {} columns; buttons have {room} at {MIN_WIDTH} columns",
+                    width::of(&label)
+                ),
+            )
+        })
+        .collect();
+    if !too_wide.is_empty() {
+        return too_wide;
+    }
+    vec![Finding::new(
+        Level::Ok,
+        "width",
+        format!("every answer fits {room} columns"),
+    )]
+}
+
+/// How many answers each locale translates.
+pub fn localization(pack: &PackFile) -> Vec<Finding> {
+    let mut missing: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for entry in &pack.answers {
+        for locale in entry.translations.keys() {
+            missing.entry(locale).or_default();
+        }
+    }
+    for entry in &pack.answers {
+        for (locale, untranslated) in missing.iter_mut() {
+            if !entry.translations.contains_key(*locale) {
+                untranslated.push(&entry.text);
+            }
+        }
+    }
+
+    if missing.is_empty() {
+        return vec![Finding::new(Level::Ok, "localization", "no translations")];
+    }
+    let total = pack.answers.len();
+    missing
+        .into_iter()
+        .map(|(locale, untranslated)| {
+            let check = format!("localization {locale}");
+            let done = total - untranslated.len();
+            if untranslated.is_empty() {
+                Finding::new(Level::Ok, &check, format!("{done}/{total} answers"))
+            } else {
+                Finding::new(
+                    Level::Warn,
+                    &check,
+                    format!(
+                        "{done}/{total} answers (missing: {})",
+                        untranslated.join(", ")
+                    ),
+                )
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{balanced, entry, pack, shipped, weighted};
+    use Sentiment::{Negative, Neutral, Positive};
+
+    fn levels(findings: &[Finding]) -> Vec<Level> {
+        findings.iter().map(|finding| finding.level).collect()
+    }
+
+    fn failures(findings: &[Finding]) -> Vec<&str> {
+        findings
+            .iter()
+            .filter(|finding| finding.level == Level::Fail)
+            .map(|finding| finding.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn balanced_pack_passes_everything() {
+        let findings = check(&balanced());
+        assert!(findings.iter().all(|finding| finding.level == Level::Ok));
+    }
+
+    #[test]
+    fn shipped_packs_are_well_formed() {
+        for file in shipped() {
+            assert_eq!(
+                failures(&structure(&file)),
+                Vec::<&str>::new(),
+                "{}",
+                file.id
+            );
+        }
+    }
+
+    #[test]
+    fn structure_needs_two_answers() {
+        let findings = structure(&pack(vec![entry("YES", Positive)]));
+        assert_eq!(
+            failures(&findings),
+            ["1 answer(s); a pack needs at least 2"]
+        );
+    }
+
+    #[test]
+    fn structure_fails_when_every_weight_is_zero() {
+        let findings = structure(&pack(vec![
+            weighted("YES", Positive, 0),
+            weighted("NO", Negative, 0),
+        ]));
+        assert_eq!(
+            failures(&findings),
+            ["every weight is 0, so nothing can be drawn"]
+        );
+    }
+
+    #[test]
+    fn structure_catches_bad_ids_duplicates_and_dangling_opposites() {
+        let mut file = pack(vec![entry("YES", Positive), entry("yes", Positive)]);
+        file.id = "no spaces".to_string();
+        file.answers[0].opposite = Some("NO".to_string());
+        file.answers[1].color = Some("chartreuse-ish".to_string());
+        let findings = structure(&file);
+        assert_eq!(
+            failures(&findings),
+            [
+                "id `no spaces` may only use letters, digits, - and _",
+                "unknown colour `chartreuse-ish` on \"yes\"",
+                "opposite \"NO\" of \"YES\" is not an answer",
+                "duplicate answer \"yes\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn distribution_follows_weights() {
+        let file = pack(vec![
+            weighted("YES", Positive, 3),
+            weighted("NO", Negative, 1),
+        ]);
+        assert_eq!(levels(&distribution(&file)), [Level::Ok]);
+    }
+
+    #[test]
+    fn sentiment_warns_about_missing_and_lopsided_packs() {
+        let file = pack(vec![
+            entry("YES", Positive),
+            entry("SURE", Positive),
+            entry("ABSOLUTELY", Positive),
+            entry("NO", Negative),
+        ]);
+        let messages: Vec<String> = sentiment(&file)
+            .into_iter()
+            .map(|finding| finding.message)
+            .collect();
+        assert_eq!(
+            messages,
+            ["3 of 4 answers are positive", "no neutral answers"]
+        );
+    }
+
+    #[test]
+    fn sentiment_reports_the_tally_when_balanced() {
+        let findings = sentiment(&balanced());
+        assert_eq!(levels(&findings), [Level::Ok]);
+        assert_eq!(findings[0].message, "1 positive, 1 neutral, 1 negative");
+    }
+
+    #[test]
+    fn width_fails_answers_wider_than_a_button() {
+        let mut file = balanced();
+        file.answers[1].text = "ASK AGAIN AFTER LUNCH, PERHAPS".to_string();
+        let findings = width(&file);
+        assert_eq!(levels(&findings), [Level::Fail]);
+        assert!(findings[0]
+            .message
+            .starts_with("\"ASK AGAIN AFTER LUNCH, PERHAPS\""));
+    }
+
+    #[test]
+    fn width_counts_wide_characters_twice() {
+        let mut file = balanced();
+        // 12 CJK characters take 24 columns, one more than a button has.
+        file.answers[0].text = "是是是是是是是是是是是是".to_string();
+        assert_eq!(levels(&width(&file)), [Level::Fail]);
+        file.answers[0].text.pop();
+        assert_eq!(levels(&width(&file)), [Level::Ok]);
+    }
+
+    #[test]
+    fn localization_lists_untranslated_answers_per_locale() {
+        let mut file = balanced();
+        for entry in &mut file.answers {
+            entry
+                .translations
+                .insert("de".to_string(), entry.text.clone());
+        }
+        file.answers[0]
+            .translations
+            .insert("fr".to_string(), "OUI".to_string());
+        let findings = localization(&file);
+        assert_eq!(levels(&findings), [Level::Ok, Level::Warn]);
+        assert_eq!(findings[0].check, "localization de");
+        assert_eq!(findings[1].message, "1/3 answers (missing: MAYBE, NO)");
+    }
+
+    #[test]
+    fn localization_is_fine_without_translations() {
+        let findings = localization(&pack(vec![entry("YES", Positive), entry("NO", Neutral)]));
+        assert_eq!(findings[0].message, "no translations");
+    }
+}
@@ -0,0 +1,168 @@
+//! In-app pack editor, opened with `E`: add, remove, and rename the current
+//! pack's answers and set their weights, with the board laid out afresh
+//! after every change. Saving writes the pack to `packs/` next to the config.
+
+use crate::answers::{Answer, AnswerPack};
+use crate::sentiment::Sentiment;
+use crate::theme::AnswerStyle;
+
+/// Fewest answers a pack may have.
+pub const MIN_ANSWERS: usize = 2;
+/// Highest weight the editor allows.
+const MAX_WEIGHT: u32 = 99;
+
+/// Text being typed for an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The answer being renamed, or `None` for a new one.
+    pub index: Option<usize>,
+    pub text: String,
+}
+
+pub struct PackEditor {
+    /// Working copy; the pack in use changes only when the edit is saved.
+    pub pack: AnswerPack,
+    pub selected: usize,
+    /// Open while an answer's text is typed.
+    pub entry: Option<Entry>,
+    /// Why the last change was refused, until the next one.
+    pub problem: Option<String>,
+    /// Whether there is anything to save.
+    pub changed: bool,
+}
+
+impl PackEditor {
+    pub fn new(pack: &AnswerPack) -> Self {
+        Self {
+            pack: pack.clone(),
+            selected: 0,
+            entry: None,
+            problem: None,
+            changed: false,
+        }
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.pack.answers.len() - 1);
+    }
+
+    /// Change the selected answer's weight. The last drawable answer cannot
+    /// go down to 0.
+    pub fn adjust(&mut self, delta: i32) {
+        let weight = self.pack.answers[self.selected].weight;
+        let new = weight.saturating_add_signed(delta).min(MAX_WEIGHT);
+        let others: u32 = self.pack.weights().iter().sum::<u32>() - weight;
+        if new == 0 && others == 0 {
+            self.problem = Some("At least one answer must be drawable.".to_string());
+            return;
+        }
+        self.problem = None;
+        if new != weight {
+            self.pack.answers[self.selected].weight = new;
+            self.changed = true;
+        }
+    }
+
+    /// Start typing a new answer.
+    pub fn start_add(&mut self) {
+        self.problem = None;
+        self.entry = Some(Entry {
+            index: None,
+            text: String::new(),
+        });
+    }
+
+    /// Start retyping the selected answer.
+    pub fn start_rename(&mut self) {
+        self.problem = None;
+        self.entry = Some(Entry {
+            index: Some(self.selected),
+            text: self.pack.answers[self.selected].text.clone(),
+        });
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        if let Some(entry) = self.entry.as_mut() {
+            entry.text.push(c);
+        }
+    }
+
+    pub fn erase(&mut self) {
+        if let Some(entry) = self.entry.as_mut() {
+            entry.text.pop();
+        }
+    }
+
+    pub fn cancel_entry(&mut self) {
+        self.entry = None;
+        self.problem = None;
+    }
+
+    /// Use the typed text, unless it is empty or names another answer, in
+    /// which case typing goes on.
+    pub fn confirm_entry(&mut self) {
+        let Some(entry) = self.entry.as_ref() else {
+            return;
+        };
+        let text = entry.text.trim().to_string();
+        if text.is_empty() {
+            self.problem = Some("An answer needs some text.".to_string());
+            return;
+        }
+        let taken =
+            self.pack.answers.iter().enumerate().any(|(i, answer)| {
+                Some(i) != entry.index && answer.text.eq_ignore_ascii_case(&text)
+            });
+        if taken {
+            self.problem = Some(format!("{text} is already an answer."));
+            return;
+        }
+        match entry.index {
+            Some(index) => {
+                // Answers naming the old text as their opposite follow it.
+                let old = std::mem::replace(&mut self.pack.answers[index].text, text.clone());
+                for answer in &mut self.pack.answers {
+                    if answer.opposite.as_deref() == Some(old.as_str()) {
+                        answer.opposite = Some(text.clone());
+                    }
+                }
+            }
+            None => {
+                self.pack.answers.push(Answer {
+                    text,
+                    sentiment: Sentiment::Neutral,
+                    weight: 1,
+                    style: AnswerStyle::default(),
+                    icon: None,
+                    opposite: None,
+                    flavor: Vec::new(),
+                });
+                self.selected = self.pack.answers.len() - 1;
+            }
+        }
+        self.entry = None;
+        self.problem = None;
+        self.changed = true;
+    }
+
+    /// Remove the selected answer, keeping at least [`MIN_ANSWERS`] and one
+    /// drawable answer.
+    pub fn remove(&mut self) {
+        if self.pack.answers.len() <= MIN_ANSWERS {
+            self.problem = Some(format!("A pack needs at least {MIN_ANSWERS} answers."));
+            return;
+        }
+        let weight = self.pack.answers[self.selected].weight;
+        if weight > 0 && self.pack.weights().iter().sum::<u32>() == weight {
+            self.problem = Some("At least one answer must be drawable.".to_string());
+            return;
+        }
+        self.pack.answers.remove(self.selected);
+        self.selected = self.selected.min(self.pack.answers.len() - 1);
+        self.problem = None;
+        self.changed = true;
+    }
+}
@@ -0,0 +1,211 @@
+//! Colour depth: what the terminal can show, and how a finished frame is
+//! brought down to it.
+//!
+//! `NO_COLOR` (<https://no-color.org>) drops colour altogether. Highlighted
+//! cells (the lit button, selected rows, badges) then stand out by reverse
+//! video and bold, with their text underlined. Terminals that only know the
+//! 16 ANSI colours, like the Linux console, get the nearest of those, so
+//! `#rrggbb` themes still work there. Anything not recognised is left as is.
+//!
+//! The other way round, [`mix`] blends theme colours for the lit button's
+//! gradient, which is only drawn on true-colour terminals.
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+use std::{env, ffi::OsString};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// No colour at all.
+    Monochrome,
+    /// The 16 ANSI colours.
+    Ansi16,
+    /// The xterm 256-colour palette.
+    Ansi256,
+    /// 24-bit colour; frames are drawn unchanged.
+    #[default]
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Work out the depth from `NO_COLOR`, `COLORTERM`, and `TERM`.
+    pub fn detect() -> Self {
+        Self::from_env(
+            env::var_os("NO_COLOR"),
+            env::var("COLORTERM").ok(),
+            env::var("TERM").ok(),
+        )
+    }
+
+    fn from_env(
+        no_color: Option<OsString>,
+        colorterm: Option<String>,
+        term: Option<String>,
+    ) -> Self {
+        if no_color.is_some_and(|value| !value.is_empty()) {
+            return Self::Monochrome;
+        }
+        if matches!(colorterm.as_deref(), Some("truecolor" | "24bit")) {
+            return Self::TrueColor;
+        }
+        let Some(term) = term else {
+            return Self::TrueColor;
+        };
+        if term == "dumb" {
+            Self::Monochrome
+        } else if term.ends_with("-direct") {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else if term.ends_with("-16color")
+            || term.ends_with("-color")
+            || matches!(
+                term.as_str(),
+                "linux" | "ansi" | "cons25" | "screen" | "rxvt"
+            )
+            || term.starts_with("vt")
+        {
+            Self::Ansi16
+        } else {
+            Self::TrueColor
+        }
+    }
+}
+
+/// Bring every cell of `buf` down to `depth`. Cells with the `highlight`
+/// background are the ones that must stay distinguishable in monochrome.
+pub fn adapt(buf: &mut Buffer, depth: ColorDepth, highlight: Color) {
+    match depth {
+        ColorDepth::TrueColor => {}
+        ColorDepth::Ansi256 => {
+            for cell in &mut buf.content {
+                cell.fg = to_256(cell.fg);
+                cell.bg = to_256(cell.bg);
+            }
+        }
+        ColorDepth::Ansi16 => {
+            for cell in &mut buf.content {
+                cell.fg = to_16(cell.fg);
+                cell.bg = to_16(cell.bg);
+            }
+        }
+        ColorDepth::Monochrome => {
+            for cell in &mut buf.content {
+                if cell.bg == highlight && highlight != Color::Reset {
+                    cell.modifier |= Modifier::REVERSED | Modifier::BOLD;
+                    if !cell.symbol().trim().is_empty() {
+                        cell.modifier |= Modifier::UNDERLINED;
+                    }
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+        }
+    }
+}
+
+/// `a` blended towards `b` by `amount` (0 is all `a`, 1 all `b`), as RGB.
+/// `a` is returned unchanged when either colour is the terminal default.
+pub fn mix(a: Color, b: Color, amount: f64) -> Color {
+    let (Some(a_rgb), Some(b_rgb)) = (rgb(a), rgb(b)) else {
+        return a;
+    };
+    let amount = amount.clamp(0.0, 1.0);
+    let channel =
+        |x: u8, y: u8| (f64::from(x) + (f64::from(y) - f64::from(x)) * amount).round() as u8;
+    Color::Rgb(
+        channel(a_rgb.0, b_rgb.0),
+        channel(a_rgb.1, b_rgb.1),
+        channel(a_rgb.2, b_rgb.2),
+    )
+}
+
+/// RGB of a colour, taking named and indexed ones at xterm's defaults.
+pub fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index) => Some(indexed_rgb(index)),
+        named => ANSI
+            .iter()
+            .find(|(ansi, _)| *ansi == named)
+            .map(|&(_, rgb)| rgb),
+    }
+}
+
+/// The 16 ANSI colours in palette order, with xterm's default RGB values.
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6×6×6 colour cube at indices 16–231.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn to_16(color: Color) -> Color {
+    let rgb = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(index) => indexed_rgb(index),
+        named => return named,
+    };
+    ANSI.iter()
+        .min_by_key(|(_, ansi)| distance(rgb, *ansi))
+        .map_or(color, |&(named, _)| named)
+}
+
+fn to_256(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let level = |value: u8| {
+        (0..CUBE.len() as u8)
+            .min_by_key(|&i| value.abs_diff(CUBE[usize::from(i)]))
+            .unwrap_or(0)
+    };
+    let cube = 16 + 36 * level(r) + 6 * level(g) + level(b);
+    // The grey ramp at 232–255 is finer than the cube's diagonal.
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let grey = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+    let rgb = (r, g, b);
+    if distance(rgb, indexed_rgb(grey)) < distance(rgb, indexed_rgb(cube)) {
+        Color::Indexed(grey)
+    } else {
+        Color::Indexed(cube)
+    }
+}
+
+/// RGB of a 256-colour palette entry.
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI[usize::from(index)].1,
+        16..=231 => {
+            let i = usize::from(index - 16);
+            (CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6])
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| u32::from(x.abs_diff(y)).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
@@ -0,0 +1,130 @@
+//! Where `edm` keeps its files, following each platform's conventions via
+//! `directories`:
+//!
+//! | | Linux | macOS | Windows |
+//! | --- | --- | --- | --- |
+//! | config | `~/.config/edm` | `~/Library/Application Support/edm` | `%APPDATA%\edm\config` |
+//! | data | `~/.local/share/edm` | `~/Library/Application Support/edm` | `%APPDATA%\edm\data` |
+//! | logs | `~/.local/state/edm` | `<data>/logs` | `%LOCALAPPDATA%\edm\data\logs` |
+//!
+//! The XDG variables are honoured on Linux. `--config` replaces the config
+//! file and `--data-dir` the data directory, logs included.
+//!
+//! A profile other than `default` keeps its config, packs, and history in
+//! `profiles/<name>/` inside both directories. Logs are shared.
+
+use directories::ProjectDirs;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const CONFIG_FILE: &str = "config.toml";
+const HISTORY_FILE: &str = "history.jsonl";
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+const LOG_DIR: &str = "logs";
+const PACKS_DIR: &str = "packs";
+const PROFILES_DIR: &str = "profiles";
+/// The profile used unless `--profile` names another. Its files live where
+/// they always have.
+pub const DEFAULT_PROFILE: &str = "default";
+
+fn project() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "edm")
+}
+
+/// `config.toml` for `profile` in the platform's config directory. Packs,
+/// themes, and transliterations are looked up next to it.
+pub fn config_file(profile: &str) -> Option<PathBuf> {
+    project().map(|dirs| profile_dir(dirs.config_dir(), profile).join(CONFIG_FILE))
+}
+
+/// `dir` itself for the default profile, `profiles/<name>` inside it for any
+/// other.
+fn profile_dir(dir: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        dir.to_path_buf()
+    } else {
+        dir.join(PROFILES_DIR).join(profile)
+    }
+}
+
+/// Whether `name` can name a profile: letters, digits, `-` and `_`.
+pub fn valid_profile(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The data directory of `profile`, under `data` if given.
+pub fn profile_data_dir(data: Option<&Path>, profile: &str) -> Option<PathBuf> {
+    data_dir(data).map(|dir| profile_dir(&dir, profile))
+}
+
+/// Every profile with a directory in the config or data directory, the
+/// default one first.
+pub fn profiles(data: Option<&Path>) -> Vec<String> {
+    let roots = project()
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .into_iter()
+        .chain(data_dir(data));
+    let mut names: Vec<String> = roots
+        .filter_map(|root| fs::read_dir(root.join(PROFILES_DIR)).ok())
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| valid_profile(name) && name != DEFAULT_PROFILE)
+        .collect();
+    names.sort();
+    names.dedup();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    names
+}
+
+/// `data` if given, otherwise the platform's data directory.
+pub fn data_dir(data: Option<&Path>) -> Option<PathBuf> {
+    match data {
+        Some(dir) => Some(dir.to_path_buf()),
+        None => project().map(|dirs| dirs.data_dir().to_path_buf()),
+    }
+}
+
+/// The history kept for decisions made outside any workspace.
+pub fn history_file(data: Option<&Path>) -> Option<PathBuf> {
+    data_dir(data).map(|dir| dir.join(HISTORY_FILE))
+}
+
+/// Where unlocked achievements are kept.
+pub fn achievements_file(data: Option<&Path>) -> Option<PathBuf> {
+    data_dir(data).map(|dir| dir.join(ACHIEVEMENTS_FILE))
+}
+
+/// Where `edm pack install` puts downloaded packs.
+pub fn downloaded_packs(data: Option<&Path>) -> Option<PathBuf> {
+    data_dir(data).map(|dir| dir.join(PACKS_DIR))
+}
+
+/// Directories packs are installed from, in order: downloaded packs, then
+/// `packs/` next to `config_file`, so the user's own files win.
+pub fn pack_dirs(config_file: Option<&Path>, data: Option<&Path>) -> Vec<PathBuf> {
+    let own = config_file
+        .and_then(Path::parent)
+        .map(|dir| dir.join(PACKS_DIR));
+    downloaded_packs(data).into_iter().chain(own).collect()
+}
+
+/// Where rotated log files go: `logs/` under `data` if given, otherwise the
+/// platform's state directory, or `logs/` in the local data directory where
+/// there is none.
+pub fn log_dir(data: Option<&Path>) -> Option<PathBuf> {
+    if let Some(dir) = data {
+        return Some(dir.join(LOG_DIR));
+    }
+    let dirs = project()?;
+    Some(match dirs.state_dir() {
+        Some(state) => state.to_path_buf(),
+        None => dirs.data_local_dir().join(LOG_DIR),
+    })
+}
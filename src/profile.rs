@@ -0,0 +1,26 @@
+//! Profile picker, opened with `P`: start over on another profile's config,
+//! packs, and history. A profile is a pair of directories; see
+//! [`crate::paths`].
+
+pub struct ProfilePicker {
+    pub names: Vec<String>,
+    pub selected: usize,
+}
+
+impl ProfilePicker {
+    /// Open the picker on `names` with `current` preselected.
+    pub fn new(names: Vec<String>, current: &str) -> Self {
+        let selected = names.iter().position(|name| name == current).unwrap_or(0);
+        Self { names, selected }
+    }
+
+    pub fn current(&self) -> &str {
+        &self.names[self.selected]
+    }
+
+    /// Move the selection by `delta`, wrapping at either end.
+    pub fn move_by(&mut self, delta: isize) {
+        let len = self.names.len() as isize;
+        self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+    }
+}
@@ -0,0 +1,50 @@
+//! QR code of a decision, shown with `s` once the answer is revealed: the
+//! question, the answer and when, for a phone to scan and drop into a chat.
+//! Drawn with half blocks, two modules to a character cell, so a code for a
+//! long question still fits a terminal.
+
+use crate::history::Decision;
+use qrcode::{types::QrError, Color, EcLevel, QrCode};
+
+/// Light modules around the code. The standard asks for four; two is
+/// enough for phone cameras and saves room.
+const QUIET_ZONE: usize = 2;
+
+/// The text the code carries: the question if one was typed, the answer
+/// and the time it was given.
+pub fn payload(decision: &Decision) -> String {
+    let mut text = String::new();
+    if let Some(question) = &decision.question {
+        text.push_str(&format!("Q: {question}\n"));
+    }
+    text.push_str(&format!("A: {}\n", decision.answer));
+    text.push_str(&decision.decided_at.format("%Y-%m-%d %H:%M").to_string());
+    text
+}
+
+/// `text` as a QR code, one string per row of character cells. `▀`, `▄`
+/// and `█` mark dark modules, so the rows are meant to be drawn dark on
+/// light whatever the theme.
+pub fn render(text: &str) -> Result<Vec<String>, QrError> {
+    let code = QrCode::with_error_correction_level(text, EcLevel::L)?;
+    let width = code.width();
+    let colors = code.to_colors();
+    let size = width + 2 * QUIET_ZONE;
+    let dark = |x: usize, y: usize| {
+        let (x, y) = (x.wrapping_sub(QUIET_ZONE), y.wrapping_sub(QUIET_ZONE));
+        x < width && y < width && colors[y * width + x] == Color::Dark
+    };
+    Ok((0..size)
+        .step_by(2)
+        .map(|y| {
+            (0..size)
+                .map(|x| match (dark(x, y), dark(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect()
+        })
+        .collect())
+}
@@ -0,0 +1,152 @@
+//! The rationale panel: with the `llm` feature and an `llm_api_key`, each
+//! answer gets a short tongue-in-cheek justification from a language model.
+//! Requests run on a background thread like the webhook's, so the board
+//! keeps animating while the model thinks; replies come back over a channel.
+
+use crate::config::Config;
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::Instant,
+};
+use tokio::sync::Notify;
+
+/// Used when `llm_url` is not set.
+pub const DEFAULT_URL: &str = "https://api.openai.com/v1/chat/completions";
+/// Used when `llm_model` is not set.
+pub const DEFAULT_MODEL: &str = "gpt-4o-mini";
+/// How often the loading spinner turns.
+pub const SPINNER_FRAME_MS: u64 = 100;
+pub const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+#[cfg(feature = "llm")]
+const PROMPT: &str = "You are the Executive Decision Maker, a 1970s desk gadget \
+    that answers questions with a lit-up button. In one or two short sentences, \
+    give a tongue-in-cheek justification for the answer you just gave. No \
+    preamble, no quotes.";
+
+/// One answer to justify.
+#[derive(Debug)]
+pub struct Request {
+    /// Matches the reply to the ask it belongs to.
+    pub id: u64,
+    pub question: Option<String>,
+    pub answer: String,
+    pub pack: String,
+}
+
+/// Where the rationale for the answer on show stands.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Status {
+    Loading { since: Instant },
+    Ready(String),
+    Failed(String),
+}
+
+pub struct Rationale {
+    jobs: Sender<Request>,
+    replies: Receiver<(u64, Result<String, String>)>,
+}
+
+impl Rationale {
+    /// Start the request thread if the config has an API key. `wake` is
+    /// notified after each reply so the event loop shows it right away.
+    pub fn from_config(config: &Config, wake: Arc<Notify>) -> Option<Self> {
+        let key = config.llm_api_key.clone().filter(|key| !key.is_empty())?;
+        let url = config
+            .llm_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_URL.to_string());
+        let model = config
+            .llm_model
+            .clone()
+            .unwrap_or_else(|| DEFAULT_MODEL.to_string());
+        let (jobs, queue) = mpsc::channel::<Request>();
+        let (report, replies) = mpsc::channel();
+        thread::spawn(move || {
+            for request in queue {
+                let reply = fetch(&url, &key, &model, &request);
+                if let Err(err) = &reply {
+                    tracing::warn!(%err, "rationale request failed");
+                }
+                let _ = report.send((request.id, reply));
+                wake.notify_one();
+            }
+        });
+        Some(Self { jobs, replies })
+    }
+
+    pub fn request(&self, request: Request) {
+        tracing::debug!(id = request.id, "rationale requested");
+        // The worker only exits when `jobs` is dropped, so this cannot fail.
+        let _ = self.jobs.send(request);
+    }
+
+    /// Replies that came in since the last call, by request id.
+    pub fn replies(&self) -> impl Iterator<Item = (u64, Result<String, String>)> + '_ {
+        self.replies.try_iter()
+    }
+}
+
+/// The chat completions request body.
+#[cfg(feature = "llm")]
+#[derive(serde::Serialize)]
+struct Chat<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    messages: [Message<'a>; 2],
+}
+
+#[cfg(feature = "llm")]
+#[derive(serde::Serialize)]
+struct Message<'a> {
+    role: &'a str,
+    content: String,
+}
+
+/// Ask the model at `url` to justify the answer, blocking until it does.
+#[cfg(feature = "llm")]
+fn fetch(url: &str, key: &str, model: &str, request: &Request) -> Result<String, String> {
+    let question = request.question.as_deref().unwrap_or("(unspoken)");
+    let chat = Chat {
+        model,
+        max_tokens: 80,
+        messages: [
+            Message {
+                role: "system",
+                content: PROMPT.to_string(),
+            },
+            Message {
+                role: "user",
+                content: format!(
+                    "Question: {question}\nAnswer: {}\nPack: {}",
+                    request.answer, request.pack
+                ),
+            },
+        ],
+    };
+    let body = serde_json::to_string(&chat).map_err(|err| err.to_string())?;
+    let text = ureq::post(url)
+        .set("Authorization", &format!("Bearer {key}"))
+        .set("Content-Type", "application/json")
+        .timeout(std::time::Duration::from_secs(20))
+        .send_string(&body)
+        .map_err(|err| err.to_string())?
+        .into_string()
+        .map_err(|err| err.to_string())?;
+    let response: serde_json::Value = serde_json::from_str(&text).map_err(|err| err.to_string())?;
+    response
+        .pointer("/choices/0/message/content")
+        .and_then(serde_json::Value::as_str)
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+        .ok_or_else(|| "the model sent no text".to_string())
+}
+
+#[cfg(not(feature = "llm"))]
+fn fetch(_url: &str, _key: &str, _model: &str, _request: &Request) -> Result<String, String> {
+    Err("built without the `llm` feature".to_string())
+}
@@ -0,0 +1,93 @@
+//! Duplicate question detection. Asking nearly the same question again soon
+//! after brings up the earlier verdict first, with the choice to reuse it or
+//! ask fresh anyway: a gentle check on oracle-shopping. Commitment mode
+//! (`commitment_hours`) is the firm one: within its cooldown the earlier
+//! verdict simply stands.
+
+use crate::{history::History, seal};
+use chrono::{DateTime, Duration, Local};
+
+/// How far back earlier questions count.
+pub const WINDOW_DAYS: i64 = 7;
+/// Share of characters (after normalising) that must line up.
+const SIMILARITY: f64 = 0.85;
+
+/// An earlier decision on a near-identical question.
+#[derive(Clone, Debug)]
+pub struct Recall {
+    /// The decision's [`Decision::key`](crate::history::Decision::key).
+    pub id: String,
+    pub question: String,
+    pub answer: String,
+    pub pack: String,
+    pub decided_at: DateTime<Local>,
+}
+
+/// The most recent decision within [`WINDOW_DAYS`] whose question is nearly
+/// `question`.
+pub fn find(history: &History, question: &str, now: DateTime<Local>) -> Option<Recall> {
+    find_since(history, question, now - Duration::days(WINDOW_DAYS))
+}
+
+/// The verdict `question` is committed to: the most recent decision on
+/// nearly the same question in the last `hours`.
+pub fn committed(
+    history: &History,
+    question: &str,
+    hours: u32,
+    now: DateTime<Local>,
+) -> Option<Recall> {
+    find_since(history, question, now - Duration::hours(hours.into()))
+}
+
+/// The most recent decision since `since` whose question is nearly
+/// `question`. Vetoed answers never stood, so they don't count.
+fn find_since(history: &History, question: &str, since: DateTime<Local>) -> Option<Recall> {
+    let wanted = seal::normalize(question);
+    if wanted.is_empty() {
+        return None;
+    }
+    history
+        .recent()
+        .take_while(|decision| decision.decided_at >= since)
+        .filter(|decision| decision.vetoed_at.is_none())
+        .find_map(|decision| {
+            let asked = decision.question.as_deref()?;
+            similar(&wanted, &seal::normalize(asked)).then(|| Recall {
+                id: decision.key(),
+                question: asked.to_string(),
+                answer: decision.answer.clone(),
+                pack: decision.pack.clone(),
+                decided_at: decision.decided_at,
+            })
+        })
+}
+
+/// Whether two normalised questions differ by at most a few typos.
+fn similar(a: &str, b: &str) -> bool {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return false;
+    }
+    1.0 - edit_distance(a, b) as f64 / longest as f64 >= SIMILARITY
+}
+
+/// Levenshtein distance in characters.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,99 @@
+//! What fills the terminal. The screens along the tab bar are the oracle's
+//! board, the history, the stats, the settings and the help; `[` and `]`
+//! step between them. The editors and pickers opened from the board cover
+//! the tab bar until they close.
+//!
+//! [`App::screen`](crate::app::App::screen) holds the one shown together
+//! with its state, so only one can be open at a time, and both key handling
+//! and drawing go through it.
+
+use crate::{
+    bracket::Bracket, gallery::Gallery, history_view::HistoryView, matrix::MatrixEditor,
+    pack_editor::PackEditor, profile::ProfilePicker, settings::Settings, weights::WeightEditor,
+    worksheet::Worksheet,
+};
+
+pub enum Screen {
+    /// The answer board.
+    Oracle,
+    History(HistoryView),
+    Stats,
+    Settings(Box<Settings>),
+    Help {
+        /// Lines scrolled past.
+        scroll: u16,
+    },
+    /// Browsing answer packs.
+    Gallery(Gallery),
+    /// Tuning the current pack's weights.
+    Weights(WeightEditor),
+    /// Editing the current pack's answers.
+    PackEditor(PackEditor),
+    /// Weighing pros and cons.
+    Worksheet(Worksheet),
+    Matrix(MatrixEditor),
+    /// Running a tournament.
+    Bracket(Bracket),
+    Profiles(ProfilePicker),
+    Achievements,
+}
+
+impl Screen {
+    /// The screen's tab, or `None` for those that cover the tab bar.
+    pub fn tab(&self) -> Option<Tab> {
+        match self {
+            Screen::Oracle => Some(Tab::Oracle),
+            Screen::History(_) => Some(Tab::History),
+            Screen::Stats => Some(Tab::Stats),
+            Screen::Settings(_) => Some(Tab::Settings),
+            Screen::Help { .. } => Some(Tab::Help),
+            _ => None,
+        }
+    }
+}
+
+/// A screen on the tab bar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tab {
+    Oracle,
+    History,
+    Stats,
+    Settings,
+    Help,
+}
+
+impl Tab {
+    /// In tab bar order.
+    pub const ALL: [Tab; 5] = [
+        Tab::Oracle,
+        Tab::History,
+        Tab::Stats,
+        Tab::Settings,
+        Tab::Help,
+    ];
+
+    /// The locale message naming the screen on its tab.
+    pub fn message_id(self) -> &'static str {
+        match self {
+            Tab::Oracle => "screen-oracle",
+            Tab::History => "screen-history",
+            Tab::Stats => "screen-stats",
+            Tab::Settings => "screen-settings",
+            Tab::Help => "screen-help",
+        }
+    }
+
+    fn position(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|&tab| tab == self)
+            .unwrap_or_default()
+    }
+
+    /// The tab `delta` along, wrapping around.
+    pub fn step(self, delta: isize) -> Tab {
+        let count = Self::ALL.len() as isize;
+        let position = (self.position() as isize + delta).rem_euclid(count);
+        Self::ALL[position as usize]
+    }
+}
@@ -0,0 +1,202 @@
+//! Screenshots, taken with `S`: the frame on screen written out with its
+//! colours and layout, as an SVG image or, with `screenshot_html = true`, a
+//! standalone HTML page, for the moment the oracle said NEVER to go in a
+//! pull request or on a slide.
+//!
+//! The front end hands over the buffer it drew last; see
+//! [`crate::frontend::Frontend::last_frame`].
+
+use crate::{palette, width};
+use chrono::{DateTime, Local};
+use ratatui::{buffer::Buffer, style::Modifier};
+use std::fmt::Write;
+
+/// Colours of cells that leave them to the terminal.
+const FOREGROUND: (u8, u8, u8) = (229, 229, 229);
+const BACKGROUND: (u8, u8, u8) = (0, 0, 0);
+/// A character cell in the SVG, in pixels.
+const CELL_WIDTH: u32 = 9;
+const CELL_HEIGHT: u32 = 18;
+/// From the top of a cell to the text's baseline.
+const BASELINE: u32 = 14;
+const FONT: &str = "ui-monospace, Menlo, Consolas, 'DejaVu Sans Mono', monospace";
+
+/// A stretch of a row drawn in one style.
+struct Run {
+    /// First cell of the run.
+    x: u32,
+    /// Cells the run covers.
+    cells: u32,
+    text: String,
+    fg: (u8, u8, u8),
+    bg: (u8, u8, u8),
+    modifier: Modifier,
+}
+
+/// The rows of `buffer`, each cut into runs of one style. Cells hidden
+/// under a wide character are left out.
+fn rows(buffer: &Buffer) -> Vec<Vec<Run>> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut runs: Vec<Run> = Vec::new();
+            let mut x = area.left();
+            while x < area.right() {
+                let cell = &buffer[(x, y)];
+                let symbol = cell.symbol();
+                let cells = width::of(symbol).max(1) as u32;
+                let (mut fg, mut bg) = (
+                    palette::rgb(cell.fg).unwrap_or(FOREGROUND),
+                    palette::rgb(cell.bg).unwrap_or(BACKGROUND),
+                );
+                if cell.modifier.contains(Modifier::REVERSED) {
+                    std::mem::swap(&mut fg, &mut bg);
+                }
+                let modifier = cell.modifier - Modifier::REVERSED;
+                match runs.last_mut() {
+                    Some(run) if (run.fg, run.bg, run.modifier) == (fg, bg, modifier) => {
+                        run.text.push_str(symbol);
+                        run.cells += cells;
+                    }
+                    _ => runs.push(Run {
+                        x: u32::from(x - area.left()),
+                        cells,
+                        text: symbol.to_string(),
+                        fg,
+                        bg,
+                        modifier,
+                    }),
+                }
+                x = x.saturating_add(cells as u16);
+            }
+            runs
+        })
+        .collect()
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// `text` safe inside XML and HTML, attributes included.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// CSS for a run's modifiers, shared by both formats.
+fn decoration(modifier: Modifier) -> String {
+    let mut css = String::new();
+    if modifier.contains(Modifier::BOLD) {
+        css.push_str("font-weight:bold;");
+    }
+    if modifier.contains(Modifier::ITALIC) {
+        css.push_str("font-style:italic;");
+    }
+    if modifier.contains(Modifier::DIM) {
+        css.push_str("opacity:0.6;");
+    }
+    match (
+        modifier.contains(Modifier::UNDERLINED),
+        modifier.contains(Modifier::CROSSED_OUT),
+    ) {
+        (true, true) => css.push_str("text-decoration:underline line-through;"),
+        (true, false) => css.push_str("text-decoration:underline;"),
+        (false, true) => css.push_str("text-decoration:line-through;"),
+        (false, false) => {}
+    }
+    css
+}
+
+/// `buffer` as an SVG image, one rectangle per coloured background and one
+/// text element per run.
+pub fn svg(buffer: &Buffer) -> String {
+    let width = u32::from(buffer.area.width) * CELL_WIDTH;
+    let height = u32::from(buffer.area.height) * CELL_HEIGHT;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(
+        out,
+        "<style>text {{ font-family: {FONT}; font-size: 15px; white-space: pre; }}</style>"
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        hex(BACKGROUND)
+    );
+    for (row, runs) in rows(buffer).into_iter().enumerate() {
+        let top = row as u32 * CELL_HEIGHT;
+        for run in runs {
+            let (x, cells) = (run.x * CELL_WIDTH, run.cells * CELL_WIDTH);
+            if run.bg != BACKGROUND {
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{x}" y="{top}" width="{cells}" height="{CELL_HEIGHT}" fill="{}"/>"#,
+                    hex(run.bg)
+                );
+            }
+            if run.text.trim().is_empty() {
+                continue;
+            }
+            let _ = writeln!(
+                out,
+                r#"<text x="{x}" y="{}" textLength="{cells}" fill="{}" style="{}">{}</text>"#,
+                top + BASELINE,
+                hex(run.fg),
+                decoration(run.modifier),
+                escape(&run.text)
+            );
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// `buffer` as a standalone HTML page: a `<pre>` of styled spans.
+pub fn html(buffer: &Buffer) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<title>Executive Decision Maker</title>\n");
+    let _ = writeln!(
+        out,
+        "<style>body {{ margin: 0; background: {bg}; }} pre {{ margin: 0; padding: 1em; \
+         font-family: {FONT}; font-size: 15px; line-height: 1.2; color: {fg}; \
+         background: {bg}; }}</style>",
+        fg = hex(FOREGROUND),
+        bg = hex(BACKGROUND),
+    );
+    out.push_str("</head>\n<body>\n<pre>");
+    for runs in rows(buffer) {
+        for run in runs {
+            let _ = write!(
+                out,
+                r#"<span style="color:{};background:{};{}">{}</span>"#,
+                hex(run.fg),
+                hex(run.bg),
+                decoration(run.modifier),
+                escape(&run.text)
+            );
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+/// Where a screenshot taken at `now` is saved, in the working directory.
+pub fn file_name(now: DateTime<Local>, html: bool) -> String {
+    let extension = if html { "html" } else { "svg" };
+    format!("edm-{}.{extension}", now.format("%Y%m%d-%H%M%S"))
+}
@@ -0,0 +1,97 @@
+//! Sealed answers: with `daily_seal` on, a question asked on a given day
+//! always lands on the same answer. The index comes from a hash of the pack,
+//! the normalised question, and the date, so re-asking to fish for a better
+//! verdict is futile.
+
+use crate::{answers::AnswerPack, chaos::Rules};
+use chrono::{Local, NaiveDate};
+
+/// A drawn verdict.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Draw {
+    pub index: usize,
+    /// Fixed for the rest of the day rather than random.
+    pub sealed: bool,
+}
+
+/// Pick an answer for `question`: sealed when the rules seal and there is a
+/// question to seal, random otherwise (weighted unless the rules say not).
+pub fn draw(pack: &AnswerPack, question: Option<&str>, rules: Rules) -> Draw {
+    match question.map(normalize) {
+        Some(question) if rules.daily_seal && !question.is_empty() => Draw {
+            index: sealed_index(pack, &question, Local::now().date_naive()),
+            sealed: true,
+        },
+        _ if rules.weighted => Draw {
+            index: pack.draw_index_biased(rules.optimism),
+            sealed: false,
+        },
+        _ => Draw {
+            index: pack.draw_index_unweighted(),
+            sealed: false,
+        },
+    }
+}
+
+/// Case, spacing, and trailing punctuation don't make a different question.
+pub fn normalize(question: &str) -> String {
+    question
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['?', '!', '.'])
+        .trim_end()
+        .to_lowercase()
+}
+
+/// The answer the normalised `question` is sealed to on `date`.
+fn sealed_index(pack: &AnswerPack, question: &str, date: NaiveDate) -> usize {
+    pack.index_for(seed(&pack.id, question, date))
+}
+
+/// FNV-1a, chosen over `DefaultHasher` because it must not change between
+/// Rust releases.
+fn seed(pack: &str, question: &str, date: NaiveDate) -> u64 {
+    let input = format!("{pack}\n{question}\n{}", date.format("%Y-%m-%d"));
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::{builtin, DEFAULT_PACK};
+
+    fn october(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn the_seed_is_fnv_1a() {
+        // Pinned: another hash would change the answers already sealed today.
+        assert_eq!(
+            seed("classic", "ship it on friday", october(16)),
+            0xb2c9_db43_b6a3_c134
+        );
+    }
+
+    #[test]
+    fn same_question_same_day_same_answer() {
+        let pack = builtin(DEFAULT_PACK).unwrap();
+        let asked = sealed_index(&pack, &normalize("Ship it on Friday?"), october(16));
+        let again = sealed_index(&pack, &normalize("  ship it ON friday "), october(16));
+        assert_eq!(asked, again);
+        assert_eq!(asked, 4);
+    }
+
+    #[test]
+    fn another_day_draws_again() {
+        let pack = builtin(DEFAULT_PACK).unwrap();
+        let question = normalize("Ship it on Friday?");
+        let days: Vec<usize> = (14..=17)
+            .map(|day| sealed_index(&pack, &question, october(day)))
+            .collect();
+        assert_eq!(days, [0, 1, 4, 5]);
+    }
+}
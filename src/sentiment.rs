@@ -0,0 +1,63 @@
+//! Sentiment mapping: which answers encourage, which discourage, and which
+//! sit on the fence. Each pack answer carries one; history entries and
+//! summaries are coloured by it.
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sentiment {
+    Positive,
+    Neutral,
+    Negative,
+}
+
+impl Sentiment {
+    pub fn color(self) -> Color {
+        match self {
+            Sentiment::Positive => Color::Green,
+            Sentiment::Neutral => Color::Yellow,
+            Sentiment::Negative => Color::Red,
+        }
+    }
+}
+
+impl fmt::Display for Sentiment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Sentiment::Positive => "positive",
+            Sentiment::Neutral => "neutral",
+            Sentiment::Negative => "negative",
+        })
+    }
+}
+
+/// Per-sentiment counts over a set of decisions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SentimentTally {
+    pub positive: usize,
+    pub neutral: usize,
+    pub negative: usize,
+}
+
+impl SentimentTally {
+    pub fn add(&mut self, sentiment: Sentiment) {
+        match sentiment {
+            Sentiment::Positive => self.positive += 1,
+            Sentiment::Neutral => self.neutral += 1,
+            Sentiment::Negative => self.negative += 1,
+        }
+    }
+}
+
+impl fmt::Display for SentimentTally {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} positive, {} neutral, {} negative",
+            self.positive, self.neutral, self.negative
+        )
+    }
+}
@@ -0,0 +1,410 @@
+//! `edm serve`: the decision engine over HTTP.
+//!
+//! - `POST /ask` makes a decision and returns it. An optional JSON body
+//!   `{"question": "...", "tags": ["deploy"]}` records the question (and
+//!   seals the answer when `daily_seal` is on) and tags.
+//! - `GET /history` lists this server's decisions, newest first.
+//! - `GET /stats` returns today's sentiment and second-opinion counts.
+//! - `GET /` is a read-only HTML page of the same: the journal's latest
+//!   decisions and today's stats, for anyone without a terminal handy.
+//!
+//! The three views take `?tag=deploy` to count only decisions with that tag.
+//! - `GET /events` is a WebSocket streaming every ask as it plays out, in the
+//!   versioned format of [`crate::events`]: `ask_started`, a `light_changed`
+//!   per light while the board flashes, then `answer_final`. A browser page
+//!   or OBS overlay can mirror the board from it.
+
+use crate::{
+    engine::Engine,
+    error::{EdmError, Result},
+};
+use std::net::SocketAddr;
+
+#[cfg(feature = "server")]
+mod routes {
+    use crate::{
+        engine::Engine,
+        events::Event,
+        history::Decision,
+        opinion::SecondOpinion,
+        sentiment::{Sentiment, SentimentTally},
+        tags,
+        widget::{random_index_except, ANIMATION_DURATION_MS, ANIMATION_STEP_MS},
+        workspace::time_ago,
+    };
+    use axum::{
+        extract::{
+            ws::{Message, WebSocket, WebSocketUpgrade},
+            Query, State,
+        },
+        response::{Html, Response},
+        routing::{get, post},
+        Json, Router,
+    };
+    use chrono::Local;
+    use serde::{Deserialize, Serialize};
+    use std::{
+        fmt::Write,
+        sync::{Arc, Mutex, MutexGuard, PoisonError},
+        time::Duration,
+    };
+    use tokio::{
+        sync::broadcast::{self, error::RecvError},
+        time::{sleep, Instant},
+    };
+
+    /// Events buffered per WebSocket client before it starts missing some.
+    const EVENT_BUFFER: usize = 64;
+    /// Decisions listed on the journal page.
+    const JOURNAL_ROWS: usize = 100;
+    /// The journal page; `{{stats}}` and `{{rows}}` are filled in per request.
+    const JOURNAL_TEMPLATE: &str = include_str!("journal.html");
+
+    #[derive(Clone)]
+    struct Shared {
+        engine: Arc<Mutex<Engine>>,
+        /// Serialized [`Event`]s for every connected WebSocket.
+        events: broadcast::Sender<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct AskRequest {
+        question: Option<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+
+    /// `?tag=` on the history, stats, and journal.
+    #[derive(Deserialize)]
+    struct TagQuery {
+        tag: Option<String>,
+    }
+
+    impl TagQuery {
+        fn tag(&self) -> Option<String> {
+            self.tag.as_deref().and_then(tags::normalize)
+        }
+    }
+
+    #[derive(Serialize)]
+    struct DecisionJson {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        question: Option<String>,
+        answer: String,
+        sentiment: Sentiment,
+        pack: String,
+        /// RFC 3339 local time.
+        decided_at: String,
+        /// Only reported on `POST /ask`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sealed: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        second_opinion: Option<OpinionJson>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+        /// Percent; missing for decisions from before the meter.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        confidence: Option<u8>,
+    }
+
+    #[derive(Serialize)]
+    struct OpinionJson {
+        pack: String,
+        answer: String,
+        agrees: bool,
+    }
+
+    #[derive(Serialize)]
+    struct StatsJson {
+        decisions: usize,
+        today: SentimentTally,
+        second_opinions_agreed: usize,
+        second_opinions_asked: usize,
+        /// A chaos week is on: weights and seals are ignored.
+        chaos: bool,
+    }
+
+    impl DecisionJson {
+        fn new(decision: &Decision, opinion: Option<&SecondOpinion>) -> Self {
+            Self {
+                question: decision.question.clone(),
+                answer: decision.answer.clone(),
+                sentiment: decision.sentiment,
+                pack: decision.pack.clone(),
+                decided_at: decision.decided_at.to_rfc3339(),
+                sealed: None,
+                second_opinion: opinion.map(|opinion| OpinionJson {
+                    pack: opinion.pack.clone(),
+                    answer: opinion.answer.text.clone(),
+                    agrees: opinion.agrees,
+                }),
+                note: decision.note.clone(),
+                tags: decision.tags.clone(),
+                confidence: decision.confidence,
+            }
+        }
+    }
+
+    pub fn router(engine: Engine) -> Router {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Router::new()
+            .route("/", get(journal))
+            .route("/ask", post(ask))
+            .route("/history", get(history))
+            .route("/stats", get(stats))
+            .route("/events", get(events_socket))
+            .with_state(Shared {
+                engine: Arc::new(Mutex::new(engine)),
+                events,
+            })
+    }
+
+    impl Shared {
+        /// A panic in one request must not take the whole server down with it.
+        fn lock(&self) -> MutexGuard<'_, Engine> {
+            self.engine.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        fn publish(&self, event: &Event) {
+            // No subscribers is not an error; nobody is watching.
+            let _ = self.events.send(event.to_json());
+        }
+    }
+
+    /// The decision is made (and returned) right away; the WebSocket replays
+    /// it with the same flashing the TUI shows.
+    async fn ask(
+        State(shared): State<Shared>,
+        request: Option<Json<AskRequest>>,
+    ) -> Json<DecisionJson> {
+        let (question, tags) = request.map_or((None, Vec::new()), |Json(request)| {
+            let tags = request
+                .tags
+                .iter()
+                .filter_map(|tag| tags::normalize(tag))
+                .collect();
+            (request.question, tags)
+        });
+        tracing::info!(typed = question.is_some(), "ask over http");
+        let (response, answers, draw, started, finale) = {
+            let mut engine = shared.lock();
+            let (draw, opinion) = engine.decide(question.as_deref(), &tags, Local::now());
+            let decision = engine
+                .history
+                .recent()
+                .next()
+                .expect("decide() always records a decision");
+            let final_event = Event::AnswerFinal {
+                index: draw.index,
+                answer: decision.answer.clone(),
+                sentiment: decision.sentiment,
+                sealed: draw.sealed,
+            };
+            let response = DecisionJson {
+                sealed: Some(draw.sealed),
+                ..DecisionJson::new(decision, opinion.as_ref())
+            };
+            let answers: Vec<String> = engine
+                .pack
+                .answers
+                .iter()
+                .map(|answer| answer.text.clone())
+                .collect();
+            let started = Event::AskStarted {
+                question,
+                pack: engine.pack.id.clone(),
+            };
+            let unlocked = engine
+                .take_unlocked()
+                .into_iter()
+                .map(Event::from)
+                .collect();
+            (
+                response,
+                answers,
+                draw,
+                started,
+                [vec![final_event], unlocked].concat(),
+            )
+        };
+
+        shared.publish(&started);
+        tokio::spawn(animate(shared, answers, draw.index, finale));
+        Json(response)
+    }
+
+    /// Flash through `answers`, then publish `finale`: the verdict and any
+    /// achievements it unlocked.
+    async fn animate(shared: Shared, answers: Vec<String>, final_index: usize, finale: Vec<Event>) {
+        let end_at = Instant::now() + Duration::from_millis(ANIMATION_DURATION_MS);
+        let mut index = random_index_except(answers.len(), final_index);
+        while Instant::now() < end_at {
+            shared.publish(&Event::LightChanged {
+                index,
+                answer: answers[index].clone(),
+            });
+            sleep(Duration::from_millis(ANIMATION_STEP_MS)).await;
+            index = random_index_except(answers.len(), index);
+        }
+        for event in &finale {
+            shared.publish(event);
+        }
+    }
+
+    async fn events_socket(ws: WebSocketUpgrade, State(shared): State<Shared>) -> Response {
+        let events = shared.events.subscribe();
+        ws.on_upgrade(move |socket| stream_events(socket, events))
+    }
+
+    async fn stream_events(mut socket: WebSocket, mut events: broadcast::Receiver<String>) {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    if socket.send(Message::Text(event)).await.is_err() {
+                        break;
+                    }
+                }
+                // A slow client just misses a few ticks.
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    async fn history(
+        State(shared): State<Shared>,
+        Query(query): Query<TagQuery>,
+    ) -> Json<Vec<DecisionJson>> {
+        let engine = shared.lock();
+        let tag = query.tag();
+        let decisions = engine
+            .history
+            .tagged(tag.as_deref())
+            .map(|decision| DecisionJson::new(decision, None))
+            .collect();
+        Json(decisions)
+    }
+
+    async fn journal(State(shared): State<Shared>, Query(query): Query<TagQuery>) -> Html<String> {
+        let engine = shared.lock();
+        let now = Local::now();
+        let tag = query.tag();
+        let tag = tag.as_deref();
+        let (agreed, asked) = engine.history.today_agreement(tag, now);
+        let today = engine.history.today_tally(tag, now);
+
+        let mut stats = String::new();
+        let mut stat = |label: &str, value: String| {
+            let _ = writeln!(stats, "<div><strong>{value}</strong>{label}</div>");
+        };
+        stat("decisions", engine.history.tagged(tag).count().to_string());
+        stat(
+            "today",
+            format!(
+                r#"<span class="positive">{}</span> / <span class="neutral">{}</span> / <span class="negative">{}</span>"#,
+                today.positive, today.neutral, today.negative
+            ),
+        );
+        if asked > 0 {
+            stat("second opinions agreed", format!("{agreed}/{asked}"));
+        }
+        if let Some(tag) = tag {
+            stat(r#"<a href="/">show all</a>"#, format!("#{}", escape(tag)));
+        }
+
+        let mut rows = String::new();
+        for decision in engine.history.tagged(tag).take(JOURNAL_ROWS) {
+            let question = decision
+                .question
+                .as_deref()
+                .map_or_else(|| r#"<span class="muted">—</span>"#.to_string(), escape);
+            let expired = if decision.expires_at.is_some_and(|at| at <= now) {
+                r#" <span class="muted">(expired)</span>"#
+            } else {
+                ""
+            };
+            let note = decision.note.as_deref().map_or_else(String::new, |note| {
+                format!(r#"<div class="note">{}</div>"#, escape(note))
+            });
+            let tag_links: String = decision
+                .tags
+                .iter()
+                .map(|tag| {
+                    let tag = escape(tag);
+                    format!(r#" <a class="tag" href="/?tag={tag}">#{tag}</a>"#)
+                })
+                .collect();
+            let _ = writeln!(
+                rows,
+                r#"<tr><td title="{}">{}</td><td>{question}{tag_links}{expired}{note}</td><td class="{}">{}</td><td>{}</td></tr>"#,
+                decision.decided_at.format("%Y-%m-%d %H:%M"),
+                time_ago(decision.decided_at, now),
+                decision.sentiment,
+                escape(&decision.answer),
+                escape(&decision.pack),
+            );
+        }
+        if rows.is_empty() {
+            rows.push_str(r#"<tr><td colspan="4" class="muted">No decisions yet.</td></tr>"#);
+        }
+
+        Html(
+            JOURNAL_TEMPLATE
+                .replace("{{stats}}", &stats)
+                .replace("{{rows}}", &rows),
+        )
+    }
+
+    /// Questions and pack files are user input; keep them out of the markup.
+    fn escape(text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                '\'' => out.push_str("&#39;"),
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    async fn stats(State(shared): State<Shared>, Query(query): Query<TagQuery>) -> Json<StatsJson> {
+        let engine = shared.lock();
+        let tag = query.tag();
+        let tag = tag.as_deref();
+        let now = Local::now();
+        let (agreed, asked) = engine.history.today_agreement(tag, now);
+        Json(StatsJson {
+            decisions: engine.history.tagged(tag).count(),
+            today: engine.history.today_tally(tag, now),
+            second_opinions_agreed: agreed,
+            second_opinions_asked: asked,
+            chaos: engine.rules().chaos,
+        })
+    }
+}
+
+/// Serve `engine` on `addr` until the process is stopped.
+#[cfg(feature = "server")]
+pub fn serve(engine: Engine, addr: SocketAddr) -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    runtime
+        .block_on(async move {
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            eprintln!("edm listening on http://{}", listener.local_addr()?);
+            axum::serve(listener, routes::router(engine)).await
+        })
+        .map_err(|err| EdmError::Network(format!("serving on {addr} failed: {err}")))
+}
+
+#[cfg(not(feature = "server"))]
+pub fn serve(_engine: Engine, _addr: SocketAddr) -> Result<()> {
+    Err(EdmError::FeatureDisabled("server"))
+}
@@ -0,0 +1,261 @@
+//! Settings screen, opened with `,`, in two tabs switched with Tab.
+//!
+//! Preferences is a [`Form`] of timings, theme, sound, and animation style.
+//! Changes show at once (the theme on the screen itself, the rest on a
+//! demo board that shuffles after every change) and reach the config only
+//! when Enter saves them; Esc puts the theme back.
+//!
+//! Keys lists the key bindings and records new ones: pick an action, press
+//! Enter, then press the chord you want. A chord that already belongs to
+//! another action asks before it is moved. Below the bindings, Enter
+//! switches [`EXPERIMENTS`] on and off. These are saved straight away.
+
+use crate::{
+    config::{Config, Sound, MAX_OPTIMISM, TIMING_MS},
+    experiments::{self, Experiment, EXPERIMENTS},
+    form::{Field, Form, Value},
+    keymap::{Chord, Keymap, BINDABLE},
+    theme::Theme,
+    widget::{DecisionState, Shuffle},
+};
+use std::time::{Duration, Instant};
+
+/// Rows of the preferences form.
+const ANIMATION: usize = 0;
+const FLASH: usize = 1;
+const THEME: usize = 2;
+const SOUND: usize = 3;
+const STYLE: usize = 4;
+const OPTIMISM: usize = 5;
+
+/// Timings move in steps this long.
+const TIMING_STEP_MS: u64 = 250;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tab {
+    Preferences,
+    Keys,
+}
+
+pub struct Settings {
+    pub tab: Tab,
+    /// The preferences being edited.
+    pub form: Form,
+    /// Themes offered by the form, in its order.
+    themes: Vec<Theme>,
+    /// The theme in use when the screen opened or was last saved.
+    pub saved_theme: Theme,
+    /// Shuffles with the edited timings and style after every change.
+    pub demo: DecisionState,
+    /// Answers on the demo board.
+    demo_count: usize,
+    /// Highlighted row: an entry of [`BINDABLE`], then of [`EXPERIMENTS`].
+    pub selected: usize,
+    /// Waiting for the chord to bind to the selected action.
+    pub recording: bool,
+    /// A recorded chord that is already bound elsewhere, awaiting a yes/no.
+    pub conflict: Option<Conflict>,
+}
+
+pub struct Conflict {
+    pub chord: Chord,
+    /// Index into [`BINDABLE`] of the action currently holding the chord.
+    pub owner: usize,
+}
+
+/// What recording a chord led to.
+pub enum Recorded {
+    /// Bound right away.
+    Bound,
+    /// Waiting for the user to confirm taking it from another action.
+    Conflict,
+}
+
+impl Settings {
+    /// Open on the preferences tab, filled in from `config`. `themes` are the
+    /// installed themes and `theme` the one in use; the demo board has
+    /// `count` answers.
+    pub fn new(config: &Config, theme: &Theme, themes: Vec<Theme>, count: usize) -> Self {
+        let timing = |label, millis: Duration| Field {
+            label,
+            value: Value::Number {
+                value: millis.as_millis() as u64,
+                range: TIMING_MS,
+                step: TIMING_STEP_MS,
+                unit: "ms",
+            },
+        };
+        let choice = |label, options: Vec<String>, index| Field {
+            label,
+            value: Value::Choice { options, index },
+        };
+        let form = Form::new(vec![
+            timing("Shuffle length", config.animation()),
+            timing("Flash length", config.flash()),
+            choice(
+                "Theme",
+                themes.iter().map(|theme| theme.name.clone()).collect(),
+                themes
+                    .iter()
+                    .position(|installed| installed.name == theme.name)
+                    .unwrap_or(0),
+            ),
+            choice(
+                "Sound",
+                vec![
+                    "Every light".to_string(),
+                    "Answer only".to_string(),
+                    "Off".to_string(),
+                ],
+                Sound::ALL
+                    .iter()
+                    .position(|&sound| sound == config.sound)
+                    .unwrap_or(0),
+            ),
+            choice(
+                "Animation",
+                vec!["Random".to_string(), "Roulette".to_string()],
+                usize::from(config.experiment(experiments::ROULETTE)),
+            ),
+            choice(
+                "Optimism",
+                (-MAX_OPTIMISM..=MAX_OPTIMISM)
+                    .map(|level| format!("{level:+}"))
+                    .collect(),
+                (config.optimism() + MAX_OPTIMISM) as usize,
+            ),
+        ]);
+        Self {
+            tab: Tab::Preferences,
+            form,
+            themes,
+            saved_theme: theme.clone(),
+            demo: DecisionState::default(),
+            demo_count: count,
+            selected: 0,
+            recording: false,
+            conflict: None,
+        }
+    }
+
+    /// The theme picked in the form.
+    pub fn theme(&self) -> &Theme {
+        let index = self.form.value(THEME).choice().unwrap_or(0);
+        self.themes.get(index).unwrap_or(&self.saved_theme)
+    }
+
+    /// When the picked sound setting rings the bell.
+    pub fn sound(&self) -> Sound {
+        Sound::ALL[self.form.value(SOUND).choice().unwrap_or(0)]
+    }
+
+    fn millis(&self, row: usize) -> u64 {
+        self.form.value(row).number().unwrap_or(0)
+    }
+
+    fn optimism(&self) -> i32 {
+        self.form.value(OPTIMISM).choice().unwrap_or(0) as i32 - MAX_OPTIMISM
+    }
+
+    fn roulette(&self) -> bool {
+        self.form.value(STYLE).choice() == Some(1)
+    }
+
+    /// Shuffle the demo board with the edited timings and style.
+    pub fn restart_demo(&mut self, now: Instant) {
+        self.demo.reset();
+        self.demo.set_shuffle(if self.roulette() {
+            Shuffle::Roulette
+        } else {
+            Shuffle::Random
+        });
+        self.demo.set_timings(
+            Duration::from_millis(self.millis(ANIMATION)),
+            Duration::from_millis(self.millis(FLASH)),
+        );
+        if self.demo_count > 0 {
+            self.demo.ask(self.demo_count, now);
+        }
+    }
+
+    /// Write the edited preferences into `config`. Defaults are left out of
+    /// the file rather than written down.
+    pub fn save(&mut self, config: &mut Config) {
+        let millis = |row, default: Duration| {
+            let millis = self.millis(row);
+            (millis != default.as_millis() as u64).then_some(millis)
+        };
+        config.animation_ms = millis(ANIMATION, Config::default().animation());
+        config.flash_ms = millis(FLASH, Config::default().flash());
+        config.sound = self.sound();
+        config.optimism = Some(self.optimism()).filter(|&level| level != 0);
+        if self.roulette() != config.experiment(experiments::ROULETTE) {
+            config
+                .experimental
+                .insert(experiments::ROULETTE.to_string(), self.roulette());
+        }
+        if self.theme().name != self.saved_theme.name {
+            config.theme = Some(self.theme().name.clone());
+            config.high_contrast = false;
+        }
+        self.saved_theme = self.theme().clone();
+    }
+
+    /// Switch between the preferences and the key bindings.
+    pub fn next_tab(&mut self) {
+        self.recording = false;
+        self.tab = match self.tab {
+            Tab::Preferences => Tab::Keys,
+            Tab::Keys => Tab::Preferences,
+        };
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(BINDABLE.len() + EXPERIMENTS.len() - 1);
+    }
+
+    /// Select the action or experiment at `index`, if there is one.
+    pub fn select(&mut self, index: usize) {
+        if index < BINDABLE.len() + EXPERIMENTS.len() {
+            self.selected = index;
+        }
+    }
+
+    /// The selected action's label, if an action is selected.
+    pub fn label(&self) -> &'static str {
+        BINDABLE.get(self.selected).map_or("", |binding| binding.2)
+    }
+
+    /// The selected experiment, once the cursor is past the bindings.
+    pub fn experiment(&self) -> Option<&'static Experiment> {
+        self.selected
+            .checked_sub(BINDABLE.len())
+            .and_then(|row| EXPERIMENTS.get(row))
+    }
+
+    /// Handle the chord pressed while recording.
+    pub fn record(&mut self, chord: Chord, keymap: &mut Keymap) -> Recorded {
+        self.recording = false;
+        let owner = (0..BINDABLE.len()).find(|&slot| keymap.chords(slot).contains(&chord));
+        match owner {
+            Some(owner) if owner != self.selected => {
+                self.conflict = Some(Conflict { chord, owner });
+                Recorded::Conflict
+            }
+            _ => {
+                keymap.bind(self.selected, chord);
+                Recorded::Bound
+            }
+        }
+    }
+
+    /// Take the conflicting chord for the selected action.
+    pub fn confirm(&mut self, keymap: &mut Keymap) -> Option<Chord> {
+        let conflict = self.conflict.take()?;
+        keymap.bind(self.selected, conflict.chord);
+        Some(conflict.chord)
+    }
+}
@@ -0,0 +1,432 @@
+//! `edm ssh`: host the oracle over SSH so coworkers can `ssh -p 2222
+//! decisions.local` and each get their own session.
+//!
+//! Every shell request starts a fresh [`App`] on its own thread, driven by the
+//! usual engine loop against an [`SshFrontend`]: ratatui renders into the
+//! SSH channel, and bytes typed by the client are decoded back into keys.
+//! Any user name and password are accepted; this is an office toy, not a
+//! bastion host. Clients that offer no credentials at all are turned away
+//! unless the server allows anonymous logins.
+
+use crate::{
+    app::App,
+    error::{EdmError, Result},
+};
+use std::{net::SocketAddr, sync::Arc};
+
+/// Builds the [`App`] for each new session; an error refuses the session.
+pub type AppFactory = dyn Fn() -> Result<App> + Send + Sync;
+
+#[cfg(feature = "ssh")]
+mod session {
+    use super::AppFactory;
+    use crate::{
+        app::App,
+        frontend::{self, Frontend},
+        input::{self, Action, Mode},
+        keymap::Keymap,
+        ui,
+    };
+    use crossterm::{
+        cursor::{Hide, Show},
+        event::{KeyCode, KeyEvent, KeyModifiers},
+        terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+        QueueableCommand,
+    };
+    use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal, TerminalOptions, Viewport};
+    use russh::{
+        server::{self, Auth, Handle, Msg, Session},
+        Channel, ChannelId, CryptoVec, Pty,
+    };
+    use std::{
+        collections::VecDeque,
+        io::{self, Write},
+        net::SocketAddr,
+        sync::{
+            mpsc::{self, Receiver, RecvTimeoutError, Sender},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    /// Sent from the SSH connection to its session thread.
+    enum Input {
+        Bytes(Vec<u8>),
+        Resize(u16, u16),
+    }
+
+    pub struct Server {
+        pub make_app: Arc<AppFactory>,
+        pub allow_anonymous: bool,
+    }
+
+    impl server::Server for Server {
+        type Handler = Connection;
+
+        fn new_client(&mut self, peer: Option<SocketAddr>) -> Connection {
+            tracing::info!(?peer, "ssh client connected");
+            Connection {
+                make_app: Arc::clone(&self.make_app),
+                allow_anonymous: self.allow_anonymous,
+                size: (80, 24),
+                input: None,
+            }
+        }
+    }
+
+    pub struct Connection {
+        make_app: Arc<AppFactory>,
+        /// Whether `auth_none` lets the client in.
+        allow_anonymous: bool,
+        /// Terminal size from the pty request, used when the shell starts.
+        size: (u16, u16),
+        /// Feeds the running session, once there is one.
+        input: Option<Sender<Input>>,
+    }
+
+    impl Connection {
+        fn send(&self, input: Input) {
+            if let Some(sender) = &self.input {
+                // The session thread may already have quit; nothing to do.
+                let _ = sender.send(input);
+            }
+        }
+    }
+
+    impl server::Handler for Connection {
+        type Error = russh::Error;
+
+        async fn auth_none(&mut self, user: &str) -> Result<Auth, Self::Error> {
+            if self.allow_anonymous {
+                return Ok(Auth::Accept);
+            }
+            tracing::info!(user, "ssh client offered no credentials");
+            Ok(Auth::Reject {
+                proceed_with_methods: None,
+            })
+        }
+
+        async fn auth_password(
+            &mut self,
+            _user: &str,
+            _password: &str,
+        ) -> Result<Auth, Self::Error> {
+            Ok(Auth::Accept)
+        }
+
+        async fn auth_publickey(
+            &mut self,
+            _user: &str,
+            _key: &russh_keys::key::PublicKey,
+        ) -> Result<Auth, Self::Error> {
+            Ok(Auth::Accept)
+        }
+
+        async fn channel_open_session(
+            &mut self,
+            _channel: Channel<Msg>,
+            _session: &mut Session,
+        ) -> Result<bool, Self::Error> {
+            Ok(true)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        async fn pty_request(
+            &mut self,
+            channel: ChannelId,
+            _term: &str,
+            col_width: u32,
+            row_height: u32,
+            _pix_width: u32,
+            _pix_height: u32,
+            _modes: &[(Pty, u32)],
+            session: &mut Session,
+        ) -> Result<(), Self::Error> {
+            self.size = (clamp(col_width), clamp(row_height));
+            session.channel_success(channel);
+            Ok(())
+        }
+
+        async fn shell_request(
+            &mut self,
+            channel: ChannelId,
+            session: &mut Session,
+        ) -> Result<(), Self::Error> {
+            let (sender, receiver) = mpsc::channel();
+            self.input = Some(sender);
+            session.channel_success(channel);
+
+            let writer = ChannelWriter {
+                handle: session.handle(),
+                channel,
+                runtime: tokio::runtime::Handle::current(),
+                buffer: Vec::new(),
+            };
+            let make_app = Arc::clone(&self.make_app);
+            let size = self.size;
+            thread::spawn(move || run_session(&*make_app, writer, size, receiver));
+            Ok(())
+        }
+
+        async fn data(
+            &mut self,
+            _channel: ChannelId,
+            data: &[u8],
+            _session: &mut Session,
+        ) -> Result<(), Self::Error> {
+            self.send(Input::Bytes(data.to_vec()));
+            Ok(())
+        }
+
+        async fn window_change_request(
+            &mut self,
+            _channel: ChannelId,
+            col_width: u32,
+            row_height: u32,
+            _pix_width: u32,
+            _pix_height: u32,
+            _session: &mut Session,
+        ) -> Result<(), Self::Error> {
+            self.send(Input::Resize(clamp(col_width), clamp(row_height)));
+            Ok(())
+        }
+    }
+
+    fn clamp(cells: u32) -> u16 {
+        cells.clamp(1, u32::from(u16::MAX)) as u16
+    }
+
+    fn run_session(
+        make_app: &AppFactory,
+        writer: ChannelWriter,
+        (width, height): (u16, u16),
+        input: Receiver<Input>,
+    ) {
+        let handle = writer.handle.clone();
+        let channel = writer.channel;
+        let runtime = writer.runtime.clone();
+
+        match make_app() {
+            Ok(mut app) => {
+                let result = SshFrontend::new(writer, width, height, input)
+                    .and_then(|mut frontend| frontend::run(&mut app, &mut frontend));
+                if let Err(err) = result {
+                    tracing::warn!(%err, "ssh session ended with an error");
+                    eprintln!("edm: ssh session ended with an error: {err}");
+                }
+            }
+            Err(err) => {
+                tracing::warn!(%err, "ssh session refused");
+                eprintln!("edm: ssh session refused: {err}");
+                let mut writer = writer;
+                let _ = write!(writer, "edm: {err}\r\n");
+                let _ = writer.flush();
+            }
+        }
+        runtime.block_on(async {
+            let _ = handle.close(channel).await;
+        });
+    }
+
+    /// Buffers terminal output and ships it over the channel on flush. Runs
+    /// on the session thread, so it blocks on the async handle.
+    struct ChannelWriter {
+        handle: Handle,
+        channel: ChannelId,
+        runtime: tokio::runtime::Handle,
+        buffer: Vec<u8>,
+    }
+
+    impl Write for ChannelWriter {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.buffer.extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            if self.buffer.is_empty() {
+                return Ok(());
+            }
+            let data = CryptoVec::from(std::mem::take(&mut self.buffer));
+            self.runtime
+                .block_on(self.handle.data(self.channel, data))
+                .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "ssh client went away"))
+        }
+    }
+
+    struct SshFrontend {
+        terminal: Terminal<CrosstermBackend<ChannelWriter>>,
+        input: Receiver<Input>,
+        /// Decoded keys not handed to the app yet.
+        pending: VecDeque<KeyEvent>,
+        /// Same as in the local front end.
+        mode: Mode,
+        keymap: Keymap,
+    }
+
+    impl SshFrontend {
+        fn new(
+            writer: ChannelWriter,
+            width: u16,
+            height: u16,
+            input: Receiver<Input>,
+        ) -> io::Result<Self> {
+            // The backend would ask the server's own tty for its size, so the
+            // viewport is pinned to the client's and updated on resize.
+            let terminal = Terminal::with_options(
+                CrosstermBackend::new(writer),
+                TerminalOptions {
+                    viewport: Viewport::Fixed(Rect::new(0, 0, width, height)),
+                },
+            )?;
+            Ok(Self {
+                terminal,
+                input,
+                pending: VecDeque::new(),
+                mode: Mode::Commands,
+                keymap: Keymap::default(),
+            })
+        }
+
+        fn next_action(&mut self) -> Option<Action> {
+            while let Some(key) = self.pending.pop_front() {
+                let action = input::map(self.mode, &self.keymap, key);
+                if action.is_some() {
+                    return action;
+                }
+            }
+            None
+        }
+    }
+
+    impl Frontend for SshFrontend {
+        fn init(&mut self) -> io::Result<()> {
+            let backend = self.terminal.backend_mut();
+            backend.queue(EnterAlternateScreen)?;
+            backend.queue(Hide)?;
+            backend.queue(Clear(ClearType::All))?;
+            backend.flush()
+        }
+
+        fn render(&mut self, app: &App) -> io::Result<()> {
+            self.mode = app.input_mode();
+            if self.keymap != app.keymap {
+                self.keymap = app.keymap.clone();
+            }
+            self.terminal.draw(|f| {
+                ui::draw(f, app);
+            })?;
+            Ok(())
+        }
+
+        fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+            let backend = self.terminal.backend_mut();
+            backend.write_all(bytes)?;
+            backend.flush()
+        }
+
+        fn handle_input(&mut self, timeout: Option<Duration>) -> io::Result<Option<Action>> {
+            if let Some(action) = self.next_action() {
+                return Ok(Some(action));
+            }
+            let received = match timeout {
+                Some(timeout) => self.input.recv_timeout(timeout),
+                None => self
+                    .input
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(Input::Bytes(bytes)) => {
+                    self.pending.extend(decode_keys(&bytes));
+                    Ok(self.next_action())
+                }
+                Ok(Input::Resize(width, height)) => {
+                    self.terminal.resize(Rect::new(0, 0, width, height))?;
+                    Ok(Some(Action::Resize))
+                }
+                Err(RecvTimeoutError::Timeout) => Ok(None),
+                // The connection is gone.
+                Err(RecvTimeoutError::Disconnected) => Ok(Some(Action::Quit)),
+            }
+        }
+
+        fn queued_input(&self) -> Option<usize> {
+            Some(self.pending.len())
+        }
+
+        fn shutdown(&mut self) -> io::Result<()> {
+            let backend = self.terminal.backend_mut();
+            backend.queue(Show)?;
+            backend.queue(LeaveAlternateScreen)?;
+            backend.flush()
+        }
+    }
+
+    /// Turn raw client bytes into key events: printable UTF-8, control
+    /// characters, and the common arrow-key escape sequences.
+    fn decode_keys(bytes: &[u8]) -> Vec<KeyEvent> {
+        let text = String::from_utf8_lossy(bytes);
+        let mut chars = text.chars().peekable();
+        let mut keys = Vec::new();
+        while let Some(c) = chars.next() {
+            let key = match c {
+                '\x1b' if chars.peek() == Some(&'[') => {
+                    chars.next();
+                    match chars.next() {
+                        Some('A') => KeyEvent::from(KeyCode::Up),
+                        Some('B') => KeyEvent::from(KeyCode::Down),
+                        Some('C') => KeyEvent::from(KeyCode::Right),
+                        Some('D') => KeyEvent::from(KeyCode::Left),
+                        _ => continue,
+                    }
+                }
+                '\x1b' => KeyEvent::from(KeyCode::Esc),
+                '\r' | '\n' => KeyEvent::from(KeyCode::Enter),
+                '\t' => KeyEvent::from(KeyCode::Tab),
+                '\x7f' => KeyEvent::from(KeyCode::Backspace),
+                '\x01'..='\x1a' => KeyEvent::new(
+                    KeyCode::Char((b'a' + c as u8 - 1) as char),
+                    KeyModifiers::CONTROL,
+                ),
+                c => KeyEvent::from(KeyCode::Char(c)),
+            };
+            keys.push(key);
+        }
+        keys
+    }
+}
+
+/// Listen on `addr` and run one oracle per SSH session until stopped.
+/// Clients without credentials get in only with `allow_anonymous`.
+#[cfg(feature = "ssh")]
+pub fn serve(addr: SocketAddr, allow_anonymous: bool, make_app: Arc<AppFactory>) -> Result<()> {
+    use russh::server::{Config, Server as _};
+    use std::time::Duration;
+
+    let config = Config {
+        // A fresh host key each start; clients will warn after a restart.
+        keys: vec![russh_keys::key::KeyPair::generate_ed25519()],
+        auth_rejection_time: Duration::from_secs(1),
+        ..Config::default()
+    };
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    eprintln!("edm listening for ssh on {addr}");
+    runtime
+        .block_on(
+            session::Server {
+                make_app,
+                allow_anonymous,
+            }
+            .run_on_address(Arc::new(config), addr),
+        )
+        .map_err(|err| EdmError::Network(format!("serving ssh on {addr} failed: {err}")))
+}
+
+#[cfg(not(feature = "ssh"))]
+pub fn serve(_addr: SocketAddr, _allow_anonymous: bool, _make_app: Arc<AppFactory>) -> Result<()> {
+    Err(EdmError::FeatureDisabled("ssh"))
+}
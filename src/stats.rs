@@ -0,0 +1,109 @@
+//! The numbers `edm stats` prints: decisions by sentiment and by answer, the
+//! busiest day, and the streaks still running.
+
+use crate::{
+    history::Decision,
+    sentiment::{Sentiment, SentimentTally},
+};
+use chrono::{Duration, NaiveDate};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashSet},
+    fmt,
+};
+
+/// Answers the summary lists.
+const TOP_ANSWERS: usize = 5;
+
+pub struct Summary {
+    pub decisions: usize,
+    pub tally: SentimentTally,
+    /// Of those made today.
+    pub today: SentimentTally,
+    /// Most common first, ties in alphabetical order.
+    pub answers: Vec<(String, usize)>,
+    /// The day with the most decisions, the latest of equals.
+    pub busiest_day: Option<(NaiveDate, usize)>,
+    /// Days in a row with a decision, up to today or, if nothing has been
+    /// decided yet today, yesterday.
+    pub day_streak: usize,
+    /// The latest decisions in a row that share a sentiment.
+    pub sentiment_streak: Option<(Sentiment, usize)>,
+}
+
+impl Summary {
+    /// Sum up `decisions`, oldest first.
+    pub fn new(decisions: &[&Decision], today: NaiveDate) -> Self {
+        let mut tally = SentimentTally::default();
+        let mut today_tally = SentimentTally::default();
+        let mut answers: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut days: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        for decision in decisions {
+            let day = decision.decided_at.date_naive();
+            tally.add(decision.sentiment);
+            if day == today {
+                today_tally.add(decision.sentiment);
+            }
+            *answers.entry(&decision.answer).or_default() += 1;
+            *days.entry(day).or_default() += 1;
+        }
+        let mut answers: Vec<(String, usize)> = answers
+            .into_iter()
+            .map(|(answer, count)| (answer.to_string(), count))
+            .collect();
+        answers.sort_by_key(|&(_, count)| Reverse(count));
+
+        let busiest_day = days
+            .iter()
+            .max_by_key(|&(day, count)| (count, day))
+            .map(|(&day, &count)| (day, count));
+
+        let active: HashSet<NaiveDate> = days.into_keys().collect();
+        let mut day = today;
+        if !active.contains(&day) {
+            day -= Duration::days(1);
+        }
+        let mut day_streak = 0;
+        while active.contains(&day) {
+            day_streak += 1;
+            day -= Duration::days(1);
+        }
+
+        let sentiment_streak = decisions.last().map(|last| {
+            let run = decisions
+                .iter()
+                .rev()
+                .take_while(|decision| decision.sentiment == last.sentiment)
+                .count();
+            (last.sentiment, run)
+        });
+
+        Self {
+            decisions: decisions.len(),
+            tally,
+            today: today_tally,
+            answers,
+            busiest_day,
+            day_streak,
+            sentiment_streak,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} decision(s): {}", self.decisions, self.tally)?;
+        writeln!(f, "Today: {}", self.today)?;
+        if let Some((day, count)) = self.busiest_day {
+            writeln!(f, "Busiest day: {} with {count}", day.format("%a %Y-%m-%d"))?;
+        }
+        writeln!(f, "Daily streak: {} day(s)", self.day_streak)?;
+        if let Some((sentiment, run)) = self.sentiment_streak {
+            writeln!(f, "Sentiment streak: {run} {sentiment} in a row")?;
+        }
+        for (answer, count) in self.answers.iter().take(TOP_ANSWERS) {
+            writeln!(f, "{count:>6}  {answer}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+//! Easter eggs for notable runs: three ASK AGAINs in a row, five yes-ish
+//! answers straight. After each reveal the [`RULES`] are checked against
+//! the latest decisions, and the first run that has just reached its length
+//! gets its message, celebrated by the TUI with a sparkling box.
+
+use crate::{history::Decision, sentiment::Sentiment};
+
+/// What a run is made of.
+pub enum Run {
+    /// This answer, ignoring case.
+    Answer(&'static str),
+    /// The same answer, whichever it is.
+    SameAnswer,
+    /// Answers with this sentiment.
+    Sentiment(Sentiment),
+}
+
+pub struct Rule {
+    pub run: Run,
+    /// The run fires when it reaches exactly this many, so once per run.
+    pub length: usize,
+    /// `{answer}` is the latest answer.
+    pub message: &'static str,
+}
+
+/// Checked in order; the first match wins.
+pub const RULES: &[Rule] = &[
+    Rule {
+        run: Run::Answer("ASK AGAIN"),
+        length: 3,
+        message: "ASK AGAIN ×3. The oracle needs a coffee break.",
+    },
+    Rule {
+        run: Run::Sentiment(Sentiment::Positive),
+        length: 5,
+        message: "Five yeses straight! The universe is nodding along.",
+    },
+    Rule {
+        run: Run::Sentiment(Sentiment::Negative),
+        length: 5,
+        message: "Five noes in a row. Maybe take the hint.",
+    },
+    Rule {
+        run: Run::Sentiment(Sentiment::Neutral),
+        length: 5,
+        message: "Five shrugs running. Even the oracle can't decide.",
+    },
+    Rule {
+        run: Run::SameAnswer,
+        length: 4,
+        message: "{answer}, four times running. It really means it.",
+    },
+];
+
+impl Rule {
+    /// How many of `recent`, newest first, belong to this rule's run.
+    fn run_length(&self, recent: &[&Decision]) -> usize {
+        let Some(latest) = recent.first() else {
+            return 0;
+        };
+        recent
+            .iter()
+            .take_while(|decision| match self.run {
+                Run::Answer(answer) => decision.answer.eq_ignore_ascii_case(answer),
+                Run::SameAnswer => decision.answer == latest.answer,
+                Run::Sentiment(sentiment) => decision.sentiment == sentiment,
+            })
+            .count()
+    }
+}
+
+/// The message for a run the newest of `recent` (newest first) has just
+/// completed, if any.
+pub fn check<'a>(recent: impl Iterator<Item = &'a Decision>) -> Option<String> {
+    let longest = RULES.iter().map(|rule| rule.length).max().unwrap_or(0);
+    let recent: Vec<&Decision> = recent.take(longest + 1).collect();
+    let latest = recent.first()?;
+    RULES
+        .iter()
+        .find(|rule| rule.run_length(&recent) == rule.length)
+        .map(|rule| rule.message.replace("{answer}", &latest.answer))
+}
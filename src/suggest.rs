@@ -0,0 +1,78 @@
+//! Question autocomplete: earlier questions that fuzzily match what is being
+//! typed, each with the answer it got last time.
+
+use crate::{history::History, seal, tags};
+use chrono::{DateTime, Local};
+use std::{cmp::Reverse, collections::HashSet};
+
+/// Most suggestions offered at once.
+pub const MAX_SUGGESTIONS: usize = 5;
+/// Fewest characters typed before anything is suggested.
+const MIN_TYPED: usize = 2;
+
+/// An earlier question and how it was last answered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub question: String,
+    pub answer: String,
+    pub decided_at: DateTime<Local>,
+}
+
+/// Earlier questions matching `typed`, best first. Each question is offered
+/// once, with its latest answer; one typed exactly already is left out.
+pub fn suggestions(history: &History, typed: &str) -> Vec<Suggestion> {
+    let typed = seal::normalize(&tags::split(typed).0);
+    if typed.chars().count() < MIN_TYPED {
+        return Vec::new();
+    }
+    let mut seen = HashSet::new();
+    let mut scored: Vec<(i32, Suggestion)> = history
+        .recent()
+        .filter_map(|decision| {
+            let question = decision.question.as_deref()?;
+            let normal = seal::normalize(question);
+            if normal == typed || !seen.insert(normal.clone()) {
+                return None;
+            }
+            let score = score(&typed, &normal)?;
+            Some((
+                score,
+                Suggestion {
+                    question: question.to_string(),
+                    answer: decision.answer.clone(),
+                    decided_at: decision.decided_at,
+                },
+            ))
+        })
+        .collect();
+    // Stable, so equal scores stay newest first.
+    scored.sort_by_key(|(score, _)| Reverse(*score));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, suggestion)| suggestion)
+        .collect()
+}
+
+/// How well `candidate` matches `query` when `query`'s characters appear in
+/// it in order: runs of adjacent characters and word starts count extra,
+/// long candidates a little less. `None` when they do not all appear.
+pub fn score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut last: Option<usize> = None;
+    for wanted in query.chars() {
+        let found = (next..candidate.len()).find(|&i| candidate[i] == wanted)?;
+        score += 1;
+        if last.is_some_and(|last| last + 1 == found) {
+            score += 3;
+        }
+        if found == 0 || candidate[found - 1] == ' ' {
+            score += 2;
+        }
+        last = Some(found);
+        next = found + 1;
+    }
+    Some(score * 10 - candidate.len() as i32)
+}
@@ -0,0 +1,61 @@
+//! Decision tags such as `#deploy` or `#personal`. Tags typed into a
+//! question are taken out of it when asking; history and stats can be
+//! narrowed down to one tag.
+
+/// Whether `c` may appear in a tag.
+fn tag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+/// `word` as a tag: without its `#`, in lower case. `None` if that leaves
+/// nothing or something that is not a tag.
+pub fn normalize(word: &str) -> Option<String> {
+    let tag = word.strip_prefix('#').unwrap_or(word).to_lowercase();
+    (!tag.is_empty() && tag.chars().all(tag_char)).then_some(tag)
+}
+
+/// Split the `#tags` off `question`: the question without them, and the
+/// tags in the order typed, each once.
+pub fn split(question: &str) -> (String, Vec<String>) {
+    let mut words = Vec::new();
+    let mut tags = Vec::new();
+    for word in question.split_whitespace() {
+        match word.starts_with('#').then(|| normalize(word)).flatten() {
+            Some(tag) => add(&mut tags, tag),
+            None => words.push(word),
+        }
+    }
+    (words.join(" "), tags)
+}
+
+/// Tags from a list typed by hand, `#` optional: `"#deploy personal"`.
+/// Returns the words that are not tags as the error.
+pub fn parse_list(text: &str) -> Result<Vec<String>, String> {
+    let mut tags = Vec::new();
+    let mut bad = Vec::new();
+    for word in text.split([' ', ',']).filter(|word| !word.is_empty()) {
+        match normalize(word) {
+            Some(tag) => add(&mut tags, tag),
+            None => bad.push(word),
+        }
+    }
+    if bad.is_empty() {
+        Ok(tags)
+    } else {
+        Err(bad.join(" "))
+    }
+}
+
+/// `tags` as typed: `#deploy #personal`.
+pub fn format(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| format!("#{tag}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn add(tags: &mut Vec<String>, tag: String) {
+    if !tags.contains(&tag) {
+        tags.push(tag);
+    }
+}
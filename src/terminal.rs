@@ -0,0 +1,161 @@
+//! Terminal setup and teardown.
+//!
+//! [`TerminalGuard`] owns the raw-mode/alternate-screen session: creating it
+//! sets the terminal up, dropping it puts everything back. That covers early
+//! returns, `?` errors and panics alike, so no exit path can forget cleanup.
+
+use crate::cast::{Output, Recorder};
+use crossterm::{
+    cursor::Show,
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture,
+    },
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    ExecutableCommand,
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::{
+    io,
+    ops::{Deref, DerefMut},
+    panic,
+    sync::Once,
+};
+
+pub type AppTerminal = Terminal<CrosstermBackend<Output>>;
+
+pub struct TerminalGuard {
+    terminal: AppTerminal,
+    active: bool,
+    /// Mouse events are reported too.
+    mouse: bool,
+}
+
+impl TerminalGuard {
+    /// Enter raw mode and the alternate screen, capturing the mouse if
+    /// `mouse` is set and copying everything drawn into `recorder` if there
+    /// is one. If any step fails, the steps already taken are undone before
+    /// the error is returned.
+    pub fn new(mouse: bool, recorder: Option<Recorder>) -> io::Result<Self> {
+        install_panic_hook();
+        enable_raw_mode()?;
+
+        let setup = move || -> io::Result<AppTerminal> {
+            enter_screen(mouse)?;
+            let mut terminal = Terminal::new(CrosstermBackend::new(Output::new(recorder)))?;
+            terminal.hide_cursor()?;
+            terminal.clear()?;
+            Ok(terminal)
+        };
+        match setup() {
+            Ok(terminal) => Ok(Self {
+                terminal,
+                active: true,
+                mouse,
+            }),
+            Err(err) => {
+                let _ = restore_terminal();
+                Err(err)
+            }
+        }
+    }
+
+    /// Ctrl+Z: put the terminal back the way the shell expects it, stop the
+    /// process group like a SIGTSTP from the shell would, and set everything
+    /// up again once the job is continued (`fg`). The screen is cleared so
+    /// the next draw repaints it in full.
+    #[cfg(unix)]
+    pub fn suspend(&mut self) -> io::Result<()> {
+        restore_terminal()?;
+        // SAFETY: kill() has no memory-safety preconditions. Signalling our
+        // own process group stops us before it returns; it returns once we
+        // receive SIGCONT.
+        if unsafe { libc::kill(0, libc::SIGTSTP) } != 0 {
+            let err = io::Error::last_os_error();
+            let _ = enable_raw_mode().and_then(|()| enter_screen(self.mouse));
+            return Err(err);
+        }
+        enable_raw_mode()?;
+        enter_screen(self.mouse)?;
+        self.terminal.hide_cursor()?;
+        self.terminal.clear()
+    }
+
+    /// Job control is a Unix thing; elsewhere Ctrl+Z does nothing.
+    #[cfg(not(unix))]
+    pub fn suspend(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Restore the terminal now, reporting any failure. Dropping the guard
+    /// does the same but has to swallow errors.
+    pub fn restore(mut self) -> io::Result<()> {
+        self.active = false;
+        restore_terminal()
+    }
+}
+
+impl Deref for TerminalGuard {
+    type Target = AppTerminal;
+
+    fn deref(&self) -> &AppTerminal {
+        &self.terminal
+    }
+}
+
+impl DerefMut for TerminalGuard {
+    fn deref_mut(&mut self) -> &mut AppTerminal {
+        &mut self.terminal
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if self.active {
+            let _ = restore_terminal();
+        }
+    }
+}
+
+/// Switch to the alternate screen and ask for focus reports, pastes in one
+/// piece, and mouse reports if `mouse` is set. Raw mode is the caller's
+/// business.
+fn enter_screen(mouse: bool) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.execute(EnterAlternateScreen)?;
+    stdout.execute(EnableFocusChange)?;
+    stdout.execute(EnableBracketedPaste)?;
+    if mouse {
+        stdout.execute(EnableMouseCapture)?;
+    }
+    Ok(())
+}
+
+/// Undo everything [`TerminalGuard::new`] did. Every step is attempted even
+/// if an earlier one fails; the first error is returned.
+fn restore_terminal() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    let steps = [
+        stdout.execute(Show).map(drop),
+        stdout.execute(DisableFocusChange).map(drop),
+        stdout.execute(DisableBracketedPaste).map(drop),
+        // Harmless when it was never enabled, and the panic hook can't tell.
+        stdout.execute(DisableMouseCapture).map(drop),
+        stdout.execute(LeaveAlternateScreen).map(drop),
+        disable_raw_mode(),
+    ];
+    steps.into_iter().collect()
+}
+
+/// Restore the terminal before the default hook prints a panic message, so
+/// the message lands on the normal screen instead of the discarded one.
+fn install_panic_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            previous(info);
+        }));
+    });
+}
@@ -0,0 +1,105 @@
+//! A small multi-line text editor: the text plus a cursor that moves by
+//! character and line. Drawn by [`crate::ui`].
+
+/// Most characters a text area holds.
+const MAX_CHARS: usize = 2_000;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextArea {
+    /// Never empty: an empty text is one empty line.
+    pub lines: Vec<String>,
+    /// Cursor line.
+    pub row: usize,
+    /// Cursor position in the line, in characters.
+    pub col: usize,
+}
+
+impl TextArea {
+    /// Start on `text` with the cursor at its end.
+    pub fn new(text: &str) -> Self {
+        let lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+        let row = lines.len() - 1;
+        let col = lines[row].chars().count();
+        Self { lines, row, col }
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    fn len(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| line.chars().count())
+            .sum::<usize>()
+            + self.lines.len()
+            - 1
+    }
+
+    /// Byte offset of the cursor in its line.
+    fn offset(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(self.col)
+            .map_or(line.len(), |(offset, _)| offset)
+    }
+
+    /// Insert `c` at the cursor; `\n` starts a new line.
+    pub fn insert(&mut self, c: char) {
+        if self.len() >= MAX_CHARS {
+            return;
+        }
+        let offset = self.offset();
+        if c == '\n' {
+            let rest = self.lines[self.row].split_off(offset);
+            self.row += 1;
+            self.col = 0;
+            self.lines.insert(self.row, rest);
+        } else {
+            self.lines[self.row].insert(offset, c);
+            self.col += 1;
+        }
+    }
+
+    /// Delete the character before the cursor, joining lines at the start
+    /// of one.
+    pub fn erase(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let offset = self.offset();
+            self.lines[self.row].remove(offset);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.lines[self.row].chars().count();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    /// Move left (`-1`) or right (`1`), wrapping onto the neighbouring line.
+    pub fn move_by(&mut self, delta: isize) {
+        let width = self.lines[self.row].chars().count();
+        if delta < 0 {
+            if self.col > 0 {
+                self.col -= 1;
+            } else if self.row > 0 {
+                self.row -= 1;
+                self.col = self.lines[self.row].chars().count();
+            }
+        } else if self.col < width {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    /// Move up (`-1`) or down (`1`) a line, keeping the column where it fits.
+    pub fn move_line(&mut self, delta: isize) {
+        self.row = self
+            .row
+            .saturating_add_signed(delta)
+            .min(self.lines.len() - 1);
+        self.col = self.col.min(self.lines[self.row].chars().count());
+    }
+}
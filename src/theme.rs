@@ -0,0 +1,335 @@
+//! Colour themes: a few built in, plus user themes loaded from TOML files in
+//! the `themes/` directory next to the config file.
+//!
+//! ```toml
+//! name = "Sunset"
+//! title = "#ffb000"
+//! active_bg = "lightred"
+//! ```
+//!
+//! Colours use ratatui's names (`lightgreen`, `darkgray`, ...), `#rrggbb`, or
+//! a 256-colour index. `border` is `plain`, `rounded`, `double` or `thick`,
+//! and `bold = true` draws all text in bold. Fields left out fall back to the
+//! Classic theme. `[answers."NEVER"]` tables style single answers' buttons
+//! with `fg`, `bg`, `active_fg` and `active_bg`.
+
+use crate::answers::Answer;
+use ratatui::{style::Color, widgets::BorderType};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path, str::FromStr};
+
+/// The theme used when nothing else is configured.
+pub const DEFAULT_THEME: &str = "Classic";
+/// Pure black and white, bold, with thick borders. Toggled with F2.
+pub const HIGH_CONTRAST_THEME: &str = "High Contrast";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    /// Headings and the help overlay text.
+    pub title: Color,
+    pub idle_fg: Color,
+    pub idle_bg: Color,
+    pub active_fg: Color,
+    pub active_bg: Color,
+    /// The status bar and hints.
+    pub status: Color,
+    /// Line style of every box and button.
+    pub border: BorderType,
+    /// Draw all text in bold.
+    pub bold: bool,
+    /// Per answer text, replacing the colours above on that answer's button.
+    pub answers: BTreeMap<String, AnswerStyle>,
+}
+
+/// Colours of one answer's button. Those left out come from the pack, then
+/// the theme.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AnswerStyle {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub active_fg: Option<Color>,
+    pub active_bg: Option<Color>,
+}
+
+impl AnswerStyle {
+    /// `self`, with the colours it leaves out taken from `fallback`.
+    pub fn or(self, fallback: AnswerStyle) -> Self {
+        Self {
+            fg: self.fg.or(fallback.fg),
+            bg: self.bg.or(fallback.bg),
+            active_fg: self.active_fg.or(fallback.active_fg),
+            active_bg: self.active_bg.or(fallback.active_bg),
+        }
+    }
+}
+
+/// An [`AnswerStyle`] as written in the config or a theme file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StyleEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_bg: Option<String>,
+}
+
+impl StyleEntry {
+    pub fn parse(&self) -> Result<AnswerStyle, String> {
+        let color = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|value| {
+                    Color::from_str(value).map_err(|_| format!("unknown colour `{value}`"))
+                })
+                .transpose()
+        };
+        Ok(AnswerStyle {
+            fg: color(&self.fg)?,
+            bg: color(&self.bg)?,
+            active_fg: color(&self.active_fg)?,
+            active_bg: color(&self.active_bg)?,
+        })
+    }
+}
+
+/// Parse answer styles by answer text, returning the good ones and a
+/// description of each bad one.
+pub fn parse_styles(
+    entries: &BTreeMap<String, StyleEntry>,
+) -> (BTreeMap<String, AnswerStyle>, Vec<String>) {
+    let mut styles = BTreeMap::new();
+    let mut errors = Vec::new();
+    for (answer, entry) in entries {
+        match entry.parse() {
+            Ok(style) => {
+                styles.insert(answer.clone(), style);
+            }
+            Err(err) => errors.push(format!("{answer}: {err}")),
+        }
+    }
+    (styles, errors)
+}
+
+/// The colours a button is drawn in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ButtonStyle {
+    pub fg: Color,
+    pub bg: Color,
+    pub active_fg: Color,
+    pub active_bg: Color,
+}
+
+impl Theme {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        name: &str,
+        title: Color,
+        idle_fg: Color,
+        idle_bg: Color,
+        active_fg: Color,
+        active_bg: Color,
+        status: Color,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            title,
+            idle_fg,
+            idle_bg,
+            active_fg,
+            active_bg,
+            status,
+            border: BorderType::Plain,
+            bold: false,
+            answers: BTreeMap::new(),
+        }
+    }
+
+    /// The theme with `styles` laid over its own answer styles. The
+    /// high-contrast theme keeps every button black and white.
+    pub fn with_styles(mut self, styles: &BTreeMap<String, AnswerStyle>) -> Self {
+        if self.name != HIGH_CONTRAST_THEME {
+            for (answer, style) in styles {
+                let own = self.answers.get(answer).copied().unwrap_or_default();
+                self.answers.insert(answer.clone(), style.or(own));
+            }
+        }
+        self
+    }
+
+    /// How the button for `answer` is drawn: the theme's style for its text,
+    /// then the pack's, then the theme's colours.
+    pub fn button(&self, answer: &Answer) -> ButtonStyle {
+        let style = if self.name == HIGH_CONTRAST_THEME {
+            AnswerStyle::default()
+        } else {
+            let own = self.answers.get(&answer.text).copied().unwrap_or_default();
+            own.or(answer.style)
+        };
+        ButtonStyle {
+            fg: style.fg.unwrap_or(self.idle_fg),
+            bg: style.bg.unwrap_or(self.idle_bg),
+            active_fg: style.active_fg.unwrap_or(self.active_fg),
+            active_bg: style.active_bg.unwrap_or(self.active_bg),
+        }
+    }
+}
+
+pub fn builtin_themes() -> Vec<Theme> {
+    use Color::*;
+    vec![
+        Theme::new(
+            DEFAULT_THEME,
+            Yellow,
+            White,
+            DarkGray,
+            Black,
+            LightGreen,
+            Cyan,
+        ),
+        Theme::new("Amber", LightYellow, Yellow, Black, Black, Yellow, Yellow),
+        Theme::new("Ocean", LightCyan, White, Blue, Black, LightCyan, Cyan),
+        Theme::new("Matrix", LightGreen, Green, Black, Black, LightGreen, Green),
+        Theme::new("Mono", White, Gray, Black, Black, White, White),
+        high_contrast(),
+    ]
+}
+
+/// The [`HIGH_CONTRAST_THEME`]: white on black, and the lit button black on
+/// white, so the two differ by the full 21:1 contrast ratio rather than by
+/// hue. RGB values keep terminals from substituting their softer palette
+/// greys for "white".
+pub fn high_contrast() -> Theme {
+    let white = Color::Rgb(255, 255, 255);
+    let black = Color::Rgb(0, 0, 0);
+    Theme {
+        border: BorderType::Thick,
+        bold: true,
+        ..Theme::new(
+            HIGH_CONTRAST_THEME,
+            white,
+            white,
+            black,
+            black,
+            white,
+            white,
+        )
+    }
+}
+
+/// On-disk theme; every colour is optional.
+#[derive(Deserialize)]
+struct ThemeFile {
+    name: String,
+    title: Option<String>,
+    idle_fg: Option<String>,
+    idle_bg: Option<String>,
+    active_fg: Option<String>,
+    active_bg: Option<String>,
+    status: Option<String>,
+    border: Option<String>,
+    bold: Option<bool>,
+    #[serde(default)]
+    answers: BTreeMap<String, StyleEntry>,
+}
+
+fn parse_theme(text: &str) -> Result<Theme, String> {
+    let file: ThemeFile = toml::from_str(text).map_err(|err| err.to_string())?;
+    let base = builtin_themes().remove(0);
+    let color = |value: Option<String>, fallback: Color| match value {
+        Some(value) => Color::from_str(&value).map_err(|_| format!("unknown colour `{value}`")),
+        None => Ok(fallback),
+    };
+    let border = match file.border.as_deref() {
+        None => base.border,
+        Some("plain") => BorderType::Plain,
+        Some("rounded") => BorderType::Rounded,
+        Some("double") => BorderType::Double,
+        Some("thick") => BorderType::Thick,
+        Some(other) => return Err(format!("unknown border `{other}`")),
+    };
+    let (answers, errors) = parse_styles(&file.answers);
+    if let Some(err) = errors.into_iter().next() {
+        return Err(err);
+    }
+    Ok(Theme {
+        title: color(file.title, base.title)?,
+        idle_fg: color(file.idle_fg, base.idle_fg)?,
+        idle_bg: color(file.idle_bg, base.idle_bg)?,
+        active_fg: color(file.active_fg, base.active_fg)?,
+        active_bg: color(file.active_bg, base.active_bg)?,
+        status: color(file.status, base.status)?,
+        border,
+        bold: file.bold.unwrap_or(base.bold),
+        answers,
+        name: file.name,
+    })
+}
+
+/// Built-in themes followed by every readable `*.toml` theme in `dir`.
+/// Broken files are skipped and described in the returned error list.
+pub fn installed_themes(dir: Option<&Path>) -> (Vec<Theme>, Vec<String>) {
+    let mut themes = builtin_themes();
+    let mut errors = Vec::new();
+
+    let Some(entries) = dir.and_then(|dir| fs::read_dir(dir).ok()) else {
+        return (themes, errors);
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        let parsed = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| parse_theme(&text));
+        match parsed {
+            Ok(theme) => {
+                // A user theme may override a built-in one of the same name.
+                themes.retain(|existing| existing.name != theme.name);
+                themes.push(theme);
+            }
+            Err(err) => errors.push(format!("{}: {err}", path.display())),
+        }
+    }
+    (themes, errors)
+}
+
+/// State of the live theme preview: the UI is drawn with the highlighted
+/// theme while the original is kept for reverting.
+pub struct ThemePreview {
+    pub themes: Vec<Theme>,
+    pub index: usize,
+    pub original: Theme,
+}
+
+impl ThemePreview {
+    /// Start previewing at the theme after `current`.
+    pub fn new(themes: Vec<Theme>, current: Theme) -> Self {
+        let position = themes
+            .iter()
+            .position(|theme| theme.name == current.name)
+            .unwrap_or(0);
+        let index = (position + 1) % themes.len();
+        Self {
+            themes,
+            index,
+            original: current,
+        }
+    }
+
+    pub fn current(&self) -> &Theme {
+        &self.themes[self.index]
+    }
+
+    /// Move by `delta` themes, wrapping at either end.
+    pub fn cycle(&mut self, delta: isize) {
+        let len = self.themes.len() as isize;
+        self.index = (self.index as isize + delta).rem_euclid(len) as usize;
+    }
+}
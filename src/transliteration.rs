@@ -0,0 +1,200 @@
+//! Latin transliterations shown under answers written in another script, so
+//! a mixed team can read each other's packs. Pick a scheme with
+//! `transliteration = "cyrillic"` in the config.
+//!
+//! A scheme is a table from characters to Latin text. Built in: `cyrillic`
+//! and `greek`. More go in `transliterations/` next to the config, one TOML
+//! file each:
+//!
+//! ```toml
+//! name = "hiragana"
+//!
+//! [map]
+//! "あ" = "a"
+//! "か" = "ka"
+//! ```
+//!
+//! Tables list lowercase letters; capitals are looked up lowercased and the
+//! result capitalised to match.
+
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::Path};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scheme {
+    pub name: String,
+    map: BTreeMap<char, String>,
+}
+
+const CYRILLIC: &[(char, &str)] = &[
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('ґ', "g"),
+    ('д', "d"),
+    ('е', "e"),
+    ('ё', "yo"),
+    ('є', "ye"),
+    ('ж', "zh"),
+    ('з', "z"),
+    ('и', "i"),
+    ('і', "i"),
+    ('ї', "yi"),
+    ('й', "y"),
+    ('к', "k"),
+    ('л', "l"),
+    ('м', "m"),
+    ('н', "n"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('у', "u"),
+    ('ф', "f"),
+    ('х', "kh"),
+    ('ц', "ts"),
+    ('ч', "ch"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', ""),
+    ('ы', "y"),
+    ('ь', ""),
+    ('э', "e"),
+    ('ю', "yu"),
+    ('я', "ya"),
+];
+
+const GREEK: &[(char, &str)] = &[
+    ('α', "a"),
+    ('ά', "a"),
+    ('β', "v"),
+    ('γ', "g"),
+    ('δ', "d"),
+    ('ε', "e"),
+    ('έ', "e"),
+    ('ζ', "z"),
+    ('η', "i"),
+    ('ή', "i"),
+    ('θ', "th"),
+    ('ι', "i"),
+    ('ί', "i"),
+    ('ϊ', "i"),
+    ('κ', "k"),
+    ('λ', "l"),
+    ('μ', "m"),
+    ('ν', "n"),
+    ('ξ', "x"),
+    ('ο', "o"),
+    ('ό', "o"),
+    ('π', "p"),
+    ('ρ', "r"),
+    ('σ', "s"),
+    ('ς', "s"),
+    ('τ', "t"),
+    ('υ', "y"),
+    ('ύ', "y"),
+    ('φ', "f"),
+    ('χ', "ch"),
+    ('ψ', "ps"),
+    ('ω', "o"),
+    ('ώ', "o"),
+];
+
+#[derive(Deserialize)]
+struct SchemeFile {
+    name: String,
+    map: BTreeMap<String, String>,
+}
+
+impl Scheme {
+    fn from_table(name: &str, table: &[(char, &str)]) -> Self {
+        Self {
+            name: name.to_string(),
+            map: table
+                .iter()
+                .map(|&(c, latin)| (c, latin.to_string()))
+                .collect(),
+        }
+    }
+
+    /// `text` in Latin letters, or `None` when the scheme has nothing to say
+    /// about it (e.g. it is already Latin).
+    pub fn apply(&self, text: &str) -> Option<String> {
+        // ALL-CAPS answers stay all caps; otherwise only the first letter of
+        // a multi-letter replacement is capitalised.
+        let shouting = !text.chars().any(char::is_lowercase);
+        let mut out = String::with_capacity(text.len());
+        let mut changed = false;
+        for c in text.chars() {
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            match self.map.get(&c).or_else(|| self.map.get(&lower)) {
+                Some(latin) if c != lower => {
+                    changed = true;
+                    if shouting {
+                        out.push_str(&latin.to_uppercase());
+                    } else {
+                        let mut letters = latin.chars();
+                        out.extend(letters.next().map(|first| first.to_ascii_uppercase()));
+                        out.extend(letters);
+                    }
+                }
+                Some(latin) => {
+                    changed = true;
+                    out.push_str(latin);
+                }
+                None => out.push(c),
+            }
+        }
+        changed.then_some(out)
+    }
+}
+
+pub fn builtin_schemes() -> Vec<Scheme> {
+    vec![
+        Scheme::from_table("cyrillic", CYRILLIC),
+        Scheme::from_table("greek", GREEK),
+    ]
+}
+
+/// Parse a scheme file. Keys must be single characters.
+fn parse_scheme(text: &str) -> Result<Scheme, String> {
+    let file: SchemeFile = toml::from_str(text).map_err(|err| err.to_string())?;
+    let mut map = BTreeMap::new();
+    for (key, latin) in file.map {
+        let mut chars = key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => {
+                map.insert(c, latin);
+            }
+            _ => return Err(format!("`{key}` is not a single character")),
+        }
+    }
+    Ok(Scheme {
+        name: file.name,
+        map,
+    })
+}
+
+/// The scheme called `name`: a file in `dir` first, then the built-ins.
+pub fn find(name: &str, dir: Option<&Path>) -> Result<Option<Scheme>, String> {
+    let Some(entries) = dir.and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Ok(builtin_schemes().into_iter().find(|s| s.name == name));
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let scheme = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| parse_scheme(&text))
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+        if scheme.name == name {
+            return Ok(Some(scheme));
+        }
+    }
+    Ok(builtin_schemes().into_iter().find(|s| s.name == name))
+}
@@ -0,0 +1,241 @@
+//! The default ratatui + crossterm front end, and [`run`], the async event
+//! loop `edm` drives it with.
+
+use crate::{
+    app::App,
+    cast::Recorder,
+    frontend::{self, Frontend},
+    input::{self, Action, Mode},
+    keymap::Keymap,
+    mouse::HitAreas,
+    terminal::TerminalGuard,
+    ui,
+};
+use crossterm::event::{self, Event, EventStream};
+use futures_util::StreamExt;
+use ratatui::buffer::Buffer;
+use std::{
+    future,
+    io::{self, Write},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+pub struct RatatuiFrontend {
+    terminal: Option<TerminalGuard>,
+    /// How the app wanted keys read at the last render (commands, typed
+    /// text, or a chord to record), and its bindings.
+    mode: Mode,
+    keymap: Keymap,
+    /// Whether to capture the mouse; see [`crate::mouse`].
+    mouse: bool,
+    /// Where the last render put what a click can hit.
+    hits: HitAreas,
+    /// The last frame, kept when the app wants a screenshot of it.
+    last_frame: Option<Buffer>,
+    /// Where `--record` copies the session to, until the terminal takes it.
+    recorder: Option<Recorder>,
+}
+
+impl RatatuiFrontend {
+    pub fn new() -> Self {
+        Self {
+            terminal: None,
+            mode: Mode::Commands,
+            keymap: Keymap::default(),
+            mouse: false,
+            hits: HitAreas::default(),
+            last_frame: None,
+            recorder: None,
+        }
+    }
+
+    /// What a terminal event means in the current input mode.
+    fn action(&self, event: Event) -> Option<Action> {
+        match event {
+            Event::Key(key) => input::map(self.mode, &self.keymap, key),
+            Event::FocusGained => Some(Action::Focus(true)),
+            Event::FocusLost => Some(Action::Focus(false)),
+            Event::Resize(..) => Some(Action::Resize),
+            // A chord being recorded is a key, never a click.
+            Event::Mouse(mouse) if self.mode != Mode::Recording => self.hits.action(mouse),
+            _ => None,
+        }
+    }
+
+    fn terminal(&mut self) -> io::Result<&mut TerminalGuard> {
+        self.terminal
+            .as_mut()
+            .ok_or_else(|| io::Error::other("terminal used before init"))
+    }
+}
+
+impl Default for RatatuiFrontend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Frontend for RatatuiFrontend {
+    fn init(&mut self) -> io::Result<()> {
+        self.terminal = Some(TerminalGuard::new(self.mouse, self.recorder.take())?);
+        Ok(())
+    }
+
+    fn render(&mut self, app: &App) -> io::Result<()> {
+        self.mode = app.input_mode();
+        if self.keymap != app.keymap {
+            self.keymap = app.keymap.clone();
+        }
+        let mut hits = HitAreas::default();
+        let frame = self.terminal()?.draw(|f| hits = ui::draw(f, app))?;
+        if app.screenshot_requested {
+            self.last_frame = Some(frame.buffer.clone());
+        }
+        self.hits = hits;
+        Ok(())
+    }
+
+    fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let backend = self.terminal()?.backend_mut();
+        backend.write_all(bytes)?;
+        backend.flush()
+    }
+
+    fn handle_input(&mut self, timeout: Option<Duration>) -> io::Result<Option<Action>> {
+        if let Some(timeout) = timeout {
+            if !event::poll(timeout)? {
+                return Ok(None);
+            }
+        }
+        Ok(self.action(event::read()?))
+    }
+
+    fn last_frame(&mut self) -> Option<Buffer> {
+        self.last_frame.take()
+    }
+
+    fn suspend(&mut self) -> io::Result<()> {
+        self.terminal()?.suspend()
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        match self.terminal.take() {
+            Some(terminal) => terminal.restore(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Run `app` in the terminal until the user quits.
+///
+/// Where [`frontend::run`] blocks on one input source at a time, this selects
+/// over crossterm's [`EventStream`], the next animation deadline, and the
+/// engine's background tasks (webhooks, history writes) on a single-threaded
+/// tokio runtime: slow I/O never holds up a frame, and a failure it reports
+/// shows up at once instead of at the next key press.
+///
+/// SIGTERM and SIGHUP end the loop like a quit would, so the terminal is
+/// restored and pending history writes are flushed before `edm` exits.
+///
+/// With a `recorder`, the session is also written to its cast file.
+pub fn run(app: &mut App, recorder: Option<Recorder>) -> io::Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let mut frontend = RatatuiFrontend {
+        mouse: app.config.mouse,
+        recorder,
+        ..RatatuiFrontend::new()
+    };
+    frontend.init()?;
+    tracing::debug!("event loop started");
+    let result = runtime.block_on(drive(app, &mut frontend));
+    let cleanup = frontend.shutdown();
+    tracing::debug!(ok = result.is_ok(), "event loop stopped");
+    match result {
+        // After a hangup there may be no terminal left to restore.
+        Ok(Some(signal)) => {
+            tracing::info!(signal, "terminated by signal");
+            Ok(())
+        }
+        Ok(None) => cleanup,
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns the name of the signal that ended the loop, if one did.
+async fn drive(app: &mut App, frontend: &mut RatatuiFrontend) -> io::Result<Option<&'static str>> {
+    let mut termination = Termination::new()?;
+    let mut events = EventStream::new();
+    let wake = Arc::clone(app.engine.wake());
+    let mut due: Option<Instant> = None;
+    loop {
+        let timeout = frontend::frame(app, frontend, due)?;
+        due = timeout.map(|timeout| Instant::now() + timeout);
+        let deadline = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => future::pending().await,
+            }
+        };
+        tokio::select! {
+            event = events.next() => {
+                // The stream only ends when stdin is gone.
+                let Some(event) = event.transpose()? else {
+                    return Ok(None);
+                };
+                if let Event::Paste(text) = &event {
+                    app.paste(text);
+                } else if let Some(action) = frontend.action(event) {
+                    if action == Action::Suspend {
+                        frontend::suspend(app, frontend)?;
+                    } else if app.handle(action) {
+                        return Ok(None);
+                    }
+                }
+            }
+            () = deadline => {}
+            () = wake.notified() => {}
+            signal = termination.recv() => return Ok(Some(signal)),
+        }
+    }
+}
+
+/// SIGTERM and SIGHUP, which would otherwise kill `edm` in raw mode.
+struct Termination {
+    #[cfg(unix)]
+    terminate: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    hangup: tokio::signal::unix::Signal,
+}
+
+impl Termination {
+    #[cfg(unix)]
+    fn new() -> io::Result<Self> {
+        use tokio::signal::unix::{signal, SignalKind};
+        Ok(Self {
+            terminate: signal(SignalKind::terminate())?,
+            hangup: signal(SignalKind::hangup())?,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn new() -> io::Result<Self> {
+        Ok(Self {})
+    }
+
+    /// The name of the next signal to arrive.
+    #[cfg(unix)]
+    async fn recv(&mut self) -> &'static str {
+        tokio::select! {
+            _ = self.terminate.recv() => "SIGTERM",
+            _ = self.hangup.recv() => "SIGHUP",
+        }
+    }
+
+    #[cfg(not(unix))]
+    async fn recv(&mut self) -> &'static str {
+        future::pending().await
+    }
+}
@@ -0,0 +1,2398 @@
+//! Rendering: lays out an [`App`] on a ratatui frame. Pure drawing, no state
+//! changes, so any front end with a ratatui backend can call [`draw`].
+
+use crate::{
+    achievements::{Achievements, ACHIEVEMENTS},
+    answers::AnswerPack,
+    app::App,
+    bracket::Bracket,
+    command_palette::{Command, CommandPalette},
+    experiments::EXPERIMENTS,
+    form::FormWidget,
+    gallery::Gallery,
+    heatmap,
+    history_view::{self, HistoryView},
+    keymap::BINDABLE,
+    locale::Strings,
+    matrix::{MatrixEditor, Target},
+    mouse::HitAreas,
+    opinion::SecondOpinion,
+    pack_editor::PackEditor,
+    palette::{self, ColorDepth},
+    paths,
+    profile::ProfilePicker,
+    rationale,
+    recall::Recall,
+    screen::{self, Screen},
+    sentiment::Sentiment,
+    settings::{Conflict, Settings, Tab},
+    stats::Summary,
+    suggest::Suggestion,
+    tags,
+    text_area::TextArea,
+    theme::{Theme, ThemePreview},
+    transliteration::Scheme,
+    weights::{WeightEditor, PREVIEW_DRAWS},
+    widget::{self, State},
+    width,
+    worksheet::{self, Column, Worksheet},
+    workspace,
+};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph, Wrap},
+};
+
+const RECENT_SHOWN: usize = 6;
+/// Space around the whole UI.
+pub const UI_MARGIN: u16 = 2;
+/// Narrowest terminal the layout is designed for.
+pub const MIN_WIDTH: u16 = 80;
+/// Below this size the layout falls apart, so [`draw`] asks for a bigger
+/// terminal instead.
+pub const WORKABLE_WIDTH: u16 = 60;
+pub const WORKABLE_HEIGHT: u16 = 16;
+
+/// Rows of the help screen's controls: keys, then the message describing
+/// them.
+const HELP_KEYS: [(&str, &str); 33] = [
+    ("Enter / Space", "help-ask"),
+    ("y / c", "help-copy"),
+    ("s", "help-qr"),
+    ("S", "help-screenshot"),
+    ("i", "help-question"),
+    ("H", "help-history"),
+    ("n", "help-note"),
+    ("#", "help-tags"),
+    ("t", "help-filter"),
+    ("g", "help-gallery"),
+    ("T", "help-theme"),
+    ("W", "help-weights"),
+    ("E", "help-pack"),
+    ("P", "help-profiles"),
+    ("A", "help-achievements"),
+    ("w", "help-worksheet"),
+    ("m", "help-matrix"),
+    ("b", "help-bracket"),
+    (",", "help-settings"),
+    ("[ / ]", "help-screens"),
+    ("r", "help-veto"),
+    ("x", "help-eliminate"),
+    ("+ / -", "help-optimism"),
+    ("R", "help-revalidate"),
+    ("u / d", "help-outcome"),
+    ("L", "help-lock"),
+    ("F2", "help-contrast"),
+    ("F12", "help-debug"),
+    ("Ctrl+P", "help-palette"),
+    ("Ctrl+H", "help-help"),
+    ("q / Esc", "help-back"),
+    ("Ctrl+C", "help-quit"),
+    ("Ctrl+Z", "help-suspend"),
+];
+
+/// Render the whole UI, returning where tabs and list rows were drawn.
+pub fn draw(f: &mut ratatui::Frame, app: &App) -> HitAreas {
+    let mut hits = HitAreas::default();
+    draw_screen(f, app, &mut hits);
+    if app.theme.bold {
+        let area = f.area();
+        f.buffer_mut()
+            .set_style(area, Style::default().add_modifier(Modifier::BOLD));
+    }
+    palette::adapt(f.buffer_mut(), app.color_depth, app.theme.active_bg);
+    hits
+}
+
+fn draw_screen(f: &mut ratatui::Frame, app: &App, hits: &mut HitAreas) {
+    let area = f.area();
+    if area.width < WORKABLE_WIDTH || area.height < WORKABLE_HEIGHT {
+        render_too_small(f, &app.theme, &app.strings);
+        return;
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5),
+            Constraint::Min(7),
+            Constraint::Length(5),
+        ])
+        .margin(UI_MARGIN)
+        .split(area);
+
+    if let Some(entry) = &app.lock {
+        render_lock(f, entry, app);
+        return;
+    }
+    if let Some(tab) = app.screen.tab() {
+        render_tab_bar(f, area, tab, app, hits);
+    }
+    match &app.screen {
+        Screen::Oracle => {
+            render_header(f, chunks[0], app);
+            render_buttons(f, chunks[1], app);
+            render_footer(f, chunks[2], app);
+            if app.typing && !app.suggestions.is_empty() {
+                render_suggestions(f, chunks[0], app);
+            }
+        }
+        Screen::History(view) => render_history(f, &chunks, view, app, hits),
+        Screen::Stats => render_stats(f, &chunks, app),
+        Screen::Settings(settings) => render_settings(f, &chunks, settings, app, hits),
+        Screen::Help { scroll } => render_help(f, &chunks, *scroll, app),
+        Screen::Gallery(gallery) => render_gallery(
+            f,
+            &chunks,
+            gallery,
+            &app.theme,
+            app.transliteration.as_ref(),
+            (app.color_depth == ColorDepth::TrueColor).then_some(1.0),
+            &app.strings,
+        ),
+        Screen::Weights(editor) => render_weights(f, &chunks, editor, app),
+        Screen::PackEditor(editor) => render_pack_editor(f, &chunks, editor, app),
+        Screen::Worksheet(sheet) => render_worksheet(f, &chunks, sheet, app),
+        Screen::Matrix(editor) => render_matrix(f, &chunks, editor, app),
+        Screen::Bracket(bracket) => render_bracket(f, &chunks, bracket, app),
+        Screen::Profiles(picker) => render_profiles(f, &chunks, picker, &app.theme),
+        Screen::Achievements => {
+            render_achievements(f, &chunks, &app.engine.achievements, &app.theme);
+        }
+    }
+    if let Some(recall) = &app.recall {
+        render_recall(f, recall, &app.theme);
+    }
+    if let Some((_, area)) = &app.note {
+        let answer = app.last_answer.map_or("", |index| app.answer_text(index));
+        render_note(f, area, answer, &app.theme);
+    }
+    if let Some((_, text)) = &app.tagging {
+        let answer = app.last_answer.map_or("", |index| app.answer_text(index));
+        render_tagging(f, text, answer, &app.theme);
+    }
+    if let Some((title, lines, _)) = &app.banner {
+        render_banner(f, title, lines, &app.theme);
+    }
+    if let (Some((message, _)), Some(frame)) = (&app.streak, app.sparkle_frame()) {
+        render_streak(f, message, frame, &app.theme);
+    }
+    if let Some(preview) = &app.theme_preview {
+        render_theme_overlay(f, preview);
+    }
+    if let Some(rows) = &app.qr {
+        render_qr(f, rows, &app.theme);
+    }
+    if let Some(palette) = &app.palette {
+        render_palette(f, palette, app);
+    }
+    if app.debug_visible {
+        render_debug_overlay(f, app);
+    }
+}
+
+/// Shown instead of everything else until the terminal is resized to at
+/// least [`WORKABLE_WIDTH`]×[`WORKABLE_HEIGHT`]. Keys keep working.
+fn render_too_small(f: &mut ratatui::Frame, theme: &Theme, strings: &Strings) {
+    let area = f.area();
+    let lines = vec![
+        directed(
+            strings,
+            Span::styled(
+                strings.get("too-small"),
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ),
+        Line::raw(""),
+        directed(
+            strings,
+            strings.format(
+                "too-small-enlarge",
+                &[
+                    ("width", WORKABLE_WIDTH.into()),
+                    ("height", WORKABLE_HEIGHT.into()),
+                ],
+            ),
+        ),
+        directed(
+            strings,
+            strings.format(
+                "too-small-now",
+                &[("width", area.width.into()), ("height", area.height.into())],
+            ),
+        ),
+    ];
+    // Vertically centred when there is room, top-aligned otherwise.
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let text = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.status))
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, text);
+}
+
+/// Draw the answer “buttons” plus the recent-answers line below them
+fn render_buttons(f: &mut ratatui::Frame, area: Rect, app: &App) {
+    if let Some(charge) = app.charge() {
+        render_suspense(f, area, charge, app);
+        return;
+    }
+    let active_index = app.decision.active();
+    let rest = render_board(
+        f,
+        area,
+        &app.engine.pack,
+        active_index,
+        app.decision.disabled(),
+        &app.theme,
+        app.transliteration.as_ref(),
+        app.glow(),
+        &app.strings,
+    );
+    let revealed = matches!(app.decision.state(), State::Showing { .. } | State::Idle);
+    let flavor = app
+        .flavor
+        .as_deref()
+        .zip(app.last_answer)
+        .filter(|_| revealed && rest.height >= 2);
+    let confidence = app
+        .confidence
+        .zip(app.last_answer)
+        .filter(|_| revealed && rest.height >= 2 + u16::from(flavor.is_some()));
+    let opinion = app.second_opinion.as_ref().filter(|_| {
+        revealed && rest.height >= 4 + u16::from(flavor.is_some()) + u16::from(confidence.is_some())
+    });
+    let above = 1
+        + u16::from(flavor.is_some())
+        + u16::from(confidence.is_some())
+        + if opinion.is_some() { 3 } else { 0 };
+    let rationale = app
+        .rationale
+        .as_ref()
+        .filter(|_| revealed && rest.height >= above + 4);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(u16::from(flavor.is_some())),
+            Constraint::Length(u16::from(confidence.is_some())),
+            Constraint::Length(if opinion.is_some() { 3 } else { 0 }),
+            Constraint::Length(if rationale.is_some() { 4 } else { 0 }),
+            Constraint::Min(0),
+        ])
+        .split(rest);
+    render_recent(f, rows[0], app);
+    if let Some((line, index)) = flavor {
+        let text = format!("{} — {line}", app.answer_text(index));
+        let style = Style::default()
+            .fg(app.theme.title)
+            .add_modifier(Modifier::ITALIC);
+        f.render_widget(
+            Paragraph::new(directed(&app.strings, Span::styled(text, style)))
+                .alignment(Alignment::Center),
+            rows[1],
+        );
+    }
+    if let Some((percent, index)) = confidence {
+        render_confidence(f, rows[2], app.answer_text(index), percent, &app.theme);
+    }
+    if let Some(opinion) = opinion {
+        render_second_opinion(f, rows[3], opinion, &app.theme);
+    }
+    if let Some(rationale) = rationale {
+        render_rationale(f, rows[4], rationale, app);
+    }
+}
+
+/// Panel with the model's justification of the answer, or a spinner while
+/// it is on its way
+fn render_rationale(f: &mut ratatui::Frame, area: Rect, rationale: &rationale::Status, app: &App) {
+    let area = Rect {
+        x: area.x + area.width / 6,
+        width: area.width * 2 / 3,
+        ..area
+    };
+    let line = match rationale {
+        rationale::Status::Loading { .. } => {
+            let frame = app.spinner_frame().unwrap_or(0) % rationale::SPINNER.len();
+            Line::from(format!(
+                "{} The oracle is justifying itself…",
+                rationale::SPINNER[frame]
+            ))
+        }
+        rationale::Status::Ready(text) => Line::styled(
+            text.as_str(),
+            Style::default().add_modifier(Modifier::ITALIC),
+        ),
+        rationale::Status::Failed(err) => Line::styled(
+            format!("No rationale: {err}"),
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    let widget = Paragraph::new(line)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(framed(&app.theme).title(" Rationale "));
+    f.render_widget(widget, area);
+}
+
+/// Drumroll reveal: the board blacked out but for a bar charging up
+fn render_suspense(f: &mut ratatui::Frame, area: Rect, charge: f64, app: &App) {
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Block::default().style(Style::default().bg(Color::Black)),
+        area,
+    );
+    let bar = Rect {
+        x: area.x + area.width / 4,
+        y: area.y + area.height.saturating_sub(1) / 2,
+        width: area.width / 2,
+        height: area.height.min(1),
+    };
+    let gauge = Gauge::default()
+        .ratio(charge.clamp(0.0, 1.0))
+        .label(app.strings.get("status-charging"))
+        .gauge_style(Style::default().fg(app.theme.active_bg).bg(Color::Black))
+        .use_unicode(true);
+    f.render_widget(gauge, bar);
+}
+
+/// Gauge of how sure the oracle claims to be about `answer`
+fn render_confidence(f: &mut ratatui::Frame, area: Rect, answer: &str, percent: u8, theme: &Theme) {
+    let area = Rect {
+        x: area.x + area.width / 4,
+        width: area.width / 2,
+        ..area
+    };
+    let gauge = Gauge::default()
+        .percent(u16::from(percent.min(100)))
+        .label(format!("{answer} — {percent}% confident"))
+        .gauge_style(Style::default().fg(theme.active_bg).bg(theme.idle_bg))
+        .use_unicode(true);
+    f.render_widget(gauge, area);
+}
+
+/// Small panel with what the second-opinion pack said
+fn render_second_opinion(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    opinion: &SecondOpinion,
+    theme: &Theme,
+) {
+    let area = Rect {
+        x: area.x + area.width / 4,
+        width: area.width / 2,
+        ..area
+    };
+    let verdict = if opinion.agrees {
+        Span::styled("agrees", Style::default().fg(Color::Green))
+    } else {
+        Span::styled("disagrees", Style::default().fg(Color::Red))
+    };
+    let line = Line::from(vec![
+        Span::styled(
+            opinion.answer.text.as_str(),
+            Style::default()
+                .fg(opinion.answer.sentiment.color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" · "),
+        verdict,
+    ]);
+    let widget = Paragraph::new(line)
+        .alignment(Alignment::Center)
+        .block(framed(theme).title(format!(" Second opinion · {} ", opinion.pack)));
+    f.render_widget(widget, area);
+}
+
+/// Draw the answer grid, returning the unused area below it.
+#[allow(clippy::too_many_arguments)]
+fn render_board(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    pack: &AnswerPack,
+    active: Option<usize>,
+    disabled: &[bool],
+    theme: &Theme,
+    transliteration: Option<&Scheme>,
+    glow: Option<f64>,
+    strings: &Strings,
+) -> Rect {
+    widget::render_board(
+        area,
+        f.buffer_mut(),
+        pack,
+        active,
+        disabled,
+        theme,
+        transliteration,
+        glow,
+        Some(strings),
+    )
+}
+
+/// One line of the most recent verdicts, coloured by sentiment
+fn render_recent(f: &mut ratatui::Frame, area: Rect, app: &App) {
+    let mut spans = Vec::new();
+    let expired = app.engine.history.needs_revalidation(app.local_now()).len();
+    if expired > 0 {
+        let expired = app
+            .strings
+            .format("recent-expired", &[("count", expired.into())]);
+        spans.push(Span::styled(
+            format!("{expired} · "),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    spans.push(Span::styled(
+        format!("{} ", app.strings.get("recent-label")),
+        Style::default().fg(Color::Gray),
+    ));
+    let filter = app.tag_filter.as_deref();
+    if let Some(tag) = filter {
+        spans.push(Span::styled(
+            format!("#{tag} "),
+            Style::default().fg(app.theme.title),
+        ));
+    }
+    let header = spans.len();
+    let recent = app.engine.history.tagged(filter).take(RECENT_SHOWN);
+    for (i, decision) in recent.enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" · "));
+        }
+        spans.push(Span::styled(
+            decision.answer.as_str(),
+            Style::default().fg(decision.sentiment.color()),
+        ));
+    }
+    if spans.len() == header {
+        return;
+    }
+
+    let widget = Paragraph::new(directed(&app.strings, spans)).alignment(Alignment::Center);
+    f.render_widget(widget, area);
+}
+
+fn render_header(f: &mut ratatui::Frame, area: Rect, app: &App) {
+    let strings = &app.strings;
+    let title_style = Style::default()
+        .fg(app.theme.title)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        directed(strings, Span::styled(strings.get("app-title"), title_style)),
+        if app.engine.rules().chaos {
+            directed(
+                strings,
+                vec![
+                    Span::styled(
+                        format!(" {} ", strings.get("chaos-badge")),
+                        Style::default()
+                            .fg(app.theme.active_fg)
+                            .bg(app.theme.active_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("  {}", strings.get("chaos-rules")),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ],
+            )
+        } else {
+            Line::raw("")
+        },
+    ];
+    if app.typing {
+        let validity = match app.valid_for {
+            Some(days) => strings.format("question-validity", &[("days", days.into())]),
+            None => strings.get("question-validity-unset"),
+        };
+        let timebox = match app.timebox {
+            Some(secs) => strings.format("question-timebox", &[("seconds", secs.into())]),
+            None => strings.get("question-timebox-unset"),
+        };
+        lines.push(directed(
+            strings,
+            vec![
+                Span::raw(format!("{} ", strings.get("question-label"))),
+                Span::styled(format!("{}▏", app.question), title_style),
+                Span::styled(
+                    format!("  ({validity}) ({timebox})"),
+                    Style::default().fg(Color::Gray),
+                ),
+            ],
+        ));
+    } else if let Some(question) = app.question() {
+        let mut line = format!("“{question}”");
+        if !app.tags.is_empty() {
+            line.push_str(&format!(" {}", tags::format(&app.tags)));
+        }
+        lines.push(directed(strings, line));
+    } else {
+        lines.push(directed(strings, strings.get("prompt-think")));
+    }
+    if let Some(seconds) = app.countdown() {
+        let countdown = strings.format("prompt-countdown", &[("seconds", seconds.into())]);
+        lines.push(directed(
+            strings,
+            Span::styled(
+                countdown,
+                Style::default()
+                    .fg(app.theme.title)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ));
+    } else {
+        let prompt = match app.decision.state() {
+            State::Animating { .. } => "prompt-shuffling",
+            State::Charging { .. } => "prompt-charging",
+            State::Showing { .. } => "prompt-landed",
+            State::Idle if app.last_answer.is_none() => "prompt-first",
+            State::Idle => "prompt-again",
+        };
+        lines.push(directed(strings, strings.get(prompt)));
+    }
+
+    let mut title = strings.format(
+        "header-box",
+        &[("pack", app.engine.pack.title.as_str().into())],
+    );
+    if app.profile != paths::DEFAULT_PROFILE {
+        title = format!("{title} · {}", app.profile);
+    }
+    let paragraph = Paragraph::new(lines).alignment(Alignment::Center).block(
+        framed(&app.theme)
+            .title(format!(" {} ", strings.visual(&title)))
+            .title_alignment(start(strings)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn render_footer(f: &mut ratatui::Frame, area: Rect, app: &App) {
+    let strings = &app.strings;
+    let answer = |index| [("answer", app.answer_text(index).into())];
+    let (status_line, help_line) = match app.decision.state() {
+        _ if app.typing => (strings.get("status-typing"), "hint-typing"),
+        State::Animating { .. } => (strings.get("status-shuffling"), "hint-shuffling"),
+        State::Charging { .. } => (strings.get("status-charging"), "hint-shuffling"),
+        State::Showing { index, .. } => (
+            strings.format("status-answer", &answer(index)),
+            "hint-landed",
+        ),
+        State::Idle => match app.last_answer {
+            Some(idx) => (strings.format("status-final", &answer(idx)), "hint-again"),
+            None => (strings.get("status-ready"), "hint-first"),
+        },
+    };
+    let help_line = strings.get(help_line);
+
+    // A long answer is cut short instead of wrapping over the lines below.
+    let room = area.width.saturating_sub(2) as usize;
+    let sealed = format!(" {} ", strings.get("status-sealed"));
+    let status_line = match &app.notice {
+        Some((message, _)) => directed(strings, Line::raw(width::fit(message, room).into_owned())),
+        None => {
+            // Badges after the status, which gets cut short to make room.
+            let mut badges = Vec::new();
+            if app.sealed && matches!(app.decision.state(), State::Showing { .. } | State::Idle) {
+                badges.push(Span::raw("  "));
+                badges.push(Span::styled(
+                    sealed.as_str(),
+                    Style::default()
+                        .fg(app.theme.active_fg)
+                        .bg(app.theme.active_bg)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            if let Some(opposite) = app.devils_advocate() {
+                let consider = strings.format(
+                    "status-consider",
+                    &[("answer", opposite.text.as_str().into())],
+                );
+                badges.push(Span::raw("  "));
+                badges.push(Span::styled(
+                    consider,
+                    Style::default().add_modifier(Modifier::DIM),
+                ));
+            }
+            let used: usize = badges.iter().map(|badge| width::of(&badge.content)).sum();
+            let mut spans = vec![Span::raw(width::fit(
+                &status_line,
+                room.saturating_sub(used),
+            ))];
+            spans.extend(badges);
+            directed(strings, spans)
+        }
+    };
+
+    let filter = app.tag_filter.as_deref();
+    let now = app.local_now();
+    let mut summary = strings.format(
+        "status-today",
+        &[(
+            "tally",
+            app.engine
+                .history
+                .today_tally(filter, now)
+                .to_string()
+                .into(),
+        )],
+    );
+    if let Some(tag) = filter {
+        summary.push_str(&format!(" #{tag}"));
+    }
+    let (agreed, asked) = app.engine.history.today_agreement(filter, now);
+    if asked > 0 {
+        let agreement = strings.format(
+            "status-agreement",
+            &[("agreed", agreed.into()), ("asked", asked.into())],
+        );
+        summary.push_str(&format!(" · {agreement}"));
+    }
+    if let Some(best) = &app.best_of {
+        let tally: Vec<String> = best
+            .tally()
+            .into_iter()
+            .map(|(index, votes)| format!("{} ×{votes}", app.answer_text(index)))
+            .collect();
+        let best_of = strings.format(
+            "status-best-of",
+            &[
+                ("rounds", best.rounds().into()),
+                ("tally", tally.join(", ").into()),
+            ],
+        );
+        summary.push_str(&format!(" · {best_of}"));
+    }
+    if app.engine.optimism != 0 {
+        let optimism = strings.format(
+            "status-optimism",
+            &[("level", format!("{:+}", app.engine.optimism).into())],
+        );
+        summary.push_str(&format!(" · {optimism}"));
+    }
+    if let Some(left) = app.vetoes_left() {
+        let vetoes = strings.format("status-vetoes", &[("left", left.into())]);
+        summary.push_str(&format!(" · {vetoes}"));
+    }
+    let content = vec![
+        status_line,
+        directed(strings, summary),
+        directed(strings, help_line),
+    ];
+    let paragraph = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(app.theme.status))
+        .block(
+            framed(&app.theme)
+                .title(format!(" {} ", strings.visual(&strings.get("status-box"))))
+                .title_alignment(start(strings)),
+        );
+
+    f.render_widget(paragraph, area);
+}
+
+/// Pack gallery: list on the left, live preview of the highlighted pack on
+/// the right
+fn render_gallery(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    gallery: &Gallery,
+    theme: &Theme,
+    transliteration: Option<&Scheme>,
+    glow: Option<f64>,
+    strings: &Strings,
+) {
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("ANSWER PACK GALLERY", title_style)),
+        Line::raw(""),
+        Line::raw("Each pack turns the oracle into a different kind of advisor."),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(" Radio Shack "));
+    f.render_widget(intro, header);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(24), Constraint::Min(20)])
+        .split(body);
+
+    let items: Vec<Line> = gallery
+        .packs
+        .iter()
+        .enumerate()
+        .map(|(i, pack)| {
+            if i == gallery.selected {
+                Line::from(Span::styled(
+                    format!("> {}", pack.title),
+                    Style::default()
+                        .fg(theme.active_fg)
+                        .bg(theme.active_bg)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::raw(format!("  {}", pack.title))
+            }
+        })
+        .collect();
+    let list = Paragraph::new(items).block(framed(theme).title(" Packs "));
+    f.render_widget(list, columns[0]);
+
+    let pack = gallery.current();
+    let preview_block = framed(theme).title(format!(" Preview · {} answers ", pack.answers.len()));
+    let preview_area = preview_block.inner(columns[1]);
+    f.render_widget(preview_block, columns[1]);
+
+    let preview = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(preview_area);
+    f.render_widget(
+        Paragraph::new(pack.description.as_str()).alignment(Alignment::Center),
+        preview[0],
+    );
+    render_board(
+        f,
+        preview[1],
+        pack,
+        Some(gallery.lit),
+        &[],
+        theme,
+        transliteration,
+        glow,
+        strings,
+    );
+
+    let hints = Paragraph::new(vec![
+        Line::raw(format!("Highlighted: {} (--pack {})", pack.title, pack.id)),
+        Line::raw(""),
+        Line::raw("↑/↓ browse · Enter make default · Esc keep current pack"),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+/// Settings screen: a row of tabs over the preferences form or the key
+/// bindings. Recording and the conflict warning show in the status box and
+/// a dialog.
+fn render_settings(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    settings: &Settings,
+    app: &App,
+    hits: &mut HitAreas,
+) {
+    let theme = &app.theme;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let tabs: Vec<Span> = [(Tab::Preferences, "Preferences"), (Tab::Keys, "Keys")]
+        .into_iter()
+        .flat_map(|(tab, name)| {
+            let style = if tab == settings.tab {
+                Style::default()
+                    .fg(theme.active_fg)
+                    .bg(theme.active_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            [Span::styled(format!(" {name} "), style), Span::raw("  ")]
+        })
+        .collect();
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("SETTINGS", title_style)),
+        Line::raw(""),
+        Line::from(tabs),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(&app.theme).title(" Radio Shack "));
+    f.render_widget(intro, header);
+
+    match settings.tab {
+        Tab::Preferences => render_preferences(f, body, settings, app, hits),
+        Tab::Keys => render_key_settings(f, body, settings, app, hits),
+    }
+
+    let status = match (&app.notice, settings.tab, settings.recording) {
+        (Some((message, _)), _, _) => message.clone(),
+        (None, Tab::Preferences, _) => {
+            "Changes show right away; Enter writes them to your config file.".to_string()
+        }
+        (None, Tab::Keys, true) => format!("Press the key you want for {}.", settings.label()),
+        (None, Tab::Keys, false) => {
+            "Bindings and experiments are saved to your config file.".to_string()
+        }
+    };
+    let hints = if settings.tab == Tab::Preferences {
+        "↑/↓ choose · ←/→ change · Enter save · Tab keys · Esc back"
+    } else if settings.recording {
+        "Esc cancels"
+    } else if settings.experiment().is_some() {
+        "↑/↓ choose · Enter switch on/off · Tab preferences · Esc back"
+    } else {
+        "↑/↓ choose · Enter record a new key · Tab preferences · Esc back"
+    };
+    let status = Paragraph::new(vec![Line::raw(status), Line::raw(""), Line::raw(hints)])
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.status))
+        .block(framed(&app.theme).title(" Status "));
+    f.render_widget(status, footer);
+
+    if let Some(conflict) = &settings.conflict {
+        render_conflict(f, conflict, settings.label(), theme);
+    }
+}
+
+/// The preferences form beside a demo board that replays the shuffle with
+/// the edited timings and style.
+fn render_preferences(
+    f: &mut ratatui::Frame,
+    body: Rect,
+    settings: &Settings,
+    app: &App,
+    hits: &mut HitAreas,
+) {
+    let theme = &app.theme;
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(body);
+    let block = framed(theme).title(" Preferences ");
+    let form_area = block.inner(columns[0]);
+    f.render_widget(block, columns[0]);
+    f.render_widget(FormWidget::new(&settings.form, theme), form_area);
+    hits.list(form_area, 0);
+
+    let block = framed(theme).title(" Preview ");
+    let preview_area = block.inner(columns[1]);
+    f.render_widget(block, columns[1]);
+    render_board(
+        f,
+        preview_area,
+        &app.engine.pack,
+        settings.demo.active(),
+        &[],
+        theme,
+        app.transliteration.as_ref(),
+        None,
+        &app.strings,
+    );
+}
+
+/// Every bindable action with its chords, then the experiments.
+fn render_key_settings(
+    f: &mut ratatui::Frame,
+    body: Rect,
+    settings: &Settings,
+    app: &App,
+    hits: &mut HitAreas,
+) {
+    let theme = &app.theme;
+    let rows: Vec<Line> = BINDABLE
+        .iter()
+        .enumerate()
+        .map(|(slot, (_, _, label))| {
+            let chords: Vec<String> = app
+                .keymap
+                .chords(slot)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            let chords = if slot == settings.selected && settings.recording {
+                "press a key…".to_string()
+            } else if chords.is_empty() {
+                "(unbound)".to_string()
+            } else {
+                chords.join(", ")
+            };
+            let text = format!("{label:<18}{chords}");
+            if slot == settings.selected {
+                Line::from(Span::styled(
+                    format!("> {text}"),
+                    Style::default()
+                        .fg(theme.active_fg)
+                        .bg(theme.active_bg)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::raw(format!("  {text}"))
+            }
+        })
+        .collect();
+    let lists = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(EXPERIMENTS.len() as u16 + 2),
+        ])
+        .split(body);
+    let block = framed(&app.theme).title(" Key bindings ");
+    hits.list(block.inner(lists[0]), 0);
+    f.render_widget(Paragraph::new(rows).block(block), lists[0]);
+
+    let rows: Vec<Line> = EXPERIMENTS
+        .iter()
+        .enumerate()
+        .map(|(row, experiment)| {
+            let mark = if app.config.experiment(experiment.name) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let text = format!("{mark} {:<14}{}", experiment.name, experiment.summary);
+            if settings.selected == BINDABLE.len() + row {
+                Line::from(Span::styled(
+                    format!("> {text}"),
+                    Style::default()
+                        .fg(theme.active_fg)
+                        .bg(theme.active_bg)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::raw(format!("  {text}"))
+            }
+        })
+        .collect();
+    let block = framed(&app.theme).title(" Experiments (unstable) ");
+    hits.list(block.inner(lists[1]), BINDABLE.len());
+    f.render_widget(Paragraph::new(rows).block(block), lists[1]);
+}
+
+/// Weight editor: each answer's weight, its probability, and how often it
+/// came up in a simulated batch of draws.
+fn render_weights(f: &mut ratatui::Frame, chunks: &[Rect], editor: &WeightEditor, app: &App) {
+    const BAR_WIDTH: usize = 24;
+    let theme = &app.theme;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("ANSWER WEIGHTS", title_style)),
+        Line::raw(""),
+        Line::raw("Heavier answers come up more often."),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(&app.theme).title(format!(" Radio Shack · {} ", editor.pack.title)));
+    f.render_widget(intro, header);
+
+    let text_width = editor
+        .pack
+        .answers
+        .iter()
+        .map(|answer| width::of(&answer.text))
+        .max()
+        .unwrap_or(0);
+    let most = editor.histogram.iter().copied().max().unwrap_or(0).max(1);
+    let rows: Vec<Line> = editor
+        .pack
+        .answers
+        .iter()
+        .enumerate()
+        .map(|(i, answer)| {
+            let count = editor.histogram[i];
+            let bar = "█".repeat(count * BAR_WIDTH / most);
+            let marker = if i == editor.selected { ">" } else { " " };
+            let label = format!(
+                "{marker} {}  ×{:<3} {:>5.1}%  ",
+                width::pad(&answer.text, text_width),
+                answer.weight,
+                editor.probabilities[i]
+            );
+            let label_style = if i == editor.selected {
+                Style::default()
+                    .fg(theme.active_fg)
+                    .bg(theme.active_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(label, label_style),
+                Span::styled(bar, Style::default().fg(answer.sentiment.color())),
+                Span::raw(format!(" {count}")),
+            ])
+        })
+        .collect();
+    let list = Paragraph::new(rows)
+        .block(framed(&app.theme).title(format!(" Weights · {PREVIEW_DRAWS} simulated draws ")));
+    f.render_widget(list, body);
+
+    let status = match &app.notice {
+        Some((message, _)) => message.clone(),
+        None => "Changes apply when you press Enter.".to_string(),
+    };
+    let hints = Paragraph::new(vec![
+        Line::raw(status),
+        Line::raw(""),
+        Line::raw("↑/↓ choose · +/- weight · Enter keep · Esc discard"),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(&app.theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+fn render_profiles(f: &mut ratatui::Frame, chunks: &[Rect], picker: &ProfilePicker, theme: &Theme) {
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("PROFILES", title_style)),
+        Line::raw(""),
+        Line::raw("Each profile keeps its own config, packs, and history."),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(" Radio Shack "));
+    f.render_widget(intro, header);
+
+    let items: Vec<Line> = picker
+        .names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            if i == picker.selected {
+                Line::from(Span::styled(
+                    format!("> {name}"),
+                    Style::default()
+                        .fg(theme.active_fg)
+                        .bg(theme.active_bg)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::raw(format!("  {name}"))
+            }
+        })
+        .collect();
+    let list = Paragraph::new(items).block(framed(theme).title(" Profiles "));
+    f.render_widget(list, body);
+
+    let hints = Paragraph::new(vec![
+        Line::raw("Add one by starting edm with --profile NAME."),
+        Line::raw(""),
+        Line::raw("↑/↓ choose · Enter switch · Esc back"),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+fn render_achievements(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    achievements: &Achievements,
+    theme: &Theme,
+) {
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("ACHIEVEMENTS", title_style)),
+        Line::raw(""),
+        Line::raw(format!(
+            "{} of {} unlocked.",
+            achievements.count(),
+            ACHIEVEMENTS.len()
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(" Trophy Case "));
+    f.render_widget(intro, header);
+
+    let items: Vec<Line> = ACHIEVEMENTS
+        .iter()
+        .map(|achievement| match achievements.unlocked_at(achievement) {
+            Some(at) => Line::from(vec![
+                Span::styled(format!("🏆 {}", achievement.title), title_style),
+                Span::raw(format!(
+                    " · {} · {}",
+                    achievement.description,
+                    at.format("%Y-%m-%d")
+                )),
+            ]),
+            None => Line::from(Span::styled(
+                format!("   {} · {}", achievement.title, achievement.description),
+                Style::default().add_modifier(Modifier::DIM),
+            )),
+        })
+        .collect();
+    let list = Paragraph::new(items).block(framed(theme).title(" Achievements "));
+    f.render_widget(list, body);
+
+    let hints = Paragraph::new(vec![
+        Line::raw("Unlocked by your decisions; purely for show."),
+        Line::raw(""),
+        Line::raw("Esc back"),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+fn render_history(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    view: &HistoryView,
+    app: &App,
+    hits: &mut HitAreas,
+) {
+    let theme = &app.theme;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let total = app.engine.history.recent().count();
+    let search = if view.searching {
+        format!("/{}▏", view.query)
+    } else if view.query.is_empty() {
+        "Press / to search questions, answers, and #tags.".to_string()
+    } else {
+        format!("/{}", view.query)
+    };
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("DECISION HISTORY", title_style)),
+        Line::raw(""),
+        Line::raw(search),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(" Radio Shack "));
+    f.render_widget(intro, header);
+
+    let block = framed(theme).title(format!(" {} of {total} ", view.matches.len()));
+    let rows = usize::from(block.inner(body).height).max(1);
+    let top = view.selected.saturating_sub(rows - 1);
+    hits.list(block.inner(body), top);
+    let decisions: Vec<_> = app.engine.history.recent().collect();
+    let marked = Style::default().fg(theme.active_fg).bg(theme.active_bg);
+    let query = view.query.trim();
+    let lines: Vec<Line> = view
+        .matches
+        .iter()
+        .enumerate()
+        .skip(top)
+        .take(rows)
+        .map(|(i, &position)| {
+            let decision = decisions[position];
+            let selected = i == view.selected;
+            let mut spans = vec![Span::raw(format!(
+                "{} {}",
+                if selected { ">" } else { " " },
+                decision.decided_at.format("%Y-%m-%d %H:%M  ")
+            ))];
+            let mut answer = Style::default().fg(decision.sentiment.color());
+            if decision.vetoed_at.is_some() {
+                answer = answer.add_modifier(Modifier::CROSSED_OUT);
+            }
+            spans.extend(highlighted(&decision.answer, query, answer, marked));
+            match decision.worked_out {
+                Some(true) => spans.push(Span::raw(" 👍")),
+                Some(false) => spans.push(Span::raw(" 👎")),
+                None => {}
+            }
+            if let Some(question) = &decision.question {
+                spans.push(Span::raw("  “"));
+                spans.extend(highlighted(question, query, Style::default(), marked));
+                spans.push(Span::raw("”"));
+            }
+            for tag in &decision.tags {
+                spans.push(Span::raw(" "));
+                let tagged = format!("#{tag}");
+                let style = Style::default().fg(theme.title);
+                spans.extend(highlighted(&tagged, query, style, marked));
+            }
+            let line = Line::from(spans);
+            if selected {
+                line.style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                line
+            }
+        })
+        .collect();
+    let list = if lines.is_empty() {
+        Paragraph::new("Nothing matches.").alignment(Alignment::Center)
+    } else {
+        Paragraph::new(lines)
+    };
+    f.render_widget(list.block(block), body);
+
+    let note = view
+        .matches
+        .get(view.selected)
+        .and_then(|&position| decisions[position].note.as_deref())
+        .and_then(|note| note.lines().next())
+        .unwrap_or("");
+    let hints = Paragraph::new(vec![
+        Line::raw(width::fit(
+            note,
+            usize::from(footer.width.saturating_sub(4)),
+        )),
+        Line::raw(""),
+        Line::raw(if view.searching {
+            "Type to search · Enter done · Esc clear"
+        } else {
+            "↑/↓ scroll · / search · Esc back"
+        }),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+/// `text` in `style`, with every match of `query` in `marked`.
+fn highlighted(text: &str, query: &str, style: Style, marked: Style) -> Vec<Span<'static>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Vec::new();
+    let mut at = 0;
+    for (start, end) in history_view::highlight(text, query) {
+        if start > at {
+            spans.push(Span::styled(
+                chars[at..start].iter().collect::<String>(),
+                style,
+            ));
+        }
+        spans.push(Span::styled(
+            chars[start..end].iter().collect::<String>(),
+            marked,
+        ));
+        at = end;
+    }
+    if at < chars.len() {
+        spans.push(Span::styled(chars[at..].iter().collect::<String>(), style));
+    }
+    spans
+}
+
+fn render_worksheet(f: &mut ratatui::Frame, chunks: &[Rect], sheet: &Worksheet, app: &App) {
+    let theme = &app.theme;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let (pros, cons) = sheet.totals();
+    let lean = sheet.lean();
+    let verdict = if lean > worksheet::TIEBREAKER {
+        "leaning yes"
+    } else if lean < -worksheet::TIEBREAKER {
+        "leaning no"
+    } else {
+        "too close to call"
+    };
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("PROS & CONS", title_style)),
+        Line::raw(""),
+        Line::raw(format!("Pros {pros} · Cons {cons} · {verdict}")),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(match &sheet.question {
+        Some(question) => format!(" {} ", width::fit(question, 60)),
+        None => " Radio Shack ".to_string(),
+    }));
+    f.render_widget(intro, header);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(body);
+    for (column, area, title, color) in [
+        (
+            Column::Pros,
+            columns[0],
+            "Pros",
+            Sentiment::Positive.color(),
+        ),
+        (
+            Column::Cons,
+            columns[1],
+            "Cons",
+            Sentiment::Negative.color(),
+        ),
+    ] {
+        let current = sheet.column == column;
+        let room = usize::from(area.width.saturating_sub(12));
+        let rows: Vec<Line> = sheet
+            .arguments(column)
+            .iter()
+            .enumerate()
+            .map(|(i, argument)| {
+                let text = match &sheet.entry {
+                    Some(entry) if current && entry.index == Some(i) => format!("{}_", entry.text),
+                    _ => argument.text.clone(),
+                };
+                let score = format!(
+                    "{}{}",
+                    "●".repeat(usize::from(argument.score)),
+                    "○".repeat(usize::from(worksheet::MAX_SCORE - argument.score))
+                );
+                let label = format!("{}  {score}", width::fit(&text, room));
+                if current && i == sheet.selected {
+                    Line::from(Span::styled(
+                        format!("> {label}"),
+                        Style::default()
+                            .fg(theme.active_fg)
+                            .bg(theme.active_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::raw(format!("  {label}"))
+                }
+            })
+            .chain(
+                sheet
+                    .entry
+                    .as_ref()
+                    .filter(|entry| current && entry.index.is_none())
+                    .map(|entry| Line::raw(format!("+ {}_", entry.text))),
+            )
+            .collect();
+        let total = match column {
+            Column::Pros => pros,
+            Column::Cons => cons,
+        };
+        let mut block = framed(theme).title(format!(" {title} · {total} "));
+        if current {
+            block = block.border_style(Style::default().fg(color));
+        }
+        f.render_widget(Paragraph::new(rows).block(block), area);
+    }
+
+    let status = match (&sheet.entry, &app.notice) {
+        (Some(_), _) => "Type the argument · Enter done · Esc cancel".to_string(),
+        (None, Some((message, _))) => message.clone(),
+        (None, None) => {
+            "Enter lets the oracle weigh it; close calls may go either way.".to_string()
+        }
+    };
+    let hints = Paragraph::new(vec![
+        Line::raw(status),
+        Line::raw(""),
+        Line::raw(
+            "a add · r reword · Del remove · +/- score · Tab column · Enter decide · Esc close",
+        ),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+fn render_bracket(f: &mut ratatui::Frame, chunks: &[Rect], bracket: &Bracket, app: &App) {
+    let theme = &app.theme;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let headline = match (&bracket.champion, bracket.rounds.len()) {
+        (Some(champion), _) => format!("🏆 {champion}"),
+        (None, 0) => format!("{} entrants", bracket.options.len()),
+        (None, rounds) => format!("Round {}", rounds - 1),
+    };
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("TOURNAMENT", title_style)),
+        Line::raw(""),
+        Line::raw(headline),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(" Radio Shack "));
+    f.render_widget(intro, header);
+
+    if bracket.rounds.is_empty() {
+        let rows: Vec<Line> = bracket
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| {
+                if i == bracket.selected {
+                    Line::from(Span::styled(
+                        format!("> {option}"),
+                        Style::default()
+                            .fg(theme.active_fg)
+                            .bg(theme.active_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::raw(format!("  {option}"))
+                }
+            })
+            .chain(
+                bracket
+                    .entry
+                    .as_ref()
+                    .map(|entry| Line::raw(format!("+ {entry}_"))),
+            )
+            .collect();
+        let list = Paragraph::new(rows).block(framed(theme).title(" Entrants "));
+        f.render_widget(list, body);
+    } else {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(20), Constraint::Length(40)])
+            .split(body);
+        render_bracket_rounds(f, columns[0], bracket, theme);
+        let block = framed(theme).title(" Matchup ");
+        let area = block.inner(columns[1]);
+        f.render_widget(block, columns[1]);
+        if let Some(matchup) = &bracket.matchup {
+            render_board(
+                f,
+                area,
+                matchup,
+                bracket.duel.active(),
+                &[],
+                theme,
+                app.transliteration.as_ref(),
+                None,
+                &app.strings,
+            );
+        }
+    }
+
+    let status = match (&bracket.entry, &app.notice) {
+        (Some(_), _) => "Type the option · Enter done · Esc cancel".to_string(),
+        (None, Some((message, _))) => message.clone(),
+        (None, None) if bracket.running() => "Esc stops the tournament.".to_string(),
+        (None, None) => "Enter seeds the entrants at random and plays it out.".to_string(),
+    };
+    let hints = Paragraph::new(vec![
+        Line::raw(status),
+        Line::raw(""),
+        Line::raw("a add · Del remove · Enter play · Esc close"),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+/// One column per round, pairs of entrants a blank line apart: winners in
+/// bold, the eliminated dimmed, the pair being played highlighted.
+fn render_bracket_rounds(f: &mut ratatui::Frame, area: Rect, bracket: &Bracket, theme: &Theme) {
+    let rounds = &bracket.rounds;
+    let constraints = vec![Constraint::Ratio(1, rounds.len() as u32); rounds.len()];
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(area);
+    let playing = rounds.len() - 2;
+    for (k, (round, column)) in rounds.iter().zip(columns.iter()).enumerate() {
+        let winners = rounds.get(k + 1);
+        let room = usize::from(column.width.saturating_sub(1));
+        let mut lines = Vec::new();
+        for (p, entrant) in round.iter().enumerate() {
+            if p > 0 && p % 2 == 0 {
+                lines.push(Line::raw(""));
+            }
+            let decided = winners.and_then(|winners| winners.get(p / 2));
+            let style = match decided {
+                Some(winner) if winner == entrant => Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+                Some(_) => Style::default().add_modifier(Modifier::DIM | Modifier::CROSSED_OUT),
+                None if k == playing
+                    && winners.is_some_and(|winners| winners.len() == p / 2)
+                    && bracket.matchup.is_some() =>
+                {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                }
+                None => Style::default(),
+            };
+            lines.push(Line::from(Span::styled(
+                width::fit(entrant, room).into_owned(),
+                style,
+            )));
+        }
+        f.render_widget(Paragraph::new(lines), *column);
+    }
+}
+
+/// Cells for an option's name in the matrix.
+const MATRIX_NAME_WIDTH: usize = 20;
+/// Cells for each criterion's column in the matrix.
+const MATRIX_CELL_WIDTH: usize = 12;
+
+fn render_matrix(f: &mut ratatui::Frame, chunks: &[Rect], editor: &MatrixEditor, app: &App) {
+    let theme = &app.theme;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let matrix = &editor.matrix;
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let title = match &editor.entry {
+        Some(entry) if entry.target == Target::Title => format!("{}_", entry.text),
+        _ => matrix.title.clone(),
+    };
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("DECISION MATRIX", title_style)),
+        Line::raw(""),
+        Line::raw(title),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(if editor.changed {
+        " Radio Shack · unsaved "
+    } else {
+        " Radio Shack "
+    }));
+    f.render_widget(intro, header);
+
+    let selected = Style::default()
+        .fg(theme.active_fg)
+        .bg(theme.active_bg)
+        .add_modifier(Modifier::BOLD);
+    let cell = |text: &str| width::pad(&width::fit(text, MATRIX_CELL_WIDTH - 1), MATRIX_CELL_WIDTH);
+    let typed = |target: Target, text: &str| match &editor.entry {
+        Some(entry) if entry.target == target => format!("{}_", entry.text),
+        _ => text.to_string(),
+    };
+
+    let mut heading = vec![Span::styled(
+        width::pad("  Option", MATRIX_NAME_WIDTH + 2),
+        title_style,
+    )];
+    for (j, criterion) in matrix.criteria.iter().enumerate() {
+        let name = typed(Target::Criterion(Some(j)), &criterion.name);
+        heading.push(Span::styled(cell(&name), title_style));
+    }
+    if let Some(entry) = editor
+        .entry
+        .as_ref()
+        .filter(|entry| entry.target == Target::Criterion(None))
+    {
+        heading.push(Span::raw(cell(&format!("+ {}_", entry.text))));
+    }
+    heading.push(Span::styled("Total", title_style));
+
+    let mut weights = vec![Span::raw(width::pad(
+        &format!("{} weight", if editor.row == 0 { ">" } else { " " }),
+        MATRIX_NAME_WIDTH + 2,
+    ))];
+    for (j, criterion) in matrix.criteria.iter().enumerate() {
+        let text = cell(&format!("×{}", criterion.weight));
+        weights.push(if editor.row == 0 && editor.column == j {
+            Span::styled(text, selected)
+        } else {
+            Span::raw(text)
+        });
+    }
+
+    let mut rows = vec![Line::from(heading), Line::from(weights), Line::raw("")];
+    let totals = matrix.totals();
+    let best = totals.iter().copied().max().unwrap_or(0);
+    for (i, option) in matrix.options.iter().enumerate() {
+        let current = editor.row == i + 1;
+        let name = typed(Target::Option(Some(i)), option);
+        let mut spans = vec![Span::raw(format!(
+            "{} {}",
+            if current { ">" } else { " " },
+            width::pad(&width::fit(&name, MATRIX_NAME_WIDTH - 1), MATRIX_NAME_WIDTH)
+        ))];
+        for (j, &score) in matrix.scores[i].iter().enumerate() {
+            let text = cell(&format!(
+                "{}{}",
+                "●".repeat(usize::from(score)),
+                "○".repeat(usize::from(crate::matrix::MAX_SCORE - score))
+            ));
+            spans.push(if current && editor.column == j {
+                Span::styled(text, selected)
+            } else {
+                Span::raw(text)
+            });
+        }
+        let total = totals[i];
+        spans.push(if total == best && best > 0 {
+            Span::styled(total.to_string(), title_style)
+        } else {
+            Span::raw(total.to_string())
+        });
+        rows.push(Line::from(spans));
+    }
+    if let Some(entry) = editor
+        .entry
+        .as_ref()
+        .filter(|entry| entry.target == Target::Option(None))
+    {
+        rows.push(Line::raw(format!("+ {}_", entry.text)));
+    }
+    let table = Paragraph::new(rows).block(framed(theme).title(format!(
+        " {} options · {} criteria ",
+        matrix.options.len(),
+        matrix.criteria.len()
+    )));
+    f.render_widget(table, body);
+
+    let status = match (&editor.entry, &app.notice) {
+        (Some(_), _) => "Type the name · Enter done · Esc cancel".to_string(),
+        (None, Some((message, _))) => message.clone(),
+        (None, None) => "Enter puts the options on the board, biased by their totals.".to_string(),
+    };
+    let hints = Paragraph::new(vec![
+        Line::raw(status),
+        Line::raw(""),
+        Line::raw(
+            "o option · c criterion · r rename · t title · Del remove · +/- score · Tab column · s save · l load",
+        ),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+fn render_pack_editor(f: &mut ratatui::Frame, chunks: &[Rect], editor: &PackEditor, app: &App) {
+    let theme = &app.theme;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("EDIT ANSWERS", title_style)),
+        Line::raw(""),
+        Line::raw("The board on the right is laid out as it will be."),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(format!(" Radio Shack · {} ", editor.pack.title)));
+    f.render_widget(intro, header);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(32), Constraint::Min(20)])
+        .split(body);
+
+    let rows: Vec<Line> = editor
+        .pack
+        .answers
+        .iter()
+        .enumerate()
+        .map(|(i, answer)| {
+            let text = match &editor.entry {
+                Some(entry) if entry.index == Some(i) => format!("{}_", entry.text),
+                _ => answer.label(),
+            };
+            let weight = format!("×{}", answer.weight);
+            let label = format!("{}  {weight}", width::fit(&text, 26 - weight.len()));
+            if i == editor.selected {
+                Line::from(Span::styled(
+                    format!("> {label}"),
+                    Style::default()
+                        .fg(theme.active_fg)
+                        .bg(theme.active_bg)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::raw(format!("  {label}"))
+            }
+        })
+        .chain(
+            editor
+                .entry
+                .as_ref()
+                .filter(|entry| entry.index.is_none())
+                .map(|entry| Line::raw(format!("+ {}_", entry.text))),
+        )
+        .collect();
+    let list = Paragraph::new(rows)
+        .block(framed(theme).title(format!(" Answers · {} ", editor.pack.answers.len())));
+    f.render_widget(list, columns[0]);
+
+    let preview_block = framed(theme).title(" Preview ");
+    let preview_area = preview_block.inner(columns[1]);
+    f.render_widget(preview_block, columns[1]);
+    render_board(
+        f,
+        preview_area,
+        &editor.pack,
+        Some(editor.selected),
+        &[],
+        theme,
+        app.transliteration.as_ref(),
+        None,
+        &app.strings,
+    );
+
+    let status = match (&editor.problem, &editor.entry) {
+        (Some(problem), _) => problem.clone(),
+        (None, Some(_)) => "Type the answer · Enter done · Esc cancel".to_string(),
+        (None, None) if editor.changed => "Enter saves the pack.".to_string(),
+        (None, None) => match &app.notice {
+            Some((message, _)) => message.clone(),
+            None => "Saved to packs/ next to your config.".to_string(),
+        },
+    };
+    let hints = Paragraph::new(vec![
+        Line::raw(status),
+        Line::raw(""),
+        Line::raw("a add · r rename · Del remove · +/- weight · Enter save · Esc discard"),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+/// Warning shown when a recorded chord already belongs to another action.
+fn render_conflict(f: &mut ratatui::Frame, conflict: &Conflict, label: &str, theme: &Theme) {
+    let area = centered_box(56, 6, f.area());
+    let owner = BINDABLE[conflict.owner].2;
+    let lines = vec![
+        Line::raw(format!("{} is already bound to {owner}.", conflict.chord)),
+        Line::raw(format!("Use it for {label} instead?")),
+        Line::raw(""),
+        Line::raw("Enter reassign · Esc cancel"),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.title))
+        .block(
+            framed(theme)
+                .title(" Key already in use ")
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// The blank lock screen: nothing of the session shows through, only the
+/// masked passphrase.
+fn render_lock(f: &mut ratatui::Frame, entry: &str, app: &App) {
+    let area = centered_box(44, 7, f.area());
+    let status = match &app.notice {
+        Some((message, _)) => message.as_str(),
+        None => "Type the passphrase, then Enter",
+    };
+    let lines = vec![
+        Line::raw(""),
+        Line::raw(format!("{}▏", "•".repeat(entry.chars().count()))),
+        Line::raw(""),
+        Line::styled(status, Style::default().fg(app.theme.idle_fg)),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(app.theme.title))
+        .block(framed(&app.theme).title(" Locked "));
+
+    f.render_widget(Clear, f.area());
+    f.render_widget(paragraph, area);
+}
+
+/// The earlier answer to nearly the same question, offered before asking
+fn render_recall(f: &mut ratatui::Frame, recall: &Recall, theme: &Theme) {
+    let area = centered_box(60, 8, f.area());
+    let when = workspace::time_ago(recall.decided_at, chrono::Local::now());
+    let answer_style = Style::default()
+        .fg(theme.active_fg)
+        .bg(theme.active_bg)
+        .add_modifier(Modifier::BOLD);
+    let lines = vec![
+        Line::raw(format!("You asked “{}” {when}.", recall.question)),
+        Line::from(vec![
+            Span::raw("The oracle said "),
+            Span::styled(format!(" {} ", recall.answer), answer_style),
+            Span::raw(format!(" ({})", recall.pack)),
+        ]),
+        Line::raw(""),
+        Line::raw("Enter reuse it · f ask fresh · Esc cancel"),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(theme.title))
+        .block(framed(theme).title(" Asked before "));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// The note editor, scrolled to keep the cursor in view.
+fn render_note(f: &mut ratatui::Frame, area: &TextArea, answer: &str, theme: &Theme) {
+    let rect = centered_box(60, 12, f.area());
+    let block = framed(theme).title(format!(" Note · {answer} "));
+    let inner = block.inner(rect);
+    let rows = usize::from(inner.height.saturating_sub(2)).max(1);
+    let columns = usize::from(inner.width).max(1);
+    let top = area.row.saturating_sub(rows - 1);
+    let left = area.col.saturating_sub(columns - 1);
+
+    let cursor_style = Style::default().fg(theme.active_fg).bg(theme.active_bg);
+    let mut lines: Vec<Line> = area
+        .lines
+        .iter()
+        .enumerate()
+        .skip(top)
+        .take(rows)
+        .map(|(row, text)| {
+            let shown: String = text.chars().skip(left).collect();
+            if row != area.row {
+                return Line::raw(shown);
+            }
+            let at = area.col - left;
+            let before: String = shown.chars().take(at).collect();
+            let under = shown.chars().nth(at).map_or(" ".to_string(), String::from);
+            let after: String = shown.chars().skip(at + 1).collect();
+            Line::from(vec![
+                Span::raw(before),
+                Span::styled(under, cursor_style),
+                Span::raw(after),
+            ])
+        })
+        .collect();
+    lines.resize(rows, Line::raw(""));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Enter new line · Ctrl+S save · Esc discard",
+        Style::default().fg(theme.status),
+    ));
+
+    f.render_widget(Clear, rect);
+    f.render_widget(block, rect);
+    f.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(theme.title)),
+        inner,
+    );
+}
+
+/// Earlier questions like the one being typed, dropped down from the header
+/// with what the oracle said last time.
+fn render_suggestions(f: &mut ratatui::Frame, header: Rect, app: &App) {
+    let theme = &app.theme;
+    let width = header.width.saturating_sub(8).max(20);
+    let area = Rect {
+        x: header.x + (header.width - width.min(header.width)) / 2,
+        y: header.bottom().saturating_sub(1),
+        width: width.min(header.width),
+        height: app.suggestions.len() as u16 + 2,
+    }
+    .intersection(f.area());
+    let now = app.local_now();
+    let room = usize::from(area.width.saturating_sub(4));
+    let line = |i: usize, suggestion: &Suggestion| -> Line<'static> {
+        let said = format!(
+            " → {} · {}",
+            suggestion.answer,
+            workspace::time_ago(suggestion.decided_at, now)
+        );
+        let question = width::fit(
+            &suggestion.question,
+            room.saturating_sub(width::of(&said)).max(8),
+        );
+        let row = Line::from(vec![
+            Span::raw(question.into_owned()),
+            Span::styled(said, Style::default().fg(Color::Gray)),
+        ]);
+        if app.suggestion == Some(i) {
+            row.style(
+                Style::default()
+                    .fg(theme.active_fg)
+                    .bg(theme.active_bg)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            row
+        }
+    };
+    let lines: Vec<Line> = app
+        .suggestions
+        .iter()
+        .enumerate()
+        .map(|(i, suggestion)| line(i, suggestion))
+        .collect();
+    let list =
+        Paragraph::new(lines).block(framed(theme).title(" Asked before · ↓ pick, Enter use "));
+
+    f.render_widget(Clear, area);
+    f.render_widget(list, area);
+}
+
+fn render_tagging(f: &mut ratatui::Frame, text: &str, answer: &str, theme: &Theme) {
+    let area = centered_box(50, 6, f.area());
+    let lines = vec![
+        Line::raw(format!("{text}▏")),
+        Line::raw(""),
+        Line::styled("Enter save · Esc cancel", Style::default().fg(theme.status)),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.title))
+        .block(framed(theme).title(format!(" Tags · {answer} ")));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Launch banner summarizing this workspace's recent rulings, or the answer
+/// a question is committed to
+fn render_banner(f: &mut ratatui::Frame, title: &str, lines: &[String], theme: &Theme) {
+    let width = lines.iter().map(|line| width::of(line)).max().unwrap_or(0) as u16 + 6;
+    let area = centered_box(width, lines.len() as u16 + 2, f.area());
+
+    let text: Vec<Line> = lines.iter().map(|line| Line::raw(line.as_str())).collect();
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.title))
+        .block(framed(theme).title(title));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// The QR code, dark on light whatever the theme so phones can read it,
+/// or what size it needs if the terminal is too small for it.
+fn render_qr(f: &mut ratatui::Frame, rows: &[String], theme: &Theme) {
+    let size = rows.first().map_or(0, |row| width::of(row)) as u16;
+    let (width, height) = (size + 2, rows.len() as u16 + 2);
+    let screen = f.area();
+    let block = framed(theme).title(" Scan me ");
+    if width > screen.width || height > screen.height {
+        let message = format!("Enlarge the terminal to {width}×{height} for the QR code.");
+        let area = centered_box(width::of(&message) as u16 + 4, 3, screen);
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(message)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(theme.status))
+                .block(block),
+            area,
+        );
+        return;
+    }
+    let area = centered_box(width, height, screen);
+    let code: Vec<Line> = rows.iter().map(|row| Line::raw(row.as_str())).collect();
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(code)
+            .style(Style::default().fg(Color::Black).bg(Color::White))
+            .block(block),
+        area,
+    );
+}
+
+/// Streak easter egg: the message between rows of twinkling sparkles.
+fn render_streak(f: &mut ratatui::Frame, message: &str, frame: usize, theme: &Theme) {
+    const SPARKLES: [char; 4] = ['✦', '✧', '·', '✧'];
+    let width = width::of(message) as u16 + 6;
+    let area = centered_box(width, 5, f.area());
+
+    let sparkles = |offset: usize| {
+        let text: String = (0..width.saturating_sub(4) as usize)
+            .map(|i| SPARKLES[(i + frame + offset) % SPARKLES.len()])
+            .collect();
+        Line::styled(text, Style::default().fg(theme.active_bg))
+    };
+    let text = vec![
+        sparkles(0),
+        Line::styled(
+            message.to_string(),
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        ),
+        sparkles(2),
+    ];
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .block(framed(theme).title(" Streak! "));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Small banner naming the theme being previewed.
+fn render_theme_overlay(f: &mut ratatui::Frame, preview: &ThemePreview) {
+    let area = centered_box(56, 5, f.area());
+
+    let theme = preview.current();
+    let name_style = Style::default()
+        .fg(theme.active_fg)
+        .bg(theme.active_bg)
+        .add_modifier(Modifier::BOLD);
+    let lines = vec![
+        Line::from(vec![
+            Span::raw("Theme: "),
+            Span::styled(format!(" {} ", theme.name), name_style),
+            Span::raw(format!(" ({}/{})", preview.index + 1, preview.themes.len())),
+        ]),
+        Line::raw(""),
+        Line::raw("T/↑/↓ cycle · Enter keep · Esc revert"),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(theme.status))
+        .block(framed(preview.current()).title(" Themes "));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// The help text, one string per line, as the help screen shows it before
+/// any right-to-left reordering.
+pub fn help_lines(strings: &Strings) -> Vec<String> {
+    let mut help = vec![
+        strings.get("app-title"),
+        String::new(),
+        strings.get("help-how-to"),
+        format!("  {}", strings.get("help-rule-ask")),
+        format!("  {}", strings.get("help-rule-flash")),
+        String::new(),
+        strings.get("help-controls"),
+    ];
+    help.extend(
+        HELP_KEYS
+            .iter()
+            .map(|(keys, id)| format!("  {}{}", width::pad(keys, 17), strings.get(id))),
+    );
+    help
+}
+
+/// The screens along the top margin, the one shown highlighted.
+fn render_tab_bar(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    shown: screen::Tab,
+    app: &App,
+    hits: &mut HitAreas,
+) {
+    let theme = &app.theme;
+    let strings = &app.strings;
+    let bar = Rect {
+        x: area.x + UI_MARGIN,
+        y: area.y + UI_MARGIN - 1,
+        width: area.width.saturating_sub(2 * UI_MARGIN),
+        height: 1,
+    };
+    let mut spans: Vec<Span> = screen::Tab::ALL
+        .into_iter()
+        .flat_map(|tab| {
+            let style = if tab == shown {
+                Style::default()
+                    .fg(theme.active_fg)
+                    .bg(theme.active_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.status)
+            };
+            let name = strings.get(tab.message_id());
+            [Span::styled(format!(" {name} "), style), Span::raw(" ")]
+        })
+        .collect();
+    spans.push(Span::styled(
+        " [ ] ",
+        Style::default()
+            .fg(theme.status)
+            .add_modifier(Modifier::DIM),
+    ));
+    // Which screen each span is the tab of, in the same order as they end
+    // up on screen.
+    let mut owners: Vec<Option<screen::Tab>> = screen::Tab::ALL
+        .into_iter()
+        .flat_map(|tab| [Some(tab), None])
+        .chain([None])
+        .collect();
+    if strings.is_rtl() {
+        owners.reverse();
+    }
+    let line = directed(strings, spans);
+    let used: u16 = line
+        .spans
+        .iter()
+        .map(|span| width::of(&span.content) as u16)
+        .sum();
+    let mut x = if strings.is_rtl() {
+        bar.x + bar.width.saturating_sub(used)
+    } else {
+        bar.x
+    };
+    for (span, owner) in line.spans.iter().zip(owners) {
+        let span_width = width::of(&span.content) as u16;
+        if let Some(tab) = owner {
+            hits.tab(
+                Rect {
+                    x,
+                    width: span_width,
+                    ..bar
+                },
+                tab,
+            );
+        }
+        x = x.saturating_add(span_width);
+    }
+    f.render_widget(Paragraph::new(line).alignment(start(strings)), bar);
+}
+
+/// The help text, scrolled with the arrows.
+fn render_help(f: &mut ratatui::Frame, chunks: &[Rect], scroll: u16, app: &App) {
+    let theme = &app.theme;
+    let strings = &app.strings;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("HELP", title_style)),
+        Line::raw(""),
+        directed(strings, strings.get("help-rule-ask")),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(" Radio Shack "));
+    f.render_widget(intro, header);
+
+    let help: Vec<Line> = help_lines(strings)
+        .into_iter()
+        .map(|line| directed(strings, line))
+        .collect();
+    let block = framed(theme)
+        .title(format!(" {} ", strings.visual(&strings.get("help-box"))))
+        .title_alignment(start(strings))
+        .style(Style::default().fg(theme.idle_fg));
+    let paragraph = Paragraph::new(help)
+        .style(Style::default().fg(theme.title))
+        .alignment(start(strings))
+        .scroll((scroll, 0))
+        .block(block);
+    f.render_widget(paragraph, body);
+
+    let hints = Paragraph::new(vec![
+        Line::raw(""),
+        Line::raw(""),
+        Line::raw("↑/↓ scroll · [ / ] switch screens · Esc back"),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+/// What `edm stats` prints, with the heatmap as wide as the body allows.
+fn render_stats(f: &mut ratatui::Frame, chunks: &[Rect], app: &App) {
+    let theme = &app.theme;
+    let (header, body, footer) = (chunks[0], chunks[1], chunks[2]);
+    let title_style = Style::default()
+        .fg(theme.title)
+        .add_modifier(Modifier::BOLD);
+    let today = app.local_now().date_naive();
+    let mut decisions: Vec<_> = app.engine.history.recent().collect();
+    decisions.reverse();
+    let summary = Summary::new(&decisions, today);
+
+    let intro = Paragraph::new(vec![
+        Line::from(Span::styled("STATISTICS", title_style)),
+        Line::raw(""),
+        Line::raw(format!(
+            "{} decision(s): {}",
+            summary.decisions, summary.tally
+        )),
+    ])
+    .alignment(Alignment::Center)
+    .block(framed(theme).title(" Radio Shack "));
+    f.render_widget(intro, header);
+
+    let block = framed(theme).title(" Stats ");
+    let inner = block.inner(body);
+    let weeks = usize::from(inner.width).saturating_sub(heatmap::LABEL_WIDTH + 1);
+    let counts = heatmap::counts(decisions.iter().copied());
+    let lines: Vec<Line> = summary
+        .to_string()
+        .lines()
+        .skip(1)
+        .map(str::to_string)
+        .chain([String::new()])
+        .chain(heatmap::render(&counts, today, weeks))
+        .map(Line::raw)
+        .collect();
+    f.render_widget(Paragraph::new(lines).block(block), body);
+
+    let hints = Paragraph::new(vec![
+        Line::raw("Every decision in this profile's history."),
+        Line::raw(""),
+        Line::raw("[ / ] switch screens · Esc back"),
+    ])
+    .alignment(Alignment::Center)
+    .style(Style::default().fg(theme.status))
+    .block(framed(theme).title(" Status "));
+    f.render_widget(hints, footer);
+}
+
+/// The command palette over everything else: the query, then the matching
+/// commands with their keys.
+fn render_palette(f: &mut ratatui::Frame, palette: &CommandPalette, app: &App) {
+    let theme = &app.theme;
+    let area = centered_rect(50, 60, f.area());
+    let block = framed(theme)
+        .title(" Commands ")
+        .style(Style::default().fg(theme.idle_fg));
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let room = usize::from(inner.width);
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("> {}_", palette.query),
+            Style::default()
+                .fg(theme.title)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::raw(""),
+    ];
+    if palette.matches.is_empty() {
+        lines.push(Line::raw("  No command matches."));
+    }
+    let rows = usize::from(inner.height).saturating_sub(lines.len());
+    let first = palette.selected.saturating_sub(rows.saturating_sub(1));
+    for (i, &(command, label)) in palette.matches.iter().enumerate().skip(first).take(rows) {
+        let keys = match command {
+            Command::Action(_, slot) => app
+                .keymap
+                .chords(slot)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+            Command::CycleSound => String::new(),
+        };
+        let marker = if i == palette.selected { "> " } else { "  " };
+        let name = width::pad(
+            &format!("{marker}{label}"),
+            room.saturating_sub(width::of(&keys) + 1),
+        );
+        let text = format!("{name}{keys}");
+        lines.push(if i == palette.selected {
+            Line::from(Span::styled(
+                text,
+                Style::default()
+                    .fg(theme.active_fg)
+                    .bg(theme.active_bg)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        } else {
+            Line::raw(text)
+        });
+    }
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// A box drawn with the theme's border style.
+fn framed(theme: &Theme) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_type(theme.border)
+}
+
+/// A line in screen order. For right-to-left interfaces its spans run from
+/// the right and each one's text is put in display order.
+fn directed<'a>(strings: &Strings, line: impl Into<Line<'a>>) -> Line<'a> {
+    let mut line = line.into();
+    if strings.is_rtl() {
+        line.spans.reverse();
+        for span in &mut line.spans {
+            let visual = strings.visual(&span.content).into_owned();
+            span.content = visual.into();
+        }
+    }
+    line
+}
+
+/// Where lines start: the left edge, or the right one for right-to-left
+/// interfaces.
+fn start(strings: &Strings) -> Alignment {
+    if strings.is_rtl() {
+        Alignment::Right
+    } else {
+        Alignment::Left
+    }
+}
+
+/// Frame timings in the top-right corner, toggled with F12
+fn render_debug_overlay(f: &mut ratatui::Frame, app: &App) {
+    let state = match app.decision.state() {
+        State::Idle => "Idle".to_string(),
+        State::Animating {
+            current_index,
+            final_index,
+            ..
+        } => format!("Animating {current_index} → {final_index}"),
+        State::Charging { final_index, .. } => format!("Charging → {final_index}"),
+        State::Showing { index, .. } => format!("Showing {index}"),
+    };
+    let state = if app.decision.is_paused() {
+        format!("{state} (paused)")
+    } else {
+        state
+    };
+    let stats = app.frame_stats;
+    let millis = |duration: std::time::Duration| format!("{:.1} ms", duration.as_secs_f64() * 1e3);
+    let queued = stats
+        .queued_input
+        .map_or_else(|| "n/a".to_string(), |queued| queued.to_string());
+    let lines = vec![
+        Line::raw(format!("state   {state}")),
+        Line::raw(format!("frame   {}", millis(stats.render))),
+        Line::raw(format!("latency {}", millis(stats.tick_latency))),
+        Line::raw(format!("input   {queued} queued")),
+    ];
+
+    let screen = f.area();
+    let width = 30.min(screen.width);
+    let area = Rect::new(
+        screen.right() - width,
+        screen.y,
+        width,
+        6.min(screen.height),
+    );
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(app.theme.status))
+        .block(framed(&app.theme).title(" Debug "));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// A `width` x `height` box in the middle of `area`, shrunk to fit.
+fn centered_box(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
@@ -0,0 +1,174 @@
+//! Webhook delivery: every completed decision is POSTed as JSON to the
+//! configured `webhook_url`. Requests run on a background thread so a slow
+//! endpoint never stalls the UI; failures come back over a channel and are
+//! shown in the status line. Dropping the [`Webhook`] waits briefly for
+//! queued deliveries to finish.
+
+use crate::sentiment::Sentiment;
+use serde::Serialize;
+use std::{
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use tokio::sync::Notify;
+
+#[derive(Debug, Serialize)]
+pub struct Payload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+    pub answer: String,
+    pub sentiment: Sentiment,
+    pub pack: String,
+    /// RFC 3339 local time of the decision.
+    pub timestamp: String,
+}
+
+/// How long dropping a [`Webhook`] waits for the queue to drain.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+pub struct Webhook {
+    /// `None` only while dropping, so the worker sees the queue close.
+    jobs: Option<Sender<Payload>>,
+    failures: Receiver<String>,
+    thread: Option<JoinHandle<()>>,
+    /// Disconnects when the worker exits.
+    finished: Receiver<()>,
+}
+
+impl Webhook {
+    /// Start the delivery thread for `url`. `wake` is notified after each
+    /// failure so an event loop waiting on it can show the error right away.
+    pub fn spawn(url: String, wake: Arc<Notify>) -> Self {
+        let (jobs, queue) = mpsc::channel::<Payload>();
+        let (report, failures) = mpsc::channel();
+        let (alive, finished) = mpsc::channel::<()>();
+        let thread = thread::spawn(move || {
+            let _alive = alive;
+            for payload in queue {
+                match post_json(&url, &payload) {
+                    Ok(()) => tracing::debug!("webhook delivered"),
+                    Err(err) => {
+                        tracing::warn!(%err, "webhook delivery failed");
+                        let _ = report.send(err);
+                        wake.notify_one();
+                    }
+                }
+            }
+        });
+        Self {
+            jobs: Some(jobs),
+            failures,
+            thread: Some(thread),
+            finished,
+        }
+    }
+
+    pub fn send(&self, payload: Payload) {
+        // The worker only exits when `jobs` is dropped, so this cannot fail.
+        if let Some(jobs) = &self.jobs {
+            let _ = jobs.send(payload);
+        }
+    }
+
+    /// Delivery errors reported since the last call.
+    pub fn failures(&self) -> impl Iterator<Item = String> + '_ {
+        self.failures.try_iter()
+    }
+}
+
+impl Drop for Webhook {
+    /// Close the queue and give the worker [`SHUTDOWN_GRACE`] to deliver
+    /// what is left. A hung endpoint is abandoned rather than holding up exit.
+    fn drop(&mut self) {
+        drop(self.jobs.take());
+        match self.finished.recv_timeout(SHUTDOWN_GRACE) {
+            Err(RecvTimeoutError::Timeout) => {
+                tracing::warn!("webhook still delivering at exit; abandoning it");
+            }
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => {
+                if let Some(thread) = self.thread.take() {
+                    let _ = thread.join();
+                }
+            }
+        }
+    }
+}
+
+/// POST `payload` as JSON, blocking until the endpoint answers.
+#[cfg(feature = "http")]
+pub fn post_json<T: Serialize>(url: &str, payload: &T) -> Result<(), String> {
+    let body = serde_json::to_string(payload).map_err(|err| err.to_string())?;
+    ureq::post(url)
+        .set("Content-Type", "application/json")
+        .timeout(std::time::Duration::from_secs(10))
+        .send_string(&body)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "http"))]
+pub fn post_json<T: Serialize>(_url: &str, _payload: &T) -> Result<(), String> {
+    Err("built without the `http` feature".to_string())
+}
+
+#[cfg(all(test, feature = "http"))]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+    };
+
+    #[test]
+    fn dropping_waits_for_the_queued_delivery() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (received, bodies) = mpsc::channel();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            received.send(String::from_utf8(body).unwrap()).unwrap();
+            // A slow endpoint: the sender must still wait for it.
+            thread::sleep(Duration::from_millis(200));
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+        });
+
+        let webhook = Webhook::spawn(url, Arc::new(Notify::new()));
+        webhook.send(Payload {
+            question: None,
+            answer: "YES".to_string(),
+            sentiment: Sentiment::Positive,
+            pack: "classic".to_string(),
+            timestamp: "2026-01-01T09:00:00+00:00".to_string(),
+        });
+        drop(webhook);
+
+        // The worker only returns once the response is in, so the body
+        // must have arrived by the time `drop` does.
+        let body = bodies.try_recv().expect("delivered before drop returned");
+        server.join().unwrap();
+        assert!(body.contains(r#""answer":"YES""#), "{body}");
+    }
+}
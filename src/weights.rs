@@ -0,0 +1,61 @@
+//! Live weight tuning, opened with `W`: nudge an answer's weight with `+`/`-`
+//! and watch the probabilities and a simulated histogram update at once.
+
+use crate::answers::{draw_weighted, probabilities, AnswerPack};
+
+/// Draws simulated for the preview histogram after every change.
+pub const PREVIEW_DRAWS: usize = 1_000;
+/// Highest weight the editor allows.
+const MAX_WEIGHT: u32 = 99;
+
+pub struct WeightEditor {
+    /// Working copy; the pack in use changes only when the edit is kept.
+    pub pack: AnswerPack,
+    pub selected: usize,
+    /// Per answer, in percent.
+    pub probabilities: Vec<f64>,
+    /// Per answer, out of [`PREVIEW_DRAWS`] simulated draws.
+    pub histogram: Vec<usize>,
+}
+
+impl WeightEditor {
+    pub fn new(pack: &AnswerPack) -> Self {
+        let mut editor = Self {
+            pack: pack.clone(),
+            selected: 0,
+            probabilities: Vec::new(),
+            histogram: Vec::new(),
+        };
+        editor.recompute();
+        editor
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.pack.answers.len() - 1);
+    }
+
+    /// Change the selected answer's weight. The last drawable answer cannot
+    /// go down to 0.
+    pub fn adjust(&mut self, delta: i32) {
+        let weight = self.pack.answers[self.selected].weight;
+        let new = weight.saturating_add_signed(delta).min(MAX_WEIGHT);
+        let others: u32 = self.pack.weights().iter().sum::<u32>() - weight;
+        if new == weight || (new == 0 && others == 0) {
+            return;
+        }
+        self.pack.answers[self.selected].weight = new;
+        self.recompute();
+    }
+
+    fn recompute(&mut self) {
+        let weights = self.pack.weights();
+        self.probabilities = probabilities(&weights);
+        self.histogram = vec![0; weights.len()];
+        for _ in 0..PREVIEW_DRAWS {
+            self.histogram[draw_weighted(&weights)] += 1;
+        }
+    }
+}
@@ -0,0 +1,754 @@
+//! The flashing answer board as a ratatui [`StatefulWidget`], so other
+//! ratatui applications (dashboards, launchers) can embed the oracle.
+//!
+//! [`DecisionMakerWidget`] only draws. The animation lives in a
+//! [`DecisionState`] the host keeps between frames: start it with
+//! [`DecisionState::ask`], call [`DecisionState::tick`] from the event loop,
+//! and render the widget with it.
+//!
+//! With [`DecisionState::set_suspense`], the board goes dark after the
+//! shuffle and the answer only lands once [`DecisionState::charge`] is full.
+//!
+//! Buttons can be [disabled](DecisionState::disable): they are drawn greyed
+//! out and the lights skip them, as elimination mode does with the answers
+//! already out.
+//!
+//! On a true-colour terminal, pass [`DecisionState::glow`] to
+//! [`DecisionMakerWidget::glow`] to paint the lit button with a gradient
+//! that fades as the answer's flash runs out.
+//!
+//! ```no_run
+//! # use executive_decision_maker::{answers, theme, widget::*};
+//! # use ratatui::{backend::TestBackend, Terminal};
+//! # use std::time::Instant;
+//! # let pack = answers::builtin("classic").unwrap();
+//! # let theme = theme::builtin_themes().remove(0);
+//! # let mut terminal = Terminal::new(TestBackend::new(80, 24))?;
+//! let mut decision = DecisionState::default();
+//! decision.ask(pack.answers.len(), Instant::now());
+//!
+//! // every frame:
+//! if let Some(Step::Landed(index)) = decision.tick(Instant::now()) {
+//!     println!("{}", pack.answers[index].text);
+//! }
+//! # terminal.draw(|frame| {
+//! # let area = frame.area();
+//! let board = DecisionMakerWidget::new(&pack, &theme);
+//! frame.render_stateful_widget(board, area, &mut decision);
+//! # })?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::{
+    answers::AnswerPack,
+    locale::Strings,
+    palette,
+    theme::{ButtonStyle, Theme},
+    transliteration::Scheme,
+    width,
+};
+use rand::Rng;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Margin, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, StatefulWidget, Widget},
+};
+use std::time::{Duration, Instant};
+
+/// How long the lights shuffle and the answer flashes, unless the config
+/// says otherwise.
+pub const ANIMATION_DURATION_MS: u64 = 2_000;
+pub const ANIMATION_STEP_MS: u64 = 120;
+pub const ANSWER_FLASH_MS: u64 = 1_500;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum State {
+    Idle,
+    Animating {
+        final_index: usize,
+        current_index: usize,
+        end_at: Instant,
+        next_switch: Instant,
+    },
+    /// The shuffle is over and the board dark until `until`, when
+    /// `final_index` lands.
+    Charging {
+        final_index: usize,
+        started: Instant,
+        until: Instant,
+    },
+    Showing {
+        index: usize,
+        until: Instant,
+    },
+}
+
+/// How the lights move while shuffling.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Shuffle {
+    /// A random light every step, at a steady pace.
+    #[default]
+    Random,
+    /// Round the board in order, slowing down towards the end (the
+    /// `roulette` experiment).
+    Roulette,
+}
+
+/// What a [`DecisionState::tick`] changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// Another light came on while shuffling.
+    Switched,
+    /// The shuffle ended and the board went dark to build suspense.
+    Charging,
+    /// The lights stopped on the answer at this index.
+    Landed(usize),
+    /// The final answer stopped flashing.
+    Cleared,
+}
+
+/// The board's animation, kept by the host between frames.
+#[derive(Clone, Debug)]
+pub struct DecisionState {
+    state: State,
+    /// Answers on the board being animated.
+    count: usize,
+    shuffle: Shuffle,
+    /// How long a shuffle and the flash after it last.
+    shuffle_time: Duration,
+    flash_time: Duration,
+    /// How long the board stays dark between shuffle and answer, if at all.
+    suspense: Option<Duration>,
+    /// Frozen since then: every timer is pushed back on resume.
+    paused_at: Option<Instant>,
+    /// Per button, whether it is greyed out and skipped by the lights.
+    disabled: Vec<bool>,
+}
+
+impl Default for DecisionState {
+    fn default() -> Self {
+        Self {
+            state: State::Idle,
+            count: 0,
+            shuffle: Shuffle::Random,
+            shuffle_time: Duration::from_millis(ANIMATION_DURATION_MS),
+            flash_time: Duration::from_millis(ANSWER_FLASH_MS),
+            suspense: None,
+            paused_at: None,
+            disabled: Vec::new(),
+        }
+    }
+}
+
+impl DecisionState {
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Shuffling, or charging up for the reveal.
+    pub fn is_animating(&self) -> bool {
+        matches!(self.state, State::Animating { .. } | State::Charging { .. })
+    }
+
+    /// When [`DecisionState::tick`] will next have something to do.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        if self.paused_at.is_some() {
+            return None;
+        }
+        match self.state {
+            State::Idle => None,
+            State::Animating {
+                end_at,
+                next_switch,
+                ..
+            } => Some(end_at.min(next_switch)),
+            State::Charging { until, .. } | State::Showing { until, .. } => Some(until),
+        }
+    }
+
+    /// How far the shuffle has got, in percent, while animating.
+    pub fn progress(&self, now: Instant) -> Option<u8> {
+        let State::Animating { end_at, .. } = self.state else {
+            return None;
+        };
+        let now = self.paused_at.unwrap_or(now);
+        let total = self.shuffle_time;
+        let left = end_at.saturating_duration_since(now).min(total);
+        Some((100 - left.as_millis() * 100 / total.as_millis().max(1)) as u8)
+    }
+
+    /// How full the suspense bar is at `now`, from 0 to 1, while the board
+    /// is dark.
+    pub fn charge(&self, now: Instant) -> Option<f64> {
+        let State::Charging { started, until, .. } = self.state else {
+            return None;
+        };
+        let now = self.paused_at.unwrap_or(now);
+        let total = until.saturating_duration_since(started).as_secs_f64();
+        let done = now.saturating_duration_since(started).as_secs_f64();
+        Some(if total > 0.0 {
+            (done / total).min(1.0)
+        } else {
+            1.0
+        })
+    }
+
+    /// How bright the lit button's glow is at `now`: full while shuffling,
+    /// then easing off over the flash. `None` with every light off.
+    pub fn glow(&self, now: Instant) -> Option<f64> {
+        match self.state {
+            State::Idle | State::Charging { .. } => None,
+            State::Animating { .. } => Some(1.0),
+            State::Showing { until, .. } => {
+                let now = self.paused_at.unwrap_or(now);
+                let total = self.flash_time;
+                let left = until.saturating_duration_since(now).min(total);
+                let left = left.as_secs_f64() / total.as_secs_f64();
+                // Smoothstep, so the fade neither starts nor ends abruptly.
+                Some(left * left * (3.0 - 2.0 * left))
+            }
+        }
+    }
+
+    /// The lit answer, if any.
+    pub fn active(&self) -> Option<usize> {
+        match self.state {
+            State::Animating { current_index, .. } => Some(current_index),
+            State::Showing { index, .. } => Some(index),
+            State::Idle | State::Charging { .. } => None,
+        }
+    }
+
+    /// Grey the button at `index` out; the lights skip it from now on.
+    pub fn disable(&mut self, index: usize) {
+        if self.disabled.len() <= index {
+            self.disabled.resize(index + 1, false);
+        }
+        self.disabled[index] = true;
+    }
+
+    /// Bring every greyed-out button back.
+    pub fn enable_all(&mut self) {
+        self.disabled.clear();
+    }
+
+    pub fn is_disabled(&self, index: usize) -> bool {
+        self.disabled.get(index).copied().unwrap_or(false)
+    }
+
+    /// Per button, whether it is disabled; buttons past the end are not.
+    pub fn disabled(&self) -> &[bool] {
+        &self.disabled
+    }
+
+    /// Buttons of the board the lights may visit, in order.
+    fn lit(&self) -> Vec<usize> {
+        (0..self.count)
+            .filter(|&index| !self.is_disabled(index))
+            .collect()
+    }
+
+    /// A random button the lights may visit other than `avoid`, when there
+    /// is one.
+    fn random_lit_except(&self, avoid: usize) -> usize {
+        let lit = self.lit();
+        match lit.iter().position(|&index| index == avoid) {
+            Some(position) if lit.len() > 1 => {
+                let pick = random_index_except(lit.len(), position);
+                lit[pick]
+            }
+            _ if !lit.is_empty() => lit[rand::thread_rng().gen_range(0..lit.len())],
+            _ => avoid,
+        }
+    }
+
+    /// The button `steps` after `from` among those the lights may visit,
+    /// going round the board; negative goes back.
+    fn lit_after(&self, from: usize, steps: isize) -> usize {
+        let lit = self.lit();
+        let Some(position) = lit.iter().position(|&index| index == from) else {
+            return from;
+        };
+        let len = lit.len() as isize;
+        lit[(position as isize + steps).rem_euclid(len) as usize]
+    }
+
+    /// Shuffle the lights of a `count`-answer board, landing on a random
+    /// answer that is not disabled. Returns its index, or `None` (and leaves
+    /// the board as it was) when there is no such answer.
+    pub fn ask(&mut self, count: usize, now: Instant) -> Option<usize> {
+        let lit: Vec<usize> = (0..count)
+            .filter(|&index| !self.is_disabled(index))
+            .collect();
+        if lit.is_empty() {
+            return None;
+        }
+        // Avoiding a position past the end leaves every answer possible.
+        let index = lit[random_index_except(lit.len(), lit.len())];
+        self.start(index, count, now);
+        Some(index)
+    }
+
+    /// How the next shuffle moves the lights.
+    pub fn set_shuffle(&mut self, shuffle: Shuffle) {
+        self.shuffle = shuffle;
+    }
+
+    /// How long the next shuffle, and the flash after it, last.
+    pub fn set_timings(&mut self, shuffle: Duration, flash: Duration) {
+        self.shuffle_time = shuffle;
+        self.flash_time = flash;
+    }
+
+    /// Keep the board dark for `suspense` between the next shuffle and its
+    /// answer; `None` reveals it straight away.
+    pub fn set_suspense(&mut self, suspense: Option<Duration>) {
+        self.suspense = suspense;
+    }
+
+    /// Shuffle the lights, landing on `final_index` (already decided, e.g.
+    /// by a sealed draw).
+    pub fn start(&mut self, final_index: usize, count: usize, now: Instant) {
+        self.count = count;
+        let current_index = match self.shuffle {
+            Shuffle::Random => self.random_lit_except(final_index),
+            // Start far enough back that the last step lands on the answer.
+            Shuffle::Roulette => {
+                let switches = roulette_switches(self.shuffle_time);
+                self.lit_after(final_index, -(switches as isize))
+            }
+        };
+        self.state = State::Animating {
+            final_index,
+            current_index,
+            end_at: now + self.shuffle_time,
+            next_switch: now,
+        };
+    }
+
+    /// Light `index` straight away, without shuffling.
+    pub fn show(&mut self, index: usize, count: usize, now: Instant) {
+        self.count = count;
+        self.state = State::Showing {
+            index,
+            until: now + self.flash_time,
+        };
+    }
+
+    /// Turn every light off and bring disabled buttons back, e.g. after the
+    /// answers changed.
+    pub fn reset(&mut self) {
+        self.state = State::Idle;
+        self.paused_at = None;
+        self.disabled.clear();
+    }
+
+    /// Freeze the shuffle and the flash where they are, e.g. while the
+    /// terminal is in the background.
+    pub fn pause(&mut self, now: Instant) {
+        self.paused_at.get_or_insert(now);
+    }
+
+    /// Carry on from where [`DecisionState::pause`] stopped.
+    pub fn resume(&mut self, now: Instant) {
+        let Some(paused_at) = self.paused_at.take() else {
+            return;
+        };
+        let away = now.saturating_duration_since(paused_at);
+        match &mut self.state {
+            State::Idle => {}
+            State::Animating {
+                end_at,
+                next_switch,
+                ..
+            } => {
+                *end_at += away;
+                *next_switch += away;
+            }
+            State::Charging { started, until, .. } => {
+                *started += away;
+                *until += away;
+            }
+            State::Showing { until, .. } => *until += away,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Advance the animation to `now`. Nothing moves while paused.
+    pub fn tick(&mut self, now: Instant) -> Option<Step> {
+        if self.paused_at.is_some() {
+            return None;
+        }
+        match self.state {
+            State::Idle => None,
+            State::Animating {
+                final_index,
+                current_index,
+                end_at,
+                next_switch,
+            } => {
+                if let (true, Some(suspense)) = (now >= end_at, self.suspense) {
+                    self.state = State::Charging {
+                        final_index,
+                        started: now,
+                        until: now + suspense,
+                    };
+                    Some(Step::Charging)
+                } else if now >= end_at {
+                    self.state = State::Showing {
+                        index: final_index,
+                        until: now + self.flash_time,
+                    };
+                    Some(Step::Landed(final_index))
+                } else if now >= next_switch {
+                    let (current_index, step) = match self.shuffle {
+                        Shuffle::Random => (
+                            self.random_lit_except(current_index),
+                            Duration::from_millis(ANIMATION_STEP_MS),
+                        ),
+                        Shuffle::Roulette => (
+                            self.lit_after(current_index, 1),
+                            roulette_step(end_at.saturating_duration_since(now), self.shuffle_time),
+                        ),
+                    };
+                    self.state = State::Animating {
+                        final_index,
+                        current_index,
+                        end_at,
+                        next_switch: now + step,
+                    };
+                    Some(Step::Switched)
+                } else {
+                    None
+                }
+            }
+            State::Charging {
+                final_index, until, ..
+            } => {
+                if now >= until {
+                    self.state = State::Showing {
+                        index: final_index,
+                        until: now + self.flash_time,
+                    };
+                    Some(Step::Landed(final_index))
+                } else {
+                    None
+                }
+            }
+            State::Showing { until, .. } => {
+                if now >= until {
+                    self.state = State::Idle;
+                    Some(Step::Cleared)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Time to the next roulette step with `left` of a `total` shuffle to go:
+/// one normal step at the start, four by the end.
+fn roulette_step(left: Duration, total: Duration) -> Duration {
+    let done = 1.0 - left.min(total).as_secs_f64() / total.as_secs_f64();
+    Duration::from_millis(ANIMATION_STEP_MS).mul_f64(1.0 + 3.0 * done * done)
+}
+
+/// Lights a `total` roulette shuffle switches through, counting the first
+/// one.
+fn roulette_switches(total: Duration) -> usize {
+    let mut elapsed = Duration::ZERO;
+    let mut switches = 0;
+    while elapsed < total {
+        switches += 1;
+        elapsed += roulette_step(total - elapsed, total);
+    }
+    switches
+}
+
+/// The answer board: one button per answer, the lit one highlighted.
+pub struct DecisionMakerWidget<'a> {
+    pack: &'a AnswerPack,
+    theme: &'a Theme,
+    transliteration: Option<&'a Scheme>,
+    glow: Option<f64>,
+    strings: Option<&'a Strings>,
+}
+
+impl<'a> DecisionMakerWidget<'a> {
+    pub fn new(pack: &'a AnswerPack, theme: &'a Theme) -> Self {
+        Self {
+            pack,
+            theme,
+            transliteration: None,
+            glow: None,
+            strings: None,
+        }
+    }
+
+    /// Show a Latin line under answers written in another script.
+    pub fn transliteration(mut self, scheme: &'a Scheme) -> Self {
+        self.transliteration = Some(scheme);
+        self
+    }
+
+    /// Paint the lit button with a gradient at this brightness (see
+    /// [`DecisionState::glow`]). Needs a true-colour terminal; without it
+    /// the button keeps the theme's flat colours.
+    pub fn glow(mut self, glow: f64) -> Self {
+        self.glow = Some(glow);
+        self
+    }
+
+    /// Lay the board out for the interface language: right-to-left ones
+    /// fill each row from the right and get their text in display order.
+    pub fn strings(mut self, strings: &'a Strings) -> Self {
+        self.strings = Some(strings);
+        self
+    }
+}
+
+impl StatefulWidget for DecisionMakerWidget<'_> {
+    type State = DecisionState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut DecisionState) {
+        render_board(
+            area,
+            buf,
+            self.pack,
+            state.active(),
+            state.disabled(),
+            self.theme,
+            self.transliteration,
+            self.glow,
+            self.strings,
+        );
+    }
+}
+
+/// Lay the pack's answers out in a grid, returning the unused area below it.
+/// Buttons lose their borders when the area is too short for the full grid,
+/// and transliterations go first when even that is not enough. With a
+/// `glow`, the lit button gets a true-colour gradient instead of flat colours.
+/// `strings` of a right-to-left language mirror the grid. Buttons marked in
+/// `disabled` are greyed out.
+#[allow(clippy::too_many_arguments)]
+pub fn render_board(
+    area: Rect,
+    buf: &mut Buffer,
+    pack: &AnswerPack,
+    active: Option<usize>,
+    disabled: &[bool],
+    theme: &Theme,
+    transliteration: Option<&Scheme>,
+    glow: Option<f64>,
+    strings: Option<&Strings>,
+) -> Rect {
+    let rtl = strings.is_some_and(Strings::is_rtl);
+    let count = pack.answers.len();
+    let columns = board_columns(count);
+    let row_count = count.div_ceil(columns);
+    let mut latin: Vec<Option<String>> = pack
+        .answers
+        .iter()
+        .map(|answer| transliteration.and_then(|scheme| scheme.apply(&answer.text)))
+        .collect();
+    let mut lines = if latin.iter().any(Option::is_some) {
+        2
+    } else {
+        1
+    };
+    if row_count * lines > area.height as usize {
+        latin.fill(None);
+        lines = 1;
+    }
+    let bordered = row_count * (lines + 2) <= area.height as usize;
+    let row_height = if bordered { lines + 2 } else { lines } as u16;
+
+    let mut row_constraints = vec![Constraint::Length(row_height); row_count];
+    row_constraints.push(Constraint::Min(0));
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(row_constraints)
+        .split(area);
+
+    for (row_index, chunk) in pack.answers.chunks(columns).enumerate() {
+        let cells = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+            .split(rows[row_index]);
+        for (column, answer) in chunk.iter().enumerate() {
+            let index = row_index * columns + column;
+            let colors = theme.button(answer);
+            let cell = if rtl {
+                cells[columns - 1 - column]
+            } else {
+                cells[column]
+            };
+            let label = answer.label();
+            let text = match strings {
+                Some(strings) => strings.visual(&label),
+                None => label.as_str().into(),
+            };
+            draw_button(
+                cell,
+                buf,
+                &text,
+                latin[index].as_deref(),
+                active == Some(index),
+                disabled.get(index).copied().unwrap_or(false),
+                bordered,
+                theme,
+                colors,
+            );
+            if let (true, Some(glow)) = (active == Some(index), glow) {
+                paint_glow(cell, buf, bordered, colors.active_bg, glow);
+            }
+        }
+    }
+
+    rows[row_count]
+}
+
+/// Buttons per row for a pack of `count` answers.
+pub fn board_columns(count: usize) -> usize {
+    if count <= 9 {
+        3
+    } else {
+        4
+    }
+}
+
+/// Render a single answer button in `colors`, or greyed out and crossed
+/// through when `disabled`, with its transliteration below the text. Text wider than the button is cut short by display
+/// width, so wide glyphs never spill onto the border, and is centred by
+/// display width too.
+#[allow(clippy::too_many_arguments)]
+fn draw_button(
+    area: Rect,
+    buf: &mut Buffer,
+    text: &str,
+    latin: Option<&str>,
+    active: bool,
+    disabled: bool,
+    bordered: bool,
+    theme: &Theme,
+    colors: ButtonStyle,
+) {
+    let style = if disabled {
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::DIM | Modifier::CROSSED_OUT)
+    } else if active {
+        Style::default()
+            .fg(colors.active_fg)
+            .bg(colors.active_bg)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(colors.fg).bg(colors.bg)
+    };
+
+    let room = if bordered {
+        area.width.saturating_sub(2)
+    } else {
+        area.width
+    } as usize;
+    let centred = |text: &str| width::center(&width::fit(text, room), room);
+    let mut lines = vec![Line::from(Span::styled(centred(text), style))];
+    if let Some(latin) = latin {
+        lines.push(Line::from(Span::styled(
+            centred(latin),
+            style.add_modifier(Modifier::ITALIC),
+        )));
+    }
+    let widget = Paragraph::new(lines);
+    let widget = if bordered {
+        widget.block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(theme.border),
+        )
+    } else {
+        widget
+    };
+    widget.render(area, buf);
+}
+
+/// Repaint a lit button as a horizontal gradient: brightest in the middle,
+/// where `glow` lifts the `highlight` towards white, and darker at the
+/// edges. The border glows along with it.
+fn paint_glow(area: Rect, buf: &mut Buffer, bordered: bool, highlight: Color, glow: f64) {
+    let face = if bordered {
+        area.inner(Margin::new(1, 1))
+    } else {
+        area
+    };
+    if face.width == 0 {
+        return;
+    }
+    let centre = f64::from(face.width - 1) / 2.0;
+    let bright = palette::mix(highlight, Color::White, 0.45 * glow);
+    let edge = palette::mix(highlight, Color::Black, 0.35);
+    for x in face.left()..face.right() {
+        let off_centre = if centre > 0.0 {
+            (f64::from(x - face.left()) - centre).abs() / centre
+        } else {
+            0.0
+        };
+        let bg = palette::mix(bright, edge, off_centre * off_centre);
+        for y in face.top()..face.bottom() {
+            buf[(x, y)].set_bg(bg);
+        }
+    }
+    if bordered {
+        let border = palette::mix(highlight, Color::White, 0.2 + 0.5 * glow);
+        for x in area.left()..area.right() {
+            for y in area.top()..area.bottom() {
+                if !face.contains(Position::new(x, y)) {
+                    buf[(x, y)].set_fg(border);
+                }
+            }
+        }
+    }
+}
+
+/// A random index in `0..len` that differs from `avoid` (when possible).
+pub fn random_index_except(len: usize, avoid: usize) -> usize {
+    let mut rng = rand::thread_rng();
+    let mut index = rng.gen_range(0..len);
+    if len > 1 {
+        while index == avoid {
+            index = rng.gen_range(0..len);
+        }
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asking_an_empty_board_draws_nothing() {
+        let mut decision = DecisionState::default();
+        assert_eq!(decision.ask(0, Instant::now()), None);
+        assert_eq!(decision.state(), State::Idle);
+    }
+
+    #[test]
+    fn asking_skips_disabled_buttons() {
+        let mut decision = DecisionState::default();
+        decision.disable(0);
+        decision.disable(2);
+        for _ in 0..20 {
+            assert_eq!(decision.ask(3, Instant::now()), Some(1));
+        }
+        decision.disable(1);
+        assert_eq!(decision.ask(3, Instant::now()), None);
+    }
+}
@@ -0,0 +1,50 @@
+//! Display width of answer text.
+//!
+//! Emoji and CJK characters take two terminal cells, combining marks none,
+//! so laying text out by `chars().count()` misjudges packs written in them.
+//! These helpers measure in cells instead.
+
+use std::borrow::Cow;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Cells `text` takes on screen.
+pub fn of(text: &str) -> usize {
+    text.width()
+}
+
+/// `text` cut down to at most `width` cells, ending in `…` when it had to
+/// be shortened. A wide character is never split in half.
+pub fn fit(text: &str, width: usize) -> Cow<'_, str> {
+    if text.width() <= width {
+        return Cow::Borrowed(text);
+    }
+    let Some(room) = width.checked_sub(1) else {
+        return Cow::Borrowed("");
+    };
+    let mut used = 0;
+    let mut fitted = String::new();
+    for c in text.chars() {
+        let cells = c.width().unwrap_or(0);
+        if used + cells > room {
+            break;
+        }
+        used += cells;
+        fitted.push(c);
+    }
+    fitted.push('…');
+    Cow::Owned(fitted)
+}
+
+/// `text` with spaces on the left that centre it in `width` cells.
+/// Centring by cells keeps emoji and CJK text in the middle where counting
+/// characters would push it right.
+pub fn center(text: &str, width: usize) -> String {
+    let gap = width.saturating_sub(text.width()) / 2;
+    format!("{}{text}", " ".repeat(gap))
+}
+
+/// `text` padded with spaces on the right to `width` cells.
+pub fn pad(text: &str, width: usize) -> String {
+    let gap = width.saturating_sub(text.width());
+    format!("{text}{}", " ".repeat(gap))
+}
@@ -0,0 +1,225 @@
+//! Pros and cons worksheet, opened with `w`: list the arguments for and
+//! against in two columns, score each from 1 to [`MAX_SCORE`], and let the
+//! oracle weigh them. Enter runs the usual animation, landing on an answer
+//! whose sentiment follows the totals.
+//!
+//! The lean is `(pros - cons) / (pros + cons)`, from -1 to 1. A random
+//! tiebreaker of up to [`TIEBREAKER`] either way is added before the sign
+//! picks positive or negative answers, so a close call can go either way
+//! and a lopsided one can't. Within the side, weights decide as usual.
+
+use crate::answers::{draw_weighted, AnswerPack};
+use crate::sentiment::Sentiment;
+use rand::Rng;
+
+/// Highest score an argument can have.
+pub const MAX_SCORE: u8 = 5;
+/// Score of a new argument.
+const DEFAULT_SCORE: u8 = 3;
+/// How far the random tiebreaker can move the lean.
+pub const TIEBREAKER: f64 = 0.25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Pros,
+    Cons,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Argument {
+    pub text: String,
+    pub score: u8,
+}
+
+/// Text being typed for an argument.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The argument being reworded, or `None` for a new one.
+    pub index: Option<usize>,
+    pub text: String,
+}
+
+pub struct Worksheet {
+    /// What is being decided, if a question was typed.
+    pub question: Option<String>,
+    pub pros: Vec<Argument>,
+    pub cons: Vec<Argument>,
+    /// The column the selection is in.
+    pub column: Column,
+    /// Index into the selected column.
+    pub selected: usize,
+    /// Open while an argument's text is typed.
+    pub entry: Option<Entry>,
+}
+
+impl Worksheet {
+    pub fn new(question: Option<&str>) -> Self {
+        Self {
+            question: question.map(str::to_string),
+            pros: Vec::new(),
+            cons: Vec::new(),
+            column: Column::Pros,
+            selected: 0,
+            entry: None,
+        }
+    }
+
+    /// The arguments in `column`.
+    pub fn arguments(&self, column: Column) -> &[Argument] {
+        match column {
+            Column::Pros => &self.pros,
+            Column::Cons => &self.cons,
+        }
+    }
+
+    fn selected_column(&mut self) -> &mut Vec<Argument> {
+        match self.column {
+            Column::Pros => &mut self.pros,
+            Column::Cons => &mut self.cons,
+        }
+    }
+
+    /// Total score of the pros and of the cons.
+    pub fn totals(&self) -> (u32, u32) {
+        let total = |arguments: &[Argument]| {
+            arguments
+                .iter()
+                .map(|argument| u32::from(argument.score))
+                .sum()
+        };
+        (total(&self.pros), total(&self.cons))
+    }
+
+    /// Which way the arguments lean, from -1 (all cons) to 1 (all pros).
+    pub fn lean(&self) -> f64 {
+        let (pros, cons) = self.totals();
+        if pros + cons == 0 {
+            return 0.0;
+        }
+        (f64::from(pros) - f64::from(cons)) / f64::from(pros + cons)
+    }
+
+    /// The index in `pack` of the verdict: the lean plus a tiebreaker picks
+    /// the sentiment, and the weights pick among its answers. A pack with
+    /// no drawable answer of that sentiment draws from all of them.
+    pub fn verdict(&self, pack: &AnswerPack) -> usize {
+        let tiebreaker = rand::thread_rng().gen_range(-TIEBREAKER..=TIEBREAKER);
+        let score = self.lean() + tiebreaker;
+        let side = if score > 0.0 {
+            Sentiment::Positive
+        } else if score < 0.0 {
+            Sentiment::Negative
+        } else {
+            Sentiment::Neutral
+        };
+        tracing::debug!(lean = self.lean(), tiebreaker, ?side, "worksheet weighed");
+        let weights: Vec<u32> = pack
+            .answers
+            .iter()
+            .map(|answer| {
+                if answer.sentiment == side {
+                    answer.weight
+                } else {
+                    0
+                }
+            })
+            .collect();
+        if weights.iter().all(|&weight| weight == 0) {
+            return pack.draw_index();
+        }
+        draw_weighted(&weights)
+    }
+
+    /// Switch to the other column.
+    pub fn switch_column(&mut self) {
+        self.column = match self.column {
+            Column::Pros => Column::Cons,
+            Column::Cons => Column::Pros,
+        };
+        self.selected = 0;
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let len = self.arguments(self.column).len();
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
+
+    /// Change the selected argument's score, within 1 and [`MAX_SCORE`].
+    pub fn adjust(&mut self, delta: i32) {
+        let selected = self.selected;
+        if let Some(argument) = self.selected_column().get_mut(selected) {
+            let score = i32::from(argument.score) + delta;
+            argument.score = score.clamp(1, i32::from(MAX_SCORE)) as u8;
+        }
+    }
+
+    /// Start typing a new argument in the selected column.
+    pub fn start_add(&mut self) {
+        self.entry = Some(Entry {
+            index: None,
+            text: String::new(),
+        });
+    }
+
+    /// Start rewording the selected argument.
+    pub fn start_rename(&mut self) {
+        let Some(argument) = self.arguments(self.column).get(self.selected) else {
+            return;
+        };
+        self.entry = Some(Entry {
+            index: Some(self.selected),
+            text: argument.text.clone(),
+        });
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        if let Some(entry) = self.entry.as_mut() {
+            entry.text.push(c);
+        }
+    }
+
+    pub fn erase(&mut self) {
+        if let Some(entry) = self.entry.as_mut() {
+            entry.text.pop();
+        }
+    }
+
+    pub fn cancel_entry(&mut self) {
+        self.entry = None;
+    }
+
+    /// Use the typed text; an empty one is dropped.
+    pub fn confirm_entry(&mut self) {
+        let Some(entry) = self.entry.take() else {
+            return;
+        };
+        let text = entry.text.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        match entry.index {
+            Some(index) => self.selected_column()[index].text = text,
+            None => {
+                let column = self.selected_column();
+                column.push(Argument {
+                    text,
+                    score: DEFAULT_SCORE,
+                });
+                self.selected = column.len() - 1;
+            }
+        }
+    }
+
+    /// Remove the selected argument.
+    pub fn remove(&mut self) {
+        let selected = self.selected;
+        let column = self.selected_column();
+        if selected < column.len() {
+            column.remove(selected);
+        }
+        self.move_by(0);
+    }
+}
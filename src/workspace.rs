@@ -0,0 +1,74 @@
+//! Workspace-local history: a project opts in by having an `.edm/`
+//! directory (in it or any parent), and decisions made there are kept in
+//! `.edm/history.jsonl`. Launching inside the project greets you with a short
+//! summary of its recent rulings.
+
+use crate::history::History;
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+
+const DIR: &str = ".edm";
+const HISTORY_FILE: &str = "history.jsonl";
+/// Expired decisions listed in the launch banner.
+const REVALIDATION_SHOWN: usize = 3;
+
+/// The history file of the workspace containing `start`, if any.
+pub fn history_path(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(DIR))
+        .find(|dir| dir.is_dir())
+        .map(|dir| dir.join(HISTORY_FILE))
+}
+
+/// Lines for the launch banner, or `None` when nothing was decided here yet.
+pub fn summary(history: &History, now: DateTime<Local>) -> Option<Vec<String>> {
+    let last = history.recent().next()?;
+    let total = history.recent().count();
+    let mut lines = vec![format!(
+        "Last decision here: {}, {}",
+        last.answer,
+        time_ago(last.decided_at, now)
+    )];
+    if let Some(question) = &last.question {
+        lines.push(format!("(“{question}”)"));
+    }
+    lines.push(format!(
+        "{total} decision{} in this project",
+        if total == 1 { "" } else { "s" }
+    ));
+    let expired = history.needs_revalidation(now);
+    if !expired.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("Needs re-validation (press R): {}", expired.len()));
+        for decision in expired.iter().take(REVALIDATION_SHOWN) {
+            let question = decision.question.as_deref().unwrap_or_default();
+            let when = decision
+                .expires_at
+                .map(|expires_at| time_ago(expires_at, now))
+                .unwrap_or_default();
+            lines.push(format!("“{question}”: {}, expired {when}", decision.answer));
+        }
+    }
+    Some(lines)
+}
+
+/// "just now", "5 minutes ago", "yesterday", "3 days ago", ...
+pub fn time_ago(then: DateTime<Local>, now: DateTime<Local>) -> String {
+    let elapsed = now - then;
+    if elapsed.num_minutes() < 1 {
+        "just now".to_string()
+    } else if elapsed.num_hours() < 1 {
+        ago(elapsed.num_minutes(), "minute")
+    } else if elapsed.num_days() < 1 {
+        ago(elapsed.num_hours(), "hour")
+    } else if elapsed.num_days() == 1 {
+        "yesterday".to_string()
+    } else {
+        ago(elapsed.num_days(), "day")
+    }
+}
+
+fn ago(count: i64, unit: &str) -> String {
+    format!("{count} {unit}{} ago", if count == 1 { "" } else { "s" })
+}
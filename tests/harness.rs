@@ -0,0 +1,195 @@
+//! The app driven headless through [`Harness`]: key presses in, state and
+//! rendered screens out.
+
+use executive_decision_maker::{
+    answers,
+    config::Config,
+    harness::Harness,
+    input::Mode,
+    screen::{Screen, Tab},
+    widget::State,
+};
+use std::time::Duration;
+
+fn classic(width: u16, height: u16) -> Harness {
+    let pack = answers::builtin("classic").expect("classic pack");
+    Harness::new(pack, Config::default(), width, height).expect("test terminal")
+}
+
+/// The first row is left blank above the tab bar.
+const IDLE_BOARD: &str = "
+   Oracle   History   Stats   Settings   Help   [ ]
+  ┌ Radio Shack · Classic ───────────────────────────────────────────────────┐
+  │                         EXECUTIVE DECISION MAKER                         │
+  │                                                                          │
+  │  Think of your question (or type it with i), then press Enter or Space.  │
+  └──────────────────────────────────────────────────────────────────────────┘
+  ┌───────────────────────┐┌────────────────────────┐┌───────────────────────┐
+  │      DEFINITELY       ││       FORGET IT        ││       ASK AGAIN       │
+  └───────────────────────┘└────────────────────────┘└───────────────────────┘
+  ┌───────────────────────┐┌────────────────────────┐┌───────────────────────┐
+  │         NEVER         ││        POSSIBLY        ││        WHY NOT        │
+  └───────────────────────┘└────────────────────────┘└───────────────────────┘
+
+
+
+
+  ┌ Status ──────────────────────────────────────────────────────────────────┐
+  │                            Ready when you are.                           │
+  │         Today: 0 positive, 0 neutral, 0 negative · vetoes left: 3        │
+  │Enter/Space to ask · i type a question · g packs · Ctrl+H help · q/Esc qui│
+  └──────────────────────────────────────────────────────────────────────────┘
+
+";
+
+#[test]
+fn idle_board_snapshot() {
+    let mut harness = classic(80, 24);
+    assert_eq!(harness.snapshot().unwrap(), IDLE_BOARD);
+}
+
+#[test]
+fn typed_question_is_asked_and_recorded() {
+    let mut harness = classic(80, 24);
+    harness.press("i");
+    assert_eq!(harness.app.input_mode(), Mode::Typing);
+    harness.type_text("Lunch?");
+    assert!(harness.snapshot().unwrap().contains("Question: Lunch?"));
+
+    harness.press("Enter");
+    assert!(matches!(
+        harness.app.decision.state(),
+        State::Animating { .. }
+    ));
+    harness.advance(harness.app.config.animation());
+
+    let index = harness.app.last_answer.expect("an answer landed");
+    let answer = harness.app.engine.pack.answers[index].text.clone();
+    let session = harness.app.engine.history.session();
+    assert_eq!(session.len(), 1);
+    assert_eq!(session[0].question.as_deref(), Some("Lunch?"));
+    assert_eq!(session[0].answer, answer);
+
+    let screen = harness.snapshot().unwrap();
+    assert!(screen.contains("“Lunch?”"));
+    assert!(screen.contains(&format!("Recent: {answer}")));
+}
+
+#[test]
+fn answer_flashes_then_the_board_goes_idle() {
+    let mut harness = classic(80, 24);
+    harness.press("Space");
+    harness.advance(harness.app.config.animation());
+    assert!(matches!(
+        harness.app.decision.state(),
+        State::Showing { .. }
+    ));
+    harness.advance(harness.app.config.flash());
+    assert_eq!(harness.app.decision.state(), State::Idle);
+    assert!(harness.app.last_answer.is_some());
+}
+
+#[test]
+fn help_opens_and_closes() {
+    let mut harness = classic(80, 24);
+    harness.press("Ctrl+H");
+    assert!(harness.snapshot().unwrap().contains("How to play:"));
+    harness.press("Esc");
+    assert_eq!(harness.snapshot().unwrap(), IDLE_BOARD);
+    assert!(!harness.quit());
+}
+
+#[test]
+fn brackets_step_through_every_tab() {
+    let mut harness = classic(80, 24);
+    let mut shown = Vec::new();
+    for _ in Tab::ALL {
+        harness.press("]");
+        shown.push(harness.app.screen.tab().unwrap());
+    }
+    assert_eq!(
+        shown,
+        [
+            Tab::History,
+            Tab::Stats,
+            Tab::Settings,
+            Tab::Help,
+            Tab::Oracle
+        ]
+    );
+    harness.press("[");
+    assert_eq!(harness.app.screen.tab(), Some(Tab::Help));
+}
+
+#[test]
+fn worksheet_takes_the_keys_until_it_closes() {
+    let mut harness = classic(80, 24);
+    harness.press("w");
+    assert!(matches!(harness.app.screen, Screen::Worksheet(_)));
+    assert_eq!(harness.app.screen.tab(), None);
+    assert!(!harness.snapshot().unwrap().contains("Oracle   History"));
+
+    harness.press("a");
+    harness.type_text("cheap");
+    harness.press("Enter");
+    let Screen::Worksheet(sheet) = &harness.app.screen else {
+        panic!("the worksheet closed");
+    };
+    assert_eq!(sheet.pros.len(), 1);
+
+    // `]` is a letter here, not the next tab.
+    harness.press("]");
+    assert!(matches!(harness.app.screen, Screen::Worksheet(_)));
+    harness.press("Esc");
+    assert!(matches!(harness.app.screen, Screen::Oracle));
+    assert!(!harness.quit());
+}
+
+#[test]
+fn q_quits_from_the_board() {
+    let mut harness = classic(80, 24);
+    harness.press("q");
+    assert!(harness.quit());
+}
+
+#[test]
+fn small_terminal_asks_to_be_enlarged() {
+    let mut harness = classic(80, 24);
+    harness.resize(40, 10);
+    let screen = harness.snapshot().unwrap();
+    assert!(screen.contains("Terminal too small"));
+    assert!(screen.contains("Now: 40×10"));
+}
+
+#[test]
+fn commitment_cooldown_follows_the_app_clock() {
+    let mut config = Config::default();
+    config.commitment_hours = Some(2);
+    let pack = answers::builtin("classic").expect("classic pack");
+    let mut harness = Harness::new(pack, config, 80, 24).expect("test terminal");
+    let ask = |harness: &mut Harness| {
+        harness.press("i");
+        harness.type_text("Lunch?");
+        harness.press("Enter");
+        harness.advance(harness.app.config.animation());
+    };
+    ask(&mut harness);
+    let first = harness.app.last_answer;
+
+    // Within the cooldown the earlier answer stands.
+    harness.clock.advance(Duration::from_secs(60 * 60));
+    ask(&mut harness);
+    assert_eq!(harness.app.engine.history.session().len(), 1);
+    assert_eq!(harness.app.last_answer, first);
+    assert!(harness.snapshot().unwrap().contains("You already asked"));
+
+    // After it, the question may be asked again.
+    harness.clock.advance(Duration::from_secs(2 * 60 * 60));
+    harness.press("Esc i");
+    harness.type_text("Lunch?");
+    harness.press("Enter");
+    assert!(harness.app.recall.is_some());
+    harness.press("f");
+    harness.advance(harness.app.config.animation());
+    assert_eq!(harness.app.engine.history.session().len(), 2);
+}